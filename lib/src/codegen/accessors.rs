@@ -0,0 +1,614 @@
+//! Synthetic accessors, through which the classes of a nest access each other's private members
+//! in class files older than Java 11. Their JVMs only let a class access its own private members,
+//! so e.g. the read of a private field of the outer class by an inner class is replaced by the call
+//! of a static method of the outer class, which reads the field of the object it is passed.
+//! See https://openjdk.org/jeps/181
+
+use crate::types::*;
+use std::collections::HashMap;
+
+/// Adds accessors to the classes, whose private members are accessed by other classes of their nest,
+/// and replaces these accesses by calls of the accessors
+pub(crate) fn add_accessors(prg: &Prg) -> Prg {
+    let mut accessors = Accessors {
+        prg,
+        current_class: String::new(),
+        methods: HashMap::new(),
+    };
+    let mut result = prg.clone();
+    for class in &mut result {
+        accessors.current_class = class.name.clone();
+        for constant in &mut class.enum_constants {
+            constant
+                .args
+                .iter_mut()
+                .for_each(|arg| accessors.rewrite_expr(arg));
+        }
+        for val in class
+            .fields
+            .iter_mut()
+            .filter_map(|field| field.val.as_mut())
+        {
+            accessors.rewrite_expr(val);
+        }
+        for (_, block) in &mut class.static_blocks {
+            accessors.rewrite_stmt(block);
+        }
+        for constructor in &mut class.constructors {
+            accessors.rewrite_stmt(&mut constructor.body);
+        }
+        for body in class
+            .methods
+            .iter_mut()
+            .filter_map(|method| method.body.as_mut())
+        {
+            accessors.rewrite_stmt(body);
+        }
+    }
+    for class in &mut result {
+        class
+            .methods
+            .append(&mut accessors.methods.remove(&class.name).unwrap_or_default());
+    }
+    result
+}
+
+struct Accessors<'a> {
+    prg: &'a Prg,
+    current_class: String,
+    // The accessors of each class, which are added to it afterwards
+    methods: HashMap<String, Vec<MethodDecl>>,
+}
+
+impl Accessors<'_> {
+    fn class(&self, name: &str) -> Option<&Class> {
+        self.prg.iter().find(|class| class.name == name)
+    }
+
+    /// The outermost class enclosing the class `name`, which is the class itself if it isn't nested
+    fn top_level_class<'b>(&'b self, name: &'b str) -> &'b str {
+        match self
+            .class(name)
+            .and_then(|class| class.outer_class.as_deref())
+        {
+            Some(outer_class) => self.top_level_class(outer_class),
+            None => name,
+        }
+    }
+
+    /// Whether a member of the class `owner` can only be accessed through an accessor, i.e. it is
+    /// private and accessed by another class of its nest
+    fn needs_accessor(&self, owner: &str, modifiers: &Modifiers) -> bool {
+        modifiers.visibility == Visibility::Private
+            && owner != self.current_class
+            && self.top_level_class(owner) == self.top_level_class(&self.current_class)
+    }
+
+    /// The class declaring the field accessed through the typed receiver and the field itself,
+    /// if it needs an accessor
+    fn nestmate_field(&self, receiver: &Expr, name: &str) -> Option<(String, FieldDecl)> {
+        let Some(Type::Class(owner)) = receiver.get_type() else {
+            return None;
+        };
+        let field = self.class(&owner)?.fields.iter().find(|f| f.name == name)?;
+        self.needs_accessor(&owner, &field.modifiers)
+            .then(|| (owner.clone(), field.clone()))
+    }
+
+    /// The method of the class `owner` called with the typed arguments, if it needs an accessor
+    fn nestmate_method(&self, owner: &str, name: &str, args: &[Type]) -> Option<MethodDecl> {
+        let method = self
+            .class(owner)?
+            .methods
+            .iter()
+            .find(|method| method.name == name && Self::takes(&method.params, args))?;
+        self.needs_accessor(owner, &method.modifiers)
+            .then(|| method.clone())
+    }
+
+    /// The constructor of the class `owner` called with the typed arguments, if it needs an accessor
+    fn nestmate_constructor(&self, owner: &str, args: &[Type]) -> Option<Constructor> {
+        let constructor = self
+            .class(owner)?
+            .constructors
+            .iter()
+            .find(|constructor| Self::takes(&constructor.params, args))?;
+        self.needs_accessor(owner, &constructor.modifiers)
+            .then(|| constructor.clone())
+    }
+
+    /// Whether the parameters take arguments of the types, which have been converted to the types
+    /// of the parameters already, except for `null`
+    fn takes(params: &[(Type, String)], args: &[Type]) -> bool {
+        params.len() == args.len()
+            && params
+                .iter()
+                .zip(args)
+                .all(|((param, _), arg)| param == arg || *arg == Type::Null)
+    }
+
+    /// Gets the name of an accessor of the class `owner`, which takes parameters of the types and
+    /// returns the typed value built by `access` from the typed parameters. It is added unless the
+    /// class has an accessor doing the same already, e.g. for another read of the same field.
+    fn accessor(
+        &mut self,
+        owner: &str,
+        param_types: Vec<Type>,
+        throws: Vec<String>,
+        access: impl FnOnce(&[Expr]) -> Expr,
+    ) -> (String, Type) {
+        let params: Vec<(Type, String)> = param_types
+            .into_iter()
+            .enumerate()
+            .map(|(i, t)| (t, format!("arg{i}")))
+            .collect();
+        let locals: Vec<Expr> = params
+            .iter()
+            .map(|(t, name)| Expr::TypedExpr(Box::new(Expr::LocalVar(name.clone())), t.clone()))
+            .collect();
+        let value = access(&locals);
+        let ret_type = value.get_type().unwrap();
+        let stmt = match value {
+            Expr::TypedExpr(call, Type::Void) => {
+                let Expr::StmtExprExpr(call) = *call else {
+                    panic!("Expected call of a void method, got {:?}", call);
+                };
+                Stmt::StmtExprStmt(*call)
+            }
+            value => Stmt::Return(value),
+        };
+        let body = Stmt::TypedStmt(
+            Box::new(Stmt::Block(vec![Stmt::TypedStmt(
+                Box::new(stmt),
+                ret_type.clone(),
+            )])),
+            ret_type.clone(),
+        );
+        let methods = self.methods.entry(owner.to_string()).or_default();
+        if let Some(method) = methods
+            .iter()
+            .find(|method| method.params == params && method.body.as_ref() == Some(&body))
+        {
+            return (method.name.clone(), ret_type);
+        }
+        let name = format!("{ACCESSOR_METHOD_PREFIX}{:03}", methods.len());
+        methods.push(MethodDecl {
+            modifiers: Modifiers {
+                visibility: Visibility::Package,
+                is_static: true,
+                is_final: false,
+            },
+            ret_type: ret_type.clone(),
+            name: name.clone(),
+            params,
+            throws,
+            body: Some(body),
+        });
+        (name, ret_type)
+    }
+
+    /// The typed call of an accessor of the class `owner`, which is passed the typed arguments.
+    /// `null` is passed as a value of the type of the parameter.
+    fn call_accessor(
+        &mut self,
+        owner: &str,
+        args: Vec<Expr>,
+        param_types: Vec<Type>,
+        throws: Vec<String>,
+        access: impl FnOnce(&[Expr]) -> Expr,
+    ) -> StmtExpr {
+        let args = args
+            .into_iter()
+            .zip(&param_types)
+            .map(|(arg, t)| match arg {
+                Expr::TypedExpr(null, Type::Null) => Expr::TypedExpr(null, t.clone()),
+                arg => arg,
+            })
+            .collect();
+        let (name, ret_type) = self.accessor(owner, param_types, throws, access);
+        StmtExpr::TypedStmtExpr(
+            Box::new(StmtExpr::MethodCall(class_name(owner), name, args)),
+            ret_type,
+        )
+    }
+
+    /// The typed call of an accessor of the private field `field` of the class `owner`, which is
+    /// accessed through the typed receiver and passed the typed values as well. The accessor performs
+    /// the access built by `access` from the field and the parameters of the values.
+    fn call_field_accessor(
+        &mut self,
+        (owner, field): (String, FieldDecl),
+        receiver: &Expr,
+        t: &Type,
+        values: Vec<(Expr, Type)>,
+        access: impl FnOnce(Expr, &[Expr]) -> Expr,
+    ) -> StmtExpr {
+        // Static fields are accessed without an object
+        let mut args = vec![];
+        let mut param_types = vec![];
+        if !field.modifiers.is_static {
+            args.push(receiver.clone());
+            param_types.push(Type::Class(owner.clone()));
+        }
+        let receivers = args.len();
+        for (value, value_type) in values {
+            args.push(value);
+            param_types.push(value_type);
+        }
+        let t = t.clone();
+        self.call_accessor(&owner, args, param_types, vec![], |locals| {
+            let receiver = match receivers {
+                0 => class_name(&owner),
+                _ => locals[0].clone(),
+            };
+            let field = Expr::TypedExpr(Box::new(Expr::InstVar(Box::new(receiver), field.name)), t);
+            access(field, &locals[receivers..])
+        })
+    }
+
+    /// Replaces the update of a private field of another class of the nest by the call of an accessor,
+    /// i.e. an assignment, compound assignment, `++` or `--`
+    fn field_update_accessor(&mut self, update: &StmtExpr, t: &Type) -> Option<StmtExpr> {
+        let (var, value) = match update {
+            StmtExpr::Assign(var, value) | StmtExpr::CompoundAssign(var, _, value) => {
+                (var, Some(value))
+            }
+            StmtExpr::PreIncDec(_, var) | StmtExpr::PostIncDec(var, _) => (var, None),
+            _ => return None,
+        };
+        let Expr::TypedExpr(field, var_type) = var else {
+            return None;
+        };
+        let Expr::InstVar(receiver, name) = &**field else {
+            return None;
+        };
+        let field = self.nestmate_field(receiver, name)?;
+        // The assigned value is of the type of the field, unless it is combined with its value first
+        let values = value
+            .map(|value| {
+                let value_type = match (update, value.get_type().unwrap()) {
+                    (StmtExpr::CompoundAssign(_, _, _), t) if t != Type::Null => t,
+                    _ => var_type.clone(),
+                };
+                (value.clone(), value_type)
+            })
+            .into_iter()
+            .collect();
+        let t = t.clone();
+        Some(
+            self.call_field_accessor(field, receiver, var_type, values, |field, values| {
+                let update = match update {
+                    StmtExpr::Assign(_, _) => StmtExpr::Assign(field, values[0].clone()),
+                    StmtExpr::CompoundAssign(_, op, _) => {
+                        StmtExpr::CompoundAssign(field, op.clone(), values[0].clone())
+                    }
+                    StmtExpr::PreIncDec(op, _) => StmtExpr::PreIncDec(op.clone(), field),
+                    StmtExpr::PostIncDec(_, op) => StmtExpr::PostIncDec(field, op.clone()),
+                    _ => unreachable!(),
+                };
+                typed_stmt_expr(update, &t)
+            }),
+        )
+    }
+
+    /// Replaces the call of a private method or constructor of another class of the nest by the call
+    /// of an accessor, which calls it with its parameters
+    fn call_accessor_of_call(&mut self, call: &StmtExpr, t: &Type) -> Option<StmtExpr> {
+        let arg_types = |args: &[Expr]| -> Vec<Type> {
+            args.iter().map(|arg| arg.get_type().unwrap()).collect()
+        };
+        let t = t.clone();
+        match call {
+            StmtExpr::MethodCall(receiver, name, args) => {
+                let Expr::TypedExpr(receiver_expr, Type::Class(owner)) = receiver else {
+                    return None;
+                };
+                if **receiver_expr == Expr::Super {
+                    return None;
+                }
+                let method = self.nestmate_method(owner, name, &arg_types(args))?;
+                let is_static = method.modifiers.is_static;
+                let mut all_args = vec![];
+                let mut param_types = vec![];
+                if !is_static {
+                    all_args.push(receiver.clone());
+                    param_types.push(Type::Class(owner.clone()));
+                }
+                all_args.extend(args.iter().cloned());
+                param_types.extend(method.params.iter().map(|(t, _)| t.clone()));
+                let owner = owner.clone();
+                Some(self.call_accessor(
+                    &owner.clone(),
+                    all_args,
+                    param_types,
+                    method.throws,
+                    |locals| {
+                        let (receiver, args) = match is_static {
+                            true => (class_name(&owner), locals),
+                            false => (locals[0].clone(), &locals[1..]),
+                        };
+                        typed_stmt_expr(
+                            StmtExpr::MethodCall(receiver, name.clone(), args.to_vec()),
+                            &t,
+                        )
+                    },
+                ))
+            }
+            // Inner classes are created with their enclosing instance, which is passed first
+            StmtExpr::New(Type::Class(owner), args)
+            | StmtExpr::QualifiedNew(_, Type::Class(owner), args) => {
+                let constructor = self.nestmate_constructor(owner, &arg_types(args))?;
+                let mut all_args = vec![];
+                let mut param_types = vec![];
+                if let StmtExpr::QualifiedNew(outer, _, _) = call {
+                    all_args.push(outer.clone());
+                    param_types.push(outer.get_type().unwrap());
+                }
+                let outers = all_args.len();
+                all_args.extend(args.iter().cloned());
+                param_types.extend(constructor.params.iter().map(|(t, _)| t.clone()));
+                let owner_type = Type::Class(owner.clone());
+                Some(self.call_accessor(
+                    &owner.clone(),
+                    all_args,
+                    param_types,
+                    constructor.throws,
+                    |locals| {
+                        let new = match outers {
+                            0 => StmtExpr::New(owner_type.clone(), locals.to_vec()),
+                            _ => StmtExpr::QualifiedNew(
+                                locals[0].clone(),
+                                owner_type.clone(),
+                                locals[1..].to_vec(),
+                            ),
+                        };
+                        typed_stmt_expr(new, &owner_type)
+                    },
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Lets a lambda expression or method reference refer to an accessor instead of a private
+    /// method or constructor of another class of the nest. The accessor is static, so the object
+    /// a private instance method is called on is passed as its first argument.
+    fn rewrite_function_ref(&mut self, function_ref: &mut FunctionRef) {
+        let owner = function_ref.class.clone();
+        let owner_type = Type::Class(owner.clone());
+        let (param_types, throws) = match function_ref.kind {
+            MethodKind::Static | MethodKind::Virtual => {
+                let Some(method) = self.nestmate_method(
+                    &owner,
+                    &function_ref.impl_method,
+                    &function_ref.impl_params,
+                ) else {
+                    return;
+                };
+                let mut param_types = function_ref.impl_params.clone();
+                if function_ref.kind == MethodKind::Virtual {
+                    param_types.insert(0, owner_type.clone());
+                }
+                (param_types, method.throws)
+            }
+            MethodKind::Constructor => {
+                let Some(constructor) =
+                    self.nestmate_constructor(&owner, &function_ref.impl_params)
+                else {
+                    return;
+                };
+                (function_ref.impl_params.clone(), constructor.throws)
+            }
+            MethodKind::Private => return,
+        };
+        let (kind, method, ret_type) = (
+            function_ref.kind,
+            function_ref.impl_method.clone(),
+            function_ref.impl_ret_type.clone(),
+        );
+        let (name, ret_type) = self.accessor(&owner, param_types.clone(), throws, |locals| {
+            let call = match kind {
+                MethodKind::Static => {
+                    StmtExpr::MethodCall(class_name(&owner), method, locals.to_vec())
+                }
+                MethodKind::Virtual => {
+                    StmtExpr::MethodCall(locals[0].clone(), method, locals[1..].to_vec())
+                }
+                _ => {
+                    return typed_stmt_expr(
+                        StmtExpr::New(owner_type.clone(), locals.to_vec()),
+                        &owner_type,
+                    )
+                }
+            };
+            typed_stmt_expr(call, &ret_type)
+        });
+        function_ref.kind = MethodKind::Static;
+        function_ref.impl_method = name;
+        function_ref.impl_params = param_types;
+        function_ref.impl_ret_type = ret_type;
+    }
+
+    fn rewrite_stmt(&mut self, stmt: &mut Stmt) {
+        match stmt {
+            Stmt::Block(stmts) => stmts.iter_mut().for_each(|stmt| self.rewrite_stmt(stmt)),
+            Stmt::Return(expr) | Stmt::Yield(expr) | Stmt::Throw(expr) => self.rewrite_expr(expr),
+            Stmt::While(expr, stmt) | Stmt::DoWhile(stmt, expr) => {
+                self.rewrite_expr(expr);
+                self.rewrite_stmt(stmt);
+            }
+            Stmt::For(init, cond, update, body) => {
+                init.iter_mut().for_each(|stmt| self.rewrite_stmt(stmt));
+                cond.iter_mut().for_each(|cond| self.rewrite_expr(cond));
+                update.iter_mut().for_each(|stmt| self.rewrite_stmt(stmt));
+                self.rewrite_stmt(body);
+            }
+            Stmt::ForEach(_, _, expr, body) => {
+                self.rewrite_expr(expr);
+                self.rewrite_stmt(body);
+            }
+            Stmt::Labeled(_, stmt) | Stmt::TypedStmt(stmt, _) => self.rewrite_stmt(stmt),
+            Stmt::Switch(expr, cases) => {
+                self.rewrite_expr(expr);
+                self.rewrite_cases(cases);
+            }
+            Stmt::If(cond, then_stmt, else_stmt) => {
+                self.rewrite_expr(cond);
+                self.rewrite_stmt(then_stmt);
+                else_stmt
+                    .iter_mut()
+                    .for_each(|stmt| self.rewrite_stmt(stmt));
+            }
+            Stmt::Try(body, catches, finally) => {
+                self.rewrite_stmt(body);
+                catches
+                    .iter_mut()
+                    .for_each(|c| self.rewrite_stmt(&mut c.body));
+                finally.iter_mut().for_each(|stmt| self.rewrite_stmt(stmt));
+            }
+            Stmt::StmtExprStmt(stmt_expr) => self.rewrite_stmt_expr(stmt_expr),
+            // The typechecker lifts local classes out of the methods, so they are rewritten as classes
+            Stmt::ReturnVoid
+            | Stmt::Break(_)
+            | Stmt::Continue(_)
+            | Stmt::LocalVarDecl(_, _)
+            | Stmt::LocalClassDecl(_) => {}
+        }
+    }
+
+    fn rewrite_cases(&mut self, cases: &mut [SwitchCase]) {
+        for case in cases {
+            case.body
+                .iter_mut()
+                .for_each(|stmt| self.rewrite_stmt(stmt));
+        }
+    }
+
+    fn rewrite_stmt_expr(&mut self, stmt_expr: &mut StmtExpr) {
+        let StmtExpr::TypedStmtExpr(inner, t) = stmt_expr else {
+            panic!("Expected typed statement expression, got {:?}", stmt_expr);
+        };
+        match &mut **inner {
+            StmtExpr::Assign(var, value) | StmtExpr::CompoundAssign(var, _, value) => {
+                self.rewrite_updated_var(var);
+                self.rewrite_expr(value);
+            }
+            StmtExpr::PreIncDec(_, var) | StmtExpr::PostIncDec(var, _) => {
+                self.rewrite_updated_var(var)
+            }
+            StmtExpr::New(_, args) | StmtExpr::ThisCall(args) | StmtExpr::SuperCall(args) => {
+                args.iter_mut().for_each(|arg| self.rewrite_expr(arg))
+            }
+            StmtExpr::QualifiedNew(receiver, _, args) | StmtExpr::MethodCall(receiver, _, args) => {
+                self.rewrite_expr(receiver);
+                args.iter_mut().for_each(|arg| self.rewrite_expr(arg));
+            }
+            StmtExpr::TypedStmtExpr(_, _) => self.rewrite_stmt_expr(inner),
+        }
+        let t = t.clone();
+        if let Some(call) = self
+            .field_update_accessor(inner, &t)
+            .or_else(|| self.call_accessor_of_call(inner, &t))
+        {
+            *stmt_expr = call;
+        }
+    }
+
+    /// Rewrites the variable updated by an assignment, `++` or `--`. A field isn't read,
+    /// so only the object it belongs to is rewritten.
+    fn rewrite_updated_var(&mut self, var: &mut Expr) {
+        match var {
+            Expr::TypedExpr(field, _) if matches!(**field, Expr::InstVar(_, _)) => {
+                let Expr::InstVar(receiver, _) = &mut **field else {
+                    unreachable!()
+                };
+                self.rewrite_expr(receiver);
+            }
+            var => self.rewrite_expr(var),
+        }
+    }
+
+    fn rewrite_expr(&mut self, expr: &mut Expr) {
+        match expr {
+            // A read of a private field of another class of the nest
+            Expr::TypedExpr(field, t) if matches!(**field, Expr::InstVar(_, _)) => {
+                let Expr::InstVar(receiver, name) = &mut **field else {
+                    unreachable!()
+                };
+                self.rewrite_expr(receiver);
+                if let Some(field) = self.nestmate_field(receiver, name) {
+                    let t = t.clone();
+                    let call =
+                        self.call_field_accessor(field, receiver, &t, vec![], |field, _| field);
+                    *expr = Expr::TypedExpr(Box::new(Expr::StmtExprExpr(Box::new(call))), t);
+                }
+            }
+            Expr::TypedExpr(expr, _)
+            | Expr::InstVar(expr, _)
+            | Expr::Unary(_, expr)
+            | Expr::ArrayLength(expr)
+            | Expr::Cast(_, expr)
+            | Expr::InstanceOf(expr, _, _)
+            | Expr::MethodRef(expr, _) => self.rewrite_expr(expr),
+            Expr::Binary(_, left, right) | Expr::ArrayAccess(left, right) => {
+                self.rewrite_expr(left);
+                self.rewrite_expr(right);
+            }
+            Expr::Conditional(cond, then_expr, else_expr) => {
+                self.rewrite_expr(cond);
+                self.rewrite_expr(then_expr);
+                self.rewrite_expr(else_expr);
+            }
+            Expr::NewArray(_, exprs) | Expr::ArrayInit(_, exprs) => {
+                exprs.iter_mut().for_each(|expr| self.rewrite_expr(expr))
+            }
+            Expr::Switch(expr, cases) => {
+                self.rewrite_expr(expr);
+                self.rewrite_cases(cases);
+            }
+            Expr::StmtExprExpr(stmt_expr) => self.rewrite_stmt_expr(stmt_expr),
+            Expr::Lambda(_, body) => self.rewrite_stmt(body),
+            Expr::FunctionRef(function_ref) => {
+                function_ref
+                    .captured
+                    .iter_mut()
+                    .for_each(|value| self.rewrite_expr(value));
+                self.rewrite_function_ref(function_ref);
+            }
+            Expr::This
+            | Expr::Super
+            | Expr::LocalOrFieldVar(_)
+            | Expr::ClassName(_)
+            | Expr::QualifiedThis(_)
+            | Expr::LocalVar(_)
+            | Expr::FieldVar(_)
+            | Expr::Integer(_)
+            | Expr::Long(_)
+            | Expr::Float(_)
+            | Expr::Double(_)
+            | Expr::Bool(_)
+            | Expr::Char(_)
+            | Expr::String(_)
+            | Expr::Jnull => {}
+        }
+    }
+}
+
+/// The typed name of the class, through which its static members are accessed
+fn class_name(class: &str) -> Expr {
+    Expr::TypedExpr(
+        Box::new(Expr::ClassName(class.to_string())),
+        Type::Class(class.to_string()),
+    )
+}
+
+/// The statement expression typed as an expression of type `t`
+fn typed_stmt_expr(stmt_expr: StmtExpr, t: &Type) -> Expr {
+    Expr::TypedExpr(
+        Box::new(Expr::StmtExprExpr(Box::new(StmtExpr::TypedStmtExpr(
+            Box::new(stmt_expr),
+            t.clone(),
+        )))),
+        t.clone(),
+    )
+}
@@ -5,106 +5,419 @@
 use super::stack::*;
 use super::Instruction::getfield;
 use super::*;
+use crate::typechecker::constants::constant_value;
+use crate::typechecker::library::library_classes;
 use crate::types::*;
 use std::fmt::Debug;
-use std::ops::Deref;
+use std::ops::{Deref, RangeInclusive};
 
 static JAVA_LANG_OBJECT: &str = "java/lang/Object";
+static JAVA_LANG_ITERABLE: &str = "java/lang/Iterable";
+static JAVA_UTIL_ITERATOR: &str = "java/util/Iterator";
+static JAVA_LANG_THROWABLE: &str = "java/lang/Throwable";
+static JAVA_LANG_ENUM: &str = "java/lang/Enum";
+/// The private field of enums holding their constants, which are returned by `values()`
+static ENUM_VALUES_FIELD: &str = "$VALUES";
 static OBJECT_INIT_METHOD: &str = "<init>";
 static OBJECT_INIT_RET: &str = "()V";
+static JAVA_LANG_STRING_BUILDER: &str = "java/lang/StringBuilder";
+static STRING_CONCAT_FACTORY: &str = "java/lang/invoke/StringConcatFactory";
+static MAKE_CONCAT_WITH_CONSTANTS: &str = "makeConcatWithConstants";
+static MAKE_CONCAT_WITH_CONSTANTS_TYPE: &str = "(Ljava/lang/invoke/MethodHandles$Lookup;Ljava/lang/String;Ljava/lang/invoke/MethodType;Ljava/lang/String;[Ljava/lang/Object;)Ljava/lang/invoke/CallSite;";
+/// The kinds of method handles, which tell how the method they refer to is called.
+/// See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.3.5
+const REF_INVOKE_VIRTUAL: u8 = 5;
+/// The kind of method handles calling a static method
+const REF_INVOKE_STATIC: u8 = 6;
+const REF_INVOKE_SPECIAL: u8 = 7;
+const REF_NEW_INVOKE_SPECIAL: u8 = 8;
+const REF_INVOKE_INTERFACE: u8 = 9;
+static LAMBDA_METAFACTORY: &str = "java/lang/invoke/LambdaMetafactory";
+static METAFACTORY: &str = "metafactory";
+static METAFACTORY_TYPE: &str = "(Ljava/lang/invoke/MethodHandles$Lookup;Ljava/lang/String;Ljava/lang/invoke/MethodType;Ljava/lang/invoke/MethodType;Ljava/lang/invoke/MethodHandle;Ljava/lang/invoke/MethodType;)Ljava/lang/invoke/CallSite;";
+/// The most slots of arguments `makeConcatWithConstants` accepts
+const MAX_CONCAT_SLOTS: u16 = 200;
+
+/// The major version of the class files generated by default, i.e. the one of Java 8.
+/// See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.1-200-B.2
+pub const DEFAULT_MAJOR_VERSION: u16 = 52;
+/// The releases of Java class files can be generated for. Java 7 and older lack the method handles
+/// used by `invokedynamic`, e.g. for lambda expressions.
+pub const SUPPORTED_RELEASES: RangeInclusive<u16> = 8..=25;
+/// The major version of Java 9, from which on strings are concatenated via `invokedynamic`
+const STRING_CONCAT_FACTORY_MAJOR_VERSION: u16 = 53;
+/// The major version of Java 11, from which on nested classes can access each other's private members
+const NEST_MAJOR_VERSION: u16 = 55;
+
+/// Gets the major version of the class files of a release of Java, e.g. 61 for Java 17,
+/// or None if class files can't be generated for it
+pub fn release_major_version(release: u16) -> Option<u16> {
+    release
+        .checked_add(44)
+        .filter(|_| SUPPORTED_RELEASES.contains(&release))
+}
 
 /// The DIR(Duck Intermediate Representation) is our IR for generating Java Bytecode
 /// from our TAST
 #[derive(Debug)]
 pub struct DIR {
-    pub(crate) constant_pool: ConstantPool,
     pub(crate) classes: Vec<IRClass>,
 }
 impl DIR {
-    /// Because this involves crating the constant pool, this is a mutable method
-    /// https://docs.oracle.com/javase/specs/jvms/se15/html/jvms-4.html#jvms-4.1
-    /// Since we have a DIR we can assume the methods have been expanded into Vectors of Instructions
-    // We also assume that the constant pool has already been filled completely
-    pub fn as_bytes(&mut self) -> Vec<u8> {
-        // Only one class for now
-        let current_class = &self.classes[0];
-        let mut result = vec![0xCA, 0xFE, 0xBA, 0xBE];
-        // Minor version, always 0
-        result.extend_from_slice(&[0, 0]);
-        // Major version, always 52
-        result.extend_from_slice(&[0, 52]);
-        // Add this_class and super class to constant pool. Super class is always java/lang/Object
-        let this_class_index = self
-            .constant_pool
-            .index_of(&Constant::Class(current_class.name.clone()))
-            .unwrap();
-        let super_class_index = self
-            .constant_pool
-            .index_of(&Constant::Class(JAVA_LANG_OBJECT.to_string()))
-            .unwrap();
-        let mut field_infos = current_class
-            .fields
-            .iter()
-            .flat_map(|f| f.as_bytes(&current_class.name, &mut self.constant_pool))
-            .collect();
-        let mut method_infos = current_class
-            .methods
+    /// Returns the bytes of one class file per class, paired with the name of the class
+    pub fn as_bytes(&mut self) -> Vec<(String, Vec<u8>)> {
+        self.classes
+            .iter_mut()
+            .map(|class| (class.name.clone(), class.as_bytes()))
+            .collect()
+    }
+}
+
+fn make_default_constructor(
+    class: &Class,
+    constant_pool: &mut ConstantPool,
+    hierarchy: &ClassHierarchy,
+) -> CompiledMethod {
+    // The default constructor has the same visibility as its class, except for enums,
+    // whose constructors are private
+    let visibility = if class.is_enum {
+        Visibility::Private
+    } else {
+        class.modifiers.visibility
+    };
+    let constructor = Constructor {
+        modifiers: Modifiers {
+            visibility,
+            ..Default::default()
+        },
+        params: vec![],
+        throws: vec![],
+        body: Stmt::TypedStmt(Box::new(Stmt::Block(vec![])), Type::Void),
+    };
+    generate_constructor(&constructor, class, constant_pool, hierarchy)
+}
+
+/// What the static initializer of a class runs, i.e. assigning the initial value of a static field
+/// or a static block
+enum StaticInitializer<'a> {
+    Field(String, Type, Expr),
+    Block(&'a Stmt),
+}
+
+/// The static fields with an initial value and the static blocks of a class in the order they are
+/// declared in. Static constants are initialized by their ConstantValue attribute instead.
+/// The constants of enums are created first, since the other static fields may use them.
+fn static_initializers(class: &Class) -> Vec<StaticInitializer<'_>> {
+    let mut result: Vec<StaticInitializer> = enum_constant_values(class)
+        .into_iter()
+        .map(|(name, field_type, value)| StaticInitializer::Field(name, field_type, value))
+        .collect();
+    for i in 0..=class.fields.len() {
+        for (_, block) in class
+            .static_blocks
             .iter()
-            .flat_map(|m| m.as_bytes(&mut self.constant_pool))
-            .collect();
-        // Constant Pool
-        result.extend_from_slice(&self.constant_pool.count().to_be_bytes());
-        result.append(&mut self.constant_pool.as_bytes());
-        // Access flags; 0x01 = public, 0x20 = super where superclass-methods are treated specially
-        result.extend_from_slice(&[0, 32]);
-        result.extend_from_slice(&this_class_index.to_be_bytes());
-        result.extend_from_slice(&super_class_index.to_be_bytes());
-        result.extend_from_slice(&[0, 0]); // Interfaces count, being 0
+            .filter(|(before, _)| *before == i)
+        {
+            result.push(StaticInitializer::Block(block));
+        }
+        let Some(field) = class.fields.get(i) else {
+            break;
+        };
+        if let Some(value) = field.val.as_ref().filter(|_| field.modifiers.is_static) {
+            if field.static_constant().is_none() {
+                result.push(StaticInitializer::Field(
+                    field.name.clone(),
+                    field.field_type.clone(),
+                    value.clone(),
+                ));
+            }
+        }
+    }
+    result
+}
 
-        // Fields
-        result.extend_from_slice(&(current_class.fields.len() as u16).to_be_bytes());
-        result.append(&mut field_infos);
+/// Generates the static initializer `<clinit>`, which initializes the static fields of a class
+/// and runs its static blocks. Returns None if there is nothing to initialize.
+fn generate_static_initializer(
+    class: &Class,
+    constant_pool: &mut ConstantPool,
+    hierarchy: &ClassHierarchy,
+) -> Option<CompiledMethod> {
+    let initializers = static_initializers(class);
+    if initializers.is_empty() {
+        return None;
+    }
+    let mut local_var_pool = LocalVarPool::new(&[], true);
+    let mut stack = StackSize::new();
+    let mut code = vec![];
+    for initializer in initializers {
+        match initializer {
+            StaticInitializer::Field(name, field_type, value) => {
+                code.append(&mut generate_code_expr(
+                    type_field_value(value, &field_type),
+                    &mut stack,
+                    constant_pool,
+                    &mut local_var_pool,
+                    &class.name,
+                    hierarchy,
+                ));
+                code.push(Instruction::putstatic(constant_pool.add(
+                    Constant::FieldRef(FieldRef {
+                        class: class.name.clone(),
+                        field: NameAndType {
+                            name,
+                            r#type: field_type.to_ir_string(),
+                        },
+                    }),
+                )));
+                stack.dec(field_type.size());
+            }
+            StaticInitializer::Block(block) => {
+                code.append(&mut generate_code_stmt(
+                    block.clone(),
+                    &mut stack,
+                    constant_pool,
+                    &mut local_var_pool,
+                    &mut JumpTargets::default(),
+                    &class.name,
+                    hierarchy,
+                ));
+            }
+        }
+    }
+    code.push(Instruction::r#return);
+    align_switches(&mut code);
+    let exception_table = exception_table(&mut code);
+    let stack_map_table =
+        StackMapTable::new(&code, &[], &exception_table, constant_pool, hierarchy, None);
+    Some(CompiledMethod {
+        name: "<clinit>".to_string(),
+        access_flags: AccessFlags::Static as u16,
+        return_type: Type::Void,
+        params: vec![],
+        max_stack: stack.max,
+        max_locals: local_var_pool.max_locals(),
+        code,
+        exception_table,
+        stack_map_table,
+        throws: vec![],
+    })
+}
 
-        // Methods
-        result.extend_from_slice(&(current_class.methods.len() as u16).to_be_bytes());
-        result.append(&mut method_infos);
+/// The names, types and initial values of the static fields holding the constants of an enum,
+/// followed by the array of all constants, which is returned by `values()`.
+/// Each constant is created with its name and ordinal, followed by its arguments.
+fn enum_constant_values(class: &Class) -> Vec<(String, Type, Expr)> {
+    if !class.is_enum {
+        return vec![];
+    }
+    let enum_type = Type::Class(class.name.clone());
+    let class_name = Expr::TypedExpr(
+        Box::new(Expr::ClassName(class.name.clone())),
+        enum_type.clone(),
+    );
+    let mut result = vec![];
+    let mut constants = vec![];
+    for (ordinal, constant) in class.enum_constants.iter().enumerate() {
+        let args = [
+            Expr::TypedExpr(Box::new(Expr::String(constant.name.clone())), Type::String),
+            Expr::TypedExpr(Box::new(Expr::Integer(ordinal as i32)), Type::Int),
+        ]
+        .into_iter()
+        .chain(constant.args.iter().cloned())
+        .collect();
+        let value = Expr::StmtExprExpr(Box::new(StmtExpr::TypedStmtExpr(
+            Box::new(StmtExpr::New(enum_type.clone(), args)),
+            enum_type.clone(),
+        )));
+        result.push((constant.name.clone(), enum_type.clone(), value));
+        constants.push(Expr::TypedExpr(
+            Box::new(Expr::InstVar(
+                Box::new(class_name.clone()),
+                constant.name.clone(),
+            )),
+            enum_type.clone(),
+        ));
+    }
+    let array_type = Type::Array(Box::new(enum_type));
+    result.push((
+        ENUM_VALUES_FIELD.to_string(),
+        array_type.clone(),
+        Expr::ArrayInit(array_type, constants),
+    ));
+    result
+}
 
-        result.extend_from_slice(&[0, 0]);
-        result.extend_from_slice(&[]);
-        println!("Generated bytecode succesfully!🎉💾");
-        result
+/// Types an expression with the type `t`, unless it is typed already. The values of fields and
+/// the lengths and elements of arrays created by them may be untyped in classes, which weren't
+/// created by the typechecker.
+fn type_field_value(expr: Expr, t: &Type) -> Expr {
+    match expr {
+        Expr::TypedExpr(_, _) => expr,
+        expr => Expr::TypedExpr(Box::new(expr), t.clone()),
+    }
+}
+
+/// Whether the typed expression is the name of a class, i.e. static members of that class are accessed
+fn is_class_name(expr: &Expr) -> bool {
+    matches!(expr, Expr::TypedExpr(expr, _) if matches!(**expr, Expr::ClassName(_)))
+}
+
+/// The parameters of the name and ordinal of the created constant, which precede the declared
+/// parameters of the constructors of enums
+fn enum_constructor_params() -> Vec<(Type, String)> {
+    vec![
+        (Type::String, "$enum$name".to_string()),
+        (Type::Int, "$enum$ordinal".to_string()),
+    ]
+}
+
+/// The parameter of the constructors of inner classes, which is their enclosing instance, or of local
+/// classes, which is the value of a captured variable, assigned to the field
+fn outer_instance_param(field: &FieldDecl) -> (Type, String) {
+    (field.field_type.clone(), field.name.clone())
+}
+
+/// Gets the call of another constructor via `this(...)` or `super(...)`,
+/// if it is the first statement of a typed constructor
+fn explicit_constructor_call(stmts: &[Stmt]) -> Option<&StmtExpr> {
+    let Some(Stmt::TypedStmt(first, _)) = stmts.first() else {
+        return None;
+    };
+    let Stmt::StmtExprStmt(StmtExpr::TypedStmtExpr(stmt_expr, _)) = first.deref() else {
+        return None;
+    };
+    match stmt_expr.deref() {
+        call @ (StmtExpr::ThisCall(_) | StmtExpr::SuperCall(_)) => Some(call),
+        _ => None,
     }
 }
 
-fn make_default_constructor(class: &IRClass, constant_pool: &mut ConstantPool) -> CompiledMethod {
-    let mut local_var_pool = LocalVarPool(vec![]);
+/// The typed statement calling another constructor via `this(...)` or `super(...)`
+fn constructor_call_stmt(call: StmtExpr) -> Stmt {
+    Stmt::TypedStmt(
+        Box::new(Stmt::StmtExprStmt(StmtExpr::TypedStmtExpr(
+            Box::new(call),
+            Type::Void,
+        ))),
+        Type::Void,
+    )
+}
+
+fn generate_constructor(
+    constructor: &Constructor,
+    class: &Class,
+    constant_pool: &mut ConstantPool,
+    hierarchy: &ClassHierarchy,
+) -> CompiledMethod {
+    let Stmt::TypedStmt(body, body_type) = &constructor.body else {
+        panic!(
+            "Expected typed constructor body, got {:?}",
+            constructor.body
+        );
+    };
+    let Stmt::Block(stmts) = body.deref() else {
+        panic!("Expected block as constructor body, got {:?}", body);
+    };
+    let mut stmts = stmts.clone();
+    let mut params = constructor.params.clone();
+    // The constructors of enums are passed the name and ordinal of the created constant first,
+    // which they pass on to the constructor they call
+    if class.is_enum {
+        params.splice(0..0, enum_constructor_params());
+        let mut args: Vec<Expr> = enum_constructor_params()
+            .into_iter()
+            .map(|(t, name)| Expr::TypedExpr(Box::new(Expr::LocalVar(name)), t))
+            .collect();
+        let call = match explicit_constructor_call(&stmts) {
+            Some(StmtExpr::ThisCall(this_args)) => {
+                args.extend(this_args.iter().cloned());
+                stmts.remove(0);
+                StmtExpr::ThisCall(args)
+            }
+            _ => StmtExpr::SuperCall(args),
+        };
+        stmts.insert(0, constructor_call_stmt(call));
+    }
+    // The constructors of inner classes are passed the enclosing instance first, which they pass on
+    // to the constructor they call via `this(...)`. Otherwise, it is stored before calling the super
+    // constructor, so the enclosing instance can already be used while the superclass is initialized.
+    let outer_instance_field = class.outer_instance_field();
+    if let Some(field) = &outer_instance_field {
+        let (t, name) = outer_instance_param(field);
+        params.insert(0, (t.clone(), name.clone()));
+        if let Some(StmtExpr::ThisCall(this_args)) = explicit_constructor_call(&stmts) {
+            let args = std::iter::once(Expr::TypedExpr(Box::new(Expr::LocalVar(name)), t))
+                .chain(this_args.iter().cloned())
+                .collect();
+            stmts[0] = constructor_call_stmt(StmtExpr::ThisCall(args));
+        }
+    }
+    // The constructors of local classes are passed the values of the captured variables last,
+    // which are passed on and stored like the enclosing instance
+    let captured_var_fields = class.captured_var_fields();
+    let captured_var_params: Vec<(Type, String)> = captured_var_fields
+        .iter()
+        .map(outer_instance_param)
+        .collect();
+    if let Some(StmtExpr::ThisCall(this_args)) = explicit_constructor_call(&stmts) {
+        let args = this_args
+            .iter()
+            .cloned()
+            .chain(captured_var_params.iter().map(|(t, name)| {
+                Expr::TypedExpr(Box::new(Expr::LocalVar(name.clone())), t.clone())
+            }))
+            .collect();
+        stmts[0] = constructor_call_stmt(StmtExpr::ThisCall(args));
+    }
+    params.extend(captured_var_params);
+    let mut local_var_pool = LocalVarPool::new(&params, false);
     let mut stack = StackSize::new();
-    let mut code = vec![
-        Instruction::aload_0,
-        Instruction::invokespecial(
-            constant_pool
-                .index_of(&Constant::MethodRef(MethodRef {
-                    class: JAVA_LANG_OBJECT.to_string(),
-                    method: NameAndType {
-                        name: OBJECT_INIT_METHOD.to_string(),
-                        r#type: OBJECT_INIT_RET.to_string(),
+    let mut code = vec![];
+    // If another constructor is called via `this(...)`, that one calls the super constructor
+    // and initializes the fields, so they must not be initialized twice
+    if !matches!(
+        explicit_constructor_call(&stmts),
+        Some(StmtExpr::ThisCall(_))
+    ) {
+        // Without an explicit `super(...)`, the constructor of the superclass without arguments is called
+        let args = match explicit_constructor_call(&stmts) {
+            Some(StmtExpr::SuperCall(args)) => {
+                let args = args.clone();
+                stmts.remove(0);
+                args
+            }
+            _ => vec![],
+        };
+        if let Some(field) = &outer_instance_field {
+            // The enclosing instance is the first parameter
+            code.push(Instruction::aload_0);
+            code.push(Instruction::aload(1));
+            stack.inc(2);
+            code.push(Instruction::putfield(constant_pool.add(
+                Constant::FieldRef(FieldRef {
+                    class: class.name.clone(),
+                    field: NameAndType {
+                        name: field.name.clone(),
+                        r#type: field.field_type.to_ir_string(),
                     },
-                }))
-                .unwrap(),
-        ),
-    ];
-    stack.inc(1); // aload_0
-    stack.dec(1); // invokespecial
-    for field in class.fields.iter() {
-        if let Some(x) = &field.val {
+                }),
+            )));
+            stack.dec(2);
+        }
+        for field in &captured_var_fields {
             code.push(Instruction::aload_0);
             stack.inc(1);
             code.append(&mut generate_code_expr(
-                Expr::TypedExpr(Box::new(x.clone()), field.field_type.clone()),
+                Expr::TypedExpr(
+                    Box::new(Expr::LocalVar(field.name.clone())),
+                    field.field_type.clone(),
+                ),
                 &mut stack,
                 constant_pool,
                 &mut local_var_pool,
                 &class.name,
+                hierarchy,
             ));
             code.push(Instruction::putfield(constant_pool.add(
                 Constant::FieldRef(FieldRef {
@@ -115,31 +428,112 @@ fn make_default_constructor(class: &IRClass, constant_pool: &mut ConstantPool) -
                     },
                 }),
             )));
-            stack.dec(2);
+            stack.dec(1 + field.field_type.size());
+        }
+        code.push(Instruction::aload_0);
+        stack.inc(1);
+        for arg in &args {
+            code.append(&mut generate_code_expr(
+                arg.clone(),
+                &mut stack,
+                constant_pool,
+                &mut local_var_pool,
+                &class.name,
+                hierarchy,
+            ));
+        }
+        code.push(Instruction::invokespecial(
+            constant_pool.add(Constant::MethodRef(MethodRef {
+                class: class
+                    .super_class
+                    .clone()
+                    .unwrap_or_else(|| JAVA_LANG_OBJECT.to_string()),
+                method: NameAndType {
+                    name: OBJECT_INIT_METHOD.to_string(),
+                    r#type: method_descriptor(&args, &Type::Void),
+                },
+            })),
+        ));
+        stack.dec(args_size(&args) + 1);
+        for field in class.fields.iter().filter(|f| !f.modifiers.is_static) {
+            if let Some(x) = &field.val {
+                code.push(Instruction::aload_0);
+                stack.inc(1);
+                code.append(&mut generate_code_expr(
+                    type_field_value(x.clone(), &field.field_type),
+                    &mut stack,
+                    constant_pool,
+                    &mut local_var_pool,
+                    &class.name,
+                    hierarchy,
+                ));
+                code.push(Instruction::putfield(constant_pool.add(
+                    Constant::FieldRef(FieldRef {
+                        class: class.name.clone(),
+                        field: NameAndType {
+                            name: field.name.clone(),
+                            r#type: field.field_type.to_ir_string(),
+                        },
+                    }),
+                )));
+                stack.dec(1 + field.field_type.size());
+            }
         }
     }
-    code.push(Instruction::r#return);
+    code.append(&mut generate_code_stmt(
+        Stmt::TypedStmt(Box::new(Stmt::Block(stmts)), body_type.clone()),
+        &mut stack,
+        constant_pool,
+        &mut local_var_pool,
+        &mut JumpTargets::default(),
+        &class.name,
+        hierarchy,
+    ));
+    if can_complete_normally(&code) {
+        code.push(Instruction::r#return);
+    }
+    align_switches(&mut code);
+    let exception_table = exception_table(&mut code);
 
-    let stack_map_table = StackMapTable::new(&code, &[], constant_pool);
+    // Until the super constructor has been called, `this` is uninitialized in constructors
+    let stack_map_table = StackMapTable::new(
+        &code,
+        &params,
+        &exception_table,
+        constant_pool,
+        hierarchy,
+        Some(VerificationType::UNINITIALIZED_THIS),
+    );
     CompiledMethod {
-        name: "<init>".to_string(),
+        name: OBJECT_INIT_METHOD.to_string(),
+        access_flags: constructor.modifiers.access_flags(),
         return_type: Type::Void,
-        params: vec![],
+        params,
         max_stack: stack.max,
-        max_locals: 1 + local_var_pool.0.len() as u16,
+        max_locals: local_var_pool.max_locals(),
         code,
+        exception_table,
         stack_map_table,
+        throws: constructor.throws.clone(),
     }
 }
 
+/// The constants of a class, followed by the name of the class, the major version of its class file
+/// and the bootstrap methods of its `invokedynamic` instructions. Each bootstrap method is given by
+/// the index of its method handle and the indices of its static arguments.
 #[derive(Debug)]
-pub struct ConstantPool(Vec<Constant>, String);
+pub struct ConstantPool(Vec<Constant>, String, u16, Vec<(u16, Vec<u16>)>);
 impl ConstantPool {
     pub fn new(name: String) -> Self {
+        Self::with_version(name, DEFAULT_MAJOR_VERSION)
+    }
+    /// Creates the constant pool of a class file with the given major version, which decides the
+    /// instructions the code of the class can use, e.g. 53 for Java 9
+    pub fn with_version(name: String, major_version: u16) -> Self {
         // This is the same boilerplate constantpool for all files
         // so we can just hardcode it here.
-        // This would only ever change, if we allowed the user
-        // to create their own constructors, which we don't
+        // The constructor of java/lang/Object is called by the constructors
+        // of every class that doesn't extend another class
         Self(
             vec![
                 Constant::MethodRef(MethodRef {
@@ -162,8 +556,13 @@ impl ConstantPool {
                 Constant::Utf8("Code".to_string()),
             ],
             name,
+            major_version,
+            vec![],
         )
     }
+    pub fn major_version(&self) -> u16 {
+        self.2
+    }
     // For some unknown reason, this is 1-indexed and we have to add 1 to the count
     pub fn count(&self) -> u16 {
         self.0.len() as u16 + 1
@@ -178,26 +577,82 @@ impl ConstantPool {
                 self.add(Constant::Utf8(class_name));
             }
             Constant::FieldRef(field_ref) => {
-                self.add(Constant::Utf8(field_ref.class));
+                self.add(Constant::Class(field_ref.class));
                 self.add(Constant::NameAndType(field_ref.field));
             }
             Constant::NameAndType(name_and_type) => {
                 self.add(Constant::Utf8(name_and_type.name));
                 self.add(Constant::Utf8(name_and_type.r#type));
             }
-            Constant::MethodRef(method_ref) => {
-                self.add(Constant::Utf8(method_ref.class));
+            Constant::MethodRef(method_ref) | Constant::InterfaceMethodRef(method_ref) => {
+                self.add(Constant::Class(method_ref.class));
                 self.add(Constant::NameAndType(method_ref.method));
             }
+            Constant::MethodHandle(_, method_ref) => {
+                self.add(*method_ref);
+            }
+            Constant::MethodType(descriptor) => {
+                self.add(Constant::Utf8(descriptor));
+            }
+            Constant::InvokeDynamic(_, name_and_type) => {
+                self.add(Constant::NameAndType(name_and_type));
+            }
             // Do nothing in these cases
             Constant::String(str) => {
                 self.add(Constant::Utf8(str));
             }
-            Constant::Integer(int) => {}
+            Constant::Integer(_)
+            | Constant::Long(_)
+            | Constant::Float(_)
+            | Constant::Double(_)
+            | Constant::Unusable => {}
             Constant::Utf8(name) => {}
         };
-        self.0.push(constant);
-        self.0.len() as u16
+        self.0.push(constant.clone());
+        let index = self.0.len() as u16;
+        // Longs and doubles take up two entries, the second of which can't be used
+        if let Constant::Long(_) | Constant::Double(_) = constant {
+            self.0.push(Constant::Unusable);
+        }
+        index
+    }
+    /// Adds a bootstrap method with the given method handle and static arguments,
+    /// returning its index into the BootstrapMethods attribute
+    pub fn add_bootstrap_method(&mut self, method_handle: Constant, args: Vec<Constant>) -> u16 {
+        let bootstrap_method = (
+            self.add(method_handle),
+            args.into_iter().map(|arg| self.add(arg)).collect(),
+        );
+        if let Some(index) = self.3.iter().position(|m| *m == bootstrap_method) {
+            return index as u16;
+        }
+        self.3.push(bootstrap_method);
+        self.3.len() as u16 - 1
+    }
+    /// The BootstrapMethods attribute of the class, which is None if the class doesn't use `invokedynamic`.
+    /// See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.23
+    pub fn bootstrap_methods_attribute(&mut self) -> Option<Vec<u8>> {
+        if self.3.is_empty() {
+            return None;
+        }
+        let mut attr = vec![];
+        attr.extend_from_slice(&(self.3.len() as u16).to_be_bytes());
+        for (method_handle, args) in &self.3 {
+            attr.extend_from_slice(&method_handle.to_be_bytes());
+            attr.extend_from_slice(&(args.len() as u16).to_be_bytes());
+            for arg in args {
+                attr.extend_from_slice(&arg.to_be_bytes());
+            }
+        }
+        let mut result = vec![];
+        result.extend_from_slice(
+            &self
+                .add(Constant::Utf8("BootstrapMethods".to_string()))
+                .to_be_bytes(),
+        );
+        result.extend_from_slice(&(attr.len() as u32).to_be_bytes());
+        result.append(&mut attr);
+        Some(result)
     }
     pub fn index_of(&self, constant: &Constant) -> Option<u16> {
         self.0
@@ -234,8 +689,13 @@ impl ConstantPool {
                             .to_be_bytes(),
                     );
                 }
-                Constant::MethodRef(MethodRef { class, method }) => {
-                    result.push(10);
+                Constant::MethodRef(MethodRef { class, method })
+                | Constant::InterfaceMethodRef(MethodRef { class, method }) => {
+                    result.push(if matches!(self.0[idx], Constant::MethodRef(_)) {
+                        10
+                    } else {
+                        11
+                    });
                     result.extend_from_slice(
                         &self
                             .index_of(&Constant::Class(class))
@@ -271,6 +731,30 @@ impl ConstantPool {
                             .to_be_bytes(),
                     );
                 }
+                Constant::MethodHandle(reference_kind, method_ref) => {
+                    result.push(15);
+                    result.push(reference_kind);
+                    result.extend_from_slice(&self.index_of(&method_ref).unwrap().to_be_bytes());
+                }
+                Constant::MethodType(descriptor) => {
+                    result.push(16);
+                    result.extend_from_slice(
+                        &self
+                            .index_of(&Constant::Utf8(descriptor))
+                            .unwrap()
+                            .to_be_bytes(),
+                    );
+                }
+                Constant::InvokeDynamic(bootstrap_method, name_and_type) => {
+                    result.push(18);
+                    result.extend_from_slice(&bootstrap_method.to_be_bytes());
+                    result.extend_from_slice(
+                        &self
+                            .index_of(&Constant::NameAndType(name_and_type))
+                            .unwrap()
+                            .to_be_bytes(),
+                    );
+                }
                 Constant::String(val) => {
                     result.push(8);
                     result.extend_from_slice(
@@ -284,6 +768,20 @@ impl ConstantPool {
                     result.push(3);
                     result.extend_from_slice(&int.to_be_bytes());
                 }
+                Constant::Float(float) => {
+                    result.push(4);
+                    result.extend_from_slice(&float.to_be_bytes());
+                }
+                Constant::Long(long) => {
+                    result.push(5);
+                    result.extend_from_slice(&long.to_be_bytes());
+                }
+                Constant::Double(double) => {
+                    result.push(6);
+                    result.extend_from_slice(&double.to_be_bytes());
+                }
+                // The second entry of a Long or Double isn't written
+                Constant::Unusable => {}
             }
         }
         result
@@ -293,80 +791,432 @@ impl ConstantPool {
 pub(crate) struct IRClass {
     pub(crate) name: String,
     pub(crate) super_name: String,
-    pub(crate) fields: Vec<FieldDecl>,
+    pub(crate) interfaces: Vec<String>,
+    pub(crate) access_flags: u16,
+    /// The fields with the access flags, which aren't represented by their modifiers
+    pub(crate) fields: Vec<(FieldDecl, u16)>,
     pub(crate) methods: Vec<CompiledMethod>,
+    pub(crate) constant_pool: ConstantPool,
+    /// The nested classes of the program, of which the ones related to the class are described by its attributes
+    pub(crate) nested_classes: Vec<NestedClass>,
+}
+
+/// A class nested in another class, i.e. a member of its outer class, or a local class
+#[derive(Debug, Clone)]
+pub(crate) struct NestedClass {
+    pub(crate) name: String,
+    pub(crate) outer_class: String,
+    /// Local classes aren't members of their outer class, so it isn't described by the attribute
+    pub(crate) is_local: bool,
+    pub(crate) simple_name: String,
+    /// The access flags of the class as a member of its outer class, which may be private or static
+    pub(crate) access_flags: u16,
 }
 
 impl IRClass {
     pub(crate) fn new(
         name: String,
-        fields: Vec<FieldDecl>,
+        fields: Vec<(FieldDecl, u16)>,
         methods: Vec<CompiledMethod>,
+        major_version: u16,
     ) -> IRClass {
         IRClass {
+            constant_pool: ConstantPool::with_version(name.clone(), major_version),
             name,
             super_name: String::from("java/lang/Object"),
+            interfaces: vec![],
+            access_flags: AccessFlags::Super as u16,
             fields,
             methods,
+            nested_classes: vec![],
         }
     }
-}
 
-#[derive(Debug)]
-pub struct IRFieldDecl {
-    pub(crate) type_index: u16,
-    pub(crate) access_flags: AccessFlags,
-    pub(crate) name_index: u16,
-}
-#[repr(u8)]
-#[derive(Debug)]
-pub(crate) enum AccessFlags {
-    Public,
-}
+    /// Because this involves crating the constant pool, this is a mutable method
+    /// https://docs.oracle.com/javase/specs/jvms/se15/html/jvms-4.html#jvms-4.1
+    /// Since we have a DIR we can assume the methods have been expanded into Vectors of Instructions
+    // We also assume that the constant pool has already been filled completely
+    pub(crate) fn as_bytes(&mut self) -> Vec<u8> {
+        let mut result = vec![0xCA, 0xFE, 0xBA, 0xBE];
+        // Minor version, always 0
+        result.extend_from_slice(&[0, 0]);
+        result.extend_from_slice(&self.constant_pool.major_version().to_be_bytes());
+        // Add this_class and super class to constant pool
+        let this_class_index = self
+            .constant_pool
+            .index_of(&Constant::Class(self.name.clone()))
+            .unwrap();
+        let super_class_index = self
+            .constant_pool
+            .add(Constant::Class(self.super_name.clone()));
+        let interface_indices: Vec<u16> = self
+            .interfaces
+            .iter()
+            .map(|interface| self.constant_pool.add(Constant::Class(interface.clone())))
+            .collect();
+        let mut field_infos = self
+            .fields
+            .iter()
+            .flat_map(|(f, flags)| f.as_bytes(*flags, &mut self.constant_pool))
+            .collect();
+        let mut method_infos = self
+            .methods
+            .iter()
+            .flat_map(|m| m.as_bytes(&mut self.constant_pool))
+            .collect();
+        // The attributes of classes are the bootstrap methods of `invokedynamic` instructions and the
+        // ones describing nested classes. The InnerClasses attribute comes last, since it describes
+        // the nested classes used by the other attributes as well.
+        let mut attributes: Vec<Vec<u8>> = self
+            .constant_pool
+            .bootstrap_methods_attribute()
+            .into_iter()
+            .chain(self.nest_attribute())
+            .chain(self.inner_classes_attribute())
+            .collect();
+        // Constant Pool
+        result.extend_from_slice(&self.constant_pool.count().to_be_bytes());
+        result.append(&mut self.constant_pool.as_bytes());
+        result.extend_from_slice(&self.access_flags.to_be_bytes());
+        result.extend_from_slice(&this_class_index.to_be_bytes());
+        result.extend_from_slice(&super_class_index.to_be_bytes());
+        result.extend_from_slice(&(interface_indices.len() as u16).to_be_bytes());
+        for index in interface_indices {
+            result.extend_from_slice(&index.to_be_bytes());
+        }
 
-impl IRFieldDecl {
-    pub(crate) fn new(type_index: u16, name_index: u16) -> IRFieldDecl {
-        IRFieldDecl {
-            type_index,
-            access_flags: AccessFlags::Public,
-            name_index,
+        // Fields
+        result.extend_from_slice(&(self.fields.len() as u16).to_be_bytes());
+        result.append(&mut field_infos);
+
+        // Methods
+        result.extend_from_slice(&(self.methods.len() as u16).to_be_bytes());
+        result.append(&mut method_infos);
+
+        result.extend_from_slice(&(attributes.len() as u16).to_be_bytes());
+        for mut attribute in attributes {
+            result.append(&mut attribute);
         }
+        println!("Generated bytecode for {} succesfully!🎉💾", self.name);
+        result
     }
-}
-struct LocalVarPool(Vec<String>);
-impl LocalVarPool {
-    pub fn add(&mut self, name: String) -> u8 {
-        // println!("Adding local var {:?}", name);
-        self.0.push(name);
-        self.0.len() as u8
-    }
-    pub fn get_index(&self, name: &str) -> u8 {
-        // +1 because the 0th local variable is `this`, which isn't captured in this structure
-        1 + self
-            .0
+
+    /// The class `class_name` followed by the classes enclosing it, from the innermost to the outermost one
+    fn enclosing_classes<'a>(&'a self, class_name: &'a str) -> Vec<&'a str> {
+        let mut result = vec![class_name];
+        while let Some(nested_class) = self
+            .nested_classes
             .iter()
-            .position(|n| n == name)
-            .map(|i| i as u8)
-            .unwrap_or_else(|| panic!("Local var {:?} not found in  {:?}", name, self.0))
+            .find(|c| c.name == *result.last().unwrap())
+        {
+            result.push(&nested_class.outer_class);
+        }
+        result
     }
-}
-#[derive(Debug)]
-pub(crate) struct CompiledMethod {
-    pub(crate) name: String,
-    pub(crate) return_type: Type,
-    pub(crate) params: Vec<(Type, String)>,
-    pub(crate) max_stack: u16,
-    pub(crate) max_locals: u16,
+
+    /// The NestHost attribute of a nested class, naming its top level class, or the NestMembers attribute
+    /// of a top level class, naming the classes nested in it. Together, they allow the classes of a nest to
+    /// access each other's private members, which is only supported by class files since Java 11.
+    /// See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.28
+    fn nest_attribute(&mut self) -> Option<Vec<u8>> {
+        if self.constant_pool.major_version() < NEST_MAJOR_VERSION {
+            return None;
+        }
+        let top_level_class = self
+            .enclosing_classes(&self.name)
+            .pop()
+            .unwrap()
+            .to_string();
+        let (name, classes) = if top_level_class != self.name {
+            ("NestHost", vec![top_level_class])
+        } else {
+            let members: Vec<String> = self
+                .nested_classes
+                .iter()
+                .filter(|c| self.enclosing_classes(&c.name).pop() == Some(&self.name))
+                .map(|c| c.name.clone())
+                .collect();
+            if members.is_empty() {
+                return None;
+            }
+            ("NestMembers", members)
+        };
+        let mut attr = vec![];
+        // NestHost names a single class, while NestMembers is a list of classes
+        if name == "NestMembers" {
+            attr.extend_from_slice(&(classes.len() as u16).to_be_bytes());
+        }
+        for class in classes {
+            attr.extend_from_slice(&self.constant_pool.add(Constant::Class(class)).to_be_bytes());
+        }
+        let mut result = vec![];
+        result.extend_from_slice(
+            &self
+                .constant_pool
+                .add(Constant::Utf8(name.to_string()))
+                .to_be_bytes(),
+        );
+        result.extend_from_slice(&(attr.len() as u32).to_be_bytes());
+        result.append(&mut attr);
+        Some(result)
+    }
+
+    /// The InnerClasses attribute, describing the nested classes the constant pool refers to, the classes
+    /// nested in the class itself and the classes enclosing them. Outer classes come before the classes
+    /// nested in them. See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.6
+    fn inner_classes_attribute(&mut self) -> Option<Vec<u8>> {
+        let referenced: Vec<&str> = self
+            .nested_classes
+            .iter()
+            .filter(|c| {
+                c.outer_class == self.name
+                    || self
+                        .constant_pool
+                        .index_of(&Constant::Class(c.name.clone()))
+                        .is_some()
+            })
+            .flat_map(|c| self.enclosing_classes(&c.name))
+            .collect();
+        // The nested classes of the program are ordered like their declarations, i.e. outer classes first
+        let described: Vec<NestedClass> = self
+            .nested_classes
+            .iter()
+            .filter(|c| referenced.contains(&c.name.as_str()))
+            .cloned()
+            .collect();
+        if described.is_empty() {
+            return None;
+        }
+        let mut attr = vec![];
+        attr.extend_from_slice(&(described.len() as u16).to_be_bytes());
+        for nested_class in described {
+            let indices = [
+                self.constant_pool.add(Constant::Class(nested_class.name)),
+                match nested_class.is_local {
+                    true => 0,
+                    false => self
+                        .constant_pool
+                        .add(Constant::Class(nested_class.outer_class)),
+                },
+                self.constant_pool
+                    .add(Constant::Utf8(nested_class.simple_name)),
+                nested_class.access_flags,
+            ];
+            for index in indices {
+                attr.extend_from_slice(&index.to_be_bytes());
+            }
+        }
+        let mut result = vec![];
+        result.extend_from_slice(
+            &self
+                .constant_pool
+                .add(Constant::Utf8("InnerClasses".to_string()))
+                .to_be_bytes(),
+        );
+        result.extend_from_slice(&(attr.len() as u32).to_be_bytes());
+        result.append(&mut attr);
+        Some(result)
+    }
+}
+
+#[derive(Debug)]
+pub struct IRFieldDecl {
+    pub(crate) type_index: u16,
+    pub(crate) access_flags: AccessFlags,
+    pub(crate) name_index: u16,
+}
+/// The access flags of classes, fields and methods, which are combined by or-ing them
+/// https://docs.oracle.com/javase/specs/jvms/se15/html/jvms-4.html#jvms-4.1-200-E.1
+#[repr(u16)]
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum AccessFlags {
+    Public = 0x0001,
+    Private = 0x0002,
+    Protected = 0x0004,
+    Static = 0x0008,
+    Final = 0x0010,
+    /// Treat methods of the superclass specially when invoked by invokespecial, set for all classes
+    Super = 0x0020,
+    Interface = 0x0200,
+    Abstract = 0x0400,
+    /// Generated by the compiler, e.g. bridge methods
+    Synthetic = 0x1000,
+    /// Set for enums and the fields of their constants
+    Enum = 0x4000,
+}
+
+impl Modifiers {
+    /// The access flags representing the modifiers of a class, field, constructor or method
+    pub(crate) fn access_flags(&self) -> u16 {
+        let mut flags = match self.visibility {
+            Visibility::Public => AccessFlags::Public as u16,
+            Visibility::Protected => AccessFlags::Protected as u16,
+            Visibility::Private => AccessFlags::Private as u16,
+            Visibility::Package => 0,
+        };
+        if self.is_static {
+            flags |= AccessFlags::Static as u16;
+        }
+        if self.is_final {
+            flags |= AccessFlags::Final as u16;
+        }
+        flags
+    }
+
+    /// The modifiers represented by the access flags of a class, field, constructor or method
+    pub(crate) fn from_access_flags(flags: u16) -> Self {
+        let visibility = if flags & AccessFlags::Public as u16 != 0 {
+            Visibility::Public
+        } else if flags & AccessFlags::Protected as u16 != 0 {
+            Visibility::Protected
+        } else if flags & AccessFlags::Private as u16 != 0 {
+            Visibility::Private
+        } else {
+            Visibility::Package
+        };
+        Modifiers {
+            visibility,
+            is_static: flags & AccessFlags::Static as u16 != 0,
+            is_final: flags & AccessFlags::Final as u16 != 0,
+        }
+    }
+}
+
+impl IRFieldDecl {
+    pub(crate) fn new(type_index: u16, name_index: u16) -> IRFieldDecl {
+        IRFieldDecl {
+            type_index,
+            access_flags: AccessFlags::Public,
+            name_index,
+        }
+    }
+}
+/// The names of the local variables of a method, starting with its parameters, with the index of
+/// their first slot. Longs and doubles take up two slots, all other variables one.
+/// The second value is the index of the next free slot, which starts at 1 unless the method is static,
+/// since the 0th local variable of non-static methods is `this`, which isn't captured in this structure
+struct LocalVarPool(Vec<(String, u8)>, u8);
+impl LocalVarPool {
+    fn new(params: &[(Type, String)], is_static: bool) -> Self {
+        let mut pool = LocalVarPool(vec![], if is_static { 0 } else { 1 });
+        for (t, name) in params {
+            pool.add(name.clone(), t);
+        }
+        pool
+    }
+    pub fn add(&mut self, name: String, t: &Type) -> u8 {
+        // println!("Adding local var {:?}", name);
+        let index = self.1;
+        self.0.push((name, index));
+        self.1 += t.size() as u8;
+        index
+    }
+    pub fn get_index(&self, name: &str) -> u8 {
+        self.0
+            .iter()
+            // Variables of loops, which ended already, may be redeclared with the same name
+            .rfind(|(n, _)| n == name)
+            .map(|(_, index)| *index)
+            .unwrap_or_else(|| panic!("Local var {:?} not found in  {:?}", name, self.0))
+    }
+    fn max_locals(&self) -> u16 {
+        self.1 as u16
+    }
+}
+/// The statements enclosing the statement being generated, which can be left by `break` or
+/// continued by `continue`, from the outermost to the innermost one. Each of them has an id,
+/// which the placeholders of the jumps to them refer to.
+#[derive(Debug, Default)]
+struct JumpTargets {
+    // The id, the label and whether it is a loop of each enclosing statement
+    enclosing: Vec<(u16, Option<String>, bool)>,
+    // The label of the loop generated next, if it is labeled
+    loop_label: Option<String>,
+    // The id and the type of the innermost switch expression, which `yield` jumps to the end of
+    yield_target: Option<(u16, Type)>,
+    // The number of enclosing statements outside of it, the id and the finally block of each
+    // enclosing try statement with a finally block, which is executed before jumping out of it
+    finally_blocks: Vec<(usize, u16, Stmt)>,
+    count: u16,
+}
+impl JumpTargets {
+    fn push(&mut self, label: Option<String>, is_loop: bool) -> u16 {
+        let id = self.new_id();
+        self.enclosing.push((id, label, is_loop));
+        id
+    }
+    /// Gets an id for jumps to a location, which isn't the start or end of an enclosing statement
+    fn new_id(&mut self) -> u16 {
+        let id = self.count;
+        self.count += 1;
+        id
+    }
+    /// Enters a loop, which is labeled if it is the statement of a labeled statement
+    fn push_loop(&mut self) -> u16 {
+        let label = self.loop_label.take();
+        self.push(label, true)
+    }
+    fn pop(&mut self) {
+        self.enclosing.pop();
+    }
+    /// Gets the id of the statement a `break` or `continue` with the given label jumps to.
+    /// Without a label this is the innermost loop, or for `break` also the innermost switch,
+    /// which is the only unlabeled statement that isn't a loop.
+    fn get_id(&self, label: &Option<String>, is_continue: bool) -> u16 {
+        self.enclosing[self.get_index(label, is_continue)].0
+    }
+    /// Like `get_id`, but gets the index of the statement in `enclosing`
+    fn get_index(&self, label: &Option<String>, is_continue: bool) -> usize {
+        self.enclosing
+            .iter()
+            .rposition(|(_, l, is_loop)| match label {
+                Some(label) => l.as_ref() == Some(label),
+                None => *is_loop || (!is_continue && l.is_none()),
+            })
+            .unwrap_or_else(|| panic!("No jump target {:?} in {:?}", label, self.enclosing))
+    }
+    /// The number of finally blocks of try statements enclosing the statement at the given index of
+    /// `enclosing`, which aren't executed when jumping to it
+    fn finally_blocks_outside(&self, index: usize) -> usize {
+        self.finally_blocks
+            .iter()
+            .take_while(|(depth, _, _)| *depth <= index)
+            .count()
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct CompiledMethod {
+    pub(crate) name: String,
+    pub(crate) access_flags: u16,
+    pub(crate) return_type: Type,
+    pub(crate) params: Vec<(Type, String)>,
+    pub(crate) max_stack: u16,
+    pub(crate) max_locals: u16,
     pub(crate) code: Vec<Instruction>,
+    pub(crate) exception_table: Vec<ExceptionHandler>,
     pub(crate) stack_map_table: StackMapTable,
+    // The names of the exception classes of the `throws` clause
+    pub(crate) throws: Vec<String>,
+}
+
+/// An entry of the exception table of a method: Exceptions thrown by the instructions from `start`
+/// up to `end` are handled by the code at `handler`, if they are instances of the class at the index
+/// `catch_type` into the constant pool. A `catch_type` of 0 handles all exceptions.
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.3
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ExceptionHandler {
+    pub(crate) start: u16,
+    pub(crate) end: u16,
+    pub(crate) handler: u16,
+    pub(crate) catch_type: u16,
 }
 
 impl CompiledMethod {
     /// Get the method info as raw bytes as described in https://docs.oracle.com/javase/specs/jvms/se15/html/jvms-4.html#jvms-4.6
     fn as_bytes(&self, constant_pool: &mut ConstantPool) -> Vec<u8> {
         let mut result = vec![];
-        // Access flags, since we don't support access flags, they are always 0
-        result.extend_from_slice(&[0, 0]);
+        result.extend_from_slice(&self.access_flags.to_be_bytes());
         // Name index
         result.extend_from_slice(
             &constant_pool
@@ -388,9 +1238,16 @@ impl CompiledMethod {
                 .to_be_bytes(),
         );
         // Attributes:
-        // For methods we only create the Code-Attribute at the moment
-        // attributes_count = 1 because we only have the Code-Attribute
-        result.extend_from_slice(&[0, 1]);
+        // For methods we only create the Code-Attribute, which abstract methods must not have,
+        // and the Exceptions-Attribute, if the method declares to throw exceptions
+        let exceptions =
+            (!self.throws.is_empty()).then(|| self.exceptions_attribute(constant_pool));
+        if self.access_flags & AccessFlags::Abstract as u16 != 0 {
+            result.extend_from_slice(&(exceptions.is_some() as u16).to_be_bytes());
+            result.extend(exceptions.into_iter().flatten());
+            return result;
+        }
+        result.extend_from_slice(&(1 + exceptions.is_some() as u16).to_be_bytes());
         // Name Index
         result.extend_from_slice(
             &constant_pool
@@ -408,7 +1265,17 @@ impl CompiledMethod {
             .for_each(|i| code_bytes.append(&mut i.as_bytes()));
         attr.extend_from_slice(&(code_bytes.len() as u32).to_be_bytes());
         attr.append(&mut code_bytes);
-        attr.extend_from_slice(&[0, 0]); // Exception table length
+        attr.extend_from_slice(&(self.exception_table.len() as u16).to_be_bytes());
+        for handler in &self.exception_table {
+            for value in [
+                handler.start,
+                handler.end,
+                handler.handler,
+                handler.catch_type,
+            ] {
+                attr.extend_from_slice(&value.to_be_bytes());
+            }
+        }
 
         // Inner Attributes (only StackMapTable)
         // First count, then the each attribute
@@ -421,21 +1288,59 @@ impl CompiledMethod {
         // Attribute length
         result.extend_from_slice(&(attr.len() as u32).to_be_bytes());
         result.append(&mut attr);
+        result.extend(exceptions.into_iter().flatten());
+        result
+    }
+
+    /// The Exceptions-Attribute, listing the classes of the exceptions the method declares to throw
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.5
+    fn exceptions_attribute(&self, constant_pool: &mut ConstantPool) -> Vec<u8> {
+        let mut result = vec![];
+        result.extend_from_slice(
+            &constant_pool
+                .add(Constant::Utf8("Exceptions".to_string()))
+                .to_be_bytes(),
+        );
+        result.extend_from_slice(&(2 + 2 * self.throws.len() as u32).to_be_bytes());
+        result.extend_from_slice(&(self.throws.len() as u16).to_be_bytes());
+        for class in &self.throws {
+            result.extend_from_slice(
+                &constant_pool
+                    .add(Constant::Class(class.clone()))
+                    .to_be_bytes(),
+            );
+        }
         result
     }
 }
 
-#[derive(PartialEq, Eq, Debug, Clone)]
+#[derive(PartialEq, Debug, Clone)]
 pub enum Constant {
     Class(String),
     /// This has to be of format `class_index.name_and_type_index`
     FieldRef(FieldRef),
     /// This has to be of format `class_index.method_name_index`. If it is later found to be beneficial however we could split this into two Strings
     MethodRef(MethodRef),
+    /// Same as `MethodRef`, but for methods declared by an interface
+    InterfaceMethodRef(MethodRef),
+    /// A handle to a method with the given kind of reference, e.g. 6 for a static method.
+    /// The method is given by a `MethodRef` or, if it is declared by an interface, an `InterfaceMethodRef`.
+    /// See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.3.5
+    MethodHandle(u8, Box<Constant>),
+    /// The descriptor of a method, e.g. `(II)I`
+    MethodType(String),
+    /// A call site of `invokedynamic`, which is given by the index of its bootstrap method and the
+    /// name and descriptor of the called method
+    InvokeDynamic(u16, NameAndType),
     NameAndType(NameAndType),
     String(String),
     Utf8(String),
     Integer(i32), // Used only for when the integer is too big to fit into a i16
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    /// The unusable second entry of a Long or Double constant
+    Unusable,
 }
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct FieldRef {
@@ -444,8 +1349,8 @@ pub struct FieldRef {
 }
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct MethodRef {
-    class: String,
-    method: NameAndType,
+    pub class: String,
+    pub method: NameAndType,
 }
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct NameAndType {
@@ -453,6 +1358,282 @@ pub struct NameAndType {
     pub r#type: String,
 }
 
+/// Loads an element of an array with elements of the given type onto the stack
+fn array_load(elem_type: &Type) -> Instruction {
+    match elem_type {
+        Type::Int => Instruction::iaload,
+        Type::Long => Instruction::laload,
+        Type::Float => Instruction::faload,
+        Type::Double => Instruction::daload,
+        Type::Bool | Type::Byte => Instruction::baload,
+        Type::Char => Instruction::caload,
+        Type::Short => Instruction::saload,
+        _ => Instruction::aaload,
+    }
+}
+
+/// Stores a value into an array with elements of the given type
+fn array_store(elem_type: &Type) -> Instruction {
+    match elem_type {
+        Type::Int => Instruction::iastore,
+        Type::Long => Instruction::lastore,
+        Type::Float => Instruction::fastore,
+        Type::Double => Instruction::dastore,
+        Type::Bool | Type::Byte => Instruction::bastore,
+        Type::Char => Instruction::castore,
+        Type::Short => Instruction::sastore,
+        _ => Instruction::aastore,
+    }
+}
+
+/// Replaces the placeholders of the `break` and `continue` statements jumping to the statement with
+/// the given id by gotos to the instructions at the given indices of `code`, which may be its end
+fn patch_jumps(code: &mut [Instruction], id: u16, break_index: usize, continue_index: usize) {
+    let location =
+        |code: &[Instruction], index: usize| get_instructions_length(&code[..index]) as i16;
+    for i in 0..code.len() {
+        let target = match code[i] {
+            Instruction::r#break(target) if target == id => break_index,
+            Instruction::r#continue(target) if target == id => continue_index,
+            _ => continue,
+        };
+        code[i] = Instruction::goto(
+            location(code, target) - location(code, i),
+            target as i16 - i as i16,
+        );
+    }
+}
+
+/// Stores a value into the local variable at the given index
+fn local_store(t: &Type, index: u8) -> Instruction {
+    match t {
+        Type::Class(_) | Type::String | Type::Array(_) => Instruction::astore(index),
+        Type::Long => Instruction::lstore(index),
+        Type::Float => Instruction::fstore(index),
+        Type::Double => Instruction::dstore(index),
+        _ => Instruction::istore(index),
+    }
+}
+
+/// Loads the value of the local variable at the given index onto the stack
+fn local_load(t: &Type, index: u8) -> Instruction {
+    match t {
+        Type::Class(_) | Type::String | Type::Array(_) => Instruction::aload(index),
+        Type::Long => Instruction::lload(index),
+        Type::Float => Instruction::fload(index),
+        Type::Double => Instruction::dload(index),
+        _ => Instruction::iload(index),
+    }
+}
+
+/// Returns a value of the given type from the method
+fn return_instruction(t: &Type) -> Instruction {
+    match t {
+        Type::Class(_) | Type::String | Type::Array(_) | Type::Null => Instruction::areturn,
+        Type::Long => Instruction::lreturn,
+        Type::Float => Instruction::freturn,
+        Type::Double => Instruction::dreturn,
+        Type::Void => Instruction::r#return,
+        _ => Instruction::ireturn,
+    }
+}
+
+/// Duplicates the value of the given type on top of the stack, inserting it beneath
+/// the given number of values below it, e.g. the object whose field is assigned.
+/// These values are references or ints, which take up one slot each.
+fn dup_value(t: &Type, beneath: u16) -> Instruction {
+    match (t.size(), beneath) {
+        (2, 0) => Instruction::dup2,
+        (2, 1) => Instruction::dup2_x1,
+        (2, _) => Instruction::dup2_x2,
+        (_, 0) => Instruction::dup,
+        (_, 1) => Instruction::dup_x1,
+        _ => Instruction::dup_x2,
+    }
+}
+
+/// Loads a numeric literal onto the stack as a value of the given type,
+/// which may be wider than the literal, e.g. `5` assigned to a long field
+fn load_number(literal: &Expr, t: &Type, constant_pool: &mut ConstantPool) -> Instruction {
+    let (int, float) = match literal {
+        Expr::Integer(i) => (*i as i64, *i as f64),
+        Expr::Char(c) => (*c as i64, *c as u32 as f64),
+        Expr::Long(l) => (*l, *l as f64),
+        Expr::Float(f) => (*f as i64, *f as f64),
+        Expr::Double(d) => (*d as i64, *d),
+        _ => panic!("Expected numeric literal, got {:?}", literal),
+    };
+    match t {
+        Type::Long => match int {
+            0 => Instruction::lconst_0,
+            1 => Instruction::lconst_1,
+            _ => Instruction::ldc2_w(constant_pool.add(Constant::Long(int))),
+        },
+        // The constants are compared by their bits, since -0.0 == 0.0
+        Type::Float => match (float as f32).to_bits() {
+            0x00000000 => Instruction::fconst_0,
+            0x3F800000 => Instruction::fconst_1,
+            0x40000000 => Instruction::fconst_2,
+            _ => load_constant(constant_pool.add(Constant::Float(float as f32))),
+        },
+        Type::Double => match float.to_bits() {
+            0x0000000000000000 => Instruction::dconst_0,
+            0x3FF0000000000000 => Instruction::dconst_1,
+            _ => Instruction::ldc2_w(constant_pool.add(Constant::Double(float))),
+        },
+        _ => {
+            let i = int as i32;
+            if i < i8::MAX as i32 && i > i8::MIN as i32 {
+                Instruction::bipush(i as i8)
+            } else if i < i16::MAX as i32 && i > i16::MIN as i32 {
+                Instruction::sipush(i as i16)
+            } else {
+                load_constant(constant_pool.add(Constant::Integer(i)))
+            }
+        }
+    }
+}
+
+/// The type the JVM computes with for values of the given type,
+/// since booleans, bytes, shorts and chars are represented by ints
+fn computational_type(t: &Type) -> Type {
+    match t {
+        Type::Long | Type::Float | Type::Double => t.clone(),
+        _ => Type::Int,
+    }
+}
+
+/// Converts a number on top of the stack from one primitive type to another.
+/// See https://docs.oracle.com/javase/specs/jls/se17/html/jls-5.html#jls-5.1.2
+fn convert_primitive(from: &Type, to: &Type) -> Vec<Instruction> {
+    let mut result = vec![];
+    match (computational_type(from), computational_type(to)) {
+        (Type::Int, Type::Long) => result.push(Instruction::i2l),
+        (Type::Int, Type::Float) => result.push(Instruction::i2f),
+        (Type::Int, Type::Double) => result.push(Instruction::i2d),
+        (Type::Long, Type::Int) => result.push(Instruction::l2i),
+        (Type::Long, Type::Float) => result.push(Instruction::l2f),
+        (Type::Long, Type::Double) => result.push(Instruction::l2d),
+        (Type::Float, Type::Int) => result.push(Instruction::f2i),
+        (Type::Float, Type::Long) => result.push(Instruction::f2l),
+        (Type::Float, Type::Double) => result.push(Instruction::f2d),
+        (Type::Double, Type::Int) => result.push(Instruction::d2i),
+        (Type::Double, Type::Long) => result.push(Instruction::d2l),
+        (Type::Double, Type::Float) => result.push(Instruction::d2f),
+        _ => {}
+    }
+    // Ints are truncated to bytes, shorts and chars, unless they are one already,
+    // e.g. a byte fits into a short
+    if from != to && !(*from == Type::Byte && *to == Type::Short) {
+        match to {
+            Type::Byte => result.push(Instruction::i2b),
+            Type::Short => result.push(Instruction::i2s),
+            Type::Char => result.push(Instruction::i2c),
+            _ => {}
+        }
+    }
+    result
+}
+
+/// The instruction applying the arithmetic, bitwise or shift operator to values of the given type,
+/// e.g. `+` to two longs. Shifts shift a value of the given type by an int.
+fn arithmetic_instruction(op: &str, t: &Type) -> Instruction {
+    match (op, computational_type(t)) {
+        ("+", Type::Long) => Instruction::ladd,
+        ("-", Type::Long) => Instruction::lsub,
+        ("*", Type::Long) => Instruction::lmul,
+        ("/", Type::Long) => Instruction::ldiv,
+        ("%", Type::Long) => Instruction::lrem,
+        ("&", Type::Long) => Instruction::land,
+        ("|", Type::Long) => Instruction::lor,
+        ("^", Type::Long) => Instruction::lxor,
+        ("<<", Type::Long) => Instruction::lshl,
+        (">>", Type::Long) => Instruction::lshr,
+        (">>>", Type::Long) => Instruction::lushr,
+        ("+", Type::Float) => Instruction::fadd,
+        ("-", Type::Float) => Instruction::fsub,
+        ("*", Type::Float) => Instruction::fmul,
+        ("/", Type::Float) => Instruction::fdiv,
+        ("%", Type::Float) => Instruction::frem,
+        ("+", Type::Double) => Instruction::dadd,
+        ("-", Type::Double) => Instruction::dsub,
+        ("*", Type::Double) => Instruction::dmul,
+        ("/", Type::Double) => Instruction::ddiv,
+        ("%", Type::Double) => Instruction::drem,
+        ("+", Type::Int) => Instruction::iadd,
+        ("-", Type::Int) => Instruction::isub,
+        ("*", Type::Int) => Instruction::imul,
+        ("/", Type::Int) => Instruction::idiv,
+        ("%", Type::Int) => Instruction::irem,
+        // Booleans are represented by 0 and 1, so `&`, `|` and `^` work for them as well
+        ("&", Type::Int) => Instruction::iand,
+        ("|", Type::Int) => Instruction::ior,
+        ("^", Type::Int) => Instruction::ixor,
+        ("<<", Type::Int) => Instruction::ishl,
+        (">>", Type::Int) => Instruction::ishr,
+        (">>>", Type::Int) => Instruction::iushr,
+        (op, t) => panic!("Operator {op} can't be applied to values of type {t}"),
+    }
+}
+
+/// Compares two values of the given type, leaving an int on the stack, whose sign is the sign of
+/// their difference. If a float or double is NaN, the comparison is false unless it checks
+/// for inequality, so the result must be positive for `<` and `<=` and negative for `>` and `>=`.
+fn compare_instruction(t: &Type, nan_is_greater: bool) -> Instruction {
+    match (computational_type(t), nan_is_greater) {
+        (Type::Long, _) => Instruction::lcmp,
+        (Type::Float, true) => Instruction::fcmpg,
+        (Type::Float, false) => Instruction::fcmpl,
+        (Type::Double, true) => Instruction::dcmpg,
+        (Type::Double, false) => Instruction::dcmpl,
+        _ => Instruction::isub,
+    }
+}
+
+/// Calls a method of the library interfaces `Iterable` and `Iterator`, which has no arguments
+fn invoke_library_interface(
+    class: &str,
+    name: &str,
+    descriptor: &str,
+    constant_pool: &mut ConstantPool,
+) -> Instruction {
+    let method_index = constant_pool.add(Constant::InterfaceMethodRef(MethodRef {
+        class: class.to_string(),
+        method: NameAndType {
+            name: name.to_string(),
+            r#type: descriptor.to_string(),
+        },
+    }));
+    Instruction::invokeinterface(method_index, 1)
+}
+
+/// Creates a one-dimensional array with elements of the given type, whose length is on the stack.
+/// See https://docs.oracle.com/javase/specs/jvms/se15/html/jvms-6.html#jvms-6.5.newarray
+fn new_array(elem_type: &Type, constant_pool: &mut ConstantPool) -> Instruction {
+    match elem_type {
+        Type::Bool => Instruction::newarray(4),
+        Type::Char => Instruction::newarray(5),
+        Type::Int => Instruction::newarray(10),
+        Type::Float => Instruction::newarray(6),
+        Type::Double => Instruction::newarray(7),
+        Type::Byte => Instruction::newarray(8),
+        Type::Short => Instruction::newarray(9),
+        Type::Long => Instruction::newarray(11),
+        _ => {
+            Instruction::anewarray(constant_pool.add(Constant::Class(elem_type.to_internal_name())))
+        }
+    }
+}
+
+/// Loads the constant at the given index of the constant pool onto the stack
+fn load_constant(index: u16) -> Instruction {
+    if index <= u8::MAX as u16 {
+        Instruction::ldc(index as u8)
+    } else {
+        Instruction::ldc_w(index)
+    }
+}
+
 fn get_instruction_length(istr: &Instruction) -> u16 {
     istr.as_bytes().len() as u16
 }
@@ -461,24 +1642,222 @@ fn get_instructions_length(instructions: &[Instruction]) -> u16 {
     instructions.iter().map(get_instruction_length).sum()
 }
 
+/// Whether the code after the given instructions may be executed next, either because
+/// the last instruction falls through or because one of the instructions jumps to its end
+fn can_complete_normally(instructions: &[Instruction]) -> bool {
+    let end = get_instructions_length(instructions) as i32;
+    let mut location = 0;
+    // Markers aren't executed, so the last instruction before them decides
+    instructions
+        .iter()
+        .rfind(|instruction| !instruction.is_marker())
+        .is_none_or(Instruction::falls_through)
+        || instructions.iter().any(|instruction| {
+            let jumps_to_end = instruction
+                .jump_offsets()
+                .iter()
+                .any(|offset| location + offset == end);
+            location += get_instruction_length(instruction) as i32;
+            jumps_to_end
+        })
+}
+
+/// Whether the typed condition of a loop is a constant expression which is true, e.g. `true` or `1 < 2`
+fn is_constant_true(cond: &Expr) -> bool {
+    matches!(constant_value(cond), Some(Expr::TypedExpr(value, _)) if *value == Expr::Bool(true))
+}
+
+/// Sets the padding of the switches in the code of a method, which aligns their tables to
+/// a multiple of 4 bytes from the start of the method. Since this moves the instructions
+/// after a switch, the offsets of all jumps are adjusted to the new locations.
+fn align_switches(code: &mut [Instruction]) {
+    let locations = |code: &[Instruction]| {
+        let mut location = 0;
+        code.iter()
+            .map(|instruction| {
+                let start = location;
+                location += get_instruction_length(instruction) as i32;
+                start
+            })
+            .chain(std::iter::once(get_instructions_length(code) as i32))
+            .collect::<Vec<_>>()
+    };
+    let old_locations = locations(code);
+    let mut location = 0;
+    for instruction in code.iter_mut() {
+        match instruction {
+            Instruction::tableswitch(padding, _, _, _)
+            | Instruction::lookupswitch(padding, _, _) => {
+                *padding = ((4 - (location + 1) % 4) % 4) as u8;
+            }
+            _ => {}
+        }
+        location += get_instruction_length(instruction) as i32;
+    }
+    let new_locations = locations(code);
+    // The index of the instruction at a location, which may also be the end of the code
+    let index_at = |location: i32| old_locations.binary_search(&location).unwrap();
+    for (i, instruction) in code.iter_mut().enumerate() {
+        let offsets: Vec<i32> = instruction
+            .jump_offsets()
+            .iter()
+            .map(|offset| new_locations[index_at(old_locations[i] + offset)] - new_locations[i])
+            .collect();
+        instruction.set_jump_offsets(&offsets);
+    }
+}
+
+/// Creates the exception table of a method from the markers of its try statements, which are
+/// removed from the code. Try statements nested in another one have greater ids, so their handlers
+/// are checked first by being earlier in the table.
+fn exception_table(code: &mut Vec<Instruction>) -> Vec<ExceptionHandler> {
+    let mut locations = Vec::with_capacity(code.len());
+    let mut location = 0;
+    for instruction in code.iter() {
+        locations.push(location);
+        location += get_instruction_length(instruction);
+    }
+    let position = |marker: Instruction| code.iter().position(|i| *i == marker);
+    let mut ids: Vec<u16> = code
+        .iter()
+        .filter_map(|instruction| match instruction {
+            Instruction::try_start(id) => Some(*id),
+            _ => None,
+        })
+        .collect();
+    ids.sort_by(|a, b| b.cmp(a));
+    let mut table = vec![];
+    for id in ids {
+        let start = position(Instruction::try_start(id)).unwrap();
+        for (i, instruction) in code.iter().enumerate() {
+            let Instruction::handler(handler_id, catch_type) = *instruction else {
+                continue;
+            };
+            if handler_id != id {
+                continue;
+            }
+            // The catch clauses only handle exceptions of the body, the finally block also
+            // the ones of the catch clauses
+            let end = if catch_type == 0 {
+                position(Instruction::try_end(id)).unwrap()
+            } else {
+                position(Instruction::try_body_end(id)).unwrap()
+            };
+            for (range_start, range_end) in handled_ranges(code, &locations, id, start, end) {
+                table.push(ExceptionHandler {
+                    start: range_start,
+                    end: range_end,
+                    handler: locations[i],
+                    catch_type,
+                });
+            }
+        }
+    }
+    code.retain(|instruction| !instruction.is_marker());
+    table
+}
+
+/// The ranges of locations between the instructions at the indices `start` and `end` of the code,
+/// which are handled by the try statement with the given id. These exclude the finally blocks
+/// of that try statement and the ones enclosing it, since they are inlined when leaving it.
+fn handled_ranges(
+    code: &[Instruction],
+    locations: &[u16],
+    id: u16,
+    start: usize,
+    end: usize,
+) -> Vec<(u16, u16)> {
+    let mut ranges = vec![];
+    let mut range_start = Some(locations[start]);
+    let mut inlined = vec![];
+    for i in start..end {
+        match code[i] {
+            Instruction::inlined_finally_start(finally_id) if finally_id <= id => {
+                if let Some(range_start) = range_start.take() {
+                    ranges.push((range_start, locations[i]));
+                }
+                inlined.push(finally_id);
+            }
+            Instruction::inlined_finally_end(finally_id) if inlined.last() == Some(&finally_id) => {
+                inlined.pop();
+                if inlined.is_empty() {
+                    range_start = Some(locations[i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(range_start) = range_start {
+        ranges.push((range_start, locations[end]));
+    }
+    ranges.retain(|(start, end)| start < end);
+    ranges
+}
+
 /// The instructions for the JVM
 /// https://docs.oracle.com/javase/specs/jvms/se7/html/jvms-6.html#jvms-6.5.areturn
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum Instruction {
     invokespecial(u16), //Calling a method from the super class (probably only used in constructor)
+    invokevirtual(u16), //Calling a method of an object, which is dispatched based on its class
+    invokeinterface(u16, u8), //Calling a method declared by an interface, with the number of arguments including the object
+    invokestatic(u16),        //Calling a static method, which doesn't need an object
+    invokedynamic(u16), //Calling the method of the call site at the given index into the constant pool, which is linked by its bootstrap method
     aload_0,
-    aload(u8),   //Load reference from local variable
-    iload(u8),   //Load int from local variable
-    ireturn,     //return int, char, boolean
-    r#return,    //return void
-    areturn,     //return object(string, integer, null)
-    bipush(i8),  //Push signed byte onto stack
-    sipush(i16), //Push signed short onto stack
-    istore(u8),  //Store int into local variable
-    astore(u8),  //Store reference into local variable
-    aconst_null, //Push null onto stack
+    aload(u8),    //Load reference from local variable
+    iload(u8),    //Load int from local variable
+    lload(u8),    //Load long from local variable
+    fload(u8),    //Load float from local variable
+    dload(u8),    //Load double from local variable
+    ireturn,      //return int, char, boolean
+    lreturn,      //return long
+    freturn,      //return float
+    dreturn,      //return double
+    r#return,     //return void
+    areturn,      //return object(string, integer, null)
+    bipush(i8),   //Push signed byte onto stack
+    sipush(i16),  //Push signed short onto stack
+    istore(u8),   //Store int into local variable
+    astore(u8),   //Store reference into local variable
+    lstore(u8),   //Store long into local variable
+    fstore(u8),   //Store float into local variable
+    dstore(u8),   //Store double into local variable
+    aconst_null,  //Push null onto stack
+    lconst_0,     //Push long 0 onto stack
+    lconst_1,     //Push long 1 onto stack
+    fconst_0,     //Push float 0 onto stack
+    fconst_1,     //Push float 1 onto stack
+    fconst_2,     //Push float 2 onto stack
+    dconst_0,     //Push double 0 onto stack
+    dconst_1,     //Push double 1 onto stack
     ldc(u8), //Push item from constant pool onto stack - For some reason only one byte for index into constant pool :shrug:
-    ineg,    //Negate int
+    ldc_w(u16), //Push item from constant pool onto stack, for when the index doesn't fit into one byte
+    ldc2_w(u16), //Push long or double from constant pool onto stack
+    ineg,       //Negate int
+    lneg,       //Negate long
+    fneg,       //Negate float
+    dneg,       //Negate double
+    iinc(u8, i8), //Increment the int in the local variable at the given index by a signed byte
+    i2c,        //Convert int to char, i.e. truncate it to its lower 16 bits
+    i2b,        //Convert int to byte, i.e. truncate it to its lower 8 bits and extend its sign
+    i2s,        //Convert int to short, i.e. truncate it to its lower 16 bits and extend its sign
+    i2l,        //Convert int to long
+    i2f,        //Convert int to float
+    i2d,        //Convert int to double
+    l2i,        //Convert long to int
+    l2f,        //Convert long to float
+    l2d,        //Convert long to double
+    f2i,        //Convert float to int
+    f2l,        //Convert float to long
+    f2d,        //Convert float to double
+    d2i,        //Convert double to int
+    d2l,        //Convert double to long
+    d2f,        //Convert double to float
+    lcmp, //Compare longs, pushing -1, 0 or 1 if the first is less than, equal to or greater than the second
+    fcmpl, //Compare floats like lcmp, pushing -1 if one of them is NaN
+    fcmpg, //Compare floats like lcmp, pushing 1 if one of them is NaN
+    dcmpl, //Compare doubles like lcmp, pushing -1 if one of them is NaN
+    dcmpg, //Compare doubles like lcmp, pushing 1 if one of them is NaN
     // @Note: All absolute jumps store first the relative offset in bytes and then in instructions
     ifeq(i16, i16), //Branch if int is 0
     iflt(i16, i16), //Branch if int is < 0
@@ -490,10 +1869,91 @@ pub(crate) enum Instruction {
     imul,           //Multiply int
     idiv,           //Divide int
     irem,           //Remainder int
+    iand,           //Bitwise and int
+    ior,            //Bitwise or int
+    ixor,           //Bitwise exclusive or int
+    ishl,           //Shift int left
+    ishr,           //Arithmetic shift int right, i.e. keeping its sign
+    iushr,          //Logical shift int right, i.e. filling it with zeros
+    ladd,           //Add long
+    lsub,           //Subtract long
+    lmul,           //Multiply long
+    ldiv,           //Divide long
+    lrem,           //Remainder long
+    land,           //Bitwise and long
+    lor,            //Bitwise or long
+    lxor,           //Bitwise exclusive or long
+    lshl,           //Shift long left by an int
+    lshr,           //Arithmetic shift long right by an int
+    lushr,          //Logical shift long right by an int
+    fadd,           //Add float
+    fsub,           //Subtract float
+    fmul,           //Multiply float
+    fdiv,           //Divide float
+    frem,           //Remainder float
+    dadd,           //Add double
+    dsub,           //Subtract double
+    dmul,           //Multiply double
+    ddiv,           //Divide double
+    drem,           //Remainder double
     putfield(u16), //Sets a value for the field at the given index. The stack must have the reference to the object to which the field belongs and on top of that the value to set the field to
     getfield(u16), // Get field from object via an index into the constant pool
+    putstatic(u16), //Sets the value of a static field, which is on top of the stack
+    getstatic(u16), //Get the value of a static field via an index into the constant pool
     new(u16),      //Create new object
     dup,           //Duplicate the top value on the stack
+    dup_x1,        //Duplicate the top value on the stack and insert it beneath the second value
+    dup_x2,        //Duplicate the top value on the stack and insert it beneath the third value
+    swap,          //Swap the top two values on the stack
+    // The dup2 instructions and pop2 work on two slots of the stack, which are either two values
+    // or one long or double
+    dup2,                    //Duplicate the top two slots on the stack
+    dup2_x1, //Duplicate the top two slots on the stack and insert them beneath the third slot
+    dup2_x2, //Duplicate the top two slots on the stack and insert them beneath the fourth slot
+    pop,     //Discard the top value on the stack
+    pop2,    //Discard the top two slots on the stack
+    newarray(u8), //Create new array of the primitive type given by its type code, e.g. 10 for int
+    anewarray(u16), //Create new array of the class at the given index into the constant pool
+    multianewarray(u16, u8), //Create new array of the array class at the given index with the given number of dimensions, whose lengths are on the stack
+    arraylength,             //Get the length of an array
+    iaload,                  //Load int from array
+    laload,                  //Load long from array
+    faload,                  //Load float from array
+    daload,                  //Load double from array
+    baload,                  //Load boolean or byte from array
+    caload,                  //Load char from array
+    saload,                  //Load short from array
+    aaload,                  //Load reference from array
+    iastore,                 //Store int into array
+    lastore,                 //Store long into array
+    fastore,                 //Store float into array
+    dastore,                 //Store double into array
+    bastore,                 //Store boolean or byte into array
+    castore,                 //Store char into array
+    sastore,                 //Store short into array
+    aastore,                 //Store reference into array
+    checkcast(u16), //Check that the reference on the stack is null or an instance of the class at the given index into the constant pool
+    if_acmpne(i16, i16), //Branch if the two references aren't equal
+    instanceof(u16), //Replace the reference on the stack with 1 if it is an instance of the class at the given index into the constant pool, otherwise 0
+    // The padding aligns the table to a multiple of 4 bytes, which is only known once the method is
+    // complete, see `align_switches`. The offsets are relative to the switch, like those of other jumps.
+    tableswitch(u8, i32, i32, Vec<i32>), //Jump to the offset of the int on the stack, with the padding, the offset for values outside of the table, the lowest value and the offsets of the values from there on
+    lookupswitch(u8, i32, Vec<(i32, i32)>), //Jump to the offset matching the int on the stack, with the padding, the offset for unmatched values and the values sorted ascendingly with their offsets
+    // Placeholders for the jumps of `break` and `continue` statements to the statement with the given id,
+    // which are replaced by a goto once the location they jump to is known
+    r#break(u16),
+    r#continue(u16),
+    athrow, //Throw the exception on top of the stack
+    // Markers of the locations of the parts of the try statement with the given id, which take up no
+    // bytes. They are replaced by the exception table of the method, see `exception_table`.
+    try_start(u16),
+    try_body_end(u16),
+    try_end(u16), // The end of the catch clauses, only marked if there is a finally block
+    handler(u16, u16), //The start of the code handling exceptions of the class at the given index into the constant pool, or of any class if it is 0
+    // The finally block of the try statement with the given id is inlined between these markers,
+    // where its handlers don't apply
+    inlined_finally_start(u16),
+    inlined_finally_end(u16),
 }
 
 impl Instruction {
@@ -502,10 +1962,28 @@ impl Instruction {
             Instruction::invokespecial(idx) => {
                 vec![183, high_byte(*idx), low_byte(*idx)]
             }
+            Instruction::invokevirtual(idx) => {
+                vec![182, high_byte(*idx), low_byte(*idx)]
+            }
+            Instruction::invokeinterface(idx, count) => {
+                vec![185, high_byte(*idx), low_byte(*idx), *count, 0]
+            }
+            Instruction::invokestatic(idx) => {
+                vec![184, high_byte(*idx), low_byte(*idx)]
+            }
+            Instruction::invokedynamic(idx) => {
+                vec![186, high_byte(*idx), low_byte(*idx), 0, 0]
+            }
             Instruction::aload_0 => vec![42],
             Instruction::aload(idx) => vec![25, *idx],
             Instruction::iload(idx) => vec![21, *idx],
+            Instruction::lload(idx) => vec![22, *idx],
+            Instruction::fload(idx) => vec![23, *idx],
+            Instruction::dload(idx) => vec![24, *idx],
             Instruction::ireturn => vec![172],
+            Instruction::lreturn => vec![173],
+            Instruction::freturn => vec![174],
+            Instruction::dreturn => vec![175],
             Instruction::r#return => vec![177],
             Instruction::areturn => vec![176],
             Instruction::bipush(byte) => vec![16, *byte as u8],
@@ -514,9 +1992,45 @@ impl Instruction {
             }
             Instruction::istore(idx) => vec![54, *idx],
             Instruction::astore(idx) => vec![58, *idx],
+            Instruction::lstore(idx) => vec![55, *idx],
+            Instruction::fstore(idx) => vec![56, *idx],
+            Instruction::dstore(idx) => vec![57, *idx],
             Instruction::aconst_null => vec![1],
+            Instruction::lconst_0 => vec![9],
+            Instruction::lconst_1 => vec![10],
+            Instruction::fconst_0 => vec![11],
+            Instruction::fconst_1 => vec![12],
+            Instruction::fconst_2 => vec![13],
+            Instruction::dconst_0 => vec![14],
+            Instruction::dconst_1 => vec![15],
             Instruction::ldc(idx) => vec![18, *idx],
+            Instruction::ldc_w(idx) => vec![19, high_byte(*idx), low_byte(*idx)],
+            Instruction::ldc2_w(idx) => vec![20, high_byte(*idx), low_byte(*idx)],
             Instruction::ineg => vec![116],
+            Instruction::lneg => vec![117],
+            Instruction::fneg => vec![118],
+            Instruction::dneg => vec![119],
+            Instruction::iinc(idx, increment) => vec![132, *idx, *increment as u8],
+            Instruction::i2l => vec![133],
+            Instruction::i2f => vec![134],
+            Instruction::i2d => vec![135],
+            Instruction::l2i => vec![136],
+            Instruction::l2f => vec![137],
+            Instruction::l2d => vec![138],
+            Instruction::f2i => vec![139],
+            Instruction::f2l => vec![140],
+            Instruction::f2d => vec![141],
+            Instruction::d2i => vec![142],
+            Instruction::d2l => vec![143],
+            Instruction::d2f => vec![144],
+            Instruction::i2b => vec![145],
+            Instruction::i2c => vec![146],
+            Instruction::i2s => vec![147],
+            Instruction::lcmp => vec![148],
+            Instruction::fcmpl => vec![149],
+            Instruction::fcmpg => vec![150],
+            Instruction::dcmpl => vec![151],
+            Instruction::dcmpg => vec![152],
             Instruction::ifeq(jmp_in_bytes, _jmp_in_inst) => {
                 vec![153, shigh_byte(*jmp_in_bytes), slow_byte(*jmp_in_bytes)]
             }
@@ -529,6 +2043,9 @@ impl Instruction {
             Instruction::iflt(jmp_in_bytes, _jmp_in_inst) => {
                 vec![155, shigh_byte(*jmp_in_bytes), slow_byte(*jmp_in_bytes)]
             }
+            Instruction::if_acmpne(jmp_in_bytes, _jmp_in_inst) => {
+                vec![166, shigh_byte(*jmp_in_bytes), slow_byte(*jmp_in_bytes)]
+            }
             Instruction::goto(jmp_in_bytes, _jmp_in_inst) => {
                 vec![167, shigh_byte(*jmp_in_bytes), slow_byte(*jmp_in_bytes)]
             }
@@ -537,81 +2054,476 @@ impl Instruction {
             Instruction::imul => vec![104],
             Instruction::idiv => vec![108],
             Instruction::irem => vec![112],
+            Instruction::iand => vec![126],
+            Instruction::ior => vec![128],
+            Instruction::ixor => vec![130],
+            Instruction::ishl => vec![120],
+            Instruction::ishr => vec![122],
+            Instruction::iushr => vec![124],
+            Instruction::ladd => vec![97],
+            Instruction::lsub => vec![101],
+            Instruction::lmul => vec![105],
+            Instruction::ldiv => vec![109],
+            Instruction::lrem => vec![113],
+            Instruction::land => vec![127],
+            Instruction::lor => vec![129],
+            Instruction::lxor => vec![131],
+            Instruction::lshl => vec![121],
+            Instruction::lshr => vec![123],
+            Instruction::lushr => vec![125],
+            Instruction::fadd => vec![98],
+            Instruction::fsub => vec![102],
+            Instruction::fmul => vec![106],
+            Instruction::fdiv => vec![110],
+            Instruction::frem => vec![114],
+            Instruction::dadd => vec![99],
+            Instruction::dsub => vec![103],
+            Instruction::dmul => vec![107],
+            Instruction::ddiv => vec![111],
+            Instruction::drem => vec![115],
             Instruction::putfield(idx) => vec![181, high_byte(*idx), low_byte(*idx)],
             Instruction::getfield(idx) => vec![180, high_byte(*idx), low_byte(*idx)],
+            Instruction::putstatic(idx) => vec![179, high_byte(*idx), low_byte(*idx)],
+            Instruction::getstatic(idx) => vec![178, high_byte(*idx), low_byte(*idx)],
             Instruction::new(idx) => vec![187, high_byte(*idx), low_byte(*idx)],
             Instruction::dup => vec![89],
+            Instruction::dup_x1 => vec![90],
+            Instruction::dup_x2 => vec![91],
+            Instruction::swap => vec![95],
+            Instruction::dup2 => vec![92],
+            Instruction::dup2_x1 => vec![93],
+            Instruction::dup2_x2 => vec![94],
+            Instruction::pop => vec![87],
+            Instruction::pop2 => vec![88],
+            Instruction::newarray(atype) => vec![188, *atype],
+            Instruction::anewarray(idx) => vec![189, high_byte(*idx), low_byte(*idx)],
+            Instruction::multianewarray(idx, dimensions) => {
+                vec![197, high_byte(*idx), low_byte(*idx), *dimensions]
+            }
+            Instruction::arraylength => vec![190],
+            Instruction::checkcast(idx) => vec![192, high_byte(*idx), low_byte(*idx)],
+            Instruction::instanceof(idx) => vec![193, high_byte(*idx), low_byte(*idx)],
+            Instruction::iaload => vec![46],
+            Instruction::laload => vec![47],
+            Instruction::faload => vec![48],
+            Instruction::daload => vec![49],
+            Instruction::baload => vec![51],
+            Instruction::caload => vec![52],
+            Instruction::saload => vec![53],
+            Instruction::aaload => vec![50],
+            Instruction::iastore => vec![79],
+            Instruction::lastore => vec![80],
+            Instruction::fastore => vec![81],
+            Instruction::dastore => vec![82],
+            Instruction::bastore => vec![84],
+            Instruction::castore => vec![85],
+            Instruction::sastore => vec![86],
+            Instruction::aastore => vec![83],
+            Instruction::tableswitch(padding, default, low, offsets) => {
+                let mut bytes = vec![170];
+                bytes.extend(std::iter::repeat_n(0, *padding as usize));
+                let high = low + offsets.len() as i32 - 1;
+                for value in [*default, *low, high].iter().chain(offsets) {
+                    bytes.extend_from_slice(&value.to_be_bytes());
+                }
+                bytes
+            }
+            Instruction::lookupswitch(padding, default, pairs) => {
+                let mut bytes = vec![171];
+                bytes.extend(std::iter::repeat_n(0, *padding as usize));
+                bytes.extend_from_slice(&default.to_be_bytes());
+                bytes.extend_from_slice(&(pairs.len() as i32).to_be_bytes());
+                for (value, offset) in pairs {
+                    bytes.extend_from_slice(&value.to_be_bytes());
+                    bytes.extend_from_slice(&offset.to_be_bytes());
+                }
+                bytes
+            }
+            // The placeholders have the length of the goto replacing them
+            Instruction::r#break(_) | Instruction::r#continue(_) => vec![167, 0, 0],
+            Instruction::athrow => vec![191],
+            marker if marker.is_marker() => vec![],
             e => panic!("Instruction {:?} not implemented or unexpected", e),
         }
     }
+
+    /// The relative offsets in bytes of the locations this instruction may jump to,
+    /// which are several for a switch and none if it isn't a jump
+    pub(crate) fn jump_offsets(&self) -> Vec<i32> {
+        match self {
+            Instruction::ifeq(jmp_in_bytes, _)
+            | Instruction::iflt(jmp_in_bytes, _)
+            | Instruction::ifge(jmp_in_bytes, _)
+            | Instruction::ifne(jmp_in_bytes, _)
+            | Instruction::if_acmpne(jmp_in_bytes, _)
+            | Instruction::goto(jmp_in_bytes, _) => vec![*jmp_in_bytes as i32],
+            Instruction::tableswitch(_, default, _, offsets) => std::iter::once(*default)
+                .chain(offsets.iter().copied())
+                .collect(),
+            Instruction::lookupswitch(_, default, pairs) => std::iter::once(*default)
+                .chain(pairs.iter().map(|(_, offset)| *offset))
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Replaces the relative offsets in bytes of the locations this instruction may jump to,
+    /// which are given in the order of `jump_offsets`
+    fn set_jump_offsets(&mut self, new_offsets: &[i32]) {
+        match self {
+            Instruction::ifeq(jmp_in_bytes, _)
+            | Instruction::iflt(jmp_in_bytes, _)
+            | Instruction::ifge(jmp_in_bytes, _)
+            | Instruction::ifne(jmp_in_bytes, _)
+            | Instruction::if_acmpne(jmp_in_bytes, _)
+            | Instruction::goto(jmp_in_bytes, _) => *jmp_in_bytes = new_offsets[0] as i16,
+            Instruction::tableswitch(_, default, _, offsets) => {
+                *default = new_offsets[0];
+                offsets.copy_from_slice(&new_offsets[1..]);
+            }
+            Instruction::lookupswitch(_, default, pairs) => {
+                *default = new_offsets[0];
+                for (pair, offset) in pairs.iter_mut().zip(&new_offsets[1..]) {
+                    pair.1 = *offset;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether the instruction only marks a location for the exception table
+    fn is_marker(&self) -> bool {
+        matches!(
+            self,
+            Instruction::try_start(_)
+                | Instruction::try_body_end(_)
+                | Instruction::try_end(_)
+                | Instruction::handler(_, _)
+                | Instruction::inlined_finally_start(_)
+                | Instruction::inlined_finally_end(_)
+        )
+    }
+
+    /// Whether the instruction after this one may be executed next
+    pub(crate) fn falls_through(&self) -> bool {
+        !matches!(
+            self,
+            Instruction::goto(_, _)
+                | Instruction::athrow
+                | Instruction::tableswitch(_, _, _, _)
+                | Instruction::lookupswitch(_, _, _)
+                | Instruction::r#break(_)
+                | Instruction::r#continue(_)
+                | Instruction::ireturn
+                | Instruction::lreturn
+                | Instruction::freturn
+                | Instruction::dreturn
+                | Instruction::areturn
+                | Instruction::r#return
+        )
+    }
 }
 
 pub fn generate_dir(ast: &Prg) -> DIR {
-    let mut dir = DIR {
-        constant_pool: ConstantPool::new(ast.get(0).unwrap().name.clone()),
-        classes: vec![],
+    generate_dir_with_classpath(ast, &Prg::new())
+}
+
+/// Like `generate_dir`, but the program may use the classes read from the classpath,
+/// whose class hierarchy has to be known to call their methods and merge their types
+pub fn generate_dir_with_classpath(ast: &Prg, classpath: &Prg) -> DIR {
+    generate_dir_with_version(ast, classpath, DEFAULT_MAJOR_VERSION)
+}
+
+/// Like `generate_dir_with_classpath`, but the class files have the given major version,
+/// so the code can use the features of newer JVMs, e.g. 61 for Java 17
+pub fn generate_dir_with_version(ast: &Prg, classpath: &Prg, major_version: u16) -> DIR {
+    let mut dir = DIR { classes: vec![] };
+    // Without nests, the classes of a nest access each other's private members through accessors
+    let ast = &if major_version < NEST_MAJOR_VERSION {
+        accessors::add_accessors(ast)
+    } else {
+        ast.clone()
     };
+    // The interfaces of the library have to be known to call their methods via invokeinterface
+    let hierarchy = ClassHierarchy::new(&[ast.as_slice(), classpath, &library_classes()].concat());
+    let nested_classes: Vec<NestedClass> = ast
+        .iter()
+        .filter_map(|class| {
+            Some(NestedClass {
+                name: class.name.clone(),
+                outer_class: class.outer_class.clone()?,
+                is_local: class.is_local,
+                simple_name: class.simple_name().to_string(),
+                access_flags: class_access_flags(class),
+            })
+        })
+        .collect();
     for class in ast {
-        let ir_class = generate_class(class, &mut dir);
+        let mut ir_class = generate_class(class, &hierarchy, major_version);
+        ir_class.nested_classes = nested_classes.clone();
         dir.classes.push(ir_class);
     }
     dir
 }
 
-fn generate_class(class: &Class, dir: &mut DIR) -> IRClass {
-    let mut ir_class = IRClass::new(class.name.clone(), vec![], vec![]);
+/// The access flags of a class including the ones, which the class file of a nested class can't have,
+/// since they only apply to it as a member of its outer class, i.e. being private, protected or static
+fn class_access_flags(class: &Class) -> u16 {
+    let mut flags = class.modifiers.access_flags();
+    if class.is_interface {
+        flags |= AccessFlags::Interface as u16 | AccessFlags::Abstract as u16;
+    } else if class.is_abstract {
+        flags |= AccessFlags::Abstract as u16;
+    }
+    if class.is_enum {
+        flags |= AccessFlags::Enum as u16;
+    }
+    // Local classes are only static, if they are interfaces or enums, even if they don't have an
+    // enclosing instance
+    if class.is_local && !class.is_interface && !class.is_enum {
+        flags &= !(AccessFlags::Static as u16);
+    }
+    flags
+}
+
+fn generate_class(class: &Class, hierarchy: &ClassHierarchy, major_version: u16) -> IRClass {
+    let mut ir_class = IRClass::new(class.name.clone(), vec![], vec![], major_version);
+    if let Some(super_class) = &class.super_class {
+        ir_class.super_name = super_class.clone();
+    }
+    ir_class.interfaces = class.interfaces.clone();
+    // The class files of nested classes are public or package private, so protected classes are public
+    let flags = class_access_flags(class);
+    let member_flags =
+        AccessFlags::Private as u16 | AccessFlags::Protected as u16 | AccessFlags::Static as u16;
+    if class.is_interface {
+        ir_class.access_flags = 0;
+    }
+    ir_class.access_flags |= flags & !member_flags;
+    if flags & AccessFlags::Protected as u16 != 0 {
+        ir_class.access_flags |= AccessFlags::Public as u16;
+    }
+    if class.is_enum {
+        ir_class.fields.append(&mut enum_fields(class));
+    }
+    if let Some(field) = class.outer_instance_field() {
+        ir_class.fields.push((field, AccessFlags::Synthetic as u16));
+    }
+    for field in class.captured_var_fields() {
+        ir_class.fields.push((field, AccessFlags::Synthetic as u16));
+    }
     for field in &class.fields {
-        ir_class.fields.push(field.clone());
+        ir_class.fields.push((field.clone(), 0));
+    }
+    // Interfaces don't have constructors
+    if class.constructors.is_empty() && !class.is_interface {
+        let constructor = make_default_constructor(class, &mut ir_class.constant_pool, hierarchy);
+        ir_class.methods.push(constructor);
+    }
+    for constructor in &class.constructors {
+        let compiled_constructor =
+            generate_constructor(constructor, class, &mut ir_class.constant_pool, hierarchy);
+        ir_class.methods.push(compiled_constructor);
+    }
+    if let Some(static_initializer) =
+        generate_static_initializer(class, &mut ir_class.constant_pool, hierarchy)
+    {
+        ir_class.methods.push(static_initializer);
+    }
+    if class.is_enum {
+        ir_class.methods.append(&mut generate_enum_methods(
+            class,
+            &mut ir_class.constant_pool,
+            hierarchy,
+        ));
     }
-    ir_class
-        .methods
-        .push(make_default_constructor(&ir_class, &mut dir.constant_pool));
     for method in &class.methods {
-        ir_class
-            .methods
-            .push(generate_method(method, &mut dir.constant_pool, &class.name));
+        let compiled_method =
+            generate_method(method, &mut ir_class.constant_pool, &class.name, hierarchy);
+        ir_class.methods.push(compiled_method);
     }
     ir_class
 }
 
-/// Generates a Vector of instructions for a given method
-fn generate_method(
-    method: &MethodDecl,
+/// The fields of the constants of an enum, followed by the array of all constants,
+/// each with the access flags, which aren't represented by its modifiers
+fn enum_fields(class: &Class) -> Vec<(FieldDecl, u16)> {
+    let enum_type = Type::Class(class.name.clone());
+    let field = |name: &str, field_type: Type, visibility: Visibility| FieldDecl {
+        modifiers: Modifiers {
+            visibility,
+            is_static: true,
+            is_final: true,
+        },
+        field_type,
+        name: name.to_string(),
+        val: None,
+    };
+    class
+        .enum_constants
+        .iter()
+        .map(|constant| {
+            (
+                field(&constant.name, enum_type.clone(), Visibility::Public),
+                AccessFlags::Enum as u16,
+            )
+        })
+        .chain(std::iter::once((
+            field(
+                ENUM_VALUES_FIELD,
+                Type::Array(Box::new(enum_type.clone())),
+                Visibility::Private,
+            ),
+            AccessFlags::Synthetic as u16,
+        )))
+        .collect()
+}
+
+/// Generates the static methods every enum implicitly declares. `values()` returns a copy of the
+/// array of its constants and `valueOf(String)` looks up the constant with the name via
+/// `Enum.valueOf(Class, String)`. Neither of them branches, so they don't need a StackMapTable.
+fn generate_enum_methods(
+    class: &Class,
     constant_pool: &mut ConstantPool,
-    class_name: &str,
-) -> CompiledMethod {
-    let mut local_var_pool = LocalVarPool(
-        method
-            .params
-            .iter()
-            .map(|(_type, name)| name.clone())
-            .collect(),
-    );
-    let mut stack = StackSize::new();
-    let mut code = generate_code_stmt(
-        method.body.clone(),
-        &mut stack,
+    hierarchy: &ClassHierarchy,
+) -> Vec<CompiledMethod> {
+    let enum_type = Type::Class(class.name.clone());
+    let array_type = Type::Array(Box::new(enum_type.clone()));
+    let values_index = constant_pool.add(Constant::FieldRef(FieldRef {
+        class: class.name.clone(),
+        field: NameAndType {
+            name: ENUM_VALUES_FIELD.to_string(),
+            r#type: array_type.to_ir_string(),
+        },
+    }));
+    let clone_index = constant_pool.add(Constant::MethodRef(MethodRef {
+        class: array_type.to_internal_name(),
+        method: NameAndType {
+            name: "clone".to_string(),
+            r#type: format!("()L{JAVA_LANG_OBJECT};"),
+        },
+    }));
+    let array_class_index = constant_pool.add(Constant::Class(array_type.to_internal_name()));
+    let class_index = constant_pool.add(Constant::Class(class.name.clone()));
+    let value_of_index = constant_pool.add(Constant::MethodRef(MethodRef {
+        class: JAVA_LANG_ENUM.to_string(),
+        method: NameAndType {
+            name: "valueOf".to_string(),
+            r#type: format!("(Ljava/lang/Class;Ljava/lang/String;)L{JAVA_LANG_ENUM};"),
+        },
+    }));
+    let access_flags = AccessFlags::Public as u16 | AccessFlags::Static as u16;
+    let mut method = |name: &str,
+                      return_type: Type,
+                      params: Vec<(Type, String)>,
+                      max_stack: u16,
+                      code: Vec<Instruction>| CompiledMethod {
+        name: name.to_string(),
+        access_flags,
+        return_type,
+        max_locals: params.len() as u16,
+        params,
+        max_stack,
+        code,
+        exception_table: vec![],
+        stack_map_table: StackMapTable::new(&[], &[], &[], constant_pool, hierarchy, None),
+        throws: vec![],
+    };
+    vec![
+        method(
+            "values",
+            array_type,
+            vec![],
+            1,
+            vec![
+                Instruction::getstatic(values_index),
+                Instruction::invokevirtual(clone_index),
+                Instruction::checkcast(array_class_index),
+                Instruction::areturn,
+            ],
+        ),
+        method(
+            "valueOf",
+            enum_type,
+            vec![(Type::String, "name".to_string())],
+            2,
+            vec![
+                load_constant(class_index),
+                Instruction::aload(0),
+                Instruction::invokestatic(value_of_index),
+                Instruction::checkcast(class_index),
+                Instruction::areturn,
+            ],
+        ),
+    ]
+}
+
+/// Generates a Vector of instructions for a given method
+fn generate_method(
+    method: &MethodDecl,
+    constant_pool: &mut ConstantPool,
+    class_name: &str,
+    hierarchy: &ClassHierarchy,
+) -> CompiledMethod {
+    let Some(body) = &method.body else {
+        return CompiledMethod {
+            name: method.name.clone(),
+            access_flags: method.modifiers.access_flags() | AccessFlags::Abstract as u16,
+            return_type: method.ret_type.clone(),
+            params: method.params.clone(),
+            max_stack: 0,
+            max_locals: 0,
+            code: vec![],
+            exception_table: vec![],
+            stack_map_table: StackMapTable::new(&[], &[], &[], constant_pool, hierarchy, None),
+            throws: method.throws.clone(),
+        };
+    };
+    let mut local_var_pool = LocalVarPool::new(&method.params, method.modifiers.is_static);
+    let mut stack = StackSize::new();
+    let mut code = generate_code_stmt(
+        body.clone(),
+        &mut stack,
         constant_pool,
         &mut local_var_pool,
+        &mut JumpTargets::default(),
         class_name,
-        true,
+        hierarchy,
     );
 
-    if code.last().unwrap_or(&Instruction::bipush(0)) != &Instruction::r#return
-        && method.ret_type == Type::Void
-    {
+    if can_complete_normally(&code) && method.ret_type == Type::Void {
         code.push(Instruction::r#return);
     }
+    align_switches(&mut code);
+    let exception_table = exception_table(&mut code);
 
-    let stack_map_table = StackMapTable::new(&code, &method.params, constant_pool);
+    let this_type = (!method.modifiers.is_static)
+        .then(|| VerificationType::OBJECT(constant_pool.index_of_this_class()));
+    let stack_map_table = StackMapTable::new(
+        &code,
+        &method.params,
+        &exception_table,
+        constant_pool,
+        hierarchy,
+        this_type,
+    );
+    // The lifted bodies of lambda expressions and accessors don't appear in the source code
+    let synthetic_flag = if method.is_lambda() || method.is_accessor() {
+        AccessFlags::Synthetic as u16
+    } else {
+        0
+    };
     CompiledMethod {
         name: method.name.clone(),
+        access_flags: method.modifiers.access_flags() | synthetic_flag,
         return_type: method.ret_type.clone(),
         params: method.params.clone(),
         max_stack: stack.max,
-        max_locals: 1 + local_var_pool.0.len() as u16,
+        max_locals: local_var_pool.max_locals(),
         code,
+        exception_table,
         stack_map_table,
+        throws: method.throws.clone(),
     }
 }
 
@@ -620,8 +2532,9 @@ fn generate_code_stmt(
     stack: &mut StackSize,
     constant_pool: &mut ConstantPool,
     local_var_pool: &mut LocalVarPool,
+    jump_targets: &mut JumpTargets,
     class_name: &str,
-    is_last_instr: bool,
+    hierarchy: &ClassHierarchy,
 ) -> Vec<Instruction> {
     let mut result = vec![];
     match stmt {
@@ -631,86 +2544,124 @@ fn generate_code_stmt(
             match stmt {
                 Stmt::Block(stmts) => {
                     for i in 0..stmts.len() {
+                        // Unreachable code would need a frame in the StackMapTable, so it isn't generated,
+                        // e.g. the `break` ending an arrow case after a `return`
+                        if !can_complete_normally(&result) {
+                            break;
+                        }
                         result.append(&mut generate_code_stmt(
                             stmts.get(i).unwrap().clone(),
                             stack,
                             constant_pool,
                             local_var_pool,
+                            jump_targets,
                             class_name,
-                            is_last_instr && i + 1 == stmts.len(),
+                            hierarchy,
                         ));
                     }
                 }
+                // The returned value is stored while the enclosing finally blocks are executed
+                Stmt::Return(expr) if !jump_targets.finally_blocks.is_empty() => {
+                    let value_type = match expr.get_type().unwrap() {
+                        Type::Null => Type::Class(JAVA_LANG_OBJECT.to_string()),
+                        t => t,
+                    };
+                    let value = (value_type != Type::Void).then(|| {
+                        result.append(&mut generate_code_expr(
+                            expr,
+                            stack,
+                            constant_pool,
+                            local_var_pool,
+                            class_name,
+                            hierarchy,
+                        ));
+                        let index = local_var_pool.add("return$value".to_string(), &value_type);
+                        result.push(local_store(&value_type, index));
+                        stack.dec(value_type.size());
+                        index
+                    });
+                    result.append(&mut generate_finally_blocks(
+                        0,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        jump_targets,
+                        class_name,
+                        hierarchy,
+                    ));
+                    if can_complete_normally(&result) {
+                        if let Some(index) = value {
+                            result.push(local_load(&value_type, index));
+                            stack.inc(value_type.size());
+                        }
+                        result.push(return_instruction(&value_type));
+                    }
+                    stack.set(0);
+                }
                 Stmt::Return(expr) => {
                     match &expr {
-                        Expr::TypedExpr(_, r#type) => match r#type {
-                            Type::Int => {
-                                result.append(&mut generate_code_expr(
-                                    expr,
-                                    stack,
-                                    constant_pool,
-                                    local_var_pool,
-                                    class_name,
-                                ));
-                                result.push(Instruction::ireturn);
-                            }
-                            Type::Void => {
-                                result.push(Instruction::r#return);
-                            }
-                            Type::String => {
-                                result.append(&mut generate_code_expr(
-                                    expr,
-                                    stack,
-                                    constant_pool,
-                                    local_var_pool,
-                                    class_name,
-                                ));
-                                result.push(Instruction::areturn);
-                            }
-                            Type::Bool => {
-                                result.append(&mut generate_code_expr(
-                                    expr,
-                                    stack,
-                                    constant_pool,
-                                    local_var_pool,
-                                    class_name,
-                                ));
-                                result.push(Instruction::ireturn);
-                            }
-                            Type::Null => {
-                                result.push(Instruction::aconst_null);
-                                result.push(Instruction::areturn);
-                            }
-                            Type::Char => {
-                                result.append(&mut generate_code_expr(
-                                    expr,
-                                    stack,
-                                    constant_pool,
-                                    local_var_pool,
-                                    class_name,
-                                ));
-                                result.push(Instruction::ireturn);
-                            }
-                            Type::Class(_) => {
-                                result.append(&mut generate_code_expr(
-                                    expr,
-                                    stack,
-                                    constant_pool,
-                                    local_var_pool,
-                                    class_name,
-                                ));
-                                result.push(Instruction::areturn);
-                            }
-                            _ => panic!("This should never happen"),
-                        },
+                        Expr::TypedExpr(_, Type::Void) => {
+                            result.push(Instruction::r#return);
+                        }
+                        Expr::TypedExpr(_, Type::Null) => {
+                            result.push(Instruction::aconst_null);
+                            result.push(Instruction::areturn);
+                        }
+                        Expr::TypedExpr(_, r#type) => {
+                            let return_instr = return_instruction(r#type);
+                            result.append(&mut generate_code_expr(
+                                expr,
+                                stack,
+                                constant_pool,
+                                local_var_pool,
+                                class_name,
+                                hierarchy,
+                            ));
+                            result.push(return_instr);
+                        }
                         _ => panic!("This should never happen"),
                     };
                     stack.set(0);
                 }
+                // The enclosing finally blocks are executed before returning
+                Stmt::ReturnVoid => {
+                    result.append(&mut generate_finally_blocks(
+                        0,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        jump_targets,
+                        class_name,
+                        hierarchy,
+                    ));
+                    if can_complete_normally(&result) {
+                        result.push(Instruction::r#return);
+                    }
+                    stack.set(0);
+                }
+                // A loop whose condition is always true is generated like a for loop without a condition
+                Stmt::While(expr, stmt) if is_constant_true(&expr) => {
+                    result.append(&mut generate_code_stmt(
+                        Stmt::TypedStmt(Box::new(Stmt::For(vec![], None, vec![], stmt)), stmt_type),
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        jump_targets,
+                        class_name,
+                        hierarchy,
+                    ));
+                }
                 Stmt::While(expr, stmt) => {
+                    let id = jump_targets.push_loop();
                     // Generate bytecode for our condition
-                    let mut cond =
-                        generate_code_expr(expr, stack, constant_pool, local_var_pool, class_name);
+                    let mut cond = generate_code_expr(
+                        expr,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    );
                     let cond_len = get_instructions_length(&cond) as i16;
                     result.append(&mut cond);
                     // Checking the condition removes one element from stack
@@ -721,8 +2672,9 @@ fn generate_code_stmt(
                         stack,
                         constant_pool,
                         local_var_pool,
+                        jump_targets,
                         class_name,
-                        is_last_instr,
+                        hierarchy,
                     );
                     let body_len = get_instructions_length(&body) as i16;
                     result.push(Instruction::ifeq(
@@ -734,11 +2686,319 @@ fn generate_code_stmt(
                         -3 - body_len - cond_len,
                         -(body.len() as i16) - 1,
                     ));
+                    jump_targets.pop();
+                    let end = result.len();
+                    patch_jumps(&mut result, id, end, 0);
+                }
+                Stmt::DoWhile(stmt, expr) => {
+                    let id = jump_targets.push_loop();
+                    // The body is executed before the condition is checked for the first time
+                    let mut body = generate_code_stmt(
+                        *stmt,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        jump_targets,
+                        class_name,
+                        hierarchy,
+                    );
+                    let mut cond = generate_code_expr(
+                        expr,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    );
+                    stack.dec(1);
+                    let jump = -(get_instructions_length(&body) as i16)
+                        - get_instructions_length(&cond) as i16;
+                    let jump_in_inst = -(body.len() as i16) - cond.len() as i16;
+                    // `continue` jumps to the condition
+                    let cond_start = body.len();
+                    result.append(&mut body);
+                    result.append(&mut cond);
+                    result.push(Instruction::ifne(jump, jump_in_inst));
+                    jump_targets.pop();
+                    let end = result.len();
+                    patch_jumps(&mut result, id, end, cond_start);
+                }
+                Stmt::For(init, expr, update, stmt) => {
+                    let id = jump_targets.push_loop();
+                    for stmt in init {
+                        result.append(&mut generate_code_stmt(
+                            stmt,
+                            stack,
+                            constant_pool,
+                            local_var_pool,
+                            jump_targets,
+                            class_name,
+                            hierarchy,
+                        ));
+                    }
+                    // Without a condition the loop can only be left by `break` or returning.
+                    // The same goes for a condition which is always true, so it isn't checked.
+                    let mut cond = match expr {
+                        Some(expr) if !is_constant_true(&expr) => {
+                            let cond = generate_code_expr(
+                                expr,
+                                stack,
+                                constant_pool,
+                                local_var_pool,
+                                class_name,
+                                hierarchy,
+                            );
+                            stack.dec(1);
+                            cond
+                        }
+                        _ => vec![],
+                    };
+                    let mut body = generate_code_stmt(
+                        *stmt,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        jump_targets,
+                        class_name,
+                        hierarchy,
+                    );
+                    // `continue` jumps to the updating statements
+                    let update_start = body.len();
+                    for stmt in update {
+                        body.append(&mut generate_code_stmt(
+                            stmt,
+                            stack,
+                            constant_pool,
+                            local_var_pool,
+                            jump_targets,
+                            class_name,
+                            hierarchy,
+                        ));
+                    }
+                    let body_len = get_instructions_length(&body) as i16;
+                    let cond_len = get_instructions_length(&cond) as i16;
+                    let mut cond_inst_len = cond.len() as i16;
+                    result.append(&mut cond);
+                    if cond_len > 0 {
+                        // If the condition is false, jump over the body and the goto
+                        result.push(Instruction::ifeq(3 + body_len + 3, body.len() as i16 + 2));
+                        cond_inst_len += 1;
+                    }
+                    let goto_back = if cond_len > 0 {
+                        -cond_len - 3 - body_len
+                    } else {
+                        -body_len
+                    };
+                    let goto_back_in_inst = -cond_inst_len - body.len() as i16;
+                    let continue_index = result.len() + update_start;
+                    result.append(&mut body);
+                    result.push(Instruction::goto(goto_back, goto_back_in_inst));
+                    jump_targets.pop();
+                    let end = result.len();
+                    patch_jumps(&mut result, id, end, continue_index);
+                }
+                Stmt::ForEach(var_type, name, expr, stmt) => {
+                    let id = jump_targets.push_loop();
+                    let expr_type = expr.get_type().unwrap();
+                    result.append(&mut generate_code_expr(
+                        expr,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    ));
+                    // The iterated array or the iterator is kept in a local variable,
+                    // whose name can't be used by the program
+                    let mut next = vec![];
+                    let mut cond = vec![];
+                    let mut step = vec![];
+                    if let Type::Array(elem_type) = &expr_type {
+                        let array = local_var_pool.add(format!("{name}$array"), &expr_type);
+                        let index = local_var_pool.add(format!("{name}$index"), &Type::Int);
+                        result.push(Instruction::astore(array));
+                        result.push(Instruction::bipush(0));
+                        result.push(Instruction::istore(index));
+                        // The loop ends once index - length >= 0
+                        cond.push(Instruction::iload(index));
+                        cond.push(Instruction::aload(array));
+                        cond.push(Instruction::arraylength);
+                        cond.push(Instruction::isub);
+                        cond.push(Instruction::ifge(0, 0));
+                        next.push(Instruction::aload(array));
+                        next.push(Instruction::iload(index));
+                        next.push(array_load(elem_type));
+                        // The element may be widened to the type of the variable, e.g. an int to a long
+                        next.append(&mut convert_primitive(elem_type, &var_type));
+                        step.push(Instruction::iload(index));
+                        step.push(Instruction::bipush(1));
+                        step.push(Instruction::iadd);
+                        step.push(Instruction::istore(index));
+                    } else {
+                        let iterator = local_var_pool.add(
+                            format!("{name}$iterator"),
+                            &Type::Class(JAVA_UTIL_ITERATOR.to_string()),
+                        );
+                        result.push(invoke_library_interface(
+                            JAVA_LANG_ITERABLE,
+                            "iterator",
+                            "()Ljava/util/Iterator;",
+                            constant_pool,
+                        ));
+                        result.push(Instruction::astore(iterator));
+                        cond.push(Instruction::aload(iterator));
+                        cond.push(invoke_library_interface(
+                            JAVA_UTIL_ITERATOR,
+                            "hasNext",
+                            "()Z",
+                            constant_pool,
+                        ));
+                        cond.push(Instruction::ifeq(0, 0));
+                        next.push(Instruction::aload(iterator));
+                        next.push(invoke_library_interface(
+                            JAVA_UTIL_ITERATOR,
+                            "next",
+                            "()Ljava/lang/Object;",
+                            constant_pool,
+                        ));
+                    }
+                    // Both loops need at most two values on the stack and leave none on it
+                    stack.inc(1);
+                    stack.dec(2);
+                    let var = local_var_pool.add(name, &var_type);
+                    next.push(local_store(&var_type, var));
+                    let mut body = generate_code_stmt(
+                        *stmt,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        jump_targets,
+                        class_name,
+                        hierarchy,
+                    );
+                    body.splice(0..0, next);
+                    // `continue` jumps to the step to the next element of an array,
+                    // which an iterator doesn't need
+                    let step_start = body.len();
+                    body.append(&mut step);
+                    // The condition jumps over the body and the goto to the end of the loop
+                    let body_len = get_instructions_length(&body) as i16;
+                    let exit = match cond.pop().unwrap() {
+                        Instruction::ifge(_, _) => {
+                            Instruction::ifge(3 + body_len + 3, body.len() as i16 + 2)
+                        }
+                        _ => Instruction::ifeq(3 + body_len + 3, body.len() as i16 + 2),
+                    };
+                    cond.push(exit);
+                    let goto_back = -(get_instructions_length(&cond) as i16) - body_len;
+                    let goto_back_in_inst = -(cond.len() as i16) - body.len() as i16;
+                    result.append(&mut cond);
+                    let continue_index = result.len() + step_start;
+                    result.append(&mut body);
+                    result.push(Instruction::goto(goto_back, goto_back_in_inst));
+                    jump_targets.pop();
+                    let end = result.len();
+                    patch_jumps(&mut result, id, end, continue_index);
+                }
+                Stmt::Break(ref label) | Stmt::Continue(ref label) => {
+                    let is_continue = matches!(stmt, Stmt::Continue(_));
+                    let index = jump_targets.get_index(label, is_continue);
+                    // The finally blocks of the try statements inside of the target are executed first
+                    result.append(&mut generate_finally_blocks(
+                        jump_targets.finally_blocks_outside(index),
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        jump_targets,
+                        class_name,
+                        hierarchy,
+                    ));
+                    if can_complete_normally(&result) {
+                        let id = jump_targets.enclosing[index].0;
+                        result.push(if is_continue {
+                            Instruction::r#continue(id)
+                        } else {
+                            Instruction::r#break(id)
+                        });
+                    }
+                }
+                Stmt::Labeled(label, stmt) => {
+                    let is_loop = matches!(
+                        &*stmt,
+                        Stmt::TypedStmt(stmt, _) if matches!(
+                            **stmt,
+                            Stmt::While(_, _)
+                                | Stmt::DoWhile(_, _)
+                                | Stmt::For(_, _, _, _)
+                                | Stmt::ForEach(_, _, _, _)
+                        )
+                    );
+                    // The label of a loop belongs to the loop, which patches the jumps to it itself
+                    let id = if is_loop {
+                        jump_targets.loop_label = Some(label);
+                        None
+                    } else {
+                        Some(jump_targets.push(Some(label), false))
+                    };
+                    result.append(&mut generate_code_stmt(
+                        *stmt,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        jump_targets,
+                        class_name,
+                        hierarchy,
+                    ));
+                    if let Some(id) = id {
+                        jump_targets.pop();
+                        let end = result.len();
+                        patch_jumps(&mut result, id, end, end);
+                    }
+                }
+                Stmt::Switch(expr, cases) => {
+                    let id = jump_targets.push(None, false);
+                    result.append(&mut generate_switch(
+                        expr,
+                        cases,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        jump_targets,
+                        class_name,
+                        hierarchy,
+                    ));
+                    jump_targets.pop();
+                    let end = result.len();
+                    patch_jumps(&mut result, id, end, end);
+                }
+                Stmt::Yield(expr) => {
+                    let value_type = expr.get_type().unwrap();
+                    let (id, switch_type) = jump_targets.yield_target.clone().unwrap();
+                    result.append(&mut generate_code_expr(
+                        expr,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    ));
+                    // The values of all cases are converted to the type of the switch expression,
+                    // e.g. an int to a long
+                    if value_type.is_numeric() && switch_type.is_numeric() {
+                        result.append(&mut convert_primitive(&value_type, &switch_type));
+                        stack.dec(value_type.size());
+                        stack.inc(switch_type.size());
+                    }
+                    // The value is left on the stack at the end of the switch expression
+                    result.push(Instruction::r#break(id));
+                    stack.dec(switch_type.size());
                 }
                 Stmt::LocalVarDecl(types, name) => {
-                    local_var_pool.add(name);
+                    local_var_pool.add(name, &types);
                     stack.inc(1);
                 }
+                // The typechecker lifts local classes out of the methods, leaving an empty block
+                Stmt::LocalClassDecl(_) => panic!("Unexpected local class declaration"),
                 Stmt::If(expr, stmt1, stmt2) => {
                     // Generate bytecode for if
                     // Evaluate the condition
@@ -748,6 +3008,7 @@ fn generate_code_stmt(
                         constant_pool,
                         local_var_pool,
                         class_name,
+                        hierarchy,
                     ));
                     stack.dec(1);
                     let mut if_body = generate_code_stmt(
@@ -755,8 +3016,9 @@ fn generate_code_stmt(
                         stack,
                         constant_pool,
                         local_var_pool,
+                        jump_targets,
                         class_name,
-                        is_last_instr,
+                        hierarchy,
                     );
                     let mut else_body = if stmt2.is_none() {
                         vec![]
@@ -766,13 +3028,14 @@ fn generate_code_stmt(
                             stack,
                             constant_pool,
                             local_var_pool,
+                            jump_targets,
                             class_name,
-                            is_last_instr,
+                            hierarchy,
                         )
                     };
 
-                    // We only want to put a goto to after the else-block if there is another instruction after this one
-                    if stmt2.is_some() && !is_last_instr {
+                    // We only want to put a goto to after the else-block if the end of the if-block can be reached
+                    if stmt2.is_some() && can_complete_normally(&if_body) {
                         // If there is an else block we need to jump over it at the end of
                         // the if block since the stack could be changed
                         if_body.push(Instruction::goto(
@@ -791,6 +3054,50 @@ fn generate_code_stmt(
                         result.append(&mut else_body);
                     }
                 }
+                Stmt::Throw(expr) => {
+                    result.append(&mut generate_code_expr(
+                        expr,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    ));
+                    result.push(Instruction::athrow);
+                    stack.dec(1);
+                }
+                Stmt::Try(body, catches, finally) => {
+                    result.append(&mut generate_try(
+                        *body,
+                        catches,
+                        finally.map(|finally| *finally),
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        jump_targets,
+                        class_name,
+                        hierarchy,
+                    ));
+                }
+                // The updated value of a variable isn't needed, so it isn't put on the stack
+                Stmt::StmtExprStmt(StmtExpr::TypedStmtExpr(update, _))
+                    if matches!(
+                        *update,
+                        StmtExpr::CompoundAssign(_, _, _)
+                            | StmtExpr::PreIncDec(_, _)
+                            | StmtExpr::PostIncDec(_, _)
+                    ) =>
+                {
+                    result.append(&mut generate_update(
+                        &update,
+                        UpdateResult::None,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    ));
+                }
                 Stmt::StmtExprStmt(stmt_expr) => {
                     result.append(&mut generate_code_stmt_expr(
                         &stmt_expr,
@@ -798,7 +3105,18 @@ fn generate_code_stmt(
                         constant_pool,
                         local_var_pool,
                         class_name,
+                        hierarchy,
                     ));
+                    // The value of the expression isn't used, so it has to be discarded
+                    if let StmtExpr::TypedStmtExpr(_, t) = stmt_expr {
+                        if t.size() == 2 {
+                            result.push(Instruction::pop2);
+                            stack.dec(2);
+                        } else if t != Type::Void {
+                            result.push(Instruction::pop);
+                            stack.dec(1);
+                        }
+                    }
                 }
                 Stmt::TypedStmt(_, _) => panic!("Expected untyped statement, got typed statement"),
             }
@@ -808,6 +3126,702 @@ fn generate_code_stmt(
     result
 }
 
+/// Generates a try statement. Its finally block is inlined after the body and each catch clause,
+/// before each jump out of the try statement and in a handler of all other exceptions, which rethrows
+/// them afterwards. The locations of its parts are marked for the exception table.
+#[allow(clippy::too_many_arguments)]
+fn generate_try(
+    body: Stmt,
+    catches: Vec<CatchClause>,
+    finally: Option<Stmt>,
+    stack: &mut StackSize,
+    constant_pool: &mut ConstantPool,
+    local_var_pool: &mut LocalVarPool,
+    jump_targets: &mut JumpTargets,
+    class_name: &str,
+    hierarchy: &ClassHierarchy,
+) -> Vec<Instruction> {
+    let mut result = vec![];
+    let id = jump_targets.new_id();
+    let end_id = jump_targets.new_id();
+    if let Some(finally) = &finally {
+        jump_targets
+            .finally_blocks
+            .push((jump_targets.enclosing.len(), id, finally.clone()));
+    }
+    // The finally blocks of the try statements enclosing this one
+    let outer_finally_blocks = jump_targets.finally_blocks.len() - finally.is_some() as usize;
+    let mut body = generate_code_stmt(
+        body,
+        stack,
+        constant_pool,
+        local_var_pool,
+        jump_targets,
+        class_name,
+        hierarchy,
+    );
+    // A body without any instructions can't throw exceptions, so the catch clauses are never executed
+    if get_instructions_length(&body) == 0 {
+        jump_targets.finally_blocks.truncate(outer_finally_blocks);
+        return match finally {
+            Some(finally) => generate_code_stmt(
+                finally,
+                stack,
+                constant_pool,
+                local_var_pool,
+                jump_targets,
+                class_name,
+                hierarchy,
+            ),
+            None => vec![],
+        };
+    }
+    result.push(Instruction::try_start(id));
+    result.append(&mut body);
+    result.push(Instruction::try_body_end(id));
+    let mut completes = can_complete_normally(&result);
+    let catch_count = catches.len();
+    for (i, catch) in catches.into_iter().enumerate() {
+        // Leave the try statement after the body or the previous catch clause, unless this clause
+        // follows them directly anyway
+        if completes {
+            let mut finally_code = generate_finally_blocks(
+                outer_finally_blocks,
+                stack,
+                constant_pool,
+                local_var_pool,
+                jump_targets,
+                class_name,
+                hierarchy,
+            );
+            if can_complete_normally(&finally_code) {
+                finally_code.push(Instruction::r#break(end_id));
+            }
+            result.append(&mut finally_code);
+        }
+        for class in &catch.classes {
+            let catch_type = constant_pool.add(Constant::Class(class.clone()));
+            result.push(Instruction::handler(id, catch_type));
+        }
+        // The caught exception is on the stack at the start of the handler
+        stack.inc(1);
+        let index = local_var_pool.add(catch.name, &Type::Class(catch.classes[0].clone()));
+        result.push(Instruction::astore(index));
+        stack.dec(1);
+        let mut catch_body = generate_code_stmt(
+            catch.body,
+            stack,
+            constant_pool,
+            local_var_pool,
+            jump_targets,
+            class_name,
+            hierarchy,
+        );
+        completes = can_complete_normally(&catch_body);
+        result.append(&mut catch_body);
+    }
+    if completes && (catch_count == 0 || finally.is_some()) {
+        result.append(&mut generate_finally_blocks(
+            outer_finally_blocks,
+            stack,
+            constant_pool,
+            local_var_pool,
+            jump_targets,
+            class_name,
+            hierarchy,
+        ));
+    }
+    jump_targets.finally_blocks.truncate(outer_finally_blocks);
+    if let Some(finally) = finally {
+        if can_complete_normally(&result) {
+            result.push(Instruction::r#break(end_id));
+        }
+        // All other exceptions are rethrown after executing the finally block
+        result.push(Instruction::try_end(id));
+        result.push(Instruction::handler(id, 0));
+        stack.inc(1);
+        let index = local_var_pool.add(
+            format!("finally${id}"),
+            &Type::Class(JAVA_LANG_THROWABLE.to_string()),
+        );
+        result.push(Instruction::astore(index));
+        stack.dec(1);
+        let mut finally_code = generate_code_stmt(
+            finally,
+            stack,
+            constant_pool,
+            local_var_pool,
+            jump_targets,
+            class_name,
+            hierarchy,
+        );
+        let completes = can_complete_normally(&finally_code);
+        result.append(&mut finally_code);
+        if completes {
+            result.push(Instruction::aload(index));
+            stack.inc(1);
+            result.push(Instruction::athrow);
+            stack.dec(1);
+        }
+    }
+    let end = result.len();
+    patch_jumps(&mut result, end_id, end, end);
+    result
+}
+
+/// Generates the finally blocks of the enclosing try statements, which are left by a jump, from the
+/// innermost to the outermost one. The first `outer` finally blocks aren't left. Each finally block
+/// is marked, since the handlers of its try statement don't apply to it.
+#[allow(clippy::too_many_arguments)]
+fn generate_finally_blocks(
+    outer: usize,
+    stack: &mut StackSize,
+    constant_pool: &mut ConstantPool,
+    local_var_pool: &mut LocalVarPool,
+    jump_targets: &mut JumpTargets,
+    class_name: &str,
+    hierarchy: &ClassHierarchy,
+) -> Vec<Instruction> {
+    let mut result = vec![];
+    for i in (outer..jump_targets.finally_blocks.len()).rev() {
+        let (depth, id, finally) = jump_targets.finally_blocks[i].clone();
+        // Jumps in the finally block can only go to the statements enclosing its try statement
+        let inner_targets = jump_targets.enclosing.split_off(depth);
+        let inner_finally_blocks = jump_targets.finally_blocks.split_off(i);
+        result.push(Instruction::inlined_finally_start(id));
+        result.append(&mut generate_code_stmt(
+            finally,
+            stack,
+            constant_pool,
+            local_var_pool,
+            jump_targets,
+            class_name,
+            hierarchy,
+        ));
+        result.push(Instruction::inlined_finally_end(id));
+        jump_targets.enclosing.extend(inner_targets);
+        jump_targets.finally_blocks.extend(inner_finally_blocks);
+        // The jump isn't executed, if the finally block jumps somewhere else
+        if !can_complete_normally(&result) {
+            break;
+        }
+    }
+    result
+}
+
+/// Generates a switch over the value of `expr`, which is an int, char or String. The `break` statements
+/// leaving the switch aren't patched, since the caller knows where they jump to.
+#[allow(clippy::too_many_arguments)]
+fn generate_switch(
+    expr: Expr,
+    cases: Vec<SwitchCase>,
+    stack: &mut StackSize,
+    constant_pool: &mut ConstantPool,
+    local_var_pool: &mut LocalVarPool,
+    jump_targets: &mut JumpTargets,
+    class_name: &str,
+    hierarchy: &ClassHierarchy,
+) -> Vec<Instruction> {
+    let is_string = expr.get_type() == Some(Type::String);
+    let mut result = generate_code_expr(
+        expr,
+        stack,
+        constant_pool,
+        local_var_pool,
+        class_name,
+        hierarchy,
+    );
+    // The switch removes the value from the stack
+    stack.dec(1);
+    let default = cases.iter().position(|case| case.is_default);
+    let mut labels = vec![];
+    let mut bodies = vec![];
+    for (i, case) in cases.into_iter().enumerate() {
+        labels.extend(case.labels.into_iter().map(|label| (label, i)));
+        bodies.push(generate_code_stmt(
+            Stmt::TypedStmt(Box::new(Stmt::Block(case.body)), Type::Void),
+            stack,
+            constant_pool,
+            local_var_pool,
+            jump_targets,
+            class_name,
+            hierarchy,
+        ));
+    }
+    let labels = labels.into_iter().map(|(label, i)| match label {
+        Expr::TypedExpr(label, _) => (*label, i),
+        label => (label, i),
+    });
+    if !is_string {
+        let values = labels
+            .map(|(label, i)| match label {
+                Expr::Integer(value) => (value, i),
+                Expr::Char(c) => (c as i32, i),
+                label => panic!(
+                    "Expected int or char constant as case label, got {:?}",
+                    label
+                ),
+            })
+            .collect();
+        result.append(&mut switch_to_blocks(values, default, bodies));
+        return result;
+    }
+    // Like javac, the index of the label equal to the string is determined first by switching over
+    // its hash code, which is then used to switch to the statements of the label
+    let strings: Vec<(String, usize)> = labels
+        .map(|(label, i)| match label {
+            Expr::String(s) => (s, i),
+            label => panic!("Expected String constant as case label, got {:?}", label),
+        })
+        .collect();
+    let string = local_var_pool.add("switch$string".to_string(), &Type::String);
+    let index = local_var_pool.add("switch$index".to_string(), &Type::Int);
+    result.push(Instruction::astore(string));
+    result.push(Instruction::bipush(-1));
+    result.push(Instruction::istore(index));
+    result.push(Instruction::aload(string));
+    result.push(Instruction::invokevirtual(constant_pool.add(
+        Constant::MethodRef(MethodRef {
+            class: "java/lang/String".to_string(),
+            method: NameAndType {
+                name: "hashCode".to_string(),
+                r#type: "()I".to_string(),
+            },
+        }),
+    )));
+    let equals = constant_pool.add(Constant::MethodRef(MethodRef {
+        class: "java/lang/String".to_string(),
+        method: NameAndType {
+            name: "equals".to_string(),
+            r#type: "(Ljava/lang/Object;)Z".to_string(),
+        },
+    }));
+    // Strings with the same hash code are compared in the same bucket
+    let mut hashes: Vec<i32> = strings.iter().map(|(s, _)| string_hash_code(s)).collect();
+    hashes.sort();
+    hashes.dedup();
+    let found = jump_targets.new_id();
+    let mut buckets = vec![vec![]; hashes.len()];
+    for (k, (s, _)) in strings.iter().enumerate() {
+        let bucket = &mut buckets[hashes.binary_search(&string_hash_code(s)).unwrap()];
+        bucket.push(Instruction::aload(string));
+        bucket.push(load_constant(
+            constant_pool.add(Constant::String(s.clone())),
+        ));
+        bucket.push(Instruction::invokevirtual(equals));
+        let store = vec![
+            if k <= i8::MAX as usize {
+                Instruction::bipush(k as i8)
+            } else {
+                Instruction::sipush(k as i16)
+            },
+            Instruction::istore(index),
+            Instruction::r#break(found),
+        ];
+        // If the strings differ, jump over storing the index
+        bucket.push(Instruction::ifeq(
+            3 + get_instructions_length(&store) as i16,
+            store.len() as i16 + 1,
+        ));
+        bucket.extend(store);
+    }
+    for bucket in &mut buckets {
+        bucket.push(Instruction::r#break(found));
+    }
+    stack.inc(2);
+    stack.dec(2);
+    let hash_values = hashes
+        .iter()
+        .enumerate()
+        .map(|(i, hash)| (*hash, i))
+        .collect();
+    result.append(&mut switch_to_blocks(hash_values, None, buckets));
+    let found_index = result.len();
+    result.push(Instruction::iload(index));
+    let indices = strings
+        .iter()
+        .enumerate()
+        .map(|(k, (_, i))| (k as i32, *i))
+        .collect();
+    result.append(&mut switch_to_blocks(indices, default, bodies));
+    patch_jumps(&mut result, found, found_index, found_index);
+    result
+}
+
+/// Generates a switch followed by the given blocks of instructions, which jumps to the block of
+/// the value on the stack, given by its index in `values`. Other values jump to the default block
+/// or, if there is none, to the end of the blocks.
+fn switch_to_blocks(
+    values: Vec<(i32, usize)>,
+    default: Option<usize>,
+    blocks: Vec<Vec<Instruction>>,
+) -> Vec<Instruction> {
+    // The length of a switch doesn't depend on its offsets
+    let switch_len = get_instruction_length(&switch_instruction(
+        0,
+        values.iter().map(|(v, _)| (*v, 0)).collect(),
+    )) as i32;
+    let mut starts = vec![];
+    let mut location = switch_len;
+    for block in &blocks {
+        starts.push(location);
+        location += get_instructions_length(block) as i32;
+    }
+    let default = default.map_or(location, |i| starts[i]);
+    let mut result = vec![switch_instruction(
+        default,
+        values.into_iter().map(|(v, i)| (v, starts[i])).collect(),
+    )];
+    result.extend(blocks.into_iter().flatten());
+    result
+}
+
+/// Creates a switch jumping to the offsets of the values or to the default offset for other values.
+/// Like javac, a tableswitch is used unless it would be much larger than a lookupswitch,
+/// which is slower since it has to search for the value.
+fn switch_instruction(default: i32, mut values: Vec<(i32, i32)>) -> Instruction {
+    values.sort();
+    let (Some((low, _)), Some((high, _))) = (values.first(), values.last()) else {
+        return Instruction::lookupswitch(0, default, values);
+    };
+    let (low, high) = (*low, *high);
+    let table_space = 4 + (high as i64 - low as i64 + 1);
+    let table_time = 3;
+    let lookup_space = 3 + 2 * values.len() as i64;
+    let lookup_time = values.len() as i64;
+    if table_space + 3 * table_time <= lookup_space + 3 * lookup_time {
+        let mut offsets = vec![default; (high - low + 1) as usize];
+        for (value, offset) in values {
+            offsets[(value - low) as usize] = offset;
+        }
+        Instruction::tableswitch(0, default, low, offsets)
+    } else {
+        Instruction::lookupswitch(0, default, values)
+    }
+}
+
+/// Computes the hash code of a string like `String.hashCode`, which is used to switch over strings
+fn string_hash_code(s: &str) -> i32 {
+    s.encode_utf16()
+        .fold(0i32, |hash, c| hash.wrapping_mul(31).wrapping_add(c as i32))
+}
+
+/// The number of slots the typed arguments of a method take up on the stack
+fn args_size(args: &[Expr]) -> u16 {
+    args.iter()
+        .map(|arg| arg.get_type().expect("Expected typed argument").size())
+        .sum()
+}
+
+/// Generates the descriptor of a method taking arguments of the type of `args`,
+/// e.g. `(ILjava/lang/String;)V`
+fn method_descriptor(args: &[Expr], return_type: &Type) -> String {
+    format!(
+        "({}){}",
+        args.iter()
+            .map(|arg| arg
+                .get_type()
+                .expect("Expected typed argument")
+                .to_ir_string())
+            .collect::<String>(),
+        return_type.to_ir_string()
+    )
+}
+
+/// A part of a string concatenation, which is either a string known at compile time, e.g. of the
+/// literals in `"x = " + 1`, or a typed expression, whose value is converted to a string at runtime
+enum ConcatPart {
+    Constant(String),
+    Value(Expr),
+    // A value of the given type which is already on the stack, e.g. of the variable `s` of `s += 1`.
+    // It can only be the first part.
+    Loaded(Type),
+}
+
+/// Appends the operands of the typed string concatenation `expr` to `parts` from left to right.
+/// Nested concatenations are flattened and adjacent literals are folded into one constant,
+/// e.g. `"a" + 1 + x` consists of the constant "a1" and the value of `x`, while `1 + 2 + "a"`
+/// consists of the value of `1 + 2` and "a". Floats and doubles are converted at runtime.
+fn concat_parts(expr: Expr, parts: &mut Vec<ConcatPart>) {
+    let Expr::TypedExpr(inner, t) = expr else {
+        panic!("Expected typed expr, got {:?}", expr);
+    };
+    let constant = match (*inner, t) {
+        (Expr::Binary(op, left, right), Type::String) if op == "+" => {
+            concat_parts(*left, parts);
+            concat_parts(*right, parts);
+            return;
+        }
+        (Expr::String(s), _) => s,
+        (Expr::Integer(i), Type::Int) => i.to_string(),
+        (Expr::Long(l), Type::Long) => l.to_string(),
+        (Expr::Char(c), Type::Char) => c.to_string(),
+        (Expr::Bool(b), _) => b.to_string(),
+        (Expr::Jnull, _) => "null".to_string(),
+        (inner, t) => {
+            parts.push(ConcatPart::Value(Expr::TypedExpr(Box::new(inner), t)));
+            return;
+        }
+    };
+    match parts.last_mut() {
+        Some(ConcatPart::Constant(s)) => s.push_str(&constant),
+        _ => parts.push(ConcatPart::Constant(constant)),
+    }
+}
+
+/// Generates the descriptor of a method with the given parameter and return types
+fn types_descriptor(params: &[Type], return_type: &Type) -> String {
+    format!(
+        "({}){}",
+        params.iter().map(|t| t.to_ir_string()).collect::<String>(),
+        return_type.to_ir_string()
+    )
+}
+
+/// Generates the creation of an instance of a functional interface of type `interface`, i.e. a lambda
+/// expression or method reference, via `invokedynamic`. The call site is bootstrapped by
+/// `LambdaMetafactory.metafactory` with a method handle of the implementing method and returns
+/// a new instance for the captured values on the stack.
+/// See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-6.html#jvms-6.5.invokedynamic
+fn generate_function_ref(
+    function_ref: FunctionRef,
+    interface: &Type,
+    stack: &mut StackSize,
+    constant_pool: &mut ConstantPool,
+    local_var_pool: &mut LocalVarPool,
+    class_name: &str,
+    hierarchy: &ClassHierarchy,
+) -> Vec<Instruction> {
+    let mut result = vec![];
+    for value in &function_ref.captured {
+        result.append(&mut generate_code_expr(
+            value.clone(),
+            stack,
+            constant_pool,
+            local_var_pool,
+            class_name,
+            hierarchy,
+        ));
+    }
+    let is_interface = hierarchy.is_interface(&function_ref.class);
+    let kind = match function_ref.kind {
+        MethodKind::Static => REF_INVOKE_STATIC,
+        MethodKind::Virtual if is_interface => REF_INVOKE_INTERFACE,
+        MethodKind::Virtual => REF_INVOKE_VIRTUAL,
+        MethodKind::Private => REF_INVOKE_SPECIAL,
+        MethodKind::Constructor => REF_NEW_INVOKE_SPECIAL,
+    };
+    let impl_method = MethodRef {
+        class: function_ref.class,
+        method: NameAndType {
+            name: function_ref.impl_method,
+            r#type: types_descriptor(&function_ref.impl_params, &function_ref.impl_ret_type),
+        },
+    };
+    let impl_method = if is_interface {
+        Constant::InterfaceMethodRef(impl_method)
+    } else {
+        Constant::MethodRef(impl_method)
+    };
+    let method_type = types_descriptor(&function_ref.params, &function_ref.ret_type);
+    let bootstrap_method = constant_pool.add_bootstrap_method(
+        Constant::MethodHandle(
+            REF_INVOKE_STATIC,
+            Box::new(Constant::MethodRef(MethodRef {
+                class: LAMBDA_METAFACTORY.to_string(),
+                method: NameAndType {
+                    name: METAFACTORY.to_string(),
+                    r#type: METAFACTORY_TYPE.to_string(),
+                },
+            })),
+        ),
+        vec![
+            Constant::MethodType(method_type.clone()),
+            Constant::MethodHandle(kind, Box::new(impl_method)),
+            Constant::MethodType(method_type),
+        ],
+    );
+    // The call site takes the captured values and returns the instance of the functional interface
+    let call_site = constant_pool.add(Constant::InvokeDynamic(
+        bootstrap_method,
+        NameAndType {
+            name: function_ref.method,
+            r#type: method_descriptor(&function_ref.captured, interface),
+        },
+    ));
+    result.push(Instruction::invokedynamic(call_site));
+    stack.dec(args_size(&function_ref.captured));
+    stack.inc(1);
+    result
+}
+
+/// The descriptor of the type a value of the given type is converted to a string as,
+/// e.g. a byte as an int and an object via `String.valueOf(Object)`
+fn string_conversion_descriptor(t: &Type) -> String {
+    match t {
+        Type::Byte | Type::Short => Type::Int.to_ir_string(),
+        Type::Int
+        | Type::Char
+        | Type::Bool
+        | Type::Long
+        | Type::Float
+        | Type::Double
+        | Type::String => t.to_ir_string(),
+        _ => format!("L{JAVA_LANG_OBJECT};"),
+    }
+}
+
+/// Generates the concatenation of the parts of a string concatenation into a new string.
+/// Like javac, class files of Java 9 and newer call `StringConcatFactory.makeConcatWithConstants`
+/// via `invokedynamic` with a recipe containing the constants, in which `\u{1}` stands for a value.
+/// Older class files append the parts to a `StringBuilder`.
+fn generate_string_concat(
+    parts: Vec<ConcatPart>,
+    stack: &mut StackSize,
+    constant_pool: &mut ConstantPool,
+    local_var_pool: &mut LocalVarPool,
+    class_name: &str,
+    hierarchy: &ClassHierarchy,
+) -> Vec<Instruction> {
+    let mut result = vec![];
+    // The concatenation of literals is a constant itself
+    if let [ConcatPart::Constant(s)] = &parts[..] {
+        result.push(load_constant(
+            constant_pool.add(Constant::String(s.clone())),
+        ));
+        stack.inc(1);
+        return result;
+    }
+    // Constants containing the tags of the recipe are passed as values instead
+    let parts: Vec<ConcatPart> = parts
+        .into_iter()
+        .map(|part| match part {
+            ConcatPart::Constant(s) if s.contains(['\u{1}', '\u{2}']) => {
+                ConcatPart::Value(Expr::TypedExpr(Box::new(Expr::String(s)), Type::String))
+            }
+            part => part,
+        })
+        .collect();
+    let values_size: u16 = parts
+        .iter()
+        .map(|part| match part {
+            ConcatPart::Constant(_) => 0,
+            ConcatPart::Value(expr) => expr.get_type().unwrap().size(),
+            ConcatPart::Loaded(t) => t.size(),
+        })
+        .sum();
+    if constant_pool.major_version() >= STRING_CONCAT_FACTORY_MAJOR_VERSION
+        && values_size <= MAX_CONCAT_SLOTS
+    {
+        let mut recipe = String::new();
+        let mut descriptors = String::new();
+        for part in parts {
+            match part {
+                ConcatPart::Constant(s) => recipe.push_str(&s),
+                ConcatPart::Loaded(t) => {
+                    recipe.push('\u{1}');
+                    descriptors.push_str(&string_conversion_descriptor(&t));
+                }
+                ConcatPart::Value(expr) => {
+                    recipe.push('\u{1}');
+                    descriptors.push_str(&string_conversion_descriptor(&expr.get_type().unwrap()));
+                    result.append(&mut generate_code_expr(
+                        expr,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    ));
+                }
+            }
+        }
+        let bootstrap_method = constant_pool.add_bootstrap_method(
+            Constant::MethodHandle(
+                REF_INVOKE_STATIC,
+                Box::new(Constant::MethodRef(MethodRef {
+                    class: STRING_CONCAT_FACTORY.to_string(),
+                    method: NameAndType {
+                        name: MAKE_CONCAT_WITH_CONSTANTS.to_string(),
+                        r#type: MAKE_CONCAT_WITH_CONSTANTS_TYPE.to_string(),
+                    },
+                })),
+            ),
+            vec![Constant::String(recipe)],
+        );
+        let call_site = constant_pool.add(Constant::InvokeDynamic(
+            bootstrap_method,
+            NameAndType {
+                name: MAKE_CONCAT_WITH_CONSTANTS.to_string(),
+                r#type: format!("({descriptors}){}", Type::String.to_ir_string()),
+            },
+        ));
+        result.push(Instruction::invokedynamic(call_site));
+        stack.dec(values_size);
+        stack.inc(1);
+        return result;
+    }
+    let string_builder_method = |name: &str, r#type: String, constant_pool: &mut ConstantPool| {
+        constant_pool.add(Constant::MethodRef(MethodRef {
+            class: JAVA_LANG_STRING_BUILDER.to_string(),
+            method: NameAndType {
+                name: name.to_string(),
+                r#type,
+            },
+        }))
+    };
+    result.push(Instruction::new(
+        constant_pool.add(Constant::Class(JAVA_LANG_STRING_BUILDER.to_string())),
+    ));
+    result.push(Instruction::dup);
+    stack.inc(2);
+    result.push(Instruction::invokespecial(string_builder_method(
+        OBJECT_INIT_METHOD,
+        OBJECT_INIT_RET.to_string(),
+        constant_pool,
+    )));
+    stack.dec(1);
+    for part in parts {
+        let (t, mut code) = match part {
+            ConcatPart::Constant(s) => {
+                stack.inc(1);
+                let index = constant_pool.add(Constant::String(s));
+                (Type::String, vec![load_constant(index)])
+            }
+            ConcatPart::Value(expr) => (
+                expr.get_type().unwrap(),
+                generate_code_expr(
+                    expr,
+                    stack,
+                    constant_pool,
+                    local_var_pool,
+                    class_name,
+                    hierarchy,
+                ),
+            ),
+            // The value is appended to the StringBuilder created on top of it
+            ConcatPart::Loaded(t) => (t, vec![Instruction::swap]),
+        };
+        result.append(&mut code);
+        // append returns the StringBuilder itself, so it stays on the stack
+        result.push(Instruction::invokevirtual(string_builder_method(
+            "append",
+            format!(
+                "({})L{JAVA_LANG_STRING_BUILDER};",
+                string_conversion_descriptor(&t)
+            ),
+            constant_pool,
+        )));
+        stack.dec(t.size());
+    }
+    result.push(Instruction::invokevirtual(string_builder_method(
+        "toString",
+        format!("(){}", Type::String.to_ir_string()),
+        constant_pool,
+    )));
+    result
+}
+
 fn generate_code_stmt_expr(
     stmt_expr: &StmtExpr,
     stack: &mut StackSize,
@@ -815,6 +3829,7 @@ fn generate_code_stmt_expr(
     constant_pool: &mut ConstantPool,
     local_var_pool: &mut LocalVarPool,
     class_name: &str,
+    hierarchy: &ClassHierarchy,
 ) -> Vec<Instruction> {
     let mut result = vec![];
     match stmt_expr {
@@ -822,27 +3837,23 @@ fn generate_code_stmt_expr(
             match new_stmt_expr.deref() {
                 StmtExpr::Assign(var, expr) => {
                     // Generate bytecode for assignment
-                    let mut expr_code = generate_code_expr(
-                        expr.clone(),
-                        stack,
-                        constant_pool,
-                        local_var_pool,
-                        class_name,
-                    );
+                    // The assigned value is left on the stack, since assignments are expressions
                     match var {
                         Expr::TypedExpr(var, t) => match var.deref() {
                             Expr::LocalVar(name) => {
                                 let idx = local_var_pool.get_index(name);
-                                result.append(&mut expr_code);
-                                if let Type::Class(n) = t {
-                                    assert_eq!(n, name);
-                                    result.push(Instruction::astore(idx));
-                                } else if let Type::String = t {
-                                    result.push(Instruction::astore(idx));
-                                } else {
-                                    result.push(Instruction::istore(idx));
-                                }
-                                stack.dec(1);
+                                result.append(&mut generate_code_expr(
+                                    expr.clone(),
+                                    stack,
+                                    constant_pool,
+                                    local_var_pool,
+                                    class_name,
+                                    hierarchy,
+                                ));
+                                result.push(dup_value(t, 0));
+                                stack.inc(t.size());
+                                result.push(local_store(t, idx));
+                                stack.dec(t.size());
                             }
                             Expr::FieldVar(name) => {
                                 let idx = constant_pool.add(Constant::FieldRef(FieldRef {
@@ -854,107 +3865,415 @@ fn generate_code_stmt_expr(
                                 }));
                                 result.push(Instruction::aload_0);
                                 stack.inc(1);
-                                result.append(&mut expr_code);
+                                result.append(&mut generate_code_expr(
+                                    expr.clone(),
+                                    stack,
+                                    constant_pool,
+                                    local_var_pool,
+                                    class_name,
+                                    hierarchy,
+                                ));
+                                result.push(dup_value(t, 1));
+                                stack.inc(t.size());
                                 result.push(Instruction::putfield(idx));
-                                stack.dec(2);
-                                result.push(Instruction::aload_0);
-                                result.push(Instruction::getfield(idx));
-                                stack.inc(1);
+                                stack.dec(1 + t.size());
                             }
-                            Expr::InstVar(expr, name) => {
+                            Expr::ArrayAccess(array, index) => {
+                                for expr in [array.deref(), index.deref(), expr] {
+                                    result.append(&mut generate_code_expr(
+                                        expr.clone(),
+                                        stack,
+                                        constant_pool,
+                                        local_var_pool,
+                                        class_name,
+                                        hierarchy,
+                                    ));
+                                }
+                                result.push(dup_value(t, 2));
+                                stack.inc(t.size());
+                                result.push(array_store(t));
+                                stack.dec(2 + t.size());
+                            }
+                            Expr::InstVar(obj, name) => {
                                 let idx = constant_pool.add(Constant::FieldRef(FieldRef {
-                                    class: class_name.to_string(),
+                                    class: obj.get_type().unwrap().to_internal_name(),
                                     field: NameAndType {
                                         name: name.to_string(),
                                         r#type: t.to_ir_string(),
                                     },
                                 }));
-                                result.append(&mut generate_code_expr(
-                                    expr.deref().clone(),
-                                    stack,
-                                    constant_pool,
-                                    local_var_pool,
-                                    class_name,
-                                ));
-                                result.append(&mut expr_code);
-                                result.push(Instruction::putfield(idx));
-                                stack.dec(2);
+                                if is_class_name(obj) {
+                                    result.append(&mut generate_code_expr(
+                                        expr.clone(),
+                                        stack,
+                                        constant_pool,
+                                        local_var_pool,
+                                        class_name,
+                                        hierarchy,
+                                    ));
+                                    result.push(dup_value(t, 0));
+                                    stack.inc(t.size());
+                                    result.push(Instruction::putstatic(idx));
+                                    stack.dec(t.size());
+                                } else {
+                                    result.append(&mut generate_code_expr(
+                                        obj.deref().clone(),
+                                        stack,
+                                        constant_pool,
+                                        local_var_pool,
+                                        class_name,
+                                        hierarchy,
+                                    ));
+                                    result.append(&mut generate_code_expr(
+                                        expr.clone(),
+                                        stack,
+                                        constant_pool,
+                                        local_var_pool,
+                                        class_name,
+                                        hierarchy,
+                                    ));
+                                    result.push(dup_value(t, 1));
+                                    stack.inc(t.size());
+                                    result.push(Instruction::putfield(idx));
+                                    stack.dec(1 + t.size());
+                                }
                             }
                             _ => panic!("Unexpected variable type for assignment: {:?}", var),
                         },
                         _ => panic!("Expected typed stmt"),
                     }
                 }
+                update @ StmtExpr::PostIncDec(_, _) => {
+                    result.append(&mut generate_update(
+                        update,
+                        UpdateResult::Old,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    ));
+                }
+                update @ (StmtExpr::CompoundAssign(_, _, _) | StmtExpr::PreIncDec(_, _)) => {
+                    result.append(&mut generate_update(
+                        update,
+                        UpdateResult::New,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    ));
+                }
                 StmtExpr::New(types, exprs) => {
                     // Generate bytecode for new
-                    let class_index = constant_pool.add(Constant::Class(types.to_ir_string()));
+                    let class_index = constant_pool.add(Constant::Class(types.to_internal_name()));
                     let method_index = constant_pool.add(Constant::MethodRef(MethodRef {
-                        class: types.to_ir_string(),
+                        class: types.to_internal_name(),
                         method: NameAndType {
-                            name: "<init>".to_string(),
-                            r#type: "()V".to_string(),
+                            name: OBJECT_INIT_METHOD.to_string(),
+                            r#type: method_descriptor(exprs, &Type::Void),
                         },
                     }));
                     result.push(Instruction::new(class_index));
                     result.push(Instruction::dup);
+                    stack.inc(2);
+                    for expr in exprs {
+                        result.append(&mut generate_code_expr(
+                            expr.clone(),
+                            stack,
+                            constant_pool,
+                            local_var_pool,
+                            class_name,
+                            hierarchy,
+                        ));
+                    }
+                    result.push(Instruction::invokespecial(method_index));
+                    stack.dec(args_size(exprs) + 1);
+                }
+                // The enclosing instance is passed to the constructor of an inner class first
+                StmtExpr::QualifiedNew(outer, t, exprs) => {
+                    let args = std::iter::once(outer.clone())
+                        .chain(exprs.iter().cloned())
+                        .collect();
+                    result.append(&mut generate_code_stmt_expr(
+                        &StmtExpr::TypedStmtExpr(
+                            Box::new(StmtExpr::New(t.clone(), args)),
+                            expr_type.clone(),
+                        ),
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    ));
+                }
+                StmtExpr::ThisCall(exprs) => {
+                    let method_index = constant_pool.add(Constant::MethodRef(MethodRef {
+                        class: class_name.to_string(),
+                        method: NameAndType {
+                            name: OBJECT_INIT_METHOD.to_string(),
+                            r#type: method_descriptor(exprs, &Type::Void),
+                        },
+                    }));
+                    result.push(Instruction::aload_0);
                     stack.inc(1);
+                    for expr in exprs {
+                        result.append(&mut generate_code_expr(
+                            expr.clone(),
+                            stack,
+                            constant_pool,
+                            local_var_pool,
+                            class_name,
+                            hierarchy,
+                        ));
+                    }
                     result.push(Instruction::invokespecial(method_index));
-                    stack.dec(1);
+                    stack.dec(args_size(exprs) + 1);
                 }
-                StmtExpr::MethodCall(_expr, name, args) => {
+                StmtExpr::MethodCall(expr, name, args) => {
                     // Generate bytecode for method call
                     // Principally this should work this way:
                     // 1. Write Function Name into Constant Pool generating the necessary Constants
-                    // 2. Push all arguments onto the stack
-                    // 3. Call invokespecial on the given back function index
-                    result.push(Instruction::aload_0);
-                    result.append(
-                        &mut args
-                            .iter()
-                            .flat_map(|arg| {
-                                generate_code_expr(
-                                    arg.clone(),
-                                    stack,
-                                    constant_pool,
-                                    local_var_pool,
-                                    class_name,
-                                )
-                            })
-                            .collect(),
-                    );
-                    fn generate_name_and_type(return_type: &Type, args: &[Expr]) -> String {
-                        // Argument types comma seperated
-                        let argument_types = args
-                            .iter()
-                            .map(|arg| {
-                                arg.get_type()
-                                    .expect("Expected typed argument")
-                                    .to_ir_string()
-                                    + ","
-                            })
-                            .collect::<String>();
-                        let argument_types = if argument_types.is_empty() {
-                            &argument_types
-                        } else {
-                            &argument_types[..argument_types.len() - 1]
-                        };
-                        format!("({}){}", argument_types, return_type.to_ir_string())
+                    // 2. Push the object the method is called on and all arguments onto the stack
+                    // 3. Call invokevirtual on the given back function index
+                    // Static methods are called without an object
+                    let is_static = is_class_name(expr);
+                    if !is_static {
+                        result.append(&mut generate_code_expr(
+                            expr.clone(),
+                            stack,
+                            constant_pool,
+                            local_var_pool,
+                            class_name,
+                            hierarchy,
+                        ));
                     }
-                    let method_index = constant_pool.add(Constant::MethodRef(MethodRef {
-                        class: class_name.to_string(),
+                    for arg in args {
+                        result.append(&mut generate_code_expr(
+                            arg.clone(),
+                            stack,
+                            constant_pool,
+                            local_var_pool,
+                            class_name,
+                            hierarchy,
+                        ));
+                    }
+                    let receiver_class = expr.get_type().unwrap().to_internal_name();
+                    let method_ref = MethodRef {
+                        class: receiver_class.clone(),
                         method: NameAndType {
                             name: name.clone(),
-                            r#type: generate_name_and_type(expr_type, args),
+                            r#type: method_descriptor(args, expr_type),
                         },
-                    }));
-                    result.push(Instruction::invokespecial(method_index));
-                    stack.inc(1);
+                    };
+                    // Methods called via `super` must not be dispatched to the overriding method
+                    if is_static {
+                        let method_index = constant_pool.add(Constant::MethodRef(method_ref));
+                        result.push(Instruction::invokestatic(method_index));
+                    } else if matches!(expr, Expr::TypedExpr(receiver, _) if **receiver == Expr::Super)
+                    {
+                        let method_index = constant_pool.add(Constant::MethodRef(method_ref));
+                        result.push(Instruction::invokespecial(method_index));
+                    } else if hierarchy.is_interface(&receiver_class) {
+                        let method_index =
+                            constant_pool.add(Constant::InterfaceMethodRef(method_ref));
+                        // The count operand is the number of arguments including the receiver
+                        result.push(Instruction::invokeinterface(
+                            method_index,
+                            args.len() as u8 + 1,
+                        ));
+                    } else {
+                        let method_index = constant_pool.add(Constant::MethodRef(method_ref));
+                        result.push(Instruction::invokevirtual(method_index));
+                    }
+                    stack.dec(args_size(args) + !is_static as u16);
+                    stack.inc(expr_type.size());
                 }
                 _ => panic!("StmtExpr typed: {:?}", new_stmt_expr),
             }
         }
-        e => panic!("StmtExpr not typed: {:?}", e),
+        e => panic!("StmtExpr not typed: {:?}", e),
+    }
+    result
+}
+
+/// Which value of a variable updated by `++`, `--` or a compound assignment is left on the stack
+enum UpdateResult {
+    None,
+    Old, // the value before the update, e.g. of `i++`
+    New, // the value after the update, e.g. of `++i` or `i += 2`
+}
+
+/// Generates the update of a variable by `++`, `--` or a compound assignment like `x += 2`.
+/// The variable is only evaluated once, e.g. the array and index of `a[i++] += 2`.
+fn generate_update(
+    update: &StmtExpr,
+    update_result: UpdateResult,
+    stack: &mut StackSize,
+    constant_pool: &mut ConstantPool,
+    local_var_pool: &mut LocalVarPool,
+    class_name: &str,
+    hierarchy: &ClassHierarchy,
+) -> Vec<Instruction> {
+    let (var, op, value) = match update {
+        StmtExpr::CompoundAssign(var, op, value) => (var, op.as_str(), Some(value.clone())),
+        StmtExpr::PreIncDec(op, var) | StmtExpr::PostIncDec(var, op) => (var, &op[..1], None),
+        _ => panic!("Expected update of a variable, got {:?}", update),
+    };
+    let Expr::TypedExpr(var, t) = var else {
+        panic!("Expected typed variable, got {:?}", var);
+    };
+    // `++` and `--` add 1 of the promoted type of the variable, e.g. a long 1 to a long
+    let value = value.unwrap_or_else(|| Expr::TypedExpr(Box::new(Expr::Integer(1)), t.promoted()));
+    // The type the operation is performed in, to which the value has been converted already,
+    // except for the distance of shifts, which is an int
+    let op_type = match op {
+        "<<" | ">>" | ">>>" => t.promoted(),
+        _ => value.get_type().unwrap(),
+    };
+    let mut result = vec![];
+    // Int variables are incremented by a constant directly, without loading them onto the stack
+    if let (Expr::LocalVar(name), Type::Int, Expr::TypedExpr(value, _)) = (&**var, t, &value) {
+        let increment = match (op, &**value) {
+            ("+", Expr::Integer(i)) => i8::try_from(*i).ok(),
+            ("-", Expr::Integer(i)) => i8::try_from(-i).ok(),
+            _ => None,
+        };
+        if let Some(increment) = increment {
+            let idx = local_var_pool.get_index(name);
+            if let UpdateResult::New = update_result {
+                result.push(Instruction::iinc(idx, increment));
+            }
+            if !matches!(update_result, UpdateResult::None) {
+                result.push(Instruction::iload(idx));
+                stack.inc(1);
+            }
+            if !matches!(update_result, UpdateResult::New) {
+                result.push(Instruction::iinc(idx, increment));
+            }
+            return result;
+        }
+    }
+    let field_ref = |class: String, name: &str, constant_pool: &mut ConstantPool| {
+        constant_pool.add(Constant::FieldRef(FieldRef {
+            class,
+            field: NameAndType {
+                name: name.to_string(),
+                r#type: t.to_ir_string(),
+            },
+        }))
+    };
+    // Loads the variable and the object or array and index it is stored in, which are needed
+    // again to store the updated value
+    let (store, refs) = match &**var {
+        Expr::LocalVar(name) => {
+            let idx = local_var_pool.get_index(name);
+            result.push(local_load(t, idx));
+            stack.inc(t.size());
+            (local_store(t, idx), 0)
+        }
+        Expr::FieldVar(name) => {
+            let idx = field_ref(class_name.to_string(), name, constant_pool);
+            result.push(Instruction::aload_0);
+            result.push(Instruction::dup);
+            result.push(Instruction::getfield(idx));
+            stack.inc(1 + t.size());
+            (Instruction::putfield(idx), 1)
+        }
+        Expr::InstVar(obj, name) if is_class_name(obj) => {
+            let idx = field_ref(
+                obj.get_type().unwrap().to_internal_name(),
+                name,
+                constant_pool,
+            );
+            result.push(Instruction::getstatic(idx));
+            stack.inc(t.size());
+            (Instruction::putstatic(idx), 0)
+        }
+        Expr::InstVar(obj, name) => {
+            let idx = field_ref(
+                obj.get_type().unwrap().to_internal_name(),
+                name,
+                constant_pool,
+            );
+            result.append(&mut generate_code_expr(
+                obj.deref().clone(),
+                stack,
+                constant_pool,
+                local_var_pool,
+                class_name,
+                hierarchy,
+            ));
+            result.push(Instruction::dup);
+            result.push(Instruction::getfield(idx));
+            stack.inc(t.size());
+            (Instruction::putfield(idx), 1)
+        }
+        Expr::ArrayAccess(array, index) => {
+            for expr in [array, index] {
+                result.append(&mut generate_code_expr(
+                    expr.deref().clone(),
+                    stack,
+                    constant_pool,
+                    local_var_pool,
+                    class_name,
+                    hierarchy,
+                ));
+            }
+            result.push(Instruction::dup2);
+            result.push(array_load(t));
+            stack.inc(2);
+            stack.dec(2 - t.size());
+            (array_store(t), 2)
+        }
+        _ => panic!("Unexpected variable type for update: {:?}", var),
+    };
+    // The result is put beneath the object or array and index, so it remains after storing the value
+    let dup_result = dup_value(t, refs);
+    if let UpdateResult::Old = update_result {
+        result.push(dup_result.clone());
+        stack.inc(t.size());
+    }
+    if *t == Type::String {
+        // The value is appended to the string, e.g. `s += 1` is `s = s + 1`
+        let mut parts = vec![ConcatPart::Loaded(Type::String)];
+        concat_parts(value, &mut parts);
+        result.append(&mut generate_string_concat(
+            parts,
+            stack,
+            constant_pool,
+            local_var_pool,
+            class_name,
+            hierarchy,
+        ));
+    } else {
+        result.append(&mut convert_primitive(t, &op_type));
+        stack.dec(t.size());
+        stack.inc(op_type.size());
+        let value_size = value.get_type().unwrap().size();
+        result.append(&mut generate_code_expr(
+            value,
+            stack,
+            constant_pool,
+            local_var_pool,
+            class_name,
+            hierarchy,
+        ));
+        result.push(arithmetic_instruction(op, &op_type));
+        stack.dec(value_size);
+        // The result of the operation is converted back to the type of the variable,
+        // e.g. an int to a char
+        result.append(&mut convert_primitive(&op_type, t));
+        stack.dec(op_type.size());
+        stack.inc(t.size());
     }
+    if let UpdateResult::New = update_result {
+        result.push(dup_result);
+        stack.inc(t.size());
+    }
+    result.push(store);
+    stack.dec(refs + t.size());
     result
 }
 
@@ -965,179 +4284,341 @@ fn generate_code_expr(
     constant_pool: &mut ConstantPool,
     local_var_pool: &mut LocalVarPool,
     class_name: &str,
+    hierarchy: &ClassHierarchy,
 ) -> Vec<Instruction> {
     let mut result = vec![];
     match expr {
         Expr::TypedExpr(expr, r#type) => {
             let expr = expr.deref().clone();
             match expr {
-                Expr::Integer(i) => {
-                    if i < i8::MAX as i32 && i > i8::MIN as i32 {
-                        result.push(Instruction::bipush(i as i8));
-                    } else if i < i16::MAX as i32 && i > i16::MIN as i32 {
-                        result.push(Instruction::sipush(i as i16));
-                    } else {
-                        result.push(Instruction::ldc(
-                            constant_pool.add(Constant::Integer(i)) as u8
-                        ));
-                    }
-                    stack.inc(1);
+                number @ (Expr::Integer(_)
+                | Expr::Long(_)
+                | Expr::Float(_)
+                | Expr::Double(_)
+                | Expr::Char(_)) => {
+                    result.push(load_number(&number, &r#type, constant_pool));
+                    stack.inc(r#type.size());
                 }
                 Expr::Bool(b) => {
                     result.push(Instruction::bipush(b as i8));
                     stack.inc(1);
                 }
-                Expr::Char(c) => {
-                    result.push(Instruction::bipush(c as i8));
-                    stack.inc(1);
-                }
                 Expr::String(s) => {
                     let index = constant_pool.add(Constant::String(s));
-                    result.push(Instruction::ldc(index as u8));
+                    result.push(load_constant(index));
                     stack.inc(1);
                 }
                 Expr::Jnull => {
                     result.push(Instruction::aconst_null);
                     stack.inc(1);
                 }
-                Expr::This => {
+                Expr::This | Expr::Super => {
                     result.push(Instruction::aload(0));
                     stack.inc(1);
                 }
-                Expr::InstVar(exprs, name) => {
-                    match exprs.deref() {
-                        Expr::TypedExpr(expr, r#type) => match expr.deref() {
-                            Expr::This => {
-                                result.push(Instruction::aload(0));
-                                stack.inc(1);
-                            }
-                            Expr::LocalVar(name) => {
-                                let idx = local_var_pool.get_index(name);
-                                result.push(Instruction::aload(idx));
-                                result.push(Instruction::getfield(constant_pool.add(
-                                    Constant::FieldRef(FieldRef {
-                                        class: class_name.to_string(),
-                                        field: NameAndType {
-                                            name: name.clone(),
-                                            r#type: r#type.to_ir_string(),
-                                        },
-                                    }),
-                                )));
-                                stack.inc(3);
-                            }
-                            Expr::FieldVar(name) => {
-                                let field_index = constant_pool.add(Constant::FieldRef(FieldRef {
-                                    class: class_name.to_string(),
-                                    field: NameAndType {
-                                        name: name.clone(),
-                                        r#type: r#type.to_ir_string(),
-                                    },
-                                }));
-                                result.push(Instruction::aload_0);
-                                result.push(Instruction::getfield(field_index));
-                                stack.inc(3);
-                            }
-                            _ => panic!("Expected this got {:?}", exprs),
-                        },
-                        _ => panic!("Expected typed stmt got {:?}", exprs),
-                    }
+                Expr::InstVar(expr, name) => {
                     let field_index = constant_pool.add(Constant::FieldRef(FieldRef {
-                        class: class_name.to_string(),
+                        class: expr.get_type().unwrap().to_internal_name(),
                         field: NameAndType {
                             name,
                             r#type: r#type.to_ir_string(),
                         },
                     }));
-                    result.push(getfield(field_index));
-                    // I'm thinking 2 here since we load the field here too and leave the class on the stack
-                    stack.inc(2);
+                    if is_class_name(&expr) {
+                        result.push(Instruction::getstatic(field_index));
+                        stack.inc(r#type.size());
+                    } else {
+                        result.append(&mut generate_code_expr(
+                            *expr,
+                            stack,
+                            constant_pool,
+                            local_var_pool,
+                            class_name,
+                            hierarchy,
+                        ));
+                        // getfield replaces the object on the stack with the value of its field
+                        result.push(Instruction::getfield(field_index));
+                        stack.inc(r#type.size() - 1);
+                    }
                 }
-                Expr::Binary(op, left, right) => {
-                    match BinaryOp::from(&op as &str) {
-                        BinaryOp::Add => {
-                            result.append(&mut generate_code_expr(
-                                *left,
-                                stack,
-                                constant_pool,
-                                local_var_pool,
-                                class_name,
-                            ));
-                            result.append(&mut generate_code_expr(
-                                *right,
-                                stack,
-                                constant_pool,
-                                local_var_pool,
-                                class_name,
-                            ));
-                            result.push(Instruction::iadd);
+                Expr::ArrayAccess(array, index) => {
+                    for expr in [*array, *index] {
+                        result.append(&mut generate_code_expr(
+                            expr,
+                            stack,
+                            constant_pool,
+                            local_var_pool,
+                            class_name,
+                            hierarchy,
+                        ));
+                    }
+                    result.push(array_load(&r#type));
+                    stack.dec(2 - r#type.size());
+                }
+                Expr::ArrayLength(array) => {
+                    result.append(&mut generate_code_expr(
+                        *array,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    ));
+                    // arraylength replaces the array on the stack with its length
+                    result.push(Instruction::arraylength);
+                }
+                Expr::NewArray(array_type, dims) => {
+                    for dim in &dims {
+                        result.append(&mut generate_code_expr(
+                            type_field_value(dim.clone(), &Type::Int),
+                            stack,
+                            constant_pool,
+                            local_var_pool,
+                            class_name,
+                            hierarchy,
+                        ));
+                    }
+                    // Arrays of arrays are created at once if the lengths of several dimensions are given
+                    match (&array_type, dims.len()) {
+                        (Type::Array(elem_type), 1) => {
+                            result.push(new_array(elem_type, constant_pool));
                         }
-                        BinaryOp::Sub => {
-                            result.append(&mut generate_code_expr(
-                                *left,
-                                stack,
-                                constant_pool,
-                                local_var_pool,
-                                class_name,
-                            ));
-                            result.append(&mut generate_code_expr(
-                                *right,
-                                stack,
-                                constant_pool,
-                                local_var_pool,
-                                class_name,
+                        _ => {
+                            result.push(Instruction::multianewarray(
+                                constant_pool.add(Constant::Class(array_type.to_internal_name())),
+                                dims.len() as u8,
                             ));
-                            result.push(Instruction::isub);
+                            stack.dec(dims.len() as u16 - 1);
                         }
-                        BinaryOp::Mul => {
-                            result.append(&mut generate_code_expr(
-                                *left,
-                                stack,
-                                constant_pool,
-                                local_var_pool,
-                                class_name,
-                            ));
+                    }
+                }
+                Expr::Conditional(cond, then_expr, else_expr) => {
+                    result.append(&mut generate_code_expr(
+                        *cond,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    ));
+                    stack.dec(1);
+                    let mut then_code = generate_code_expr(
+                        *then_expr,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    );
+                    // Only the value of one of the branches is on the stack afterwards
+                    stack.dec(r#type.size());
+                    let mut else_code = generate_code_expr(
+                        *else_expr,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    );
+                    then_code.push(Instruction::goto(
+                        3 + get_instructions_length(&else_code) as i16,
+                        else_code.len() as i16,
+                    ));
+                    // If the condition is false, jump to the value of the else branch
+                    result.push(Instruction::ifeq(
+                        3 + get_instructions_length(&then_code) as i16,
+                        1 + then_code.len() as i16,
+                    ));
+                    result.append(&mut then_code);
+                    result.append(&mut else_code);
+                }
+                Expr::Cast(t, expr) => {
+                    let from = expr.get_type().unwrap();
+                    result.append(&mut generate_code_expr(
+                        *expr,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    ));
+                    if from.is_numeric() && t.is_numeric() {
+                        result.append(&mut convert_primitive(&from, &t));
+                        stack.dec(from.size());
+                        stack.inc(t.size());
+                    } else if from != t && t.is_reference() {
+                        let class_index = constant_pool.add(Constant::Class(t.to_internal_name()));
+                        result.push(Instruction::checkcast(class_index));
+                    }
+                }
+                Expr::FunctionRef(function_ref) => {
+                    result.append(&mut generate_function_ref(
+                        *function_ref,
+                        &r#type,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    ));
+                }
+                Expr::InstanceOf(expr, t, name) => {
+                    result.append(&mut generate_code_expr(
+                        *expr,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    ));
+                    let class_index = constant_pool.add(Constant::Class(t.to_internal_name()));
+                    let Some(name) = name else {
+                        result.push(Instruction::instanceof(class_index));
+                        return result;
+                    };
+                    // The pattern variable is assigned the cast value if it is an instance of the type,
+                    // otherwise null, so it has the type in both cases
+                    let index = local_var_pool.add(name, &t);
+                    stack.inc(1);
+                    let then_code = vec![
+                        Instruction::checkcast(class_index),
+                        Instruction::astore(index),
+                        Instruction::bipush(1),
+                    ];
+                    let else_code = vec![
+                        Instruction::pop,
+                        Instruction::aconst_null,
+                        Instruction::astore(index),
+                        Instruction::bipush(0),
+                    ];
+                    result.push(Instruction::dup);
+                    result.push(Instruction::instanceof(class_index));
+                    result.push(Instruction::ifeq(
+                        3 + get_instructions_length(&then_code) as i16 + 3,
+                        1 + then_code.len() as i16 + 1,
+                    ));
+                    result.extend(then_code);
+                    result.push(Instruction::goto(
+                        3 + get_instructions_length(&else_code) as i16,
+                        else_code.len() as i16 + 1,
+                    ));
+                    result.extend(else_code);
+                    stack.dec(1);
+                }
+                Expr::Switch(expr, cases) => {
+                    // `break` and `continue` can't leave a switch expression, so only `yield`
+                    // needs a jump target
+                    let mut jump_targets = JumpTargets::default();
+                    let id = jump_targets.new_id();
+                    jump_targets.yield_target = Some((id, r#type.clone()));
+                    result.append(&mut generate_switch(
+                        *expr,
+                        cases,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        &mut jump_targets,
+                        class_name,
+                        hierarchy,
+                    ));
+                    let end = result.len();
+                    patch_jumps(&mut result, id, end, end);
+                    stack.inc(r#type.size());
+                }
+                Expr::ArrayInit(array_type, exprs) => {
+                    let Type::Array(elem_type) = &array_type else {
+                        panic!("Expected array type for array initializer, got {array_type}");
+                    };
+                    result.append(&mut generate_code_expr(
+                        Expr::TypedExpr(Box::new(Expr::Integer(exprs.len() as i32)), Type::Int),
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    ));
+                    result.push(new_array(elem_type, constant_pool));
+                    for (i, expr) in exprs.into_iter().enumerate() {
+                        let expr = type_field_value(expr, elem_type);
+                        result.push(Instruction::dup);
+                        stack.inc(1);
+                        for expr in [
+                            Expr::TypedExpr(Box::new(Expr::Integer(i as i32)), Type::Int),
+                            expr,
+                        ] {
                             result.append(&mut generate_code_expr(
-                                *right,
+                                expr,
                                 stack,
                                 constant_pool,
                                 local_var_pool,
                                 class_name,
+                                hierarchy,
                             ));
-                            result.push(Instruction::imul);
                         }
-                        BinaryOp::Div => {
-                            result.append(&mut generate_code_expr(
-                                *left,
-                                stack,
-                                constant_pool,
-                                local_var_pool,
-                                class_name,
-                            ));
-                            result.append(&mut generate_code_expr(
-                                *right,
-                                stack,
-                                constant_pool,
-                                local_var_pool,
-                                class_name,
-                            ));
-                            result.push(Instruction::idiv);
+                        result.push(array_store(elem_type));
+                        stack.dec(2 + elem_type.size());
+                    }
+                }
+                Expr::Binary(_, left, right) if r#type == Type::String => {
+                    let mut parts = vec![];
+                    concat_parts(*left, &mut parts);
+                    concat_parts(*right, &mut parts);
+                    result.append(&mut generate_string_concat(
+                        parts,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    ));
+                }
+                Expr::Binary(op, left, right) => {
+                    // Both operands have the same type, except for the distance of shifts, which is an int
+                    let operand_type = left.get_type().unwrap();
+                    let operands_size = operand_type.size() + right.get_type().unwrap().size();
+                    match BinaryOp::from(&op as &str) {
+                        // References are equal if they refer to the same object
+                        BinaryOp::Eq | BinaryOp::Ne
+                            if operand_type.is_reference() || operand_type == Type::Null =>
+                        {
+                            for operand in [left, right] {
+                                result.append(&mut generate_code_expr(
+                                    *operand,
+                                    stack,
+                                    constant_pool,
+                                    local_var_pool,
+                                    class_name,
+                                    hierarchy,
+                                ));
+                            }
+                            let (equal, unequal) = if op == "==" { (1, 0) } else { (0, 1) };
+                            result.push(Instruction::if_acmpne(8, 3));
+                            result.push(Instruction::bipush(equal));
+                            result.push(Instruction::goto(5, 2));
+                            result.push(Instruction::bipush(unequal));
                         }
-                        BinaryOp::Mod => {
-                            result.append(&mut generate_code_expr(
-                                *left,
-                                stack,
-                                constant_pool,
-                                local_var_pool,
-                                class_name,
-                            ));
-                            result.append(&mut generate_code_expr(
-                                *right,
-                                stack,
-                                constant_pool,
-                                local_var_pool,
-                                class_name,
-                            ));
-                            result.push(Instruction::irem);
+                        BinaryOp::Add
+                        | BinaryOp::Sub
+                        | BinaryOp::Mul
+                        | BinaryOp::Div
+                        | BinaryOp::Mod
+                        | BinaryOp::BitAnd
+                        | BinaryOp::BitOr
+                        | BinaryOp::BitXor
+                        | BinaryOp::Shl
+                        | BinaryOp::Shr
+                        | BinaryOp::UShr => {
+                            for operand in [left, right] {
+                                result.append(&mut generate_code_expr(
+                                    *operand,
+                                    stack,
+                                    constant_pool,
+                                    local_var_pool,
+                                    class_name,
+                                    hierarchy,
+                                ));
+                            }
+                            result.push(arithmetic_instruction(&op, &operand_type));
                         }
                         BinaryOp::And => {
                             let mut left_code = generate_code_expr(
@@ -1146,6 +4627,7 @@ fn generate_code_expr(
                                 constant_pool,
                                 local_var_pool,
                                 class_name,
+                                hierarchy,
                             );
                             let mut right_code = generate_code_expr(
                                 *right,
@@ -1153,6 +4635,7 @@ fn generate_code_expr(
                                 constant_pool,
                                 local_var_pool,
                                 class_name,
+                                hierarchy,
                             );
 
                             //   ...left
@@ -1185,6 +4668,7 @@ fn generate_code_expr(
                                 constant_pool,
                                 local_var_pool,
                                 class_name,
+                                hierarchy,
                             );
                             let mut right_code = generate_code_expr(
                                 *right,
@@ -1192,6 +4676,7 @@ fn generate_code_expr(
                                 constant_pool,
                                 local_var_pool,
                                 class_name,
+                                hierarchy,
                             );
                             result.append(&mut left_code);
                             // If left operand is true (!= 0), return true immediately
@@ -1213,6 +4698,7 @@ fn generate_code_expr(
                                 constant_pool,
                                 local_var_pool,
                                 class_name,
+                                hierarchy,
                             ));
                             result.append(&mut generate_code_expr(
                                 *right,
@@ -1220,12 +4706,13 @@ fn generate_code_expr(
                                 constant_pool,
                                 local_var_pool,
                                 class_name,
+                                hierarchy,
                             ));
                             // a <= b
                             // a - b <= 0
                             // a - b - 1 < 0
-                            result.push(Instruction::isub);
-                            result.push(Instruction::iload(1));
+                            result.push(compare_instruction(&operand_type, true));
+                            result.push(Instruction::bipush(1));
                             result.push(Instruction::isub);
                             result.push(Instruction::iflt(8, 3));
                             result.push(Instruction::bipush(0));
@@ -1239,6 +4726,7 @@ fn generate_code_expr(
                                 constant_pool,
                                 local_var_pool,
                                 class_name,
+                                hierarchy,
                             ));
                             result.append(&mut generate_code_expr(
                                 *right,
@@ -1246,8 +4734,9 @@ fn generate_code_expr(
                                 constant_pool,
                                 local_var_pool,
                                 class_name,
+                                hierarchy,
                             ));
-                            result.push(Instruction::isub);
+                            result.push(compare_instruction(&operand_type, false));
                             result.push(Instruction::ifge(8, 3));
                             result.push(Instruction::bipush(0));
                             result.push(Instruction::goto(5, 2));
@@ -1260,6 +4749,7 @@ fn generate_code_expr(
                                 constant_pool,
                                 local_var_pool,
                                 class_name,
+                                hierarchy,
                             ));
                             result.append(&mut generate_code_expr(
                                 *right,
@@ -1267,8 +4757,9 @@ fn generate_code_expr(
                                 constant_pool,
                                 local_var_pool,
                                 class_name,
+                                hierarchy,
                             ));
-                            result.push(Instruction::isub);
+                            result.push(compare_instruction(&operand_type, true));
                             result.push(Instruction::iflt(8, 3));
                             result.push(Instruction::bipush(0));
                             result.push(Instruction::goto(2 + 2 + 1, 2));
@@ -1281,6 +4772,7 @@ fn generate_code_expr(
                                 constant_pool,
                                 local_var_pool,
                                 class_name,
+                                hierarchy,
                             ));
                             result.append(&mut generate_code_expr(
                                 *right,
@@ -1288,13 +4780,14 @@ fn generate_code_expr(
                                 constant_pool,
                                 local_var_pool,
                                 class_name,
+                                hierarchy,
                             ));
-                            // a >= b
-                            // a - b >= 0
-                            // a - b + 1 > 0
-                            result.push(Instruction::isub);
+                            // a > b
+                            // a - b > 0
+                            // a - b - 1 >= 0
+                            result.push(compare_instruction(&operand_type, false));
                             result.push(Instruction::bipush(1));
-                            result.push(Instruction::iadd);
+                            result.push(Instruction::isub);
                             result.push(Instruction::ifge(8, 3));
                             result.push(Instruction::bipush(0));
                             result.push(Instruction::goto(5, 2));
@@ -1307,6 +4800,7 @@ fn generate_code_expr(
                                 constant_pool,
                                 local_var_pool,
                                 class_name,
+                                hierarchy,
                             ));
                             result.append(&mut generate_code_expr(
                                 *right,
@@ -1314,8 +4808,9 @@ fn generate_code_expr(
                                 constant_pool,
                                 local_var_pool,
                                 class_name,
+                                hierarchy,
                             ));
-                            result.push(Instruction::isub);
+                            result.push(compare_instruction(&operand_type, false));
                             result.push(Instruction::ifeq(2 + 2 + 3 + 1, 3));
                             result.push(Instruction::bipush(0));
                             result.push(Instruction::goto(2 + 2 + 1, 2));
@@ -1328,6 +4823,7 @@ fn generate_code_expr(
                                 constant_pool,
                                 local_var_pool,
                                 class_name,
+                                hierarchy,
                             ));
                             result.append(&mut generate_code_expr(
                                 *right,
@@ -1335,14 +4831,16 @@ fn generate_code_expr(
                                 constant_pool,
                                 local_var_pool,
                                 class_name,
+                                hierarchy,
                             ));
+                            result.push(compare_instruction(&operand_type, false));
                             result.push(Instruction::ifeq(8, 3));
                             result.push(Instruction::bipush(1));
                             result.push(Instruction::goto(5, 2));
                             result.push(Instruction::bipush(0))
                         }
                     }
-                    stack.dec(1);
+                    stack.dec(operands_size - r#type.size());
                 }
                 Expr::Unary(op, expr) => {
                     result.append(&mut generate_code_expr(
@@ -1351,6 +4849,7 @@ fn generate_code_expr(
                         constant_pool,
                         local_var_pool,
                         class_name,
+                        hierarchy,
                     ));
                     match UnaryOp::from(&op as &str) {
                         UnaryOp::Not => {
@@ -1360,29 +4859,28 @@ fn generate_code_expr(
                             result.push(Instruction::bipush(0));
                         }
                         UnaryOp::Neg => {
-                            result.push(Instruction::ineg);
+                            result.push(match r#type {
+                                Type::Long => Instruction::lneg,
+                                Type::Float => Instruction::fneg,
+                                Type::Double => Instruction::dneg,
+                                _ => Instruction::ineg,
+                            });
                         }
                         UnaryOp::Pos => {}
+                        // `~x` is `x ^ -1`, as all bits of -1 are set
+                        UnaryOp::BitNot => {
+                            let minus_one = Expr::Integer(-1);
+                            result.push(load_number(&minus_one, &r#type, constant_pool));
+                            stack.inc(r#type.size());
+                            result.push(arithmetic_instruction("^", &r#type));
+                            stack.dec(r#type.size());
+                        }
                     }
                 }
                 Expr::LocalVar(name) => {
-                    stack.inc(1);
+                    stack.inc(r#type.size());
                     let index = local_var_pool.get_index(&name);
-                    match r#type {
-                        Type::Int => {
-                            result.push(Instruction::iload(index));
-                        }
-                        Type::Bool => {
-                            result.push(Instruction::iload(index));
-                        }
-                        Type::Char => {
-                            result.push(Instruction::iload(index));
-                        }
-                        Type::String => {
-                            result.push(Instruction::aload(index));
-                        }
-                        _ => panic!("Unexpected type: {:?}", r#type),
-                    }
+                    result.push(local_load(&r#type, index));
                 }
 
                 Expr::StmtExprExpr(stmt_expr) => {
@@ -1392,6 +4890,7 @@ fn generate_code_expr(
                         constant_pool,
                         local_var_pool,
                         class_name,
+                        hierarchy,
                     ));
                 }
                 Expr::FieldVar(name) => {
@@ -1406,7 +4905,26 @@ fn generate_code_expr(
                     // with the field
                     result.push(Instruction::aload_0);
                     result.push(Instruction::getfield(index));
-                    stack.inc(2);
+                    stack.inc(r#type.size());
+                }
+                // An implicit conversion of the inner expression to the outer type,
+                // e.g. when passing null as an argument. References don't need any instructions for this,
+                // while numbers may have to be converted, e.g. an int to a long
+                Expr::TypedExpr(_, _) => {
+                    let from = expr.get_type().unwrap();
+                    result.append(&mut generate_code_expr(
+                        expr,
+                        stack,
+                        constant_pool,
+                        local_var_pool,
+                        class_name,
+                        hierarchy,
+                    ));
+                    if from.is_numeric() && r#type.is_numeric() {
+                        result.append(&mut convert_primitive(&from, &r#type));
+                        stack.dec(from.size());
+                        stack.inc(r#type.size());
+                    }
                 }
                 p => panic!(
                     "Unexpected expression where untyped expression was expected: {:?}",
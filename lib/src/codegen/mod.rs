@@ -1,7 +1,10 @@
+mod accessors;
 mod bytecode;
 pub mod ir;
+mod reader;
 mod stack;
 pub use ir::*;
+pub use reader::*;
 
 pub fn high_byte(short: u16) -> u8 {
     (short >> 8) as u8
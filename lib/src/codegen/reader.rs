@@ -0,0 +1,255 @@
+use super::AccessFlags;
+use crate::types::*;
+use std::fs;
+use std::path::Path;
+
+/// The entries of a constant pool needed to read the signatures of a class
+enum RawConstant {
+    Utf8(String),
+    Class(u16),
+    /// Any other constant, whose content is skipped
+    Other,
+    /// The unusable second slot of a Long or Double constant
+    Unusable,
+}
+
+/// Reads the bytes of a class file, keeping track of the current position
+struct ClassFileReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    constant_pool: Vec<RawConstant>,
+}
+
+impl<'a> ClassFileReader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let bytes = self
+            .bytes
+            .get(self.pos..self.pos + len)
+            .ok_or("Unexpected end of class file")?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, String> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, String> {
+        let bytes = self.read_bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, String> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// See https://docs.oracle.com/javase/specs/jvms/se15/html/jvms-4.html#jvms-4.4
+    fn read_constant_pool(&mut self) -> Result<(), String> {
+        let count = self.read_u16()?;
+        // The constant pool is indexed starting at 1
+        self.constant_pool.push(RawConstant::Unusable);
+        while self.constant_pool.len() < count as usize {
+            let tag = self.read_u8()?;
+            let constant = match tag {
+                1 => {
+                    let len = self.read_u16()? as usize;
+                    // Class files use modified UTF-8, which only differs for characters we don't use in names
+                    RawConstant::Utf8(String::from_utf8_lossy(self.read_bytes(len)?).to_string())
+                }
+                7 => RawConstant::Class(self.read_u16()?),
+                // MethodType, Module and Package
+                8 | 16 | 19 | 20 => {
+                    self.read_bytes(2)?;
+                    RawConstant::Other
+                }
+                // MethodHandle
+                15 => {
+                    self.read_bytes(3)?;
+                    RawConstant::Other
+                }
+                // Integer, Float, FieldRef, MethodRef, InterfaceMethodRef, NameAndType, Dynamic and InvokeDynamic
+                3 | 4 | 9 | 10 | 11 | 12 | 17 | 18 => {
+                    self.read_bytes(4)?;
+                    RawConstant::Other
+                }
+                // Long and Double take up two entries
+                5 | 6 => {
+                    self.read_bytes(8)?;
+                    self.constant_pool.push(RawConstant::Other);
+                    RawConstant::Unusable
+                }
+                tag => return Err(format!("Invalid constant pool tag {tag}")),
+            };
+            self.constant_pool.push(constant);
+        }
+        Ok(())
+    }
+
+    fn utf8(&self, index: u16) -> Result<String, String> {
+        match self.constant_pool.get(index as usize) {
+            Some(RawConstant::Utf8(s)) => Ok(s.clone()),
+            _ => Err(format!("Constant {index} is not a Utf8 constant")),
+        }
+    }
+
+    fn class_name(&self, index: u16) -> Result<String, String> {
+        match self.constant_pool.get(index as usize) {
+            Some(RawConstant::Class(name_index)) => self.utf8(*name_index),
+            _ => Err(format!("Constant {index} is not a Class constant")),
+        }
+    }
+
+    /// Reads a field or method, returning its access flags, name and descriptor, as well as the
+    /// exception classes a method declares to throw in its `Exceptions` attribute.
+    /// See https://docs.oracle.com/javase/specs/jvms/se15/html/jvms-4.html#jvms-4.7.5
+    fn read_member(&mut self) -> Result<(u16, String, String, Vec<String>), String> {
+        let access_flags = self.read_u16()?;
+        let name_index = self.read_u16()?;
+        let descriptor_index = self.read_u16()?;
+        let (name, descriptor) = (self.utf8(name_index)?, self.utf8(descriptor_index)?);
+        let mut exceptions = vec![];
+        for _ in 0..self.read_u16()? {
+            let attribute_name = self.read_u16()?;
+            let len = self.read_u32()? as usize;
+            if self.utf8(attribute_name)? == "Exceptions" {
+                for _ in 0..self.read_u16()? {
+                    let exception = self.read_u16()?;
+                    exceptions.push(self.class_name(exception)?);
+                }
+            } else {
+                self.read_bytes(len)?;
+            }
+        }
+        Ok((access_flags, name, descriptor, exceptions))
+    }
+}
+
+/// Reads the signatures of the class declared by a class file, i.e. its fields, constructors and
+/// methods without their bodies, which are only used to typecheck programs using the class.
+/// Members using types we don't support are left out.
+pub fn read_class_file(bytes: &[u8]) -> Result<Class, String> {
+    let mut reader = ClassFileReader {
+        bytes,
+        pos: 0,
+        constant_pool: vec![],
+    };
+    if reader.read_u32()? != 0xCAFEBABE {
+        return Err("Invalid magic number of class file".to_string());
+    }
+    // Minor and major version
+    reader.read_u32()?;
+    reader.read_constant_pool()?;
+
+    let access_flags = reader.read_u16()?;
+    let this_class = reader.read_u16()?;
+    let mut class = Class {
+        modifiers: Modifiers::from_access_flags(access_flags),
+        is_interface: access_flags & AccessFlags::Interface as u16 != 0,
+        is_abstract: access_flags & AccessFlags::Abstract as u16 != 0,
+        name: reader.class_name(this_class)?,
+        ..Default::default()
+    };
+    let super_class = reader.read_u16()?;
+    // Only java/lang/Object doesn't have a superclass and classes extending it have none in the AST
+    if super_class != 0 {
+        class.super_class = Some(reader.class_name(super_class)?)
+            .filter(|super_class| super_class != "java/lang/Object");
+    }
+    for _ in 0..reader.read_u16()? {
+        let interface = reader.read_u16()?;
+        class.interfaces.push(reader.class_name(interface)?);
+    }
+
+    for _ in 0..reader.read_u16()? {
+        let (access_flags, name, descriptor, _) = reader.read_member()?;
+        if access_flags & AccessFlags::Synthetic as u16 != 0 {
+            continue;
+        }
+        if let Some((field_type, "")) = Type::from_ir_string(&descriptor) {
+            class.fields.push(FieldDecl {
+                modifiers: Modifiers::from_access_flags(access_flags),
+                field_type,
+                name,
+                val: None,
+            });
+        }
+    }
+
+    for _ in 0..reader.read_u16()? {
+        let (access_flags, name, descriptor, throws) = reader.read_member()?;
+        if access_flags & AccessFlags::Synthetic as u16 != 0 || name == "<clinit>" {
+            continue;
+        }
+        let Some((param_types, ret_type)) = Type::from_method_descriptor(&descriptor) else {
+            continue;
+        };
+        let modifiers = Modifiers::from_access_flags(access_flags);
+        let params = param_types
+            .into_iter()
+            .enumerate()
+            .map(|(i, t)| (t, format!("arg{i}")))
+            .collect();
+        // The bodies aren't needed, but only abstract methods are declared without one
+        let body = Stmt::Block(vec![]);
+        if name == "<init>" {
+            class.constructors.push(Constructor {
+                modifiers,
+                params,
+                throws,
+                body,
+            });
+        } else {
+            class.methods.push(MethodDecl {
+                modifiers,
+                ret_type,
+                name,
+                params,
+                throws,
+                body: (access_flags & AccessFlags::Abstract as u16 == 0).then_some(body),
+            });
+        }
+    }
+    Ok(class)
+}
+
+/// Reads the classes of all class files on the classpath.
+/// Each entry is either a class file or a directory, whose class files are read recursively.
+pub fn read_classpath(entries: &[&Path]) -> Result<Prg, String> {
+    let mut classes = vec![];
+    for entry in entries {
+        read_classpath_entry(entry, &mut classes)?;
+    }
+    Ok(classes)
+}
+
+fn read_classpath_entry(entry: &Path, classes: &mut Prg) -> Result<(), String> {
+    if entry.is_dir() {
+        let mut paths = fs::read_dir(entry)
+            .and_then(|dir| {
+                dir.map(|e| e.map(|e| e.path()))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map_err(|e| format!("Failed to read directory {}: {e}", entry.display()))?;
+        // Sorted, so the classes are always read in the same order
+        paths.sort();
+        for path in paths {
+            if path.is_dir() || path.extension().is_some_and(|ext| ext == "class") {
+                read_classpath_entry(&path, classes)?;
+            }
+        }
+    } else if entry.extension().is_some_and(|ext| ext == "class") {
+        let bytes = fs::read(entry)
+            .map_err(|e| format!("Failed to read class file {}: {e}", entry.display()))?;
+        classes.push(
+            read_class_file(&bytes)
+                .map_err(|e| format!("Failed to read class file {}: {e}", entry.display()))?,
+        );
+    } else {
+        return Err(format!(
+            "Classpath entry {} is neither a directory nor a class file",
+            entry.display()
+        ));
+    }
+    Ok(())
+}
@@ -4,8 +4,72 @@
 
 use super::*;
 use crate::types::*;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 
+/// The superclass of every class of the program, in the internal form of their names.
+/// Needed to find the common superclass of two objects, when merging the stacks of two paths.
+/// Interfaces are treated as extending java/lang/Object, just like the JVM does when verifying.
+#[derive(Debug, Default)]
+pub(crate) struct ClassHierarchy {
+    super_classes: HashMap<String, String>,
+    interfaces: HashSet<String>,
+}
+
+impl ClassHierarchy {
+    pub(crate) fn new(prg: &Prg) -> Self {
+        ClassHierarchy {
+            super_classes: prg
+                .iter()
+                .map(|class| {
+                    (
+                        class.name.clone(),
+                        class
+                            .super_class
+                            .clone()
+                            .unwrap_or_else(|| "java/lang/Object".to_string()),
+                    )
+                })
+                .collect(),
+            interfaces: prg
+                .iter()
+                .filter(|class| class.is_interface)
+                .map(|class| class.name.clone())
+                .collect(),
+        }
+    }
+
+    /// Whether the class is an interface of the program, so its methods are called via invokeinterface
+    pub(crate) fn is_interface(&self, class: &str) -> bool {
+        self.interfaces.contains(class)
+    }
+
+    /// The class itself followed by all of its superclasses up to java/lang/Object.
+    /// Classes that aren't part of the program are assumed to extend java/lang/Object.
+    fn superclasses(&self, class: &str) -> Vec<String> {
+        let mut result = vec![class.to_string()];
+        let mut current = class;
+        while current != "java/lang/Object" {
+            current = self
+                .super_classes
+                .get(current)
+                .map(|s| s.as_str())
+                .unwrap_or("java/lang/Object");
+            result.push(current.to_string());
+        }
+        result
+    }
+
+    /// The most specific class both classes inherit from
+    pub(crate) fn common_superclass(&self, a: &str, b: &str) -> String {
+        let superclasses_of_a = self.superclasses(a);
+        self.superclasses(b)
+            .into_iter()
+            .find(|class| superclasses_of_a.contains(class))
+            .unwrap()
+    }
+}
+
 #[derive(Debug)]
 pub struct StackSize {
     pub current: u16,
@@ -36,32 +100,6 @@ impl StackSize {
     }
 }
 
-// Sorts the vector and removes any duplicate elements
-pub fn sort_unique<T, F1, F2>(a: &mut Vec<T>, mut eq: F1, mut le: F2)
-where
-    F1: FnMut(&T, &T) -> bool,
-    F2: FnMut(&T, &T) -> bool,
-{
-    let mut i = 0;
-    while i < a.len() {
-        let mut min = i;
-        for j in i + 1..a.len() {
-            if le(&a[j], &a[min]) {
-                min = j;
-            }
-        }
-
-        if i != 0 && eq(&a[i - 1], &a[min]) {
-            a.swap_remove(min);
-        } else {
-            if min != i {
-                a.swap(i, min);
-            }
-            i += 1;
-        }
-    }
-}
-
 // Good explanation of what a StackMapTable is and why it exists: https://stackoverflow.com/a/25110513
 // Documentation for StackMapTable: https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-4.html#jvms-4.7.4
 // Basic Idea: It exists to simplify the typechecking of the bytecode performed by the JVM
@@ -72,259 +110,493 @@ pub(crate) struct StackMapTable {
 }
 
 impl StackMapTable {
-    fn create_stacks_for_branch(
-        code: &[Instruction],
-        mut instruction_idx: usize,
-        mut bytes_idx: usize,
+    /// Simulates the effect of a single instruction on the locals and operands of the stack
+    // @Note: location is the offset of the instruction in the bytes-vector, which `new` needs
+    fn simulate_instruction(
+        instruction: &Instruction,
+        location: u16,
         current_stack: &mut VerificationStack,
-        stacks: &mut Vec<VerificationStack>,
-        taken_branches_idxs: &mut Vec<usize>, // indexes are for instructon vector
         constant_pool: &mut ConstantPool,
     ) {
-        while instruction_idx < code.len() {
-            match code.get(instruction_idx).unwrap() {
-                Instruction::invokespecial(idx) => {
-                    bytes_idx += 3;
-                    if let Some(Constant::MethodRef(m)) = constant_pool.get(*idx) {
-                        let pop_amount = 1;
-                        let l = current_stack.operands.len();
-                        current_stack.operands.splice(l - pop_amount..l, []);
-                    } else {
-                        unreachable!();
-                    }
-                }
-                Instruction::ldc(idx) => {
-                    bytes_idx += 2;
-                    match constant_pool.get(*idx as u16).unwrap() {
-                        Constant::String(_) => {
-                            current_stack.operands.push(VerificationType::OBJECT(
-                                constant_pool.add(Constant::Utf8("Ljava/lang/String".to_string())),
-                            ))
-                        }
-                        Constant::Integer(_) => {
-                            current_stack.operands.push(VerificationType::INTEGER)
+        match instruction {
+            Instruction::invokestatic(idx) => {
+                let Some(Constant::MethodRef(m)) = constant_pool.get(*idx).cloned() else {
+                    unreachable!()
+                };
+                let (params, ret) = split_method_descriptor(&m.method.r#type);
+                current_stack.pop_amount(params.len());
+                current_stack.push_descriptor(&ret, constant_pool);
+            }
+            Instruction::invokespecial(idx)
+            | Instruction::invokevirtual(idx)
+            | Instruction::invokeinterface(idx, _) => {
+                let Some(Constant::MethodRef(m) | Constant::InterfaceMethodRef(m)) =
+                    constant_pool.get(*idx).cloned()
+                else {
+                    unreachable!()
+                };
+                let (params, ret) = split_method_descriptor(&m.method.r#type);
+                current_stack.pop_amount(params.len());
+                let receiver = current_stack.operands.pop().unwrap();
+                if m.method.name == "<init>" {
+                    // Calling the constructor initializes every copy of the object on the stack
+                    let initialized = match receiver {
+                        VerificationType::UNINITIALIZED_THIS => {
+                            VerificationType::OBJECT(constant_pool.index_of_this_class())
                         }
-                        Constant::FieldRef(f) => {
-                            current_stack.operands.push(match f.field.r#type.as_str() {
-                                "Z" | "C" | "I" => VerificationType::INTEGER,
-                                "Ljava/lang/String" => VerificationType::OBJECT(
-                                    constant_pool
-                                        .index_of(&Constant::Utf8("Ljava/lang/String".to_string()))
-                                        .unwrap(),
-                                ),
-                                _ => VerificationType::OBJECT(constant_pool.index_of_this_class()),
-                            })
+                        VerificationType::UNINITIALIZED(_) => {
+                            VerificationType::OBJECT(constant_pool.add(Constant::Class(m.class)))
                         }
                         _ => unreachable!(),
-                    }
-                }
-                Instruction::aconst_null => {
-                    bytes_idx += 1;
-                    current_stack.operands.push(VerificationType::NULL)
-                }
-                Instruction::new(_) => unimplemented!(),
-                Instruction::aload_0 => {
-                    bytes_idx += 1;
-                    current_stack.operands.push(VerificationType::OBJECT(
-                        constant_pool.index_of_this_class(),
-                    ))
-                }
-                Instruction::aload(idx) => {
-                    bytes_idx += 2;
-                    current_stack
-                        .operands
-                        .push(VerificationType::OBJECT(*idx as u16))
-                }
-                Instruction::iload(_) | Instruction::bipush(_) => {
-                    bytes_idx += 2;
-                    current_stack.operands.push(VerificationType::INTEGER)
-                }
-                Instruction::sipush(_) => {
-                    bytes_idx += 3;
-                    current_stack
-                        .operands
-                        .extend_from_slice(&[VerificationType::INTEGER, VerificationType::INTEGER])
-                }
-                Instruction::ireturn | Instruction::r#return | Instruction::areturn => {
-                    bytes_idx += 1;
-                    current_stack.operands.clear()
-                }
-                Instruction::putfield(_) => {
-                    bytes_idx += 3;
-                    current_stack.operands.pop();
-                    current_stack.operands.pop();
+                    };
+                    current_stack.replace(&receiver, &initialized);
                 }
-                Instruction::ineg => {
-                    bytes_idx += 1;
-                    // No changes in stack
-                }
-
-                // Locals
-                // this x
-                Instruction::istore(x) => {
-                    bytes_idx += 2;
-                    current_stack.operands.pop();
-                    if *x as usize >= current_stack.locals.len() {
-                        current_stack.locals.push(VerificationType::INTEGER)
-                    }
-                }
-                Instruction::astore(x) => {
-                    let a = current_stack.operands.pop().unwrap();
-                    if *x as usize >= current_stack.locals.len() {
-                        current_stack.locals.push(a);
-                    }
+                current_stack.push_descriptor(&ret, constant_pool);
+            }
+            Instruction::invokedynamic(idx) => {
+                let Some(Constant::InvokeDynamic(_, name_and_type)) =
+                    constant_pool.get(*idx).cloned()
+                else {
+                    unreachable!()
+                };
+                let (params, ret) = split_method_descriptor(&name_and_type.r#type);
+                current_stack.pop_amount(params.len());
+                current_stack.push_descriptor(&ret, constant_pool);
+            }
+            Instruction::ldc(_) | Instruction::ldc_w(_) => {
+                let idx = match instruction {
+                    Instruction::ldc(idx) => *idx as u16,
+                    Instruction::ldc_w(idx) => *idx,
+                    _ => unreachable!(),
+                };
+                match constant_pool.get(idx).unwrap() {
+                    Constant::String(_) => current_stack.operands.push(VerificationType::OBJECT(
+                        constant_pool.add(Constant::Class("java/lang/String".to_string())),
+                    )),
+                    Constant::Integer(_) => current_stack.operands.push(VerificationType::INTEGER),
+                    Constant::Float(_) => current_stack.operands.push(VerificationType::FLOAT),
+                    _ => unreachable!(),
                 }
-                Instruction::iadd
-                | Instruction::isub
-                | Instruction::imul
-                | Instruction::idiv
-                | Instruction::irem => {
-                    bytes_idx += 1;
+            }
+            Instruction::ldc2_w(idx) => match constant_pool.get(*idx).unwrap() {
+                Constant::Long(_) => current_stack.operands.push(VerificationType::LONG),
+                Constant::Double(_) => current_stack.operands.push(VerificationType::DOUBLE),
+                _ => unreachable!(),
+            },
+            Instruction::lload(_) | Instruction::lconst_0 | Instruction::lconst_1 => {
+                current_stack.operands.push(VerificationType::LONG)
+            }
+            Instruction::fload(_)
+            | Instruction::fconst_0
+            | Instruction::fconst_1
+            | Instruction::fconst_2 => current_stack.operands.push(VerificationType::FLOAT),
+            Instruction::dload(_) | Instruction::dconst_0 | Instruction::dconst_1 => {
+                current_stack.operands.push(VerificationType::DOUBLE)
+            }
+            Instruction::aconst_null => current_stack.operands.push(VerificationType::NULL),
+            Instruction::new(_) => current_stack
+                .operands
+                .push(VerificationType::UNINITIALIZED(location)),
+            Instruction::aload_0 => current_stack
+                .operands
+                .push(current_stack.locals.first().unwrap().clone()),
+            Instruction::aload(idx) => current_stack
+                .operands
+                .push(current_stack.locals.get(*idx as usize).unwrap().clone()),
+            Instruction::iload(_) | Instruction::bipush(_) | Instruction::sipush(_) => {
+                current_stack.operands.push(VerificationType::INTEGER)
+            }
+            Instruction::ireturn
+            | Instruction::lreturn
+            | Instruction::freturn
+            | Instruction::dreturn
+            | Instruction::r#return
+            | Instruction::areturn => current_stack.operands.clear(),
+            Instruction::putfield(_) => current_stack.pop_amount(2),
+            Instruction::putstatic(_) => current_stack.pop_amount(1),
+            Instruction::getstatic(idx) => {
+                let Some(Constant::FieldRef(f)) = constant_pool.get(*idx).cloned() else {
+                    unreachable!()
+                };
+                current_stack.push_descriptor(&f.field.r#type, constant_pool);
+            }
+            Instruction::getfield(idx) => {
+                // Use idx to figure out what type the field has
+                // pops once and pushes type of field then
+                current_stack.operands.pop();
+                let Some(Constant::FieldRef(f)) = constant_pool.get(*idx).cloned() else {
+                    unreachable!()
+                };
+                current_stack.push_descriptor(&f.field.r#type, constant_pool);
+            }
+            Instruction::ineg
+            | Instruction::lneg
+            | Instruction::fneg
+            | Instruction::dneg
+            | Instruction::iinc(_, _)
+            | Instruction::i2c
+            | Instruction::i2b
+            | Instruction::i2s => {
+                // No changes in stack
+            }
+            // Conversions replace the value with one of another type
+            Instruction::l2i | Instruction::f2i | Instruction::d2i => {
+                current_stack.operands.pop();
+                current_stack.operands.push(VerificationType::INTEGER);
+            }
+            Instruction::i2l | Instruction::f2l | Instruction::d2l => {
+                current_stack.operands.pop();
+                current_stack.operands.push(VerificationType::LONG);
+            }
+            Instruction::i2f | Instruction::l2f | Instruction::d2f => {
+                current_stack.operands.pop();
+                current_stack.operands.push(VerificationType::FLOAT);
+            }
+            Instruction::i2d | Instruction::l2d | Instruction::f2d => {
+                current_stack.operands.pop();
+                current_stack.operands.push(VerificationType::DOUBLE);
+            }
+            Instruction::lcmp
+            | Instruction::fcmpl
+            | Instruction::fcmpg
+            | Instruction::dcmpl
+            | Instruction::dcmpg => {
+                current_stack.pop_amount(2);
+                current_stack.operands.push(VerificationType::INTEGER);
+            }
+            // Locals
+            Instruction::istore(x) => {
+                current_stack.operands.pop();
+                current_stack.set_local(*x, VerificationType::INTEGER);
+            }
+            Instruction::astore(x)
+            | Instruction::lstore(x)
+            | Instruction::fstore(x)
+            | Instruction::dstore(x) => {
+                let a = current_stack.operands.pop().unwrap();
+                current_stack.set_local(*x, a);
+            }
+            Instruction::iadd
+            | Instruction::isub
+            | Instruction::imul
+            | Instruction::idiv
+            | Instruction::irem
+            | Instruction::iand
+            | Instruction::ior
+            | Instruction::ixor
+            | Instruction::ishl
+            | Instruction::ishr
+            | Instruction::iushr
+            | Instruction::ladd
+            | Instruction::lsub
+            | Instruction::lmul
+            | Instruction::ldiv
+            | Instruction::lrem
+            | Instruction::land
+            | Instruction::lor
+            | Instruction::lxor
+            | Instruction::lshl
+            | Instruction::lshr
+            | Instruction::lushr
+            | Instruction::fadd
+            | Instruction::fsub
+            | Instruction::fmul
+            | Instruction::fdiv
+            | Instruction::frem
+            | Instruction::dadd
+            | Instruction::dsub
+            | Instruction::dmul
+            | Instruction::ddiv
+            | Instruction::drem => {
+                current_stack.operands.pop();
+            }
+            Instruction::dup => current_stack.dup(1, 0),
+            Instruction::dup_x1 => current_stack.dup(1, 1),
+            Instruction::dup_x2 => current_stack.dup(1, 2),
+            Instruction::swap => {
+                let len = current_stack.operands.len();
+                current_stack.operands.swap(len - 2, len - 1);
+            }
+            Instruction::dup2 => current_stack.dup(2, 0),
+            Instruction::dup2_x1 => current_stack.dup(2, 1),
+            Instruction::dup2_x2 => current_stack.dup(2, 2),
+            Instruction::pop => {
+                current_stack.operands.pop();
+            }
+            // Either a long or double or two other values are discarded
+            Instruction::pop2 => {
+                if current_stack.operands.pop().unwrap().size() == 1 {
                     current_stack.operands.pop();
                 }
-                Instruction::getfield(idx) => {
-                    // Use idx to figure out what type the field has
-                    // pops once and pushes type of field then
-                    bytes_idx += 3;
-                    current_stack.operands.pop();
-                    if let Constant::FieldRef(f) = constant_pool.get(*idx).unwrap() {
-                        match f.field.r#type.as_str() {
-                            "Z" | "C" | "I" => {
-                                current_stack.operands.push(VerificationType::INTEGER)
-                            }
-                            "Ljava/lang/String" => {
-                                current_stack.operands.push(VerificationType::OBJECT(
-                                    constant_pool
-                                        .index_of(&Constant::Utf8("Ljava/lang/String".to_string()))
-                                        .unwrap(),
-                                ))
-                            }
-                            _ => current_stack.operands.push(VerificationType::OBJECT(
-                                constant_pool.index_of_this_class(),
-                            )),
-                        }
-                    } else {
-                        unreachable!();
+            }
+            // Arrays are described by their descriptor in CONSTANT_Class entries
+            Instruction::newarray(atype) => {
+                current_stack.operands.pop();
+                let descriptor = match atype {
+                    4 => "[Z",
+                    5 => "[C",
+                    6 => "[F",
+                    7 => "[D",
+                    8 => "[B",
+                    9 => "[S",
+                    10 => "[I",
+                    11 => "[J",
+                    _ => unreachable!(),
+                };
+                current_stack.push_descriptor(descriptor, constant_pool);
+            }
+            Instruction::anewarray(idx) => {
+                current_stack.operands.pop();
+                let Some(Constant::Class(elem)) = constant_pool.get(*idx).cloned() else {
+                    unreachable!()
+                };
+                let descriptor = if elem.starts_with('[') {
+                    format!("[{elem}")
+                } else {
+                    format!("[L{elem};")
+                };
+                current_stack.push_descriptor(&descriptor, constant_pool);
+            }
+            Instruction::multianewarray(idx, dimensions) => {
+                current_stack.pop_amount(*dimensions as usize);
+                current_stack.operands.push(VerificationType::OBJECT(*idx));
+            }
+            Instruction::arraylength | Instruction::instanceof(_) => {
+                current_stack.operands.pop();
+                current_stack.operands.push(VerificationType::INTEGER);
+            }
+            Instruction::checkcast(idx) => {
+                current_stack.operands.pop();
+                current_stack.operands.push(VerificationType::OBJECT(*idx));
+            }
+            Instruction::iaload
+            | Instruction::baload
+            | Instruction::caload
+            | Instruction::saload => {
+                current_stack.pop_amount(2);
+                current_stack.operands.push(VerificationType::INTEGER);
+            }
+            Instruction::laload => {
+                current_stack.pop_amount(2);
+                current_stack.operands.push(VerificationType::LONG);
+            }
+            Instruction::faload => {
+                current_stack.pop_amount(2);
+                current_stack.operands.push(VerificationType::FLOAT);
+            }
+            Instruction::daload => {
+                current_stack.pop_amount(2);
+                current_stack.operands.push(VerificationType::DOUBLE);
+            }
+            Instruction::aaload => {
+                current_stack.operands.pop();
+                match current_stack.operands.pop().unwrap() {
+                    // The component of an array class is its descriptor without the first `[`
+                    VerificationType::OBJECT(idx) => {
+                        let Some(Constant::Class(array)) = constant_pool.get(idx).cloned() else {
+                            unreachable!()
+                        };
+                        current_stack.push_descriptor(&array[1..], constant_pool);
                     }
+                    // Loading from a null array throws, so the element can have any type
+                    _ => current_stack.operands.push(VerificationType::NULL),
                 }
-                Instruction::dup => {
-                    bytes_idx += 1;
-                    let last = current_stack.operands.last().unwrap();
-                    current_stack.operands.push(last.clone());
-                }
-                ////// JUMPS
-                ////// Here it gets interesting
-                // @Note: byte_offset is offset in bytes-vector, instruction_offset is relative offset instructions-vector
-                Instruction::ifeq(byte_offset, instruction_offset)
-                | Instruction::iflt(byte_offset, instruction_offset)
-                | Instruction::ifge(byte_offset, instruction_offset)
-                | Instruction::ifne(byte_offset, instruction_offset) => {
-                    assert!(
-                        *instruction_offset != 0,
-                        "Instruction {:?} has instruction_offset 0",
-                        code[instruction_idx]
-                    );
-                    if taken_branches_idxs.iter().any(|x| *x == instruction_idx) {
-                        return;
-                    }
-                    taken_branches_idxs.push(instruction_idx);
+            }
+            Instruction::iastore
+            | Instruction::lastore
+            | Instruction::fastore
+            | Instruction::dastore
+            | Instruction::bastore
+            | Instruction::castore
+            | Instruction::sastore
+            | Instruction::aastore => current_stack.pop_amount(3),
+            // Jumps only pop the value they compare, where they go is handled by `create_stacks`
+            Instruction::ifeq(_, _)
+            | Instruction::iflt(_, _)
+            | Instruction::ifge(_, _)
+            | Instruction::ifne(_, _) => {
+                current_stack.operands.pop();
+            }
+            Instruction::if_acmpne(_, _) => current_stack.pop_amount(2),
+            Instruction::tableswitch(_, _, _, _) | Instruction::lookupswitch(_, _, _) => {
+                current_stack.operands.pop();
+            }
+            Instruction::goto(_, _) => {}
+            Instruction::r#break(_) | Instruction::r#continue(_) => {
+                unreachable!("The jumps of break and continue are patched before computing frames")
+            }
+            Instruction::athrow => {
+                current_stack.operands.pop();
+            }
+            marker => {
+                unreachable!("The marker {marker:?} is removed before computing frames")
+            }
+        }
+    }
 
-                    current_stack.operands.pop();
-                    let mut new_stack = current_stack.clone();
-                    new_stack.location = (bytes_idx as i16 + *byte_offset) as u16;
-                    stacks.push(new_stack.clone());
-                    Self::create_stacks_for_branch(
-                        code,
-                        (instruction_idx as i16 + *instruction_offset) as usize,
-                        new_stack.location as usize,
-                        &mut new_stack,
-                        stacks,
-                        taken_branches_idxs,
-                        constant_pool,
-                    );
-                    bytes_idx += 3;
+    /// Computes the stack for every location that is jumped to.
+    /// Since a location may be reached via several paths (e.g. the condition of a while-loop
+    /// is reached both before the first and after every other iteration), the stacks of all
+    /// paths leading to it are merged, so the resulting frame is valid for each of them.
+    /// Handlers of exceptions are reached from every instruction they handle the exceptions of,
+    /// with the locals before that instruction and only the caught exception as operand.
+    fn create_stacks(
+        code: &[Instruction],
+        initial_stack: VerificationStack,
+        exception_table: &[ExceptionHandler],
+        constant_pool: &mut ConstantPool,
+        hierarchy: &ClassHierarchy,
+    ) -> Vec<VerificationStack> {
+        if code.is_empty() {
+            return vec![];
+        }
+        // Location of every instruction in the bytes-vector
+        let mut locations = Vec::with_capacity(code.len());
+        let mut location = 0;
+        for instruction in code {
+            locations.push(location);
+            location += instruction.as_bytes().len() as u16;
+        }
+        let jump_targets = |instruction_idx: usize| {
+            code[instruction_idx]
+                .jump_offsets()
+                .into_iter()
+                .map(|byte_offset| {
+                    let target = (locations[instruction_idx] as i32 + byte_offset) as u16;
+                    locations.binary_search(&target).unwrap_or_else(|_| {
+                        panic!(
+                            "Instruction {:?} jumps to {target}, where no instruction starts",
+                            code[instruction_idx]
+                        )
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+        let handler_index = |handler: &ExceptionHandler| {
+            locations
+                .binary_search(&handler.handler)
+                .expect("Exception handler doesn't start at an instruction")
+        };
+
+        // incoming[i] is the stack right before the i-th instruction is executed
+        let mut incoming: Vec<Option<VerificationStack>> = vec![None; code.len()];
+        incoming[0] = Some(initial_stack);
+        let mut worklist = vec![0];
+        while let Some(instruction_idx) = worklist.pop() {
+            let mut current_stack = incoming[instruction_idx].clone().unwrap();
+            let location = locations[instruction_idx];
+            for handler in exception_table
+                .iter()
+                .filter(|h| h.start <= location && location < h.end)
+            {
+                let caught = match handler.catch_type {
+                    0 => constant_pool.add(Constant::Class("java/lang/Throwable".to_string())),
+                    catch_type => catch_type,
+                };
+                let handler_stack = VerificationStack {
+                    location: 0,
+                    locals: current_stack.locals.clone(),
+                    operands: vec![VerificationType::OBJECT(caught)],
+                };
+                let handler_idx = handler_index(handler);
+                let merged = match &incoming[handler_idx] {
+                    Some(stack) => stack.merge(&handler_stack, constant_pool, hierarchy),
+                    None => handler_stack,
+                };
+                if incoming[handler_idx].as_ref() != Some(&merged) {
+                    incoming[handler_idx] = Some(merged);
+                    worklist.push(handler_idx);
                 }
-                Instruction::goto(byte_offset, instruction_offset) => {
-                    // -1 because +1 is added at the end of the loop again
-                    if taken_branches_idxs.iter().any(|x| *x == instruction_idx) {
-                        return;
-                    }
-                    taken_branches_idxs.push(instruction_idx);
-                    instruction_idx = (instruction_idx as i16 + *instruction_offset) as usize;
-                    current_stack.location = (bytes_idx as i16 + *byte_offset) as u16;
-                    stacks.push(current_stack.clone());
+            }
+            Self::simulate_instruction(
+                &code[instruction_idx],
+                locations[instruction_idx],
+                &mut current_stack,
+                constant_pool,
+            );
+            let mut successors = jump_targets(instruction_idx);
+            if code[instruction_idx].falls_through() && instruction_idx + 1 < code.len() {
+                successors.push(instruction_idx + 1);
+            }
+            for successor in successors {
+                let merged = match &incoming[successor] {
+                    Some(stack) => stack.merge(&current_stack, constant_pool, hierarchy),
+                    None => current_stack.clone(),
+                };
+                if incoming[successor].as_ref() != Some(&merged) {
+                    incoming[successor] = Some(merged);
+                    worklist.push(successor);
                 }
             }
-            instruction_idx += 1;
         }
+
+        let mut targets: Vec<usize> = (0..code.len())
+            .flat_map(jump_targets)
+            .chain(exception_table.iter().map(handler_index))
+            .collect();
+        targets.sort();
+        targets.dedup();
+        targets
+            .into_iter()
+            .filter_map(|target| {
+                incoming[target].clone().map(|mut stack| {
+                    stack.location = locations[target];
+                    stack
+                })
+            })
+            .collect()
     }
 
     // @Note: Expects the code to already be expanded
+    /// `this_type` is the type of `this` at the start of the method or None if the method is static
     pub(crate) fn new(
         code: &[Instruction],
         params: &[(Type, String)],
+        exception_table: &[ExceptionHandler],
         constant_pool: &mut ConstantPool,
+        hierarchy: &ClassHierarchy,
+        this_type: Option<VerificationType>,
     ) -> Self {
         // We calculate the actual frames here
         // We do this via 2 passes
-        // First, we create the stacks for all different branches
-        // storing the snapshot of the stack for every location that we jump to
+        // First, we create the stacks for all locations that are jumped to
         // Second, we create a Frame from each of those stacks
         let mut frames = vec![];
-        let mut stacks = vec![];
-        let mut initial_locals: Vec<VerificationType> = vec![VerificationType::OBJECT(
-            constant_pool.index_of_this_class(),
-        )];
-        initial_locals.append(
-            &mut params
-                .iter()
-                .map(|(t, _)| match t {
-                    Type::Bool | Type::Char | Type::Int => VerificationType::INTEGER,
-                    Type::Null => VerificationType::NULL,
-                    Type::String => VerificationType::OBJECT(
-                        constant_pool.add(Constant::Utf8("Ljava/lang/String".to_string())),
-                    ),
-                    Type::Class(name) => VerificationType::OBJECT(
-                        constant_pool
-                            .index_of(&Constant::Class(name.to_string()))
-                            .unwrap(),
-                    ),
-                    Type::Void => unreachable!(),
-                })
-                .collect(),
-        );
+        let mut initial_locals: Vec<VerificationType> = this_type.into_iter().collect();
+        for (t, _) in params {
+            let t = VerificationType::from_descriptor(&t.to_ir_string(), constant_pool).unwrap();
+            // The second slot of a long or double is TOP
+            if t.size() == 2 {
+                initial_locals.extend([t, VerificationType::TOP]);
+            } else {
+                initial_locals.push(t);
+            }
+        }
 
-        let mut current_stack = VerificationStack {
-            location: 0,
-            locals: initial_locals.clone(),
-            operands: vec![],
-        };
-        Self::create_stacks_for_branch(
+        let stacks = Self::create_stacks(
             code,
-            0,
-            0,
-            &mut current_stack,
-            &mut stacks,
-            &mut vec![],
+            VerificationStack {
+                location: 0,
+                locals: initial_locals.clone(),
+                operands: vec![],
+            },
+            exception_table,
             constant_pool,
-        );
-        sort_unique(
-            &mut stacks,
-            |a, b| a.location == b.location,
-            |a, b| a.location < b.location,
+            hierarchy,
         );
 
         // First stack/frame are implicit
         let mut last_stack = VerificationStack {
             location: 0,
-            locals: initial_locals,
+            locals: frame_locals(&initial_locals),
             operands: vec![],
         };
         let mut is_first = true;
-        for stack in stacks {
+        for mut stack in stacks {
+            stack.locals = frame_locals(&stack.locals);
             let offset_delta = stack.location - last_stack.location - if !is_first { 1 } else { 0 };
             is_first = false;
-            let frame = if stack == last_stack {
+            let frame = if stack.operands.is_empty() && stack.locals == last_stack.locals {
                 if offset_delta < 64 {
                     StackMapFrame::SAME(offset_delta as u8)
                 } else {
@@ -468,21 +740,98 @@ impl StackMapFrame {
         }
     }
 }
+/// Frames describe the locals without the TOP in the second slot of longs and doubles,
+/// which is implied by them
+fn frame_locals(locals: &[VerificationType]) -> Vec<VerificationType> {
+    let mut result = vec![];
+    let mut slots = locals.iter();
+    while let Some(t) = slots.next() {
+        if t.size() == 2 {
+            slots.next();
+        }
+        result.push(t.clone());
+    }
+    result
+}
+
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum VerificationType {
     TOP,
     INTEGER,
+    FLOAT,
+    DOUBLE,
+    LONG,
     NULL,
     UNINITIALIZED_THIS,
-    OBJECT(u16), // index in constant pool
+    OBJECT(u16),        // index in constant pool
+    UNINITIALIZED(u16), // location of the `new` instruction that created the object
 }
 
 impl VerificationType {
+    /// The most specific type values of both types can be used as, None if there is none
+    fn merge(
+        a: &Self,
+        b: &Self,
+        constant_pool: &mut ConstantPool,
+        hierarchy: &ClassHierarchy,
+    ) -> Option<Self> {
+        match (a, b) {
+            (a, b) if a == b => Some(a.clone()),
+            (VerificationType::NULL, o @ VerificationType::OBJECT(_))
+            | (o @ VerificationType::OBJECT(_), VerificationType::NULL) => Some(o.clone()),
+            (VerificationType::OBJECT(a), VerificationType::OBJECT(b)) => {
+                let (Some(Constant::Class(a)), Some(Constant::Class(b))) = (
+                    constant_pool.get(*a).cloned(),
+                    constant_pool.get(*b).cloned(),
+                ) else {
+                    unreachable!()
+                };
+                Some(VerificationType::OBJECT(
+                    constant_pool.add(Constant::Class(hierarchy.common_superclass(&a, &b))),
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// The number of slots a value of this type takes up, which is 2 for longs and doubles
+    fn size(&self) -> usize {
+        match self {
+            VerificationType::LONG | VerificationType::DOUBLE => 2,
+            _ => 1,
+        }
+    }
+
+    /// Gets the verification type of a value with the given field descriptor, None for void
+    pub(crate) fn from_descriptor(
+        descriptor: &str,
+        constant_pool: &mut ConstantPool,
+    ) -> Option<Self> {
+        match descriptor {
+            "V" => None,
+            "Z" | "B" | "C" | "S" | "I" => Some(VerificationType::INTEGER),
+            "F" => Some(VerificationType::FLOAT),
+            "D" => Some(VerificationType::DOUBLE),
+            "J" => Some(VerificationType::LONG),
+            // Arrays are described by their descriptor in CONSTANT_Class entries
+            d if d.starts_with('[') => Some(VerificationType::OBJECT(
+                constant_pool.add(Constant::Class(d.to_string())),
+            )),
+            d if d.starts_with('L') && d.ends_with(';') => Some(VerificationType::OBJECT(
+                constant_pool.add(Constant::Class(d[1..d.len() - 1].to_string())),
+            )),
+            d => panic!("Invalid descriptor: {d}"),
+        }
+    }
+
     pub(crate) fn as_bytes(&self) -> Vec<u8> {
         match self {
             VerificationType::TOP => vec![0],
             VerificationType::INTEGER => vec![1],
+            VerificationType::FLOAT => vec![2],
+            VerificationType::DOUBLE => vec![3],
+            VerificationType::LONG => vec![4],
             VerificationType::NULL => vec![5],
             VerificationType::UNINITIALIZED_THIS => vec![6],
             VerificationType::OBJECT(cp_idx) => {
@@ -491,6 +840,12 @@ impl VerificationType {
                 v.extend_from_slice(&cp_idx.to_be_bytes());
                 v
             }
+            VerificationType::UNINITIALIZED(offset) => {
+                let mut v = Vec::with_capacity(4);
+                v.push(8);
+                v.extend_from_slice(&offset.to_be_bytes());
+                v
+            }
         }
     }
 }
@@ -520,3 +875,128 @@ impl PartialEq for VerificationStack {
         true
     }
 }
+
+impl VerificationStack {
+    fn pop_amount(&mut self, amount: usize) {
+        self.operands.truncate(self.operands.len() - amount);
+    }
+
+    fn push_descriptor(&mut self, descriptor: &str, constant_pool: &mut ConstantPool) {
+        if let Some(t) = VerificationType::from_descriptor(descriptor, constant_pool) {
+            self.operands.push(t);
+        }
+    }
+
+    /// Sets the type of the local variable at the given slot. Longs and doubles take up two slots,
+    /// the second of which is TOP.
+    fn set_local(&mut self, idx: u8, t: VerificationType) {
+        let idx = idx as usize;
+        let size = t.size();
+        if idx + size > self.locals.len() {
+            self.locals.resize(idx + size, VerificationType::TOP);
+        }
+        // Overwriting the second slot of a long or double makes it unusable
+        if idx > 0 && self.locals[idx - 1].size() == 2 {
+            self.locals[idx - 1] = VerificationType::TOP;
+        }
+        self.locals[idx] = t;
+        if size == 2 {
+            self.locals[idx + 1] = VerificationType::TOP;
+        }
+    }
+
+    /// Duplicates the values in the top `count` slots of the operands, inserting them beneath
+    /// the values in the `skip` slots below them, like the dup instructions do
+    fn dup(&mut self, count: usize, skip: usize) {
+        let below_slots = |operands: &[VerificationType], end: usize, slots: usize| {
+            let mut start = end;
+            let mut taken = 0;
+            while taken < slots {
+                start -= 1;
+                taken += operands[start].size();
+            }
+            start
+        };
+        let start = below_slots(&self.operands, self.operands.len(), count);
+        let copied = self.operands[start..].to_vec();
+        let insert_at = below_slots(&self.operands, start, skip);
+        self.operands.splice(insert_at..insert_at, copied);
+    }
+
+    /// Replaces every occurence of `old` in the locals and operands with `new`
+    fn replace(&mut self, old: &VerificationType, new: &VerificationType) {
+        self.locals
+            .iter_mut()
+            .chain(self.operands.iter_mut())
+            .filter(|t| *t == old)
+            .for_each(|t| *t = new.clone());
+    }
+
+    /// Merges the stacks of two paths leading to the same location.
+    /// Locals with incompatible types in both paths can't be used afterwards, so they become TOP.
+    fn merge(
+        &self,
+        other: &Self,
+        constant_pool: &mut ConstantPool,
+        hierarchy: &ClassHierarchy,
+    ) -> Self {
+        assert_eq!(
+            self.operands.len(),
+            other.operands.len(),
+            "Stack heights differ when merging {:?} and {:?}",
+            self,
+            other
+        );
+        let mut locals: Vec<VerificationType> = (0..self.locals.len().max(other.locals.len()))
+            .map(|i| match (self.locals.get(i), other.locals.get(i)) {
+                (Some(a), Some(b)) => VerificationType::merge(a, b, constant_pool, hierarchy)
+                    .unwrap_or(VerificationType::TOP),
+                _ => VerificationType::TOP,
+            })
+            .collect();
+        while locals.last() == Some(&VerificationType::TOP) {
+            locals.pop();
+        }
+        let operands = self
+            .operands
+            .iter()
+            .zip(other.operands.iter())
+            .map(|(a, b)| {
+                VerificationType::merge(a, b, constant_pool, hierarchy).unwrap_or_else(|| {
+                    panic!("Can't merge {:?} and {:?} on the operand stack", a, b)
+                })
+            })
+            .collect();
+        VerificationStack {
+            location: self.location,
+            locals,
+            operands,
+        }
+    }
+}
+
+/// Splits a method descriptor like `(ILjava/lang/String;)V` into the descriptors of its
+/// parameters and its return type
+pub(crate) fn split_method_descriptor(descriptor: &str) -> (Vec<String>, String) {
+    let (params, ret) = descriptor[1..].split_once(')').unwrap();
+    let mut result = vec![];
+    let mut chars = params.chars();
+    let mut current = String::new();
+    while let Some(c) = chars.next() {
+        current.push(c);
+        match c {
+            '[' => continue,
+            'L' => {
+                for c in chars.by_ref() {
+                    current.push(c);
+                    if c == ';' {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+        result.push(std::mem::take(&mut current));
+    }
+    (result, ret.to_string())
+}
@@ -5,7 +5,10 @@
 extern crate pest;
 extern crate pest_derive;
 
-use crate::types::{BinaryOp, Class, Expr, FieldDecl, MethodDecl, Stmt, StmtExpr, Type};
+use crate::types::{
+    BinaryOp, CatchClause, Class, Constructor, EnumConstant, Expr, FieldDecl, MethodDecl,
+    Modifiers, Stmt, StmtExpr, SwitchCase, Type, Visibility,
+};
 use pest::error::Error;
 use pest::iterators::{Pair, Pairs};
 use pest::Parser;
@@ -22,36 +25,112 @@ pub fn parse_programm(file: &str) -> Result<Vec<Class>, Error<Rule>> {
     if prg.as_rule() != Rule::Program {
         panic!();
     }
-    let pased_clases = prg.into_inner().map(parse_class).collect();
+    let pased_clases = prg
+        .into_inner()
+        .filter(|pair| pair.as_rule() != Rule::EOI)
+        .flat_map(|pair| parse_class(pair, None))
+        .collect();
     println!("Parsed program successfully!🎉✍️");
     Ok(pased_clases)
 }
 
-fn parse_class(pair: Pair<Rule>) -> Class {
+/// Parses a class, interface or enum, which is nested in the class `outer_class`, if given.
+/// Nested classes are returned as separate classes after the class declaring them.
+fn parse_class(pair: Pair<Rule>, outer_class: Option<&str>) -> Vec<Class> {
     match pair.as_rule() {
-        Rule::ClassDecl => {
+        Rule::ClassDecl | Rule::InterfaceDecl | Rule::EnumDecl => {
+            let is_interface = pair.as_rule() == Rule::InterfaceDecl;
+            let is_enum = pair.as_rule() == Rule::EnumDecl;
             let mut inners = pair.into_inner();
-            let other_name = next_id(&mut inners);
+            // Nested classes are members of their outer class, so they can be private and static
+            let nested_modifiers: &[&str] = match outer_class {
+                Some(_) => &["protected", "private", "static"],
+                None => &[],
+            };
+            let allowed = |modifiers: &[&'static str]| [modifiers, nested_modifiers].concat();
+            let (mut modifiers, is_abstract) = if is_interface {
+                let (modifiers, _) =
+                    parse_modifiers(inners.next().unwrap(), &allowed(&["public", "abstract"]));
+                (modifiers, true)
+            } else if is_enum {
+                parse_modifiers(inners.next().unwrap(), &allowed(&["public"]))
+            } else {
+                parse_modifiers(
+                    inners.next().unwrap(),
+                    &allowed(&["public", "abstract", "final"]),
+                )
+            };
+            // Nested interfaces and enums are implicitly static
+            if outer_class.is_some() && (is_interface || is_enum) {
+                modifiers.is_static = true;
+            }
+            let other_name = inners.next().unwrap().as_str().trim().to_string();
+            let name = match outer_class {
+                Some(outer_class) => format!("{outer_class}${other_name}"),
+                None => other_name.clone(),
+            };
+            let mut nested_classes = vec![];
+            let mut super_class = None;
+            let mut interfaces = vec![];
+            let mut enum_constants = vec![];
             let mut fields = vec![];
+            let mut constructors = vec![];
             let mut methods = vec![];
+            let mut static_blocks = vec![];
             for fieldOrMethod in inners {
                 match fieldOrMethod.as_rule() {
                     Rule::FieldDecl => {
                         fields.append(&mut parse_field(fieldOrMethod));
                     }
+                    Rule::SuperClass => {
+                        super_class =
+                            Some(parse_class_name(fieldOrMethod.into_inner().next().unwrap()));
+                    }
+                    Rule::Interfaces | Rule::SuperInterfaces => {
+                        interfaces = fieldOrMethod.into_inner().map(parse_class_name).collect();
+                    }
+                    Rule::EnumConstants => {
+                        enum_constants = fieldOrMethod
+                            .into_inner()
+                            .map(parse_enum_constant)
+                            .collect();
+                    }
+                    Rule::ConstructorDecl => {
+                        constructors.push(parse_constructor(fieldOrMethod, &other_name, is_enum));
+                    }
                     Rule::MethodDecl => {
-                        methods.push(parse_method(fieldOrMethod));
+                        methods.push(parse_method(fieldOrMethod, is_interface));
+                    }
+                    Rule::StaticInit => {
+                        let block = parse_BlockStmt(fieldOrMethod.into_inner().next().unwrap());
+                        static_blocks.push((fields.len(), Stmt::Block(block)));
+                    }
+                    Rule::ClassDecl | Rule::InterfaceDecl | Rule::EnumDecl => {
+                        nested_classes.append(&mut parse_class(fieldOrMethod, Some(&name)));
                     }
                     _ => {
                         unreachable!()
                     }
                 };
             }
-            Class {
-                name: other_name,
+            let class = Class {
+                modifiers,
+                name,
+                is_interface,
+                is_abstract,
+                is_enum,
+                super_class,
+                interfaces,
+                enum_constants,
                 fields,
+                constructors,
                 methods,
-            }
+                static_blocks,
+                outer_class: outer_class.map(str::to_string),
+                is_local: false,
+                captured_vars: vec![],
+            };
+            std::iter::once(class).chain(nested_classes).collect()
         }
         _ => unreachable!(),
     }
@@ -64,26 +143,81 @@ fn next_id(inners: &mut Pairs<Rule>) -> String {
     inners.next().unwrap().as_str().trim().to_string()
 }
 
-fn parse_method(pair: Pair<Rule>) -> MethodDecl {
+/// Parses the modifiers of a declaration, which may only use the `allowed` ones.
+/// Returns whether the declaration is abstract separately, since that isn't part of `Modifiers`.
+fn parse_modifiers(pair: Pair<Rule>, allowed: &[&str]) -> (Modifiers, bool) {
+    assert_eq!(pair.as_rule(), Rule::Modifiers);
+    let mut modifiers = Modifiers::default();
+    let mut is_abstract = false;
+    let mut seen: Vec<&str> = vec![];
+    for modifier in pair.into_inner() {
+        let modifier = modifier.as_str();
+        if !allowed.contains(&modifier) {
+            panic!("Modifier {modifier} not allowed here");
+        }
+        if seen.contains(&modifier) {
+            panic!("Repeated modifier {modifier}");
+        }
+        let conflicting = match modifier {
+            "public" | "protected" | "private" => &["public", "protected", "private"][..],
+            "abstract" => &["static", "final", "private"],
+            "static" | "final" | "private" => &["abstract"],
+            _ => &[],
+        };
+        if let Some(other) = seen.iter().find(|other| conflicting.contains(other)) {
+            panic!("Illegal combination of modifiers {other} and {modifier}");
+        }
+        match modifier {
+            "public" => modifiers.visibility = Visibility::Public,
+            "protected" => modifiers.visibility = Visibility::Protected,
+            "private" => modifiers.visibility = Visibility::Private,
+            "static" => modifiers.is_static = true,
+            "final" => modifiers.is_final = true,
+            "abstract" => is_abstract = true,
+            _ => unreachable!(),
+        }
+        seen.push(modifier);
+    }
+    (modifiers, is_abstract)
+}
+
+fn parse_method(pair: Pair<Rule>, is_interface: bool) -> MethodDecl {
     match pair.as_rule() {
+        // Abstract methods are the ones without a body
         Rule::MethodDecl => {
             let mut inners = pair.into_inner();
+            let (modifiers, is_abstract) = if is_interface {
+                // Methods of interfaces are implicitly public and abstract
+                parse_modifiers(inners.next().unwrap(), &["public", "abstract"]);
+                (
+                    Modifiers {
+                        visibility: Visibility::Public,
+                        ..Default::default()
+                    },
+                    true,
+                )
+            } else {
+                parse_modifiers(
+                    inners.next().unwrap(),
+                    &[
+                        "public",
+                        "protected",
+                        "private",
+                        "static",
+                        "final",
+                        "abstract",
+                    ],
+                )
+            };
             let ret_type = parse_Type(inners.next().unwrap());
             let method_name = next_id(&mut inners);
             let mut params = vec![];
+            let mut throws = vec![];
             let mut body = None;
             for p in inners {
                 match p.as_rule() {
-                    Rule::ParamDeclList => {
-                        let mut inner_param = p.into_inner();
-                        for parm in inner_param {
-                            assert_eq!(parm.as_rule(), Rule::ParamDecl);
-                            let mut inTheParm = parm.into_inner();
-                            let param_type = parse_Type(inTheParm.next().unwrap());
-                            let param_name = next_id(&mut inTheParm);
-                            params.push((param_type, param_name));
-                        }
-                    }
+                    Rule::ParamDeclList => params = parse_params(p),
+                    Rule::Throws => throws = parse_class_names(p),
                     Rule::BlockStmt => body = Some(parse_BlockStmt(p)),
                     _ => {
                         dbg!("REGEL NICHT ABGEFANGEN: ");
@@ -93,11 +227,18 @@ fn parse_method(pair: Pair<Rule>) -> MethodDecl {
                 };
             }
 
+            match (is_abstract, &body) {
+                (true, Some(_)) => panic!("Abstract method {method_name} can't have a body"),
+                (false, None) => panic!("Method {method_name} is missing a body"),
+                _ => {}
+            }
             MethodDecl {
+                modifiers,
                 ret_type,
                 name: method_name,
                 params,
-                body: Stmt::Block(body.unwrap()),
+                throws,
+                body: body.map(Stmt::Block),
             }
         }
         _ => {
@@ -108,6 +249,77 @@ fn parse_method(pair: Pair<Rule>) -> MethodDecl {
     }
 }
 
+/// Parses a constant of an enum with the arguments passed to the constructor of the enum
+fn parse_enum_constant(pair: Pair<Rule>) -> EnumConstant {
+    assert_eq!(pair.as_rule(), Rule::EnumConstant);
+    let mut inners = pair.into_inner();
+    let name = next_id(&mut inners);
+    let args = match inners.next() {
+        Some(paramList) => paramList.into_inner().map(parse_expr).collect(),
+        None => vec![],
+    };
+    EnumConstant { name, args }
+}
+
+/// Parses a constructor of the class `class_name`. The constructors of enums can only be called
+/// to create their constants, so they are implicitly private.
+fn parse_constructor(pair: Pair<Rule>, class_name: &str, is_enum: bool) -> Constructor {
+    assert_eq!(pair.as_rule(), Rule::ConstructorDecl);
+    let mut inners = pair.into_inner();
+    let allowed: &[&str] = if is_enum {
+        &["private"]
+    } else {
+        &["public", "protected", "private"]
+    };
+    let (mut modifiers, _) = parse_modifiers(inners.next().unwrap(), allowed);
+    if is_enum {
+        modifiers.visibility = Visibility::Private;
+    }
+    let constructor_name = next_id(&mut inners);
+    if constructor_name != class_name {
+        panic!(
+            "Method {} is missing a return type, constructors must have the name of their class {}",
+            constructor_name, class_name
+        );
+    }
+    let mut params = vec![];
+    let mut throws = vec![];
+    let mut body = None;
+    for p in inners {
+        match p.as_rule() {
+            Rule::ParamDeclList => params = parse_params(p),
+            Rule::Throws => throws = parse_class_names(p),
+            Rule::BlockStmt => body = Some(parse_BlockStmt(p)),
+            _ => unreachable!(),
+        };
+    }
+    Constructor {
+        modifiers,
+        params,
+        throws,
+        body: Stmt::Block(body.unwrap()),
+    }
+}
+
+/// Parses the names of the exception classes of a `throws` clause or a catch clause
+fn parse_class_names(pair: Pair<Rule>) -> Vec<String> {
+    assert!(matches!(pair.as_rule(), Rule::Throws | Rule::CatchClasses));
+    pair.into_inner().map(parse_class_name).collect()
+}
+
+fn parse_params(pair: Pair<Rule>) -> Vec<(Type, String)> {
+    assert_eq!(pair.as_rule(), Rule::ParamDeclList);
+    let mut params = vec![];
+    for parm in pair.into_inner() {
+        assert_eq!(parm.as_rule(), Rule::ParamDecl);
+        let mut inTheParm = parm.into_inner();
+        let param_type = parse_Type(inTheParm.next().unwrap());
+        let param_name = next_id(&mut inTheParm);
+        params.push((param_type, param_name));
+    }
+    params
+}
+
 fn parse_BlockStmt(pair: Pair<Rule>) -> Vec<Stmt> {
     /*println!(
         "parse_BlockStmt: rule = {:?}, str = {}",
@@ -145,6 +357,82 @@ fn parse_Stmt(pair: Pair<Rule>) -> Vec<Stmt> {
             let Stmt = parse_Stmt(inners.next().unwrap());
             vec![Stmt::While(Expr, Box::new(Stmt::Block(Stmt)))]
         }
+        Rule::DoWhileStmt => {
+            let mut inners = pair.into_inner();
+            let Stmt = parse_Stmt(inners.next().unwrap());
+            let Expr = parse_expr(inners.next().unwrap());
+            vec![Stmt::DoWhile(Box::new(Stmt::Block(Stmt)), Expr)]
+        }
+        Rule::ForStmt => {
+            let mut init = vec![];
+            let mut cond = None;
+            let mut update = vec![];
+            let mut body = vec![];
+            for inner in pair.into_inner() {
+                match inner.as_rule() {
+                    Rule::LocalVarDeclStmt => init = parse_Stmt(inner),
+                    Rule::ForInit => init = parse_stmt_expr_list(inner),
+                    Rule::Expr => cond = Some(parse_expr(inner)),
+                    Rule::ForUpdate => update = parse_stmt_expr_list(inner),
+                    Rule::Stmt => body = parse_Stmt(inner),
+                    _ => unreachable!(),
+                }
+            }
+            vec![Stmt::For(init, cond, update, Box::new(Stmt::Block(body)))]
+        }
+        Rule::ForEachStmt => {
+            let mut inners = pair.into_inner();
+            let typeJ = parse_Type(inners.next().unwrap());
+            let name = next_id(&mut inners);
+            let Expr = parse_expr(inners.next().unwrap());
+            let Stmt = parse_Stmt(inners.next().unwrap());
+            vec![Stmt::ForEach(
+                typeJ,
+                name,
+                Expr,
+                Box::new(Stmt::Block(Stmt)),
+            )]
+        }
+        Rule::SwitchStmt => {
+            let mut inners = pair.into_inner();
+            let Expr = parse_expr(inners.next().unwrap());
+            let cases = parse_switch_block(inners.next().unwrap(), false);
+            vec![Stmt::Switch(Expr, cases)]
+        }
+        Rule::YieldStmt => {
+            let mut inners = pair.into_inner();
+            let Expr = parse_expr(inners.next().unwrap());
+            vec![Stmt::Yield(Expr)]
+        }
+        Rule::BreakStmt => {
+            let label = pair
+                .into_inner()
+                .next()
+                .map(|id| id.as_str().trim().to_string());
+            vec![Stmt::Break(label)]
+        }
+        Rule::ContinueStmt => {
+            let label = pair
+                .into_inner()
+                .next()
+                .map(|id| id.as_str().trim().to_string());
+            vec![Stmt::Continue(label)]
+        }
+        Rule::LabeledStmt => {
+            let mut inners = pair.into_inner();
+            let label = next_id(&mut inners);
+            let Stmt = inners.next().unwrap();
+            // Blocks are parsed as the list of their statements, but a labeled block containing
+            // only a loop isn't a labeled loop
+            let is_block = Stmt.clone().into_inner().next().unwrap().as_rule() == Rule::BlockStmt;
+            let mut Stmt = parse_Stmt(Stmt);
+            let Stmt = if Stmt.len() == 1 && !is_block {
+                Stmt.pop().unwrap()
+            } else {
+                Stmt::Block(Stmt)
+            };
+            vec![Stmt::Labeled(label, Box::new(Stmt))]
+        }
         Rule::IfElseStmt => {
             let mut inners = pair.into_inner();
 
@@ -166,11 +454,54 @@ fn parse_Stmt(pair: Pair<Rule>) -> Vec<Stmt> {
             let Stmt = parse_Stmt(inners.next().unwrap());
             vec![Stmt::If(Expr, Box::new(Stmt::Block(Stmt)), None)]
         }
+        Rule::ThrowStmt => {
+            let mut inners = pair.into_inner();
+            let Expr = parse_expr(inners.next().unwrap());
+            vec![Stmt::Throw(Expr)]
+        }
+        Rule::TryStmt => {
+            let mut inners = pair.into_inner();
+            let body = parse_BlockStmt(inners.next().unwrap());
+            let mut catches = vec![];
+            let mut finally = None;
+            for inner in inners {
+                match inner.as_rule() {
+                    Rule::CatchClause => {
+                        let mut clause = inner.into_inner();
+                        let classes = parse_class_names(clause.next().unwrap());
+                        let name = next_id(&mut clause);
+                        let body = parse_BlockStmt(clause.next().unwrap());
+                        catches.push(CatchClause {
+                            classes,
+                            name,
+                            body: Stmt::Block(body),
+                        });
+                    }
+                    Rule::Finally => {
+                        let block = parse_BlockStmt(inner.into_inner().next().unwrap());
+                        finally = Some(Box::new(Stmt::Block(block)));
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            vec![Stmt::Try(Box::new(Stmt::Block(body)), catches, finally)]
+        }
         Rule::ReturnStmt => {
             let mut inners = pair.into_inner();
 
-            let Expr = parse_expr(inners.next().unwrap());
-            vec![Stmt::Return(Expr)]
+            match inners.next() {
+                Some(expr) => vec![Stmt::Return(parse_expr(expr))],
+                None => vec![Stmt::ReturnVoid],
+            }
+        }
+        Rule::LocalClassDecl => {
+            let decl = pair.into_inner().next().unwrap();
+            // Local classes aren't members of a class, so they can't have an access modifier or be static
+            parse_modifiers(
+                decl.clone().into_inner().next().unwrap(),
+                &["abstract", "final"],
+            );
+            vec![Stmt::LocalClassDecl(parse_class(decl, None))]
         }
         Rule::LocalVarDeclStmt => {
             let mut inners = pair.into_inner();
@@ -182,30 +513,44 @@ fn parse_Stmt(pair: Pair<Rule>) -> Vec<Stmt> {
             for inner in inners {
                 match inner.as_rule() {
                     Rule::Identifier => {
-                        if last_var_name.is_some() {
-                            result.push(Stmt::LocalVarDecl(typeJ.clone(), last_var_name.unwrap()));
+                        if let Some(last_var_name) = last_var_name {
+                            result.push(Stmt::LocalVarDecl(typeJ.clone(), last_var_name));
                         }
                         last_var_name = Some(inner.as_str().trim().to_string());
                     }
-                    Rule::Expr => {
+                    Rule::Expr | Rule::ArrayInit | Rule::LambdaExpr => {
                         result.push(Stmt::LocalVarDecl(
                             typeJ.clone(),
                             last_var_name.as_ref().unwrap().clone(),
                         ));
                         result.push(Stmt::StmtExprStmt(StmtExpr::Assign(
                             Expr::LocalOrFieldVar(last_var_name.unwrap()),
-                            parse_expr(inner),
+                            parse_var_init(inner, &typeJ),
                         )));
                         last_var_name = None;
                     }
                     _ => unreachable!(),
                 }
             }
-            if last_var_name.is_some() {
-                result.push(Stmt::LocalVarDecl(typeJ, last_var_name.unwrap()));
+            if let Some(last_var_name) = last_var_name {
+                result.push(Stmt::LocalVarDecl(typeJ, last_var_name));
             }
             result
         }
+        Rule::ThisCallStmt | Rule::SuperCallStmt => {
+            let rule = pair.as_rule();
+            let mut exprList: Vec<Expr> = vec![];
+            if let Some(paramList) = pair.into_inner().next() {
+                for param in paramList.into_inner() {
+                    exprList.push(parse_expr(param));
+                }
+            }
+            if rule == Rule::ThisCallStmt {
+                vec![Stmt::StmtExprStmt(StmtExpr::ThisCall(exprList))]
+            } else {
+                vec![Stmt::StmtExprStmt(StmtExpr::SuperCall(exprList))]
+            }
+        }
         Rule::StmtExpr => {
             vec![Stmt::StmtExprStmt(parse_StmtExpr(
                 pair.into_inner().next().unwrap(),
@@ -219,6 +564,57 @@ fn parse_Stmt(pair: Pair<Rule>) -> Vec<Stmt> {
     }
 }
 
+/// Parses the cases of a switch statement or, if `is_expr` is set, a switch expression.
+/// The arrow form of a case, e.g. `case 1 -> x;`, is parsed into a group ending with
+/// a `break`, or with a `yield` of the value in a switch expression.
+fn parse_switch_block(pair: Pair<Rule>, is_expr: bool) -> Vec<SwitchCase> {
+    assert_eq!(pair.as_rule(), Rule::SwitchBlock);
+    pair.into_inner()
+        .map(|case| {
+            let rule = case.as_rule();
+            let mut result = SwitchCase {
+                labels: vec![],
+                is_default: false,
+                body: vec![],
+            };
+            for inner in case.into_inner() {
+                match inner.as_rule() {
+                    Rule::SwitchLabel => {
+                        let mut labels = inner.into_inner().peekable();
+                        if labels.peek().unwrap().as_rule() == Rule::Default {
+                            result.is_default = true;
+                        } else {
+                            result.labels.extend(labels.map(parse_expr));
+                        }
+                    }
+                    Rule::Stmt => result.body.append(&mut parse_Stmt(inner)),
+                    Rule::BlockStmt => result.body = vec![Stmt::Block(parse_BlockStmt(inner))],
+                    Rule::Expr if is_expr => result.body = vec![Stmt::Yield(parse_expr(inner))],
+                    Rule::Expr => match parse_expr(inner) {
+                        Expr::StmtExprExpr(stmt_expr) => {
+                            result.body = vec![Stmt::StmtExprStmt(*stmt_expr)]
+                        }
+                        _ => panic!("Not a statement"),
+                    },
+                    _ => unreachable!(),
+                }
+            }
+            // The statements of an arrow case don't fall through to the next case
+            if rule == Rule::SwitchRule && !is_expr {
+                result.body.push(Stmt::Break(None));
+            }
+            result
+        })
+        .collect()
+}
+
+/// Parses the comma separated statement expressions initializing or updating a for-loop
+fn parse_stmt_expr_list(pair: Pair<Rule>) -> Vec<Stmt> {
+    pair.into_inner()
+        .map(|stmt_expr| Stmt::StmtExprStmt(parse_StmtExpr(stmt_expr.into_inner().next().unwrap())))
+        .collect()
+}
+
 fn parse_StmtExpr(pair: Pair<Rule>) -> StmtExpr {
     debug!(
         "parse_StmtExpr: rule = {:?}, str = {}",
@@ -233,27 +629,57 @@ fn parse_StmtExpr(pair: Pair<Rule>) -> StmtExpr {
             let mut name = inners.next().unwrap();
             let var = match name.as_rule() {
                 Rule::Identifier => Expr::LocalOrFieldVar(name.as_str().trim().to_string()),
-                Rule::InstVarExpr => parse_expr(name),
+                Rule::InstVarExpr | Rule::ArrayAccessExpr => parse_expr(name),
                 _ => {
                     unreachable!()
                 }
             };
+            let op = inners.next().unwrap().as_str();
             let Expr = parse_expr(inners.next().unwrap());
 
-            StmtExpr::Assign(var, Expr)
+            match op.strip_suffix('=').unwrap() {
+                "" => StmtExpr::Assign(var, Expr),
+                op => StmtExpr::CompoundAssign(var, op.to_string(), Expr),
+            }
+        }
+        Rule::PreIncDecExpr => {
+            let mut inners = pair.into_inner();
+            let op = next_id(&mut inners);
+            StmtExpr::PreIncDec(op, parse_expr(inners.next().unwrap()))
+        }
+        Rule::PostIncDecExpr => {
+            let mut inners = pair.into_inner();
+            let var = parse_expr(inners.next().unwrap());
+            StmtExpr::PostIncDec(var, next_id(&mut inners))
         }
         Rule::NewExpr => {
             let mut inners = pair.into_inner();
 
             let id_name = parse_Type(inners.next().unwrap());
-            let paramList = inners.next().unwrap().into_inner();
             let mut exprList: Vec<Expr> = vec![];
-            for param in paramList {
-                exprList.push(parse_expr(param));
+            if let Some(paramList) = inners.next() {
+                for param in paramList.into_inner() {
+                    exprList.push(parse_expr(param));
+                }
             }
 
             StmtExpr::New(id_name, exprList)
         }
+        Rule::QualifiedNewExpr => {
+            let mut inners = pair.into_inner().peekable();
+            let first = inners.next().unwrap();
+            let mut outer = match first.as_rule() {
+                Rule::Identifier => Expr::LocalOrFieldVar(first.as_str().trim().to_string()),
+                _ => parse_expr(first),
+            };
+            while let Some(field) = inners.next_if(|p| p.as_rule() == Rule::Identifier) {
+                outer = Expr::InstVar(Box::new(outer), field.as_str().trim().to_string());
+            }
+            let StmtExpr::New(class_type, args) = parse_StmtExpr(inners.next().unwrap()) else {
+                unreachable!()
+            };
+            StmtExpr::QualifiedNew(outer, class_type, args)
+        }
         Rule::MethodCallExpr => {
             let mut inners = pair.into_inner();
             let mut identifORinstVar = inners.next().unwrap();
@@ -265,7 +691,9 @@ fn parse_StmtExpr(pair: Pair<Rule>) -> StmtExpr {
                     MethodExpr = Expr::This;
                 }
                 Rule::InstVarExpr => {
-                    let Expr::InstVar(a, b) = parse_expr(identifORinstVar) else { unreachable!() };
+                    let Expr::InstVar(a, b) = parse_expr(identifORinstVar) else {
+                        unreachable!()
+                    };
                     MethodExpr = *a;
                     String_name = b;
                 }
@@ -284,6 +712,11 @@ fn parse_StmtExpr(pair: Pair<Rule>) -> StmtExpr {
 
             StmtExpr::MethodCall(MethodExpr, String_name, exprList)
         }
+        // Only chains ending with a method call are statements
+        Rule::CallChain => match parse_expr(pair) {
+            Expr::StmtExprExpr(stmt_expr) => *stmt_expr,
+            _ => panic!("Not a statement"),
+        },
         _ => {
             dbg!(pair);
             unreachable!()
@@ -297,8 +730,12 @@ fn parse_field(pair: Pair<Rule>) -> Vec<FieldDecl> {
     match pair.as_rule() {
         Rule::FieldDecl => {
             let mut inners = pair.into_inner();
+            let (modifiers, _) = parse_modifiers(
+                inners.next().unwrap(),
+                &["public", "protected", "private", "static", "final"],
+            );
             let jtype = parse_Type(inners.next().unwrap());
-            parse_field_var_decl_list(jtype, inners.next().unwrap())
+            parse_field_var_decl_list(modifiers, jtype, inners.next().unwrap())
         }
 
         _ => {
@@ -308,36 +745,51 @@ fn parse_field(pair: Pair<Rule>) -> Vec<FieldDecl> {
     }
 }
 
-fn parse_field_var_decl_list(jtype: Type, pair: Pair<Rule>) -> Vec<FieldDecl> {
+fn parse_field_var_decl_list(
+    modifiers: Modifiers,
+    jtype: Type,
+    pair: Pair<Rule>,
+) -> Vec<FieldDecl> {
     assert_eq!(pair.as_rule(), Rule::FieldVarDeclList);
     let mut inners = pair.into_inner();
     let mut var_decl = inners.next().unwrap().into_inner();
     let name = next_id(&mut var_decl);
-    let val = var_decl.next().map(parse_expr);
+    let val = var_decl.next().map(|val| parse_var_init(val, &jtype));
     let mut out = vec![FieldDecl {
+        modifiers,
         field_type: jtype.clone(),
         name,
         val,
     }];
     if let Some(p) = inners.next() {
-        out.append(&mut parse_field_var_decl_list(jtype, p));
+        out.append(&mut parse_field_var_decl_list(modifiers, jtype, p));
     }
     out
 }
 
 fn parse_Type(pair: Pair<Rule>) -> Type {
     match pair.as_rule() {
-        Rule::JType => parse_Type(pair.into_inner().next().unwrap()),
+        Rule::JType => {
+            let mut inners = pair.into_inner();
+            let elem_type = parse_Type(inners.next().unwrap());
+            // The remaining pairs are the dimensions of an array type
+            inners.fold(elem_type, |t, _| Type::Array(Box::new(t)))
+        }
         Rule::PrimitiveType => match pair.as_str() {
             "boolean" => Type::Bool,
             "int" => Type::Int,
             "char" => Type::Char,
+            "byte" => Type::Byte,
+            "short" => Type::Short,
+            "long" => Type::Long,
+            "float" => Type::Float,
+            "double" => Type::Double,
             "String" => Type::String,
             "void" => Type::Void,
             "null" => Type::Null,
             _ => unreachable!(),
         },
-        Rule::Identifier => Type::Class(next_id(&mut pair.into_inner())),
+        Rule::Identifier | Rule::QualifiedName => Type::Class(parse_class_name(pair)),
         _ => {
             dbg!(pair.as_rule());
             unreachable!()
@@ -345,6 +797,12 @@ fn parse_Type(pair: Pair<Rule>) -> Type {
     }
 }
 
+/// Parses the name of a class, converting qualified names like `java.util.Iterator`
+/// into the internal form `java/util/Iterator`
+fn parse_class_name(pair: Pair<Rule>) -> String {
+    pair.as_str().trim().replace('.', "/")
+}
+
 fn parse_expr(pair: Pair<Rule>) -> Expr {
     let rule = pair.as_rule();
     /* println!(
@@ -353,74 +811,194 @@ fn parse_expr(pair: Pair<Rule>) -> Expr {
         pair.as_str()
     );*/
     match rule {
-        Rule::Expr => parse_expr(pair.into_inner().next().unwrap()),
+        Rule::Expr => {
+            let mut inners = pair.into_inner().map(parse_expr);
+            let expr = inners.next().unwrap();
+            match (inners.next(), inners.next()) {
+                (Some(then_expr), Some(else_expr)) => {
+                    Expr::Conditional(Box::new(expr), Box::new(then_expr), Box::new(else_expr))
+                }
+                _ => expr,
+            }
+        }
         Rule::ThisExpr => Expr::This,
+        Rule::QualifiedThis => {
+            let class_name = pair.as_str().trim().strip_suffix(".this").unwrap();
+            Expr::QualifiedThis(class_name.replace('.', "/"))
+        }
         Rule::JNull => Expr::Jnull,
-        Rule::InstVarExpr => {
+        Rule::InstVarExpr | Rule::ArrayAccessExpr => {
             let mut pairs = pair.into_inner();
             let x = pairs.next().unwrap();
             let mut obj = match x.as_rule() {
                 Rule::Identifier => Expr::LocalOrFieldVar(x.as_str().trim().to_string()),
                 Rule::ThisExpr => Expr::This,
+                Rule::QualifiedThis => parse_expr(x),
+                Rule::SuperExpr => Expr::Super,
                 _ => {
                     dbg!(x.as_rule());
                     unreachable!()
                 }
             };
             for p in pairs {
-                assert_eq!(p.as_rule(), Rule::Identifier);
-                obj = Expr::InstVar(Box::new(obj), p.as_str().trim().to_string());
+                obj = match p.as_rule() {
+                    Rule::Identifier => Expr::InstVar(Box::new(obj), p.as_str().trim().to_string()),
+                    Rule::Index => Expr::ArrayAccess(
+                        Box::new(obj),
+                        Box::new(parse_expr(p.into_inner().next().unwrap())),
+                    ),
+                    _ => unreachable!(),
+                };
             }
             obj
         }
+        Rule::NewArrayExpr => {
+            let mut inners = pair.into_inner();
+            let elem_type = parse_Type(inners.next().unwrap());
+            let mut dims = vec![];
+            let mut array_type = elem_type;
+            for p in inners {
+                match p.as_rule() {
+                    Rule::Index => {
+                        dims.push(parse_expr(p.into_inner().next().unwrap()));
+                        array_type = Type::Array(Box::new(array_type));
+                    }
+                    Rule::ArrayDim => array_type = Type::Array(Box::new(array_type)),
+                    // The array initializer is always the last pair
+                    Rule::ArrayInit => return parse_array_init(p, &array_type),
+                    _ => unreachable!(),
+                }
+            }
+            Expr::NewArray(array_type, dims)
+        }
         Rule::UnaryExpr => {
             let mut inners = pair.into_inner();
             let unaryOP = next_id(&mut inners);
             let noBinExpr = parse_expr(inners.next().unwrap());
             Expr::Unary(unaryOP, Box::new(noBinExpr))
         }
+        Rule::SwitchExpr => {
+            let mut inners = pair.into_inner();
+            let Expr = parse_expr(inners.next().unwrap());
+            let cases = parse_switch_block(inners.next().unwrap(), true);
+            Expr::Switch(Box::new(Expr), cases)
+        }
         Rule::ParanthesizedExpr => parse_expr(pair.into_inner().next().unwrap()),
+        Rule::LambdaExpr => {
+            let mut inners = pair.into_inner();
+            let params = inners.next().unwrap().into_inner().next();
+            let params = match params {
+                None => vec![],
+                Some(p) if p.as_rule() == Rule::ParamDeclList => parse_params(p)
+                    .into_iter()
+                    .map(|(t, name)| (Some(t), name))
+                    .collect(),
+                // The types of the parameters are inferred from the functional interface
+                Some(p) if p.as_rule() == Rule::InferredParams => p
+                    .into_inner()
+                    .map(|name| (None, name.as_str().trim().to_string()))
+                    .collect(),
+                Some(name) => vec![(None, name.as_str().trim().to_string())],
+            };
+            let body = inners.next().unwrap();
+            let body = match body.as_rule() {
+                Rule::BlockStmt => Stmt::Block(parse_BlockStmt(body)),
+                _ => Stmt::Return(parse_expr(body)),
+            };
+            Expr::Lambda(params, Box::new(body))
+        }
+        Rule::MethodRefExpr => {
+            let mut inners = pair.into_inner();
+            let obj = parse_expr(inners.next().unwrap());
+            // The name is `new` for a reference to a constructor
+            let name = inners.next().unwrap().as_str().trim().to_string();
+            Expr::MethodRef(Box::new(obj), name)
+        }
+        Rule::CastExpr => {
+            let mut inners = pair.into_inner();
+            let t = parse_Type(inners.next().unwrap());
+            let expr = parse_expr(inners.next().unwrap());
+            Expr::Cast(t, Box::new(expr))
+        }
         Rule::IntLiteral => Expr::Integer(pair.as_str().trim().parse().unwrap()),
+        Rule::LongLiteral => {
+            let digits = pair.as_str().trim_end_matches(['L', 'l']);
+            Expr::Long(digits.parse().unwrap())
+        }
+        Rule::FloatLiteral => {
+            let literal = pair.as_str();
+            match literal.strip_suffix(['f', 'F']) {
+                Some(digits) => Expr::Float(digits.parse().unwrap()),
+                None => Expr::Double(literal.trim_end_matches(['d', 'D']).parse().unwrap()),
+            }
+        }
         Rule::BoolLiteral => Expr::Bool(pair.as_str().parse().unwrap()),
-        Rule::CharLiteral => Expr::Char(get_str_content(pair.as_str()).parse().unwrap()),
-        Rule::StrLiteral => Expr::String(get_str_content(pair.as_str()).to_string()),
+        Rule::CharLiteral => Expr::Char(unescape(get_str_content(pair.as_str())).parse().unwrap()),
+        Rule::StrLiteral => Expr::String(unescape(get_str_content(pair.as_str()))),
         Rule::StmtExpr => {
-            Expr::StmtExprExpr(Box::new(parse_StmtExpr(pair.into_inner().next().unwrap())))
+            let stmt_expr = pair.into_inner().next().unwrap();
+            match stmt_expr.as_rule() {
+                // A chain may end with a field or array access, which isn't a statement expression
+                Rule::CallChain => parse_expr(stmt_expr),
+                _ => Expr::StmtExprExpr(Box::new(parse_StmtExpr(stmt_expr))),
+            }
+        }
+        Rule::CallChain => {
+            let mut pairs = pair.into_inner();
+            let mut obj = Expr::StmtExprExpr(Box::new(parse_StmtExpr(pairs.next().unwrap())));
+            for p in pairs {
+                obj = match p.as_rule() {
+                    Rule::ChainedCall => {
+                        let mut inners = p.into_inner();
+                        let name = next_id(&mut inners);
+                        let args = inners
+                            .next()
+                            .map(|params| params.into_inner().map(parse_expr).collect())
+                            .unwrap_or_default();
+                        Expr::StmtExprExpr(Box::new(StmtExpr::MethodCall(obj, name, args)))
+                    }
+                    Rule::Identifier => Expr::InstVar(Box::new(obj), p.as_str().trim().to_string()),
+                    Rule::Index => Expr::ArrayAccess(
+                        Box::new(obj),
+                        Box::new(parse_expr(p.into_inner().next().unwrap())),
+                    ),
+                    _ => unreachable!(),
+                };
+            }
+            obj
         }
         Rule::NonBinaryExpr => parse_expr(pair.into_inner().next().unwrap()),
-        Rule::Prec4BinExpr
+        Rule::Prec9BinExpr
+        | Rule::Prec8BinExpr
+        | Rule::Prec7BinExpr
+        | Rule::Prec6BinExpr
+        | Rule::Prec5BinExpr
+        | Rule::Prec4BinExpr
         | Rule::Prec3BinExpr
         | Rule::Prec2BinExpr
         | Rule::Prec1BinExpr
         | Rule::Prec0BinExpr => {
-            let pc = pair.clone();
+            // The grammar nests operators of equal precedence to the right, e.g. `a - b - c` is
+            // `a - (b - c)`, so the chain is collected and folded to the left
+            let rule = pair.as_rule();
             let mut inners = pair.into_inner();
-            let left = inners.next().unwrap();
-            let left = parse_expr(left);
-            match inners.next() {
-                None => left,
-                Some(Op) => {
-                    let opStr = Op.as_str().trim().to_string();
-                    let right = inners.next().unwrap();
-                    let right = parse_expr(right);
-
-                    match right {
-                        Expr::Binary(op, rl, rr) => {
-                            if BinaryOp::prec(&op) == BinaryOp::prec(&opStr) {
-                                Expr::Binary(
-                                    op,
-                                    Box::new(Expr::Binary(opStr, Box::new(left), rl)),
-                                    rr,
-                                )
-                            } else {
-                                Expr::Binary(
-                                    opStr,
-                                    Box::new(left),
-                                    Box::new(Expr::Binary(op, rl, rr)),
-                                )
-                            }
-                        }
-                        _ => Expr::Binary(opStr, Box::new(left), Box::new(right)),
+            let mut expr = parse_expr(inners.next().unwrap());
+            loop {
+                match inners.next() {
+                    None => return expr,
+                    Some(instance_of) if instance_of.as_rule() == Rule::InstanceOf => {
+                        let mut inners = instance_of.into_inner();
+                        let t = parse_Type(inners.next().unwrap());
+                        let name = inners.next().map(|p| p.as_str().trim().to_string());
+                        return Expr::InstanceOf(Box::new(expr), t, name);
+                    }
+                    Some(op) => {
+                        let op = op.as_str().trim().to_string();
+                        let rest = inners.next().unwrap();
+                        debug_assert_eq!(rest.as_rule(), rule);
+                        inners = rest.into_inner();
+                        let right = parse_expr(inners.next().unwrap());
+                        expr = Expr::Binary(op, Box::new(expr), Box::new(right));
                     }
                 }
             }
@@ -434,10 +1012,60 @@ fn parse_expr(pair: Pair<Rule>) -> Expr {
     }
 }
 
+/// Parses the initial value of a variable or field of the given type,
+/// which may be an array initializer like `{1, 2}`
+fn parse_var_init(pair: Pair<Rule>, var_type: &Type) -> Expr {
+    match pair.as_rule() {
+        Rule::ArrayInit => parse_array_init(pair, var_type),
+        _ => parse_expr(pair),
+    }
+}
+
+/// Parses an array initializer creating an array of the given type.
+/// The elements of nested initializers are arrays themselves, e.g. `{{1}, {2, 3}}`.
+fn parse_array_init(pair: Pair<Rule>, array_type: &Type) -> Expr {
+    assert_eq!(pair.as_rule(), Rule::ArrayInit);
+    let Type::Array(elem_type) = array_type else {
+        panic!("Illegal initializer for {array_type}");
+    };
+    Expr::ArrayInit(
+        array_type.clone(),
+        pair.into_inner()
+            .map(|elem| parse_var_init(elem, elem_type))
+            .collect(),
+    )
+}
+
 fn get_str_content(s: &str) -> &str {
     &s[1..s.len() - 1]
 }
 
+/// Replaces the escape sequences of a string or char literal by the characters they stand for
+fn unescape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        result.push(match chars.next().unwrap() {
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'u' => {
+                let code = chars.by_ref().take(4).collect::<String>();
+                char::from_u32(u32::from_str_radix(&code, 16).unwrap())
+                    .expect("Unicode escapes of surrogates aren't supported")
+            }
+            c => c,
+        });
+    }
+    result
+}
+
 fn parse_value(pair: Pair<Rule>) -> Expr {
     match pair.as_rule() {
         // Rule::ID => Example::ID(String::from(pair.as_str())),
@@ -0,0 +1,50 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("AbstractClasses");
+}
+
+#[test]
+#[should_panic(expected = "A is abstract and can't be instantiated")]
+fn test_instantiate_abstract_class() {
+    typecheck_source("abstract class A {} class B { A f() { return new A(); } }").unwrap();
+}
+
+#[test]
+fn test_abstract_method_in_class() {
+    let result = typecheck_source("class A { abstract int f(); }");
+    assert_eq!(
+        result,
+        Err(
+            "Class A must be declared abstract, since it declares the abstract method f"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn test_unimplemented_abstract_method() {
+    let result = typecheck_source(
+        "abstract class A { abstract int f(int x); } class B extends A { int f() { return 1; } }",
+    );
+    assert_eq!(
+        result,
+        Err(
+            "Class B must be declared abstract or implement the abstract method f of A".to_string()
+        )
+    );
+}
+
+#[test]
+fn test_redeclared_abstract_method() {
+    let result = typecheck_source(
+        "class A { int f() { return 1; } } abstract class B extends A { abstract int f(); } class C extends B {}",
+    );
+    assert_eq!(
+        result,
+        Err(
+            "Class C must be declared abstract or implement the abstract method f of B".to_string()
+        )
+    );
+}
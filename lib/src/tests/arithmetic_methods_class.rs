@@ -28,28 +28,28 @@ fn arithmetic_methods_class() -> Class {
     Class {
         name: "ArithmeticMethods".to_string(),
         fields: vec![
-            FieldDecl {
-                field_type: Type::Int,
-                name: "x".to_string(),
-                val: Some(Expr::Integer(69)),
-            },
-            FieldDecl {
-                field_type: Type::Int,
-                name: "y".to_string(),
-                val: Some(Expr::Integer(420)),
-            },
-            FieldDecl {
-                field_type: Type::Int,
-                name: "bigInt".to_string(),
-                val: Some(Expr::Integer(131072)),
-            },
+            field_decl(
+                Type::Int,
+                "x",
+                Some(TypedExpr(Box::new(Integer(69)), Type::Int)),
+            ),
+            field_decl(
+                Type::Int,
+                "y",
+                Some(TypedExpr(Box::new(Integer(420)), Type::Int)),
+            ),
+            field_decl(
+                Type::Int,
+                "bigInt",
+                Some(TypedExpr(Box::new(Integer(131072)), Type::Int)),
+            ),
         ],
         methods: vec![
-            MethodDecl {
-                ret_type: Type::Int,
-                name: "addX".to_string(),
-                params: vec![(Type::Int, "a".to_string())],
-                body: TypedStmt(
+            method_decl(
+                Type::Int,
+                "addX",
+                vec![(Type::Int, "a".to_string())],
+                TypedStmt(
                     Box::new(Block(vec![TypedStmt(
                         Box::new(Return(TypedExpr(
                             Box::new(Binary(
@@ -63,12 +63,12 @@ fn arithmetic_methods_class() -> Class {
                     )])),
                     Type::Int,
                 ),
-            },
-            MethodDecl {
-                ret_type: Type::Int,
-                name: "addY".to_string(),
-                params: vec![(Type::Int, "a".to_string())],
-                body: TypedStmt(
+            ),
+            method_decl(
+                Type::Int,
+                "addY",
+                vec![(Type::Int, "a".to_string())],
+                TypedStmt(
                     Box::new(Block(vec![TypedStmt(
                         Box::new(Return(TypedExpr(
                             Box::new(Binary(
@@ -82,12 +82,12 @@ fn arithmetic_methods_class() -> Class {
                     )])),
                     Type::Int,
                 ),
-            },
-            MethodDecl {
-                ret_type: Type::Int,
-                name: "complexMath".to_string(),
-                params: vec![(Type::Int, "a".to_string()), (Type::Int, "b".to_string())],
-                body: TypedStmt(
+            ),
+            method_decl(
+                Type::Int,
+                "complexMath",
+                vec![(Type::Int, "a".to_string()), (Type::Int, "b".to_string())],
+                TypedStmt(
                     Box::new(Block(vec![
                         TypedStmt(
                             Box::new(StmtExprStmt(TypedStmtExpr(
@@ -233,7 +233,8 @@ fn arithmetic_methods_class() -> Class {
                     ])),
                     Type::Int,
                 ),
-            },
+            ),
         ],
+        ..Default::default()
     }
 }
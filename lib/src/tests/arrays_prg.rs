@@ -0,0 +1,36 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("Arrays");
+}
+
+#[test]
+#[should_panic(expected = "Array index must be int, but is boolean")]
+fn test_bool_index() {
+    typecheck_source("class A { int f(int[] a) { return a[true]; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Array required, but int found")]
+fn test_index_non_array() {
+    typecheck_source("class A { int f(int a) { return a[0]; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Value of type boolean can't be assigned to a variable of type int")]
+fn test_wrong_element_type() {
+    typecheck_source("class A { void f() { int[] a = {1, false}; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Cannot assign a value to final variable length")]
+fn test_assign_length() {
+    typecheck_source("class A { void f(int[] a) { a.length = 1; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Illegal initializer for int")]
+fn test_initializer_for_non_array() {
+    typecheck_source("class A { void f() { int a = {1}; } }").unwrap();
+}
@@ -28,32 +28,39 @@ fn assigned_fields_class() -> Class {
     Class {
         name: "AssignedFields".to_string(),
         fields: vec![
-            FieldDecl {
-                field_type: Type::Int,
-                name: "x".to_string(),
-                val: Some(Expr::Integer(69)),
-            },
-            FieldDecl {
-                field_type: Type::Char,
-                name: "c".to_string(),
-                val: Some(Expr::Char('x')),
-            },
-            FieldDecl {
-                field_type: Type::String,
-                name: "s".to_string(),
-                val: Some(Expr::String("Hello World".to_string())),
-            },
-            FieldDecl {
-                field_type: Type::String,
-                name: "stringsCanBeNull".to_string(),
-                val: Some(Expr::Jnull),
-            },
-            FieldDecl {
-                field_type: Type::Bool,
-                name: "b".to_string(),
-                val: Some(Expr::Bool(true)),
-            },
+            field_decl(
+                Type::Int,
+                "x",
+                Some(TypedExpr(Box::new(Integer(69)), Type::Int)),
+            ),
+            field_decl(
+                Type::Char,
+                "c",
+                Some(TypedExpr(Box::new(Char('x')), Type::Char)),
+            ),
+            field_decl(
+                Type::String,
+                "s",
+                Some(TypedExpr(
+                    Box::new(String("Hello World".to_string())),
+                    Type::String,
+                )),
+            ),
+            field_decl(
+                Type::String,
+                "stringsCanBeNull",
+                Some(TypedExpr(
+                    Box::new(TypedExpr(Box::new(Jnull), Type::Null)),
+                    Type::String,
+                )),
+            ),
+            field_decl(
+                Type::Bool,
+                "b",
+                Some(TypedExpr(Box::new(Bool(true)), Type::Bool)),
+            ),
         ],
         methods: vec![],
+        ..Default::default()
     }
 }
@@ -0,0 +1,53 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("Bitwise");
+}
+
+#[test]
+fn test_precedence() {
+    let ast =
+        parser::parse_programm("class A { int f(int a) { return a | a ^ a & a << 1; } }").unwrap();
+    let Some(Stmt::Block(stmts)) = &ast[0].methods[0].body else {
+        panic!("Expected block");
+    };
+    let [Return(expr)] = &stmts[..] else {
+        panic!("Expected return");
+    };
+    let a = || Box::new(LocalOrFieldVar("a".to_string()));
+    assert_eq!(
+        *expr,
+        Binary(
+            "|".to_string(),
+            a(),
+            Box::new(Binary(
+                "^".to_string(),
+                a(),
+                Box::new(Binary(
+                    "&".to_string(),
+                    a(),
+                    Box::new(Binary("<<".to_string(), a(), Box::new(Integer(1))))
+                ))
+            ))
+        )
+    );
+}
+
+#[test]
+#[should_panic(expected = "Operator & can't be applied to values of type int and boolean")]
+fn test_mixed_operands() {
+    typecheck_source("class A { int f(int n, boolean b) { return n & b; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Operator << can't be applied to values of type boolean and boolean")]
+fn test_bool_shift() {
+    typecheck_source("class A { boolean f(boolean b) { return b << b; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Operator ~ can't be applied to a value of type boolean")]
+fn test_bool_complement() {
+    typecheck_source("class A { boolean f(boolean b) { return ~b; } }").unwrap();
+}
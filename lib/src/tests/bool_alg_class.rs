@@ -28,15 +28,15 @@ fn bool_alg_class() -> Class {
     Class {
         name: "BoolAlg".to_string(),
         fields: vec![],
-        methods: vec![MethodDecl {
-            ret_type: Type::Bool,
-            name: "f".to_string(),
-            params: vec![
+        methods: vec![method_decl(
+            Type::Bool,
+            "f",
+            vec![
                 (Type::Bool, "a".to_string()),
                 (Type::Bool, "b".to_string()),
                 (Type::Bool, "c".to_string()),
             ],
-            body: TypedStmt(
+            TypedStmt(
                 Box::new(Block(vec![TypedStmt(
                     Box::new(Return(TypedExpr(
                         Box::new(Binary(
@@ -63,6 +63,7 @@ fn bool_alg_class() -> Class {
                 )])),
                 Type::Bool,
             ),
-        }],
+        )],
+        ..Default::default()
     }
 }
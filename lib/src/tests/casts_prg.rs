@@ -0,0 +1,123 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("Casts");
+}
+
+#[test]
+fn test_parse_casts() {
+    let ast = parser::parse_programm(
+        "class A { void f() { g((int) -x, (A) y, (a) - b, (int[]) z, o instanceof A a); } }",
+    )
+    .unwrap();
+    let Some(Stmt::Block(stmts)) = &ast[0].methods[0].body else {
+        panic!("Expected block");
+    };
+    let [StmtExprStmt(StmtExpr::MethodCall(_, _, args))] = &stmts[..] else {
+        panic!("Expected method call");
+    };
+    let var = |name: &str| Box::new(LocalOrFieldVar(name.to_string()));
+    assert_eq!(
+        *args,
+        vec![
+            Cast(Type::Int, Box::new(Unary("-".to_string(), var("x")))),
+            Cast(Type::Class("A".to_string()), var("y")),
+            Binary("-".to_string(), var("a"), var("b")),
+            Cast(Type::Array(Box::new(Type::Int)), var("z")),
+            InstanceOf(
+                var("o"),
+                Type::Class("A".to_string()),
+                Some("a".to_string())
+            ),
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Value of type boolean can't be cast to int")]
+fn test_cast_bool() {
+    typecheck_source("class A { int f(boolean b) { return (int) b; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Value of type String can't be cast to A")]
+fn test_cast_unrelated() {
+    typecheck_source("class A { A f(String s) { return (A) s; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Operator instanceof can't be applied to a value of type int and type A")]
+fn test_instanceof_primitive() {
+    typecheck_source("class A { boolean f(int i) { return i instanceof A; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unknown variable: a")]
+fn test_pattern_scope() {
+    typecheck_source(
+        "class A { int f(Object o) { if (o instanceof A a) { return 1; } return a.f(o); } }",
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_pattern_in_condition() {
+    typecheck_source(
+        "class A { int x; int f(Object o) { if (o instanceof A a && a.x > 3) { return a.x; } return 0; } }",
+    )
+    .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unknown variable: s")]
+fn test_pattern_not_definitely_matched() {
+    typecheck_source(
+        "class A { int f(Object o) { if (o instanceof String s || o == null) { return s.length(); } return 0; } }",
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_negated_pattern() {
+    typecheck_source(
+        "class A { int x; int f(Object o) { if (!(o instanceof A a)) { return 0; } return a.x; } int g(Object o) { return !(o instanceof A a) || a.x > 0 ? 1 : 0; } }",
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_pattern_after_loop() {
+    // The loop is only left once the pattern is matched
+    typecheck_source(
+        "class A { int f(Object o) { while (!(o instanceof String t)) { o = \"abc\"; } return t.length(); } }",
+    )
+    .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unknown variable: t")]
+fn test_pattern_after_loop_with_break() {
+    typecheck_source(
+        "class A { int f(Object o) { while (!(o instanceof String t)) { if (o == null) { break; } o = \"abc\"; } return t.length(); } }",
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_pattern_after_loop_with_nested_break() {
+    // A break of a nested loop or switch doesn't leave the loop, unlike one with its label
+    typecheck_source(
+        "class A { int f(Object o) { a: for (; !(o instanceof String t); ) { while (true) { break; } o = \"abc\"; } return t.length(); } }",
+    )
+    .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unknown variable: t")]
+fn test_pattern_after_loop_with_labeled_break() {
+    typecheck_source(
+        "class A { int f(Object o) { a: for (; !(o instanceof String t); ) { while (true) { break a; } } return t.length(); } }",
+    )
+    .unwrap();
+}
@@ -0,0 +1,103 @@
+use super::*;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Compiles `lib/testcases/ClasspathHelper.java` with javac and reads the resulting class files.
+/// The tests run in parallel, so it is only compiled once.
+fn helper_classpath() -> Prg {
+    static CLASSPATH: OnceLock<Prg> = OnceLock::new();
+    CLASSPATH
+        .get_or_init(|| {
+            compile_java("ClasspathHelper");
+            read_classpath(&[
+                Path::new("lib/testcases/ClasspathHelper.class"),
+                Path::new("lib/testcases/ClasspathShape.class"),
+            ])
+            .unwrap()
+        })
+        .clone()
+}
+
+fn typecheck_with_helper(code: &str) -> Result<Prg, std::string::String> {
+    let ast = parser::parse_programm(code).unwrap();
+    TypeChecker::with_classpath(ast, helper_classpath())?.check_and_type_program()
+}
+
+#[test]
+fn test_run() {
+    source_run_test_with_classpath("ClasspathUser", helper_classpath());
+}
+
+#[test]
+fn test_read_generated_class_files() {
+    let tast = typecheck_source(
+        &read_to_string(File::open("lib/testcases/Modifiers.java").unwrap()).unwrap(),
+    )
+    .unwrap();
+    for ((_, bytes), class) in generate_dir(&tast).as_bytes().iter().zip(&tast) {
+        let read_class = read_class_file(bytes).unwrap();
+        assert_eq!(read_class.name, class.name);
+        assert_eq!(read_class.modifiers, class.modifiers);
+        assert_eq!(read_class.super_class, class.super_class);
+        let fields: Vec<FieldDecl> = class
+            .fields
+            .iter()
+            .map(|field| FieldDecl {
+                val: None,
+                ..field.clone()
+            })
+            .collect();
+        assert_eq!(read_class.fields, fields);
+        let signature = |m: &MethodDecl| {
+            (
+                m.modifiers,
+                m.name.clone(),
+                m.params
+                    .iter()
+                    .map(|(t, _)| t.clone())
+                    .collect::<Vec<Type>>(),
+                m.ret_type.clone(),
+            )
+        };
+        assert_eq!(
+            read_class.methods.iter().map(signature).collect::<Vec<_>>(),
+            class.methods.iter().map(signature).collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn test_read_javac_class_file() {
+    let classpath = helper_classpath();
+    let helper = &classpath[0];
+    assert_eq!(helper.name, "ClasspathHelper");
+    assert_eq!(helper.constructors.len(), 1);
+    assert_eq!(helper.constructors[0].params[0].0, Type::Int);
+    let big = helper.methods.iter().find(|m| m.name == "big").unwrap();
+    assert_eq!(big.ret_type, Type::Long);
+    let shape = &classpath[1];
+    assert!(shape.is_interface);
+    assert_eq!(shape.methods[0].body, None);
+}
+
+#[test]
+#[should_panic(expected = "hidden has private access in ClasspathHelper")]
+fn test_private_classpath_field() {
+    typecheck_with_helper("class A { int f(ClasspathHelper h) { return h.hidden; } }").unwrap();
+}
+
+#[test]
+fn test_long_classpath_method() {
+    typecheck_with_helper("class A { double f(ClasspathHelper h) { return h.big(); } }").unwrap();
+}
+
+#[test]
+fn test_missing_classpath_entry() {
+    assert_eq!(
+        read_classpath(&[Path::new("lib/testcases/Missing.jar")]),
+        Err(
+            "Classpath entry lib/testcases/Missing.jar is neither a directory nor a class file"
+                .to_string()
+        )
+    );
+}
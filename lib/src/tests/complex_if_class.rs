@@ -28,11 +28,11 @@ fn complex_if_class() -> Class {
     Class {
         name: "ComplexIf".to_string(),
         fields: vec![],
-        methods: vec![MethodDecl {
-            ret_type: Type::Bool,
-            name: "f".to_string(),
-            params: vec![(Type::Char, "c".to_string())],
-            body: TypedStmt(
+        methods: vec![method_decl(
+            Type::Bool,
+            "f",
+            vec![(Type::Char, "c".to_string())],
+            TypedStmt(
                 Box::new(Block(vec![TypedStmt(
                     Box::new(If(
                         TypedExpr(
@@ -245,6 +245,7 @@ fn complex_if_class() -> Class {
                 )])),
                 Type::Bool,
             ),
-        }],
+        )],
+        ..Default::default()
     }
 }
@@ -0,0 +1,41 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("Conditionals");
+}
+
+#[test]
+fn test_right_associative() {
+    let ast = parser::parse_programm(
+        "class A { int f(boolean a, boolean b) { return a ? 1 : b ? 2 : 3; } }",
+    )
+    .unwrap();
+    let Some(Stmt::Block(stmts)) = &ast[0].methods[0].body else {
+        panic!("Expected block");
+    };
+    let [Return(Expr::Conditional(_, _, else_expr))] = &stmts[..] else {
+        panic!("Expected return of a conditional expression");
+    };
+    assert!(matches!(**else_expr, Expr::Conditional(_, _, _)));
+}
+
+#[test]
+#[should_panic(expected = "Ternary condition must be bool")]
+fn test_int_condition() {
+    typecheck_source("class A { int f(int n) { return n ? 1 : 2; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Incompatible types in conditional expression: int and boolean")]
+fn test_incompatible_values() {
+    typecheck_source("class A { int f(boolean b) { return b ? 1 : false; } }").unwrap();
+}
+
+#[test]
+#[should_panic(
+    expected = "Value of type java/lang/Object can't be assigned to a variable of type String"
+)]
+fn test_unrelated_objects() {
+    typecheck_source("class A { void f(boolean b) { String s = b ? \"a\" : new A(); } }").unwrap();
+}
@@ -0,0 +1,135 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("Constants");
+}
+
+#[test]
+fn test_inlined_constant() {
+    let tast = typecheck_source(
+        "class A { static final int X = B.Y * 2; int f() { return X + 1; } } class B { static final int Y = 3 << 2; }",
+    )
+    .unwrap();
+    // The value of the constant is computed, even though it uses a constant of a later class
+    assert_eq!(
+        tast[0].fields[0].val,
+        Some(TypedExpr(Box::new(Integer(24)), Type::Int))
+    );
+    let Some(Stmt::TypedStmt(block, _)) = &tast[0].methods[0].body else {
+        panic!("Expected typed block");
+    };
+    let Stmt::Block(stmts) = &**block else {
+        panic!("Expected block");
+    };
+    let Stmt::TypedStmt(ret, _) = &stmts[0] else {
+        panic!("Expected typed return");
+    };
+    let Stmt::Return(TypedExpr(sum, Type::Int)) = &**ret else {
+        panic!("Expected return of an int");
+    };
+    // Uses of the constant are replaced by its value
+    let Binary(_, x, _) = &**sum else {
+        panic!("Expected binary expression");
+    };
+    assert_eq!(**x, TypedExpr(Box::new(Integer(24)), Type::Int));
+}
+
+#[test]
+fn test_parse_static_block() {
+    let ast =
+        parser::parse_programm("class A { static int x; static { x = 1; } int y; static {} }")
+            .unwrap();
+    // Each block is stored with the number of fields declared before it
+    assert_eq!(
+        ast[0].static_blocks,
+        vec![
+            (
+                1,
+                Block(vec![StmtExprStmt(Assign(
+                    LocalOrFieldVar("x".to_string()),
+                    Integer(1)
+                ))])
+            ),
+            (2, Block(vec![])),
+        ]
+    );
+}
+
+#[test]
+fn test_non_constant_field() {
+    // Fields, which aren't final or aren't initialized with a constant expression, aren't inlined
+    let tast = typecheck_source(
+        "class A { static int x = 1; static final String s = null; final int y = x; int f() { return x + y; } }",
+    )
+    .unwrap();
+    assert_eq!(
+        tast[0].fields[2].val,
+        Some(TypedExpr(
+            Box::new(InstVar(
+                Box::new(TypedExpr(
+                    Box::new(ClassName("A".to_string())),
+                    Type::Class("A".to_string())
+                )),
+                "x".to_string()
+            )),
+            Type::Int
+        ))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Cannot assign a value to final variable X")]
+fn test_assign_constant() {
+    typecheck_source("class A { static final int X = 1; void f() { X = 2; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Cannot assign a value to final variable X")]
+fn test_assign_static_final_in_method() {
+    typecheck_source("class A { static final int X; static { X = 1; } void f() { X = 2; } }")
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Cannot assign a value to final variable y")]
+fn test_assign_final_in_static_block() {
+    typecheck_source("class A { final int y; static { A a = new A(); a.y = 1; } A() { y = 0; } }")
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Non-static field y can't be referenced from a static context")]
+fn test_instance_field_in_static_block() {
+    typecheck_source("class A { int y; static { y = 1; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unreported exception java/lang/Exception")]
+fn test_checked_exception_in_static_block() {
+    typecheck_source("class A { static { throw new Exception(); } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Value of type int can't be assigned to a variable of type byte")]
+fn test_constant_out_of_range() {
+    typecheck_source("class A { static final int X = 100; byte b = X * 2; }").unwrap();
+}
+
+#[test]
+fn test_floating_constant_strings() {
+    let tast = typecheck_source(
+        "class A { static final String S = 1.0 + \"|\" + 1e7 + \"|\" + 12345678.9 + \"|\" + 0.001 + \"|\" + 1.5e-4f + \"|\" + -0.0 + \"|\" + 100f + \"|\" + 1 / 0.0; }",
+    )
+    .unwrap();
+    // Floats and doubles are formatted like Java's `Double.toString`
+    assert_eq!(
+        tast[0].fields[0].val,
+        Some(TypedExpr(
+            Box::new(Expr::String(
+                "1.0|1.0E7|1.23456789E7|0.001|1.5E-4|-0.0|100.0|Infinity".to_string()
+            )),
+            Type::String
+        ))
+    );
+}
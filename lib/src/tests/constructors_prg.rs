@@ -0,0 +1,44 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("Constructors");
+}
+
+#[test]
+#[should_panic(expected = "No constructor of class Point takes arguments of types (int)")]
+fn test_no_matching_constructor() {
+    typecheck_source("class Point { Point() {} } class A { Point f() { return new Point(1); } }")
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "No constructor of class Point takes arguments of types (int)")]
+fn test_default_constructor_without_params() {
+    typecheck_source("class Point {} class A { Point f() { return new Point(1); } }").unwrap();
+}
+
+#[test]
+fn test_misplaced_this_call() {
+    let result =
+        typecheck_source("class Point { int x; Point() { x = 1; this(2); } Point(int x) {} }");
+    assert_eq!(
+        result,
+        Err("Call to this(...) must be the first statement in a constructor".to_string())
+    );
+}
+
+#[test]
+fn test_recursive_this_call() {
+    let result = typecheck_source("class Point { Point() { this(1); } Point(int x) { this(); } }");
+    assert_eq!(
+        result,
+        Err("Recursive constructor invocation in class Point".to_string())
+    );
+}
+
+#[test]
+#[should_panic(expected = "constructors must have the name of their class Point")]
+fn test_constructor_name() {
+    parser::parse_programm("class Point { Pointer() {} }").unwrap();
+}
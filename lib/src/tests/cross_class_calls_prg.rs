@@ -0,0 +1,54 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("CrossClassCalls");
+}
+
+#[test]
+#[should_panic(expected = "Method area not found in class Point")]
+fn test_unknown_method() {
+    typecheck_source("class Point {} class A { int f(Point p) { return p.area(); } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unknown field z in class Point")]
+fn test_unknown_field() {
+    typecheck_source("class Point { int x; } class A { int f(Point p) { return p.z; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Value of type boolean can't be assigned to a variable of type int")]
+fn test_argument_type_mismatch() {
+    typecheck_source(
+        "class Point { void set(int x) {} } class A { void f(Point p) { p.set(true); } }",
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_parse_call_chain() {
+    let ast = parser::parse_programm("class A { int f() { return g(1).h()[2].x; } }").unwrap();
+    let Some(Block(stmts)) = &ast[0].methods[0].body else {
+        panic!("Expected block");
+    };
+    // The calls, array access and field access are applied from left to right
+    let call = |obj: Expr, name: &str, args: Vec<Expr>| {
+        StmtExprExpr(Box::new(MethodCall(obj, name.to_string(), args)))
+    };
+    let chain = call(call(This, "g", vec![Integer(1)]), "h", vec![]);
+    assert_eq!(
+        stmts[0],
+        Return(InstVar(
+            Box::new(ArrayAccess(Box::new(chain), Box::new(Integer(2)))),
+            "x".to_string()
+        ))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Not a statement")]
+fn test_field_of_call_as_statement() {
+    parser::parse_programm("class A { int x; A g() { return this; } void f() { g().x; } }")
+        .unwrap();
+}
@@ -29,5 +29,6 @@ fn empty_class() -> Class {
         name: "Empty".to_string(),
         fields: vec![],
         methods: vec![],
+        ..Default::default()
     }
 }
@@ -28,11 +28,12 @@ fn empty_method_class() -> Class {
     Class {
         name: "EmptyMethod".to_string(),
         fields: vec![],
-        methods: vec![MethodDecl {
-            ret_type: Type::Void,
-            name: "f".to_string(),
-            params: vec![],
-            body: TypedStmt(Box::new(Block(vec![])), Type::Void),
-        }],
+        methods: vec![method_decl(
+            Type::Void,
+            "f",
+            vec![],
+            TypedStmt(Box::new(Block(vec![])), Type::Void),
+        )],
+        ..Default::default()
     }
 }
@@ -0,0 +1,74 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("Enums");
+}
+
+#[test]
+fn test_parse_enum() {
+    let ast =
+        parser::parse_programm("enum E { A, B(1, 'b'), ; E() {} E(int i, char c) {} }").unwrap();
+    let class = &ast[0];
+    assert!(class.is_enum);
+    assert_eq!(
+        class.enum_constants,
+        vec![
+            EnumConstant {
+                name: "A".to_string(),
+                args: vec![],
+            },
+            EnumConstant {
+                name: "B".to_string(),
+                args: vec![Integer(1), Char('b')],
+            },
+        ]
+    );
+    // The constructors of enums are implicitly private
+    assert!(class
+        .constructors
+        .iter()
+        .all(|c| c.modifiers.visibility == Visibility::Private));
+}
+
+#[test]
+#[should_panic(expected = "Enum E can't be instantiated")]
+fn test_instantiate_enum() {
+    typecheck_source("enum E { A } class B { E f() { return new E(); } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Modifier public not allowed here")]
+fn test_public_enum_constructor() {
+    parser::parse_programm("enum E { A; public E() {} }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Call to super(...) not allowed in enum constructor")]
+fn test_enum_super_call() {
+    typecheck_source("enum E { A; E() { super(); } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Class B can't extend the final class E")]
+fn test_extend_enum() {
+    typecheck_source("enum E { A } class B extends E {}").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Duplicate field name: A")]
+fn test_duplicate_constant() {
+    typecheck_source("enum E { A, A }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Duplicate method values() in class E")]
+fn test_declare_values() {
+    typecheck_source("enum E { A; static E[] values() { return null; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "No constructor of class E takes arguments of types (String)")]
+fn test_constant_arguments() {
+    typecheck_source("enum E { A(\"a\"); E(int i) {} }").unwrap();
+}
@@ -0,0 +1,117 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("Exceptions");
+}
+
+#[test]
+fn test_parse_try() {
+    let ast = parser::parse_programm(
+        "class A { void f() throws E, F { try { throwIt(); } catch (E | F e) { throw e; } finally {} } }",
+    )
+    .unwrap();
+    let method = &ast[0].methods[0];
+    assert_eq!(method.throws, vec!["E".to_string(), "F".to_string()]);
+    let Some(Stmt::Block(stmts)) = &method.body else {
+        panic!("Expected block");
+    };
+    let [Try(body, catches, Some(finally))] = &stmts[..] else {
+        panic!("Expected try statement with finally block");
+    };
+    // `throwIt` is a method, not a throw statement
+    assert_eq!(
+        **body,
+        Block(vec![StmtExprStmt(StmtExpr::MethodCall(
+            This,
+            "throwIt".to_string(),
+            vec![]
+        ))])
+    );
+    assert_eq!(
+        *catches,
+        vec![CatchClause {
+            classes: vec!["E".to_string(), "F".to_string()],
+            name: "e".to_string(),
+            body: Block(vec![Throw(LocalOrFieldVar("e".to_string()))]),
+        }]
+    );
+    assert_eq!(**finally, Block(vec![]));
+}
+
+#[test]
+#[should_panic(expected = "Unreported exception E; must be caught or declared to be thrown")]
+fn test_unreported_exception() {
+    typecheck_source("class E extends Exception {} class A { void f() { throw new E(); } }")
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unreported exception E; must be caught or declared to be thrown")]
+fn test_unreported_exception_of_call() {
+    typecheck_source(
+        "class E extends Exception {} class A { void f() throws E {} void g() { try { f(); } catch (RuntimeException e) {} } }",
+    )
+    .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Exception E has already been caught")]
+fn test_already_caught() {
+    typecheck_source(
+        "class E extends RuntimeException {} class A { void f() { try {} catch (RuntimeException e) {} catch (E e) {} } }",
+    )
+    .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Exception E is never thrown in body of corresponding try statement")]
+fn test_never_thrown() {
+    typecheck_source("class E extends Exception {} class A { void f() { try {} catch (E e) {} } }")
+        .unwrap();
+}
+
+#[test]
+#[should_panic(
+    expected = "Alternatives in a multi-catch statement cannot be related by subclassing"
+)]
+fn test_related_alternatives() {
+    typecheck_source(
+        "class E extends RuntimeException {} class A { void f() { try {} catch (E | RuntimeException e) {} } }",
+    )
+    .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Incompatible types: A cannot be converted to Throwable")]
+fn test_throw_non_throwable() {
+    typecheck_source("class A { void f() { throw new A(); } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "since the overridden method does not throw E")]
+fn test_override_throws() {
+    typecheck_source(
+        "class E extends Exception {} class A { void f() {} } class B extends A { void f() throws E {} }",
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_parse_return_void() {
+    let ast =
+        parser::parse_programm("class A { void f() { try { return; } finally {} } }").unwrap();
+    let Some(Stmt::Block(stmts)) = &ast[0].methods[0].body else {
+        panic!("Expected block");
+    };
+    let [Try(body, _, _)] = &stmts[..] else {
+        panic!("Expected try statement");
+    };
+    assert_eq!(**body, Block(vec![ReturnVoid]));
+}
+
+#[test]
+#[should_panic(expected = "Missing return value: expected int")]
+fn test_return_void_from_int() {
+    typecheck_source("class A { int f() { try { return; } finally {} } }").unwrap();
+}
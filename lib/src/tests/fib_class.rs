@@ -29,11 +29,11 @@ fn fib_class() -> Class {
         name: "Fib".to_string(),
         fields: vec![],
         methods: vec![
-            MethodDecl {
-                ret_type: Type::Int,
-                name: "rec".to_string(),
-                params: vec![(Type::Int, "n".to_string())],
-                body: TypedStmt(
+            method_decl(
+                Type::Int,
+                "rec",
+                vec![(Type::Int, "n".to_string())],
+                TypedStmt(
                     Box::new(Block(vec![TypedStmt(
                         Box::new(If(
                             TypedExpr(
@@ -132,12 +132,12 @@ fn fib_class() -> Class {
                     )])),
                     Type::Int,
                 ),
-            },
-            MethodDecl {
-                ret_type: Type::Int,
-                name: "iter".to_string(),
-                params: vec![(Type::Int, "n".to_string())],
-                body: TypedStmt(
+            ),
+            method_decl(
+                Type::Int,
+                "iter",
+                vec![(Type::Int, "n".to_string())],
+                TypedStmt(
                     Box::new(Block(vec![
                         TypedStmt(
                             Box::new(If(
@@ -354,7 +354,8 @@ fn fib_class() -> Class {
                     ])),
                     Type::Int,
                 ),
-            },
+            ),
         ],
+        ..Default::default()
     }
 }
@@ -28,27 +28,12 @@ fn fields_class() -> Class {
     Class {
         name: "Fields".to_string(),
         fields: vec![
-            FieldDecl {
-                field_type: Type::Int,
-                name: "x".to_string(),
-                val: None,
-            },
-            FieldDecl {
-                field_type: Type::Bool,
-                name: "b".to_string(),
-                val: None,
-            },
-            FieldDecl {
-                field_type: Type::Char,
-                name: "c".to_string(),
-                val: None,
-            },
-            FieldDecl {
-                field_type: Type::String,
-                name: "s".to_string(),
-                val: None,
-            },
+            field_decl(Type::Int, "x", None),
+            field_decl(Type::Bool, "b", None),
+            field_decl(Type::Char, "c", None),
+            field_decl(Type::String, "s", None),
         ],
         methods: vec![],
+        ..Default::default()
     }
 }
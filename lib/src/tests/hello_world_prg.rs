@@ -0,0 +1,20 @@
+use super::*;
+
+#[test]
+fn test_run() {
+    source_run_test("HelloWorld");
+}
+
+#[test]
+#[should_panic(
+    expected = "No method println of class java/io/PrintStream is applicable to the arguments (int, int)"
+)]
+fn test_println_arguments() {
+    typecheck_source("class A { void f() { System.out.println(1, 2); } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unknown field in in class java/lang/System")]
+fn test_unknown_library_field() {
+    typecheck_source("class A { void f() { System.in.println(1); } }").unwrap();
+}
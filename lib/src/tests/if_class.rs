@@ -28,11 +28,11 @@ fn if_class() -> Class {
     Class {
         name: "If".to_string(),
         fields: vec![],
-        methods: vec![MethodDecl {
-            name: "f".to_string(),
-            params: vec![(Type::Char, "c".to_string())],
-            ret_type: Type::Bool,
-            body: TypedStmt(
+        methods: vec![method_decl(
+            Type::Bool,
+            "f",
+            vec![(Type::Char, "c".to_string())],
+            TypedStmt(
                 Box::new(Block(vec![
                     TypedStmt(
                         Box::new(If(
@@ -65,6 +65,7 @@ fn if_class() -> Class {
                 ])),
                 Type::Bool,
             ),
-        }],
+        )],
+        ..Default::default()
     }
 }
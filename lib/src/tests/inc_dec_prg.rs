@@ -0,0 +1,30 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("IncDec");
+}
+
+#[test]
+#[should_panic(expected = "Operator ++ can't be applied to a variable of type boolean")]
+fn test_bool_increment() {
+    typecheck_source("class A { void f(boolean b) { b++; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Operator += can't be applied to a value of type boolean")]
+fn test_bool_compound_assignment() {
+    typecheck_source("class A { void f(int n) { n += true; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Operator *= can't be applied to a variable of type String")]
+fn test_string_compound_assignment() {
+    typecheck_source("class A { void f(String s) { s *= 2; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Cannot assign a value to final variable")]
+fn test_final_increment() {
+    typecheck_source("class A { final int n = 1; void f() { --n; } }").unwrap();
+}
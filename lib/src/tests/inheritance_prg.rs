@@ -0,0 +1,57 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("Inheritance");
+}
+
+#[test]
+fn test_unknown_super_class() {
+    let result = typecheck_source("class B extends A {}");
+    assert_eq!(
+        result,
+        Err("Superclass A of class B does not exist".to_string())
+    );
+}
+
+#[test]
+fn test_cyclic_inheritance() {
+    let result = typecheck_source("class A extends B {} class B extends A {}");
+    assert_eq!(
+        result,
+        Err("Cyclic inheritance involving class A".to_string())
+    );
+}
+
+#[test]
+fn test_overriding_return_type() {
+    let result = typecheck_source(
+        "class A { int f() { return 1; } } class B extends A { boolean f() { return true; } }",
+    );
+    assert_eq!(
+        result,
+        Err(
+            "Method f of class B overrides a method of class A with a different return type"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+#[should_panic(expected = "Value of type A can't be assigned to a variable of type B")]
+fn test_assign_super_class_to_sub_class() {
+    typecheck_source("class A {} class B extends A { B f() { B b = new A(); return b; } }")
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Class A doesn't extend another class, so super can't be used")]
+fn test_super_without_super_class() {
+    typecheck_source("class A { int x; int f() { return super.x; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "No constructor of class A takes arguments of types ()")]
+fn test_implicit_super_call() {
+    typecheck_source("class A { A(int x) {} } class B extends A {}").unwrap();
+}
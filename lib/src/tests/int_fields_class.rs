@@ -28,27 +28,12 @@ fn int_fields_class() -> Class {
     Class {
         name: "IntFields".to_string(),
         fields: vec![
-            FieldDecl {
-                field_type: Type::Int,
-                name: "x".to_string(),
-                val: None,
-            },
-            FieldDecl {
-                field_type: Type::Int,
-                name: "y".to_string(),
-                val: None,
-            },
-            FieldDecl {
-                field_type: Type::Int,
-                name: "z".to_string(),
-                val: None,
-            },
-            FieldDecl {
-                field_type: Type::Int,
-                name: "another_int".to_string(),
-                val: None,
-            },
+            field_decl(Type::Int, "x", None),
+            field_decl(Type::Int, "y", None),
+            field_decl(Type::Int, "z", None),
+            field_decl(Type::Int, "another_int", None),
         ],
         methods: vec![],
+        ..Default::default()
     }
 }
@@ -0,0 +1,93 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("Interfaces");
+}
+
+#[test]
+#[should_panic(expected = "I is abstract and can't be instantiated")]
+fn test_instantiate_interface() {
+    typecheck_source("interface I {} class A { I f() { return new I(); } }").unwrap();
+}
+
+#[test]
+fn test_unimplemented_interface_method() {
+    let result = typecheck_source(
+        "interface I { int f(); } interface J extends I {} class A implements J {}",
+    );
+    assert_eq!(
+        result,
+        Err(
+            "Class A must be declared abstract or implement the abstract method f of I".to_string()
+        )
+    );
+}
+
+#[test]
+fn test_implement_class() {
+    let result = typecheck_source("class A {} class B implements A {}");
+    assert_eq!(
+        result,
+        Err("A implemented by class B is not an interface".to_string())
+    );
+}
+
+#[test]
+fn test_extend_interface() {
+    let result = typecheck_source("interface I {} class A extends I {}");
+    assert_eq!(
+        result,
+        Err("Class A can't extend the interface I".to_string())
+    );
+}
+
+#[test]
+fn test_unknown_interface() {
+    let result = typecheck_source("class A implements I {}");
+    assert_eq!(
+        result,
+        Err("Interface I of class A does not exist".to_string())
+    );
+}
+
+#[test]
+fn test_cyclic_interfaces() {
+    let result = typecheck_source("interface I extends J {} interface J extends I {}");
+    assert_eq!(
+        result,
+        Err("Cyclic inheritance involving class I".to_string())
+    );
+}
+
+#[test]
+fn test_interface_return_type() {
+    let result = typecheck_source(
+        "interface I { int f(); } class A implements I { public boolean f() { return true; } }",
+    );
+    assert_eq!(
+        result,
+        Err(
+            "Method f of class A overrides a method of class I with a different return type"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+#[should_panic(expected = "Value of type A can't be assigned to a variable of type I")]
+fn test_assign_to_unimplemented_interface() {
+    typecheck_source("interface I {} class A { I f() { I i = new A(); return i; } }").unwrap();
+}
+
+#[test]
+fn test_implementation_not_public() {
+    let result =
+        typecheck_source("interface I { int f(); } class A implements I { int f() { return 1; } }");
+    assert_eq!(
+        result,
+        Err(
+            "Method f of class A can't override a method of class I with weaker access".to_string()
+        )
+    );
+}
@@ -0,0 +1,37 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("Jumps");
+}
+
+#[test]
+#[should_panic(expected = "Break outside of switch or loop")]
+fn test_break_outside_of_loop() {
+    typecheck_source("class A { void f() { break; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Continue outside of loop")]
+fn test_continue_in_labeled_block() {
+    typecheck_source("class A { void f() { a: { continue; } } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Undefined label: outer")]
+fn test_undefined_label() {
+    typecheck_source("class A { void f() { while (true) { break outer; } } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Not a loop label: a")]
+fn test_continue_non_loop_label() {
+    typecheck_source("class A { void f() { a: { while (true) { continue a; } } } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Label a is already in use")]
+fn test_duplicate_label() {
+    typecheck_source("class A { void f() { a: while (true) { a: while (true) {} } } }").unwrap();
+}
+
@@ -0,0 +1,86 @@
+use super::*;
+
+#[test]
+fn test_run() {
+    source_run_test("Lambdas");
+}
+
+#[test]
+fn test_parse_lambdas() {
+    let ast = parser::parse_programm(
+        "class A { void f() { g(() -> 1, x -> x, (int a, int b) -> { return a; }, this::h, A::new); } }",
+    )
+    .unwrap();
+    let Some(Stmt::Block(stmts)) = &ast[0].methods[0].body else {
+        panic!("Expected block");
+    };
+    let Stmt::StmtExprStmt(StmtExpr::MethodCall(_, _, args)) = &stmts[0] else {
+        panic!("Expected method call");
+    };
+    let ret = |expr: Expr| Box::new(Stmt::Return(expr));
+    let var = |name: &str| LocalOrFieldVar(name.to_string());
+    assert_eq!(
+        args,
+        &vec![
+            Lambda(vec![], ret(Integer(1))),
+            Lambda(vec![(None, "x".to_string())], ret(var("x"))),
+            Lambda(
+                vec![
+                    (Some(Type::Int), "a".to_string()),
+                    (Some(Type::Int), "b".to_string())
+                ],
+                Box::new(Stmt::Block(vec![Stmt::Return(var("a"))]))
+            ),
+            MethodRef(Box::new(This), "h".to_string()),
+            MethodRef(Box::new(var("A")), "new".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_lifted_lambda() {
+    let tast = typecheck_source(
+        "interface F { int f(int a); } class A { int x; F g(int y) { return a -> a + x + y; } }",
+    )
+    .unwrap();
+    // The body is lifted to a private method, which is passed the captured local variable first
+    let lambda = &tast[1].methods[1];
+    assert_eq!(lambda.name, format!("{LAMBDA_METHOD_PREFIX}0"));
+    assert!(lambda.is_lambda());
+    assert_eq!(lambda.modifiers.visibility, Visibility::Private);
+    assert!(!lambda.modifiers.is_static);
+    assert_eq!(
+        lambda.params,
+        vec![(Type::Int, "y".to_string()), (Type::Int, "a".to_string())]
+    );
+}
+
+#[test]
+#[should_panic(expected = "No method g of class A is applicable to the arguments (lambda)")]
+fn test_lambda_to_overloads() {
+    typecheck_source("class A { void g(int i) {} void g(String s) {} void f() { g(() -> 1); } }")
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Incompatible types: java/lang/Object is not a functional interface")]
+fn test_lambda_to_class() {
+    typecheck_source("class A { void f() { Object o = () -> 1; } }").unwrap();
+}
+
+#[test]
+#[should_panic(
+    expected = "Local variables referenced from a lambda expression must be final or effectively final"
+)]
+fn test_assign_captured_var() {
+    typecheck_source("class A { void f() { int i = 0; Runnable r = () -> i++; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Bad return type in method reference: int can't be converted to boolean")]
+fn test_method_ref_return_type() {
+    typecheck_source(
+        "interface P { boolean p(int a); } class A { static int g(int a) { return a; } P f() { return A::g; } }",
+    )
+    .unwrap();
+}
@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("Library");
+}
+
+#[test]
+#[should_panic(
+    expected = "No method valueOf of class java/lang/String is applicable to the arguments (int, int)"
+)]
+fn test_inapplicable_library_method() {
+    typecheck_source("class A { String f() { return String.valueOf(1, 2); } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Non-static method length can't be referenced from a static context")]
+fn test_instance_method_via_class() {
+    typecheck_source("class A { int f() { return String.length(); } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Cannot assign a value to final variable MAX_VALUE")]
+fn test_assign_library_constant() {
+    typecheck_source("class A { void f() { Integer.MAX_VALUE = 1; } }").unwrap();
+}
@@ -0,0 +1,71 @@
+use super::*;
+
+#[test]
+fn test_run() {
+    source_run_test("LocalClasses");
+}
+
+#[test]
+fn test_parse_local_classes() {
+    let ast = parser::parse_programm(
+        "class A { void f() { int x; final class L { class M {} } interface I {} } }",
+    )
+    .unwrap();
+    let Some(Stmt::Block(stmts)) = &ast[0].methods[0].body else {
+        panic!("Expected block");
+    };
+    // The local class is followed by its nested classes, which are named after it
+    let Stmt::LocalClassDecl(classes) = &stmts[1] else {
+        panic!("Expected local class declaration");
+    };
+    let names: Vec<(&str, Option<&str>)> = classes
+        .iter()
+        .map(|class| (class.name.as_str(), class.outer_class.as_deref()))
+        .collect();
+    assert_eq!(names, vec![("L", None), ("L$M", Some("L"))]);
+    assert!(classes[0].modifiers.is_final);
+    assert!(matches!(&stmts[2], Stmt::LocalClassDecl(classes) if classes[0].is_interface));
+}
+
+#[test]
+#[should_panic(expected = "Modifier private not allowed here")]
+fn test_private_local_class() {
+    parser::parse_programm("class A { void f() { private class L {} } }").unwrap();
+}
+
+#[test]
+fn test_lifted_local_classes() {
+    let tast = typecheck_source(
+        "class A { int f(int x) { int y = 1; class L { int g() { return x; } } L l = new L(); return l.g(); }
+                   static void h() { class L { class M {} } } }",
+    )
+    .unwrap();
+    // The local classes follow the classes of the program and are numbered per name
+    let names: Vec<&str> = tast.iter().map(|class| class.name.as_str()).collect();
+    assert_eq!(names, vec!["A", "A$1L", "A$2L", "A$2L$M"]);
+    assert!(tast[1].is_local);
+    assert_eq!(tast[1].simple_name(), "L");
+    assert_eq!(tast[1].outer_class.as_deref(), Some("A"));
+    // Only the used local variable is captured
+    assert_eq!(tast[1].captured_vars, vec![(Type::Int, "x".to_string())]);
+    assert!(tast[1].is_inner());
+    // Local classes in a static context don't have an enclosing instance, unlike their inner classes
+    assert!(!tast[2].is_inner());
+    assert!(!tast[3].is_local);
+    assert!(tast[3].is_inner());
+}
+
+#[test]
+#[should_panic(
+    expected = "Local variables referenced from an inner class must be final or effectively final"
+)]
+fn test_assign_captured_var() {
+    typecheck_source("class A { void f(int x) { class L { void g() { x = 1; } } } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unknown class L")]
+fn test_local_class_out_of_scope() {
+    typecheck_source("class A { void f(boolean b) { if (b) { class L {} } L l = new L(); } }")
+        .unwrap();
+}
@@ -28,11 +28,11 @@ fn local_var_decl_class() -> Class {
     Class {
         name: "LocalVarDecl".to_string(),
         fields: vec![],
-        methods: vec![MethodDecl {
-            ret_type: Type::Int,
-            name: "f".to_string(),
-            params: vec![],
-            body: TypedStmt(
+        methods: vec![method_decl(
+            Type::Int,
+            "f",
+            vec![],
+            TypedStmt(
                 Box::new(Block(vec![
                     TypedStmt(
                         Box::new(LocalVarDecl(Type::Int, "x".to_string())),
@@ -61,6 +61,7 @@ fn local_var_decl_class() -> Class {
                 ])),
                 Type::Int,
             ),
-        }],
+        )],
+        ..Default::default()
     }
 }
@@ -0,0 +1,37 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("Loops");
+}
+
+#[test]
+#[should_panic(expected = "For condition must be bool")]
+fn test_int_condition() {
+    typecheck_source("class A { void f() { for (int i = 0; i; i = i + 1) {} } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Do-while condition must be bool")]
+fn test_do_while_int_condition() {
+    typecheck_source("class A { void f() { do {} while (1); } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unknown variable: i")]
+fn test_loop_variable_out_of_scope() {
+    typecheck_source("class A { int f() { for (int i = 0; i < 3; i = i + 1) {} return i; } }")
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "For-each loop can't iterate over a value of type int")]
+fn test_for_each_over_int() {
+    typecheck_source("class A { void f(int n) { for (int i : n) {} } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Element of type java/lang/Object can't be assigned to a variable of type int")]
+fn test_wrong_element_type() {
+    typecheck_source("class A { void f(Iterable xs) { for (int x : xs) {} } }").unwrap();
+}
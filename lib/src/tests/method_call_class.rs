@@ -29,35 +29,35 @@ fn method_call_class() -> Class {
         name: "MethodCall".to_string(),
         fields: vec![],
         methods: vec![
-            MethodDecl {
-                ret_type: Type::Int,
-                name: "a".to_string(),
-                params: vec![],
-                body: TypedStmt(
+            method_decl(
+                Type::Int,
+                "a",
+                vec![],
+                TypedStmt(
                     Box::new(Block(vec![TypedStmt(
                         Box::new(Return(TypedExpr(Box::new(Expr::Integer(2)), Type::Int))),
                         Type::Int,
                     )])),
                     Type::Int,
                 ),
-            },
-            MethodDecl {
-                ret_type: Type::Int,
-                name: "b".to_string(),
-                params: vec![],
-                body: TypedStmt(
+            ),
+            method_decl(
+                Type::Int,
+                "b",
+                vec![],
+                TypedStmt(
                     Box::new(Block(vec![TypedStmt(
                         Box::new(Return(TypedExpr(Box::new(Expr::Integer(5)), Type::Int))),
                         Type::Int,
                     )])),
                     Type::Int,
                 ),
-            },
-            MethodDecl {
-                ret_type: Type::Int,
-                name: "f".to_string(),
-                params: vec![],
-                body: TypedStmt(
+            ),
+            method_decl(
+                Type::Int,
+                "f",
+                vec![],
+                TypedStmt(
                     Box::new(Block(vec![TypedStmt(
                         Box::new(Return(TypedExpr(
                             Box::new(Binary(
@@ -97,7 +97,8 @@ fn method_call_class() -> Class {
                     )])),
                     Type::Int,
                 ),
-            },
+            ),
         ],
+        ..Default::default()
     }
 }
@@ -2,22 +2,49 @@
 #![allow(unused)]
 #![allow(non_snake_case)]
 
+mod abstract_classes_prg;
 mod arithmetic_methods_class;
+mod arrays_prg;
 mod assigned_fields_class;
+mod bitwise_prg;
 mod bool_alg_class;
+mod casts_prg;
+mod classpath_prg;
 mod complex_if_class;
+mod conditionals_prg;
+mod constants_prg;
+mod constructors_prg;
+mod cross_class_calls_prg;
 mod empty_class;
 mod empty_method_class;
+mod enums_prg;
+mod exceptions_prg;
 mod fib_class;
 mod fields_class;
+mod hello_world_prg;
 mod if_class;
+mod inc_dec_prg;
+mod inheritance_prg;
+mod interfaces_prg;
 mod int_fields_class;
+mod jumps_prg;
+mod lambdas_prg;
+mod library_prg;
+mod local_classes_prg;
 mod local_var_decl_class;
+mod loops_prg;
 mod method_call_class;
+mod modifiers_prg;
+mod multi_class_prg;
 mod naming_conflict_class;
 mod negator_class;
+mod nested_classes_prg;
+mod overloading_prg;
+mod primitives_prg;
 mod return_class;
 mod setter_getter_class;
+mod strings_prg;
+mod switches_prg;
 mod tast_to_ast;
 mod to_java;
 mod while_class;
@@ -59,13 +86,40 @@ fn normalize_str(s: std::string::String) -> std::string::String {
         .fold("".to_string(), |acc, s| acc + s)
 }
 
+/// A field of the hand-written fixtures, which is declared without modifiers
+pub fn field_decl(field_type: Type, name: &str, val: Option<Expr>) -> FieldDecl {
+    FieldDecl {
+        modifiers: Modifiers::default(),
+        field_type,
+        name: name.to_string(),
+        val,
+    }
+}
+
+/// A method of the hand-written fixtures, which is declared without modifiers and throws clause
+pub fn method_decl(
+    ret_type: Type,
+    name: &str,
+    params: Vec<(Type, std::string::String)>,
+    body: Stmt,
+) -> MethodDecl {
+    MethodDecl {
+        modifiers: Modifiers::default(),
+        ret_type,
+        name: name.to_string(),
+        params,
+        throws: vec![],
+        body: Some(body),
+    }
+}
+
 pub fn parser_test(ast: &Class, name: &str) {
     // Call parser with java code
     let parse_res = parser::parse_programm(
         &read_to_string(File::open(format!("lib/testcases/{name}.java")).unwrap()).unwrap(),
     )
     .unwrap();
-    let parse_res = parse_res.get(0).unwrap();
+    let parse_res = parse_res.first().unwrap();
     assert_eq!(parse_res, ast);
 }
 
@@ -75,6 +129,20 @@ pub fn typechecker_test(ast: &Class, tast: &Class) {
     assert_eq!(typed_classes[0], *tast);
 }
 
+pub fn prg_parser_test(ast: &Prg, name: &str) {
+    let parse_res = parser::parse_programm(
+        &read_to_string(File::open(format!("lib/testcases/{name}.java")).unwrap()).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(parse_res, *ast);
+}
+
+pub fn prg_typechecker_test(ast: &Prg, tast: &Prg) {
+    let mut tc = TypeChecker::new(ast.clone()).unwrap();
+    let typed_classes = tc.check_and_type_program().unwrap();
+    assert_eq!(typed_classes, *tast);
+}
+
 const TEST_VALS_AMOUNT: usize = 5;
 static BOOL_TEST_VALS: [&str; 4] = ["true", "false", "false", "true"];
 static CHAR_TEST_VALS: [&str; TEST_VALS_AMOUNT] = ["'c'", "'x'", "'!'", "'a'", "'f'"];
@@ -91,40 +159,94 @@ pub fn get_test_val(t: Type, i: usize) -> std::string::String {
         Type::Bool => BOOL_TEST_VALS[xorshift().wrapping_add(i) % BOOL_TEST_VALS.len()].to_string(),
         Type::Char => CHAR_TEST_VALS[i % CHAR_TEST_VALS.len()].to_string(),
         Type::Int => (xorshift().wrapping_add(i) % 30).to_string(),
+        Type::Long => format!("{}L", (xorshift().wrapping_add(i) % 30) << 40),
+        Type::Float => format!("{}f", (xorshift().wrapping_add(i) % 30) as f32 / 4.0),
+        Type::Double => format!("{:?}", (xorshift().wrapping_add(i) % 30) as f64 / 8.0),
+        // Ints can't be passed as bytes or shorts without a cast
+        Type::Byte | Type::Short => format!("({t}) {}", xorshift().wrapping_add(i) % 300),
         Type::Null => "null".to_string(),
         Type::Void => panic!("can't create a test value for parameters of type 'void'"),
         Type::String => STR_TEST_VALS[i % STR_TEST_VALS.len()].to_string(),
         Type::Class(name) => format!("new {name}()"),
+        // The length has to be given for the first dimension, e.g. `new int[0][]`
+        Type::Array(_) => {
+            let array_type = t.to_string();
+            let (elem_type, dims) = array_type.split_once("[]").unwrap();
+            format!("new {elem_type}[0]{dims}")
+        }
     }
 }
 
 pub fn codegen_test(tast: &Class, name: &str) {
-    // Create code to run tests on generated class file
+    prg_codegen_test(&vec![tast.clone()], name);
+}
+
+/// Like `codegen_test`, but for a whole program consisting of several classes, which are all
+/// expected to be declared in `lib/testcases/{name}.java`
+pub fn prg_codegen_test(tast: &Prg, name: &str) {
+    prg_codegen_test_with_version(tast, name, DEFAULT_MAJOR_VERSION);
+}
+
+/// Like `prg_codegen_test`, but the class files are generated with the given major version
+pub fn prg_codegen_test_with_version(tast: &Prg, name: &str, major_version: u16) {
+    // Create code to run tests on generated class files
+    // The tested methods may declare to throw checked exceptions
     let mut java_code = format!(
-        "class {name}Test {{\npublic static void main(String[] args) {{\n{} m = new {}();\n",
-        tast.name, tast.name
+        "class {name}Test {{\npublic static void main(String[] args) throws Exception {{\n"
     );
-    for method in tast.methods.iter() {
-        let n = if method.params.is_empty() {
-            1
-        } else {
-            TEST_VALS_AMOUNT
-        };
-        for i in 0..n {
-            let test_inputs: Vec<std::string::String> = method
-                .params
-                .iter()
-                .enumerate()
-                .map(|(j, p)| get_test_val(p.0.clone(), i * j))
-                .collect();
-            let test_inputs = test_inputs.join(",");
-            let method_call = format!("m.{}({})", method.name, test_inputs);
-            if method.ret_type == Type::Void {
-                java_code.push_str(&method_call);
+    for (class_idx, class) in tast.iter().enumerate() {
+        // Abstract classes and interfaces can't be instantiated, but their methods are tested
+        // through the classes implementing them. The same goes for enums and their constants.
+        // Nested classes are tested through the classes enclosing them.
+        if class.is_abstract || class.is_enum || class.outer_class.is_some() {
+            continue;
+        }
+        // Classes without a constructor taking no arguments are created with the one with the fewest parameters
+        let constructor_args = class
+            .constructors
+            .iter()
+            .min_by_key(|constructor| constructor.params.len())
+            .map(|constructor| {
+                constructor
+                    .params
+                    .iter()
+                    .enumerate()
+                    .map(|(j, p)| get_test_val(p.0.clone(), j))
+                    .collect::<Vec<std::string::String>>()
+                    .join(",")
+            })
+            .unwrap_or_default();
+        java_code.push_str(&format!(
+            "{} m{class_idx} = new {}({constructor_args});\n",
+            class.name, class.name
+        ));
+        // Private methods can't be called from the test class
+        for method in class
+            .methods
+            .iter()
+            .filter(|m| m.modifiers.visibility != Visibility::Private)
+        {
+            let n = if method.params.is_empty() {
+                1
             } else {
-                java_code.push_str(&format!("System.out.println({method_call})"));
+                TEST_VALS_AMOUNT
+            };
+            for i in 0..n {
+                let test_inputs: Vec<std::string::String> = method
+                    .params
+                    .iter()
+                    .enumerate()
+                    .map(|(j, p)| get_test_val(p.0.clone(), i * j))
+                    .collect();
+                let test_inputs = test_inputs.join(",");
+                let method_call = format!("m{class_idx}.{}({})", method.name, test_inputs);
+                if method.ret_type == Type::Void {
+                    java_code.push_str(&method_call);
+                } else {
+                    java_code.push_str(&format!("System.out.println({method_call})"));
+                }
+                java_code.push_str(";\n");
             }
-            java_code.push_str(";\n");
         }
     }
     java_code.push_str("}}");
@@ -136,26 +258,35 @@ pub fn codegen_test(tast: &Class, name: &str) {
 
     // Compile original java code for expected result
     compile_java(name);
-    let mut expected_bytes = vec![];
-    File::open(format!("lib/testcases/{name}.class"))
-        .unwrap()
-        .read_to_end(&mut expected_bytes)
-        .unwrap();
-    println!("Expected bytes:  {:?}", expected_bytes);
-    disassemble_java(name, &format!("{name}")); // Probably useful for debugging
+    for class in tast {
+        let expected_bytes = disassemble_java(&class.name, &class.name); // Probably useful for debugging
+        println!("Expected bytes:  {:?}", expected_bytes);
+    }
     compile_java(&format!("{name}Test"));
     let expected_out = run_java(&format!("{name}Test"));
 
+    // The test class is compiled by us as well, so it is typed together with the tested classes
+    let mut prg: Prg = tast.iter().map(tast_to_ast).collect();
+    prg.append(&mut parser::parse_programm(&java_code).unwrap());
+    let typed_test_class = TypeChecker::new(prg)
+        .unwrap()
+        .check_and_type_program()
+        .unwrap()
+        .pop()
+        .unwrap();
+    let mut tast = tast.clone();
+    tast.push(typed_test_class);
+
     // Compile & run tests on generated DIR
-    let mut dir = generate_dir(&vec![tast.clone()]);
-    let generated_bytes = dir.as_bytes();
-    File::create(format!("lib/testcases/{name}.class"))
-        .unwrap_or_else(|_| panic!("failed to create {name}.class"))
-        .write_all(&generated_bytes)
-        .unwrap_or_else(|_| panic!("failed to write generated DIR into {name}.class"));
-    println!("Generated bytes: {:?}", generated_bytes);
-    disassemble_java(name, &format!("{name}-codegen")); // Probably useful for debugging
-    compile_java(&format!("{name}Test"));
+    let mut dir = generate_dir_with_version(&tast, &Prg::new(), major_version);
+    for (class_name, generated_bytes) in dir.as_bytes() {
+        File::create(format!("lib/testcases/{class_name}.class"))
+            .unwrap_or_else(|_| panic!("failed to create {class_name}.class"))
+            .write_all(&generated_bytes)
+            .unwrap_or_else(|_| panic!("failed to write generated DIR into {class_name}.class"));
+        println!("Generated bytes: {:?}", generated_bytes);
+        disassemble_java(&class_name, &format!("{class_name}-codegen")); // Probably useful for debugging
+    }
     let codegen_out = run_java(&format!("{name}Test"));
 
     let (codegen_out, expected_out) = dbg!(codegen_out, expected_out);
@@ -165,6 +296,63 @@ pub fn codegen_test(tast: &Class, name: &str) {
     assert_eq!(codegen_out.stdout, expected_out.stdout);
 }
 
+/// Parses and typechecks `lib/testcases/{name}.java` and then compares the behaviour of the
+/// generated classes with the ones compiled by javac
+pub fn source_codegen_test(name: &str) {
+    source_codegen_test_with_version(name, DEFAULT_MAJOR_VERSION);
+}
+
+/// Like `source_codegen_test`, but the class files are generated with the given major version
+pub fn source_codegen_test_with_version(name: &str, major_version: u16) {
+    let tast = typecheck_source(
+        &read_to_string(File::open(format!("lib/testcases/{name}.java")).unwrap()).unwrap(),
+    )
+    .unwrap();
+    prg_codegen_test_with_version(&tast, name, major_version);
+}
+
+/// Compiles `lib/testcases/{name}.java`, whose class `name` declares a main method, and compares
+/// the output of running the generated classes with the one of the classes compiled by javac
+pub fn source_run_test(name: &str) {
+    source_run_test_with_classpath(name, vec![]);
+}
+
+/// Like `source_run_test`, but the program is compiled against the classes read from the classpath,
+/// whose class files have to be in `lib/testcases`
+pub fn source_run_test_with_classpath(name: &str, classpath: Prg) {
+    let ast = parser::parse_programm(
+        &read_to_string(File::open(format!("lib/testcases/{name}.java")).unwrap()).unwrap(),
+    )
+    .unwrap();
+    let tast = TypeChecker::with_classpath(ast, classpath.clone())
+        .unwrap()
+        .check_and_type_program()
+        .unwrap();
+
+    compile_java(name);
+    let expected_out = run_java(name);
+
+    let mut dir = generate_dir_with_classpath(&tast, &classpath);
+    for (class_name, generated_bytes) in dir.as_bytes() {
+        File::create(format!("lib/testcases/{class_name}.class"))
+            .unwrap_or_else(|_| panic!("failed to create {class_name}.class"))
+            .write_all(&generated_bytes)
+            .unwrap_or_else(|_| panic!("failed to write generated DIR into {class_name}.class"));
+        disassemble_java(&class_name, &format!("{class_name}-codegen")); // Probably useful for debugging
+    }
+    let codegen_out = run_java(name);
+
+    let (codegen_out, expected_out) = dbg!(codegen_out, expected_out);
+    assert_eq!(codegen_out.status.code().unwrap(), 0);
+    assert_eq!(codegen_out.stderr, expected_out.stderr);
+    assert_eq!(codegen_out.stdout, expected_out.stdout);
+}
+
+pub fn typecheck_source(code: &str) -> Result<Prg, std::string::String> {
+    let ast = parser::parse_programm(code).unwrap();
+    TypeChecker::new(ast)?.check_and_type_program()
+}
+
 pub fn class_test(ast: &Class, tast: Option<&Class>, name: &str) {
     // Write AST & TAST to files
     let mut file =
@@ -202,7 +390,7 @@ pub fn class_test(ast: &Class, tast: Option<&Class>, name: &str) {
     file.write_all(og_java_code.as_bytes())
         .expect("failed to write original java code back");
     compile_java(name);
-    let og_clz = disassemble_java(name, &format!("{name}"));
+    let og_clz = disassemble_java(name, name);
 
     assert_eq!(og_clz, gen_clz);
 }
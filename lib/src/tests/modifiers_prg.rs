@@ -0,0 +1,111 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("Modifiers");
+}
+
+#[test]
+#[should_panic(expected = "Illegal combination of modifiers public and private")]
+fn test_multiple_visibilities() {
+    typecheck_source("class A { public private int x; }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Modifier static not allowed here")]
+fn test_static_class() {
+    typecheck_source("static class A {}").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Method f is missing a body")]
+fn test_method_without_body() {
+    typecheck_source("class A { int f(); }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "x has private access in A")]
+fn test_private_field() {
+    typecheck_source("class A { private int x; } class B { int f(A a) { return a.x; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "f has private access in A")]
+fn test_private_method() {
+    typecheck_source(
+        "class A { private int f() { return 1; } } class B extends A { int g() { return f(); } }",
+    )
+    .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "A(int) has private access in A")]
+fn test_private_constructor() {
+    typecheck_source("class A { private A(int x) {} } class B { A f() { return new A(1); } }")
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Cannot assign a value to final variable x")]
+fn test_assign_final_field() {
+    typecheck_source("class A { final int x = 1; void f() { x = 2; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Cannot assign a value to final variable x")]
+fn test_assign_final_field_of_other_object() {
+    typecheck_source("class A { final int x; A(A other) { other.x = 2; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Non-static variable this can't be referenced from a static context")]
+fn test_this_in_static_method() {
+    typecheck_source("class A { int x; static int f() { return this.x; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Non-static field x can't be referenced from a static context")]
+fn test_field_in_static_method() {
+    typecheck_source("class A { int x; static int f() { return x; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Non-static method g can't be referenced from a static context")]
+fn test_instance_method_via_class() {
+    typecheck_source("class A { int g() { return 1; } } class B { int f() { return A.g(); } }")
+        .unwrap();
+}
+
+#[test]
+fn test_extend_final_class() {
+    let result = typecheck_source("final class A {} class B extends A {}");
+    assert_eq!(
+        result,
+        Err("Class B can't extend the final class A".to_string())
+    );
+}
+
+#[test]
+fn test_override_final_method() {
+    let result = typecheck_source(
+        "class A { final int f() { return 1; } } class B extends A { int f() { return 2; } }",
+    );
+    assert_eq!(
+        result,
+        Err("Method f of class B can't override the final method of class A".to_string())
+    );
+}
+
+#[test]
+fn test_override_static_method() {
+    let result = typecheck_source(
+        "class A { static int f() { return 1; } } class B extends A { int f() { return 2; } }",
+    );
+    assert_eq!(
+        result,
+        Err(
+            "Method f of class B can't override a method of class A, since only one of them is static"
+                .to_string()
+        )
+    );
+}
@@ -0,0 +1,80 @@
+use super::*;
+
+#[test]
+fn test_parser() {
+    let prg = multi_class_prg();
+    prg_parser_test(&prg.iter().map(tast_to_ast).collect(), "MultiClass");
+}
+
+#[test]
+fn test_typechecker() {
+    let prg = multi_class_prg();
+    prg_typechecker_test(&prg.iter().map(tast_to_ast).collect(), &prg);
+}
+
+#[test]
+fn test_codegen() {
+    let prg = multi_class_prg();
+    prg_codegen_test(&prg, "MultiClass");
+}
+
+#[test]
+fn test_parse_error_in_later_class() {
+    // A class, which doesn't parse, isn't dropped silently
+    assert!(parser::parse_programm("class A {} class B { int f() { return 1 } }").is_err());
+}
+
+fn multi_class_prg() -> Prg {
+    vec![
+        Class {
+            name: "MultiClassA".to_string(),
+            fields: vec![field_decl(
+                Type::Int,
+                "x",
+                Some(TypedExpr(Box::new(Integer(3)), Type::Int)),
+            )],
+            methods: vec![method_decl(
+                Type::Int,
+                "getX",
+                vec![],
+                TypedStmt(
+                    Box::new(Block(vec![TypedStmt(
+                        Box::new(Return(TypedExpr(
+                            Box::new(FieldVar("x".to_string())),
+                            Type::Int,
+                        ))),
+                        Type::Int,
+                    )])),
+                    Type::Int,
+                ),
+            )],
+            ..Default::default()
+        },
+        Class {
+            name: "MultiClassB".to_string(),
+            fields: vec![],
+            methods: vec![method_decl(
+                Type::Bool,
+                "negate",
+                vec![(Type::Bool, "b".to_string())],
+                TypedStmt(
+                    Box::new(Block(vec![TypedStmt(
+                        Box::new(Return(TypedExpr(
+                            Box::new(Unary(
+                                "!".to_string(),
+                                Box::new(TypedExpr(
+                                    Box::new(LocalVar("b".to_string())),
+                                    Type::Bool,
+                                )),
+                            )),
+                            Type::Bool,
+                        ))),
+                        Type::Bool,
+                    )])),
+                    Type::Bool,
+                ),
+            )],
+            ..Default::default()
+        },
+    ]
+}
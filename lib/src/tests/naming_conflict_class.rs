@@ -27,16 +27,16 @@ fn test_codegen() {
 fn naming_conflict() -> Class {
     Class {
         name: "NamingConflict".to_string(),
-        fields: vec![FieldDecl {
-            field_type: Type::Int,
-            name: "x".to_string(),
-            val: Some(Expr::Integer(69)),
-        }],
-        methods: vec![MethodDecl {
-            ret_type: Type::Int,
-            name: "f".to_string(),
-            params: vec![(Type::Int, "x".to_string())],
-            body: TypedStmt(
+        fields: vec![field_decl(
+            Type::Int,
+            "x",
+            Some(TypedExpr(Box::new(Integer(69)), Type::Int)),
+        )],
+        methods: vec![method_decl(
+            Type::Int,
+            "f",
+            vec![(Type::Int, "x".to_string())],
+            TypedStmt(
                 Box::new(Block(vec![TypedStmt(
                     Box::new(Return(TypedExpr(
                         Box::new(Binary(
@@ -59,6 +59,7 @@ fn naming_conflict() -> Class {
                 )])),
                 Type::Int,
             ),
-        }],
+        )],
+        ..Default::default()
     }
 }
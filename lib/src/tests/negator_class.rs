@@ -29,11 +29,11 @@ fn negator_class() -> Class {
         name: "Negator".to_string(),
         fields: vec![],
         methods: vec![
-            MethodDecl {
-                ret_type: Type::Int,
-                name: "neg1".to_string(),
-                params: vec![(Type::Int, "x".to_string())],
-                body: TypedStmt(
+            method_decl(
+                Type::Int,
+                "neg1",
+                vec![(Type::Int, "x".to_string())],
+                TypedStmt(
                     Box::new(Block(vec![TypedStmt(
                         Box::new(Return(TypedExpr(
                             Box::new(Unary(
@@ -49,12 +49,12 @@ fn negator_class() -> Class {
                     )])),
                     Type::Int,
                 ),
-            },
-            MethodDecl {
-                ret_type: Type::Int,
-                name: "neg2".to_string(),
-                params: vec![(Type::Int, "x".to_string())],
-                body: TypedStmt(
+            ),
+            method_decl(
+                Type::Int,
+                "neg2",
+                vec![(Type::Int, "x".to_string())],
+                TypedStmt(
                     Box::new(Block(vec![TypedStmt(
                         Box::new(Return(TypedExpr(
                             Box::new(Unary(
@@ -92,7 +92,8 @@ fn negator_class() -> Class {
                     )])),
                     Type::Int,
                 ),
-            },
+            ),
         ],
+        ..Default::default()
     }
 }
@@ -0,0 +1,165 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("NestedClasses");
+}
+
+#[test]
+fn test_accessors_before_nests() {
+    let tast = typecheck_source(
+        "class A { private int x; class B { int f(A a) { a.x++; return a.x; } } }",
+    )
+    .unwrap();
+    // Before Java 11, the private field of the outer class is accessed through its accessors,
+    // since then as a member of the same nest
+    let accessor = |major_version| {
+        let classes = generate_dir_with_version(&tast, &Prg::new(), major_version).as_bytes();
+        let has_accessor = |bytes: &Vec<u8>| bytes.windows(10).any(|name| name == b"access$001");
+        (has_accessor(&classes[0].1), has_accessor(&classes[1].1))
+    };
+    assert_eq!(accessor(DEFAULT_MAJOR_VERSION), (true, true));
+    assert_eq!(accessor(55), (false, false));
+}
+
+#[test]
+fn test_parse_nested_classes() {
+    let ast = parser::parse_programm(
+        "class A { static class B { interface C {} } private class D {} int x; }",
+    )
+    .unwrap();
+    // Nested classes follow the class declaring them and are named after their outer classes
+    let names: Vec<(&str, Option<&str>)> = ast
+        .iter()
+        .map(|class| (class.name.as_str(), class.outer_class.as_deref()))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            ("A", None),
+            ("A$B", Some("A")),
+            ("A$B$C", Some("A$B")),
+            ("A$D", Some("A")),
+        ]
+    );
+    assert_eq!(ast[0].fields.len(), 1);
+    assert!(!ast[1].is_inner());
+    // Nested interfaces are implicitly static
+    assert!(!ast[2].is_inner());
+    assert!(ast[3].is_inner());
+    assert_eq!(ast[3].modifiers.visibility, Visibility::Private);
+}
+
+#[test]
+fn test_outer_this() {
+    let tast = typecheck_source(
+        "class A { int x; class B { class C { int f() { return A.this.x + x; } } } }",
+    )
+    .unwrap();
+    // The fields holding the enclosing instances aren't part of the typed classes
+    assert!(tast[2].fields.is_empty());
+    let Some(Stmt::TypedStmt(block, _)) = &tast[2].methods[0].body else {
+        panic!("Expected typed block");
+    };
+    let Stmt::Block(stmts) = &**block else {
+        panic!("Expected block");
+    };
+    let Stmt::TypedStmt(ret, _) = &stmts[0] else {
+        panic!("Expected typed return");
+    };
+    let Stmt::Return(TypedExpr(sum, Type::Int)) = &**ret else {
+        panic!("Expected return of an int");
+    };
+    let Binary(_, qualified, simple) = &**sum else {
+        panic!("Expected binary expression");
+    };
+    // Both access the field via the enclosing instances of C and B
+    let outer_instance = |obj: Expr, outer_class: &str| {
+        TypedExpr(
+            Box::new(InstVar(Box::new(obj), OUTER_INSTANCE_FIELD.to_string())),
+            Type::Class(outer_class.to_string()),
+        )
+    };
+    let a = outer_instance(
+        outer_instance(
+            TypedExpr(Box::new(This), Type::Class("A$B$C".to_string())),
+            "A$B",
+        ),
+        "A",
+    );
+    let field = TypedExpr(Box::new(InstVar(Box::new(a), "x".to_string())), Type::Int);
+    assert_eq!(**qualified, field);
+    assert_eq!(**simple, field);
+}
+
+#[test]
+fn test_implicit_enclosing_instance() {
+    let tast = typecheck_source("class A { class B {} B f() { return new B(); } }").unwrap();
+    let Some(Stmt::TypedStmt(block, _)) = &tast[0].methods[0].body else {
+        panic!("Expected typed block");
+    };
+    let Stmt::Block(stmts) = &**block else {
+        panic!("Expected block");
+    };
+    let Stmt::TypedStmt(ret, _) = &stmts[0] else {
+        panic!("Expected typed return");
+    };
+    let Stmt::Return(TypedExpr(new, _)) = &**ret else {
+        panic!("Expected return");
+    };
+    // `new B()` creates an instance enclosed by `this`
+    assert_eq!(
+        **new,
+        StmtExprExpr(Box::new(TypedStmtExpr(
+            Box::new(QualifiedNew(
+                TypedExpr(Box::new(This), Type::Class("A".to_string())),
+                Type::Class("A$B".to_string()),
+                vec![]
+            )),
+            Type::Class("A$B".to_string())
+        )))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Non-static field x can't be referenced from a static context")]
+fn test_instance_field_in_static_nested_class() {
+    typecheck_source("class A { int x; static class B { int f() { return x; } } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "No enclosing instance of type A is in scope")]
+fn test_inner_class_in_static_method() {
+    typecheck_source("class A { class B {} static void f() { new B(); } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Qualified new of static class A$B")]
+fn test_qualified_new_of_static_class() {
+    typecheck_source("class A { static class B {} void f() { this.new B(); } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Not an enclosing class: C")]
+fn test_outer_this_of_other_class() {
+    typecheck_source("class A { class B { Object f() { return C.this; } } } class C {}").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "x has private access in A$B")]
+fn test_private_member_outside_of_nest() {
+    typecheck_source("class A { static class B { private static int x; } } class C { int f() { return A.B.x; } }")
+        .unwrap();
+}
+
+#[test]
+fn test_extend_inner_class() {
+    let ast = parser::parse_programm("class A { class B {} } class C extends A.B {}").unwrap();
+    assert_eq!(
+        TypeChecker::new(ast)
+            .unwrap()
+            .check_and_type_program()
+            .err(),
+        Some("Class C can't extend the inner class A$B".to_string())
+    );
+}
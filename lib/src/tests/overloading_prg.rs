@@ -0,0 +1,79 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("Overloading");
+}
+
+#[test]
+fn test_selected_overload() {
+    let tast = typecheck_source(
+        "class A { int f(long l) { return 1; } int f(double d) { return 2; } int g() { return f('a'); } }",
+    )
+    .unwrap();
+    let Some(Stmt::TypedStmt(block, _)) = &tast[0].methods[2].body else {
+        panic!("Expected typed block");
+    };
+    let Stmt::Block(stmts) = &**block else {
+        panic!("Expected block");
+    };
+    let Stmt::TypedStmt(ret, _) = &stmts[0] else {
+        panic!("Expected typed return");
+    };
+    let Stmt::Return(TypedExpr(call, Type::Int)) = &**ret else {
+        panic!("Expected return of an int");
+    };
+    let StmtExprExpr(call) = &**call else {
+        panic!("Expected method call");
+    };
+    let StmtExpr::TypedStmtExpr(call, _) = &**call else {
+        panic!("Expected typed method call");
+    };
+    let StmtExpr::MethodCall(_, name, args) = &**call else {
+        panic!("Expected method call");
+    };
+    // The char is widened to a long, since f(long) is more specific than f(double)
+    assert_eq!(name, "f");
+    assert_eq!(args[0].get_type(), Some(Type::Long));
+}
+
+#[test]
+#[should_panic(expected = "Duplicate method f(int) in class A")]
+fn test_duplicate_signature() {
+    typecheck_source("class A { int f(int a) { return a; } boolean f(int b) { return true; } }")
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Ambiguous method call f(int, int) of class A")]
+fn test_ambiguous_call() {
+    typecheck_source(
+        "class A { void f(int a, long b) {} void f(long a, int b) {} void g() { f(1, 2); } }",
+    )
+    .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Ambiguous method call f(null) of class A")]
+fn test_ambiguous_null() {
+    typecheck_source("class A { void f(String s) {} void f(A a) {} void g() { f(null); } }")
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "No method f of class A is applicable to the arguments (double)")]
+fn test_no_applicable_overload() {
+    typecheck_source("class A { void f(int a) {} void f(long a) {} void g() { f(1.5); } }")
+        .unwrap();
+}
+
+#[test]
+#[should_panic(
+    expected = "Ambiguous constructor call of class A with arguments of types (int, int)"
+)]
+fn test_ambiguous_constructor() {
+    typecheck_source(
+        "class A { A(int a, long b) {} A(long a, int b) {} A f() { return new A(1, 2); } }",
+    )
+    .unwrap();
+}
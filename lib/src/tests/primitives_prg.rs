@@ -0,0 +1,68 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("Primitives");
+}
+
+#[test]
+fn test_literals() {
+    let ast =
+        parser::parse_programm("class A { void f() { g(1L, -2l, 1.5, .5e1, 2f, 3.25F, 4d, 7); } }")
+            .unwrap();
+    let Some(Stmt::Block(stmts)) = &ast[0].methods[0].body else {
+        panic!("Expected block");
+    };
+    let [StmtExprStmt(StmtExpr::MethodCall(_, _, args))] = &stmts[..] else {
+        panic!("Expected method call");
+    };
+    assert_eq!(
+        *args,
+        vec![
+            Long(1),
+            Long(-2),
+            Double(1.5),
+            Double(5.0),
+            Float(2.0),
+            Float(3.25),
+            Double(4.0),
+            Integer(7)
+        ]
+    );
+}
+
+#[test]
+fn test_narrowed_constant() {
+    typecheck_source("class A { void f() { byte b = -128; short s = 'a'; char c = 65; } }")
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Value of type double can't be assigned to a variable of type int")]
+fn test_narrowing_double() {
+    typecheck_source("class A { void f() { int i = 1.5; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Value of type int can't be assigned to a variable of type byte")]
+fn test_narrowing_variable() {
+    typecheck_source("class A { void f(int i) { byte b = i; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Value of type int can't be assigned to a variable of type byte")]
+fn test_constant_out_of_range() {
+    typecheck_source("class A { void f() { byte b = 128; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Value of type long can't be assigned to a variable of type int")]
+fn test_promoted_result() {
+    typecheck_source("class A { int f(int i) { return i + 1L; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Operator & can't be applied to values of type double and int")]
+fn test_bitwise_double() {
+    typecheck_source("class A { double f(double d) { return d & 1; } }").unwrap();
+}
@@ -29,11 +29,11 @@ fn return_class() -> Class {
         name: "Return".to_string(),
         fields: vec![],
         methods: vec![
-            MethodDecl {
-                ret_type: Type::Char,
-                name: "id".to_string(),
-                params: vec![(Type::Char, "x".to_string())],
-                body: TypedStmt(
+            method_decl(
+                Type::Char,
+                "id",
+                vec![(Type::Char, "x".to_string())],
+                TypedStmt(
                     Box::new(Block(vec![TypedStmt(
                         Box::new(Return(TypedExpr(
                             Box::new(Expr::LocalVar("x".to_string())),
@@ -43,12 +43,12 @@ fn return_class() -> Class {
                     )])),
                     Type::Char,
                 ),
-            },
-            MethodDecl {
-                ret_type: Type::Bool,
-                name: "id".to_string(),
-                params: vec![(Type::Bool, "b".to_string())],
-                body: TypedStmt(
+            ),
+            method_decl(
+                Type::Bool,
+                "id",
+                vec![(Type::Bool, "b".to_string())],
+                TypedStmt(
                     Box::new(Block(vec![TypedStmt(
                         Box::new(Return(TypedExpr(
                             Box::new(Expr::LocalVar("b".to_string())),
@@ -58,7 +58,8 @@ fn return_class() -> Class {
                     )])),
                     Type::Bool,
                 ),
-            },
+            ),
         ],
+        ..Default::default()
     }
 }
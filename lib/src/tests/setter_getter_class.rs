@@ -28,33 +28,17 @@ fn setter_getter_class() -> Class {
     Class {
         name: "SetterGetter".to_string(),
         fields: vec![
-            FieldDecl {
-                field_type: Type::Int,
-                name: "x".to_string(),
-                val: None,
-            },
-            FieldDecl {
-                field_type: Type::Bool,
-                name: "b".to_string(),
-                val: None,
-            },
-            FieldDecl {
-                field_type: Type::Char,
-                name: "c".to_string(),
-                val: None,
-            },
-            FieldDecl {
-                field_type: Type::String,
-                name: "s".to_string(),
-                val: None,
-            },
+            field_decl(Type::Int, "x", None),
+            field_decl(Type::Bool, "b", None),
+            field_decl(Type::Char, "c", None),
+            field_decl(Type::String, "s", None),
         ],
         methods: vec![
-            MethodDecl {
-                ret_type: Type::Int,
-                name: "getX".to_string(),
-                params: vec![],
-                body: TypedStmt(
+            method_decl(
+                Type::Int,
+                "getX",
+                vec![],
+                TypedStmt(
                     Box::new(Block(vec![TypedStmt(
                         Box::new(Return(TypedExpr(
                             Box::new(InstVar(
@@ -70,12 +54,12 @@ fn setter_getter_class() -> Class {
                     )])),
                     Type::Int,
                 ),
-            },
-            MethodDecl {
-                ret_type: Type::Void,
-                name: "setX".to_string(),
-                params: vec![(Type::Int, "x".to_string())],
-                body: TypedStmt(
+            ),
+            method_decl(
+                Type::Void,
+                "setX",
+                vec![(Type::Int, "x".to_string())],
+                TypedStmt(
                     Box::new(Block(vec![TypedStmt(
                         Box::new(StmtExprStmt(TypedStmtExpr(
                             Box::new(Assign(
@@ -97,12 +81,12 @@ fn setter_getter_class() -> Class {
                     )])),
                     Type::Void,
                 ),
-            },
-            MethodDecl {
-                ret_type: Type::Bool,
-                name: "getB".to_string(),
-                params: vec![],
-                body: TypedStmt(
+            ),
+            method_decl(
+                Type::Bool,
+                "getB",
+                vec![],
+                TypedStmt(
                     Box::new(Block(vec![TypedStmt(
                         Box::new(Return(TypedExpr(
                             Box::new(InstVar(
@@ -118,12 +102,12 @@ fn setter_getter_class() -> Class {
                     )])),
                     Type::Bool,
                 ),
-            },
-            MethodDecl {
-                ret_type: Type::Void,
-                name: "setB".to_string(),
-                params: vec![(Type::Bool, "b".to_string())],
-                body: TypedStmt(
+            ),
+            method_decl(
+                Type::Void,
+                "setB",
+                vec![(Type::Bool, "b".to_string())],
+                TypedStmt(
                     Box::new(Block(vec![TypedStmt(
                         Box::new(StmtExprStmt(TypedStmtExpr(
                             Box::new(Assign(
@@ -145,12 +129,12 @@ fn setter_getter_class() -> Class {
                     )])),
                     Type::Void,
                 ),
-            },
-            MethodDecl {
-                ret_type: Type::Char,
-                name: "getC".to_string(),
-                params: vec![],
-                body: TypedStmt(
+            ),
+            method_decl(
+                Type::Char,
+                "getC",
+                vec![],
+                TypedStmt(
                     Box::new(Block(vec![TypedStmt(
                         Box::new(Return(TypedExpr(
                             Box::new(InstVar(
@@ -166,12 +150,12 @@ fn setter_getter_class() -> Class {
                     )])),
                     Type::Char,
                 ),
-            },
-            MethodDecl {
-                ret_type: Type::Void,
-                name: "setC".to_string(),
-                params: vec![(Type::Char, "c".to_string())],
-                body: TypedStmt(
+            ),
+            method_decl(
+                Type::Void,
+                "setC",
+                vec![(Type::Char, "c".to_string())],
+                TypedStmt(
                     Box::new(Block(vec![TypedStmt(
                         Box::new(StmtExprStmt(TypedStmtExpr(
                             Box::new(Assign(
@@ -193,12 +177,12 @@ fn setter_getter_class() -> Class {
                     )])),
                     Type::Void,
                 ),
-            },
-            MethodDecl {
-                ret_type: Type::String,
-                name: "getS".to_string(),
-                params: vec![],
-                body: TypedStmt(
+            ),
+            method_decl(
+                Type::String,
+                "getS",
+                vec![],
+                TypedStmt(
                     Box::new(Block(vec![TypedStmt(
                         Box::new(Return(TypedExpr(
                             Box::new(InstVar(
@@ -214,12 +198,12 @@ fn setter_getter_class() -> Class {
                     )])),
                     Type::String,
                 ),
-            },
-            MethodDecl {
-                ret_type: Type::Void,
-                name: "setS".to_string(),
-                params: vec![(Type::String, "s".to_string())],
-                body: TypedStmt(
+            ),
+            method_decl(
+                Type::Void,
+                "setS",
+                vec![(Type::String, "s".to_string())],
+                TypedStmt(
                     Box::new(Block(vec![TypedStmt(
                         Box::new(StmtExprStmt(TypedStmtExpr(
                             Box::new(Assign(
@@ -241,7 +225,8 @@ fn setter_getter_class() -> Class {
                     )])),
                     Type::Void,
                 ),
-            },
+            ),
         ],
+        ..Default::default()
     }
 }
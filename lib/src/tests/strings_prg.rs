@@ -0,0 +1,59 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("Strings");
+    // Java 9 concatenates strings via invokedynamic. Both are tested by the same test, since
+    // they write the same files.
+    source_codegen_test_with_version("Strings", 53);
+}
+
+#[test]
+fn test_concat_type() {
+    let tast =
+        typecheck_source("class A { String f(int i) { return 1 + i + \"a\" + 'b'; } }").unwrap();
+    let Some(Stmt::TypedStmt(block, _)) = &tast[0].methods[0].body else {
+        panic!("Expected typed block");
+    };
+    let Stmt::Block(stmts) = &**block else {
+        panic!("Expected block");
+    };
+    let Stmt::TypedStmt(ret, _) = &stmts[0] else {
+        panic!("Expected typed return");
+    };
+    let Stmt::Return(TypedExpr(concat, Type::String)) = &**ret else {
+        panic!("Expected return of a String");
+    };
+    // `1 + i` is an int addition, since `+` is left-associative
+    let Binary(_, left, _) = &**concat else {
+        panic!("Expected concatenation");
+    };
+    let TypedExpr(left, Type::String) = &**left else {
+        panic!("Expected String");
+    };
+    let Binary(_, sum, _) = &**left else {
+        panic!("Expected concatenation");
+    };
+    assert_eq!(sum.get_type(), Some(Type::Int));
+}
+
+#[test]
+#[should_panic(expected = "Operator + can't be applied to values of type String and void")]
+fn test_concat_void() {
+    typecheck_source("class A { void g() {} String f() { return \"a\" + g(); } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Type mismatch")]
+fn test_compare_unrelated_references() {
+    typecheck_source("class A { boolean f(String s, A a) { return s == a; } }").unwrap();
+}
+
+#[test]
+fn test_release_major_version() {
+    assert_eq!(release_major_version(8), Some(DEFAULT_MAJOR_VERSION));
+    assert_eq!(release_major_version(17), Some(61));
+    // Java 7 lacks method handles and too large releases would overflow the major version
+    assert_eq!(release_major_version(7), None);
+    assert_eq!(release_major_version(u16::MAX), None);
+}
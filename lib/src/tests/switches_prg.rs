@@ -0,0 +1,77 @@
+use super::*;
+
+#[test]
+fn test_codegen() {
+    source_codegen_test("Switches");
+}
+
+#[test]
+#[should_panic(expected = "Switch can't be applied to a value of type boolean")]
+fn test_bool_selector() {
+    typecheck_source("class A { void f(boolean b) { switch (b) { default: } } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Duplicate case label")]
+fn test_duplicate_label() {
+    typecheck_source("class A { void f(int n) { switch (n) { case 97: break; case 'a': } } }")
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Duplicate default label")]
+fn test_duplicate_default() {
+    typecheck_source("class A { void f(int n) { switch (n) { default: break; default: } } }")
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Case label must be a constant")]
+fn test_variable_label() {
+    typecheck_source("class A { void f(int n, int m) { switch (n) { case m: } } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Case label of type String can't be compared to a value of type int")]
+fn test_wrong_label_type() {
+    typecheck_source("class A { void f(int n) { switch (n) { case \"a\": } } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "The switch expression does not cover all possible input values")]
+fn test_not_exhaustive() {
+    typecheck_source("class A { int f(int n) { return switch (n) { case 1 -> 2; }; } }").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Switch expression completes without providing a value")]
+fn test_missing_yield() {
+    typecheck_source(
+        "class A { int f(int n) { return switch (n) { case 1 -> {} default -> 2; }; } }",
+    )
+    .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Incompatible types in switch expression: int and String")]
+fn test_incompatible_values() {
+    typecheck_source(
+        "class A { int f(int n) { return switch (n) { case 1 -> 2; default -> \"a\"; }; } }",
+    )
+    .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Break outside of switch or loop")]
+fn test_break_out_of_switch_expression() {
+    typecheck_source(
+        "class A { int f(int n) { while (true) { n = switch (n) { default -> { break; } }; } } }",
+    )
+    .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Yield outside of switch expression")]
+fn test_yield_outside_of_switch_expression() {
+    typecheck_source("class A { void f(int n) { switch (n) { default: yield 1; } } }").unwrap();
+}
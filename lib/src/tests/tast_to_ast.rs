@@ -5,28 +5,87 @@ pub fn stmt_tast_to_ast(stmt: &Stmt) -> Stmt {
         Stmt::TypedStmt(x, _typ) => stmt_tast_to_ast(x),
         Stmt::Block(stmts) => Block(stmts.iter().map(stmt_tast_to_ast).collect()),
         Stmt::Return(expr) => Return(expr_tast_to_ast(expr)),
+        Stmt::ReturnVoid => ReturnVoid,
         Stmt::While(cond, body) => While(expr_tast_to_ast(cond), Box::new(stmt_tast_to_ast(body))),
+        Stmt::DoWhile(body, cond) => {
+            DoWhile(Box::new(stmt_tast_to_ast(body)), expr_tast_to_ast(cond))
+        }
+        Stmt::For(init, cond, update, body) => For(
+            init.iter().map(stmt_tast_to_ast).collect(),
+            cond.as_ref().map(expr_tast_to_ast),
+            update.iter().map(stmt_tast_to_ast).collect(),
+            Box::new(stmt_tast_to_ast(body)),
+        ),
+        Stmt::ForEach(typ, name, expr, body) => ForEach(
+            typ.clone(),
+            name.clone(),
+            expr_tast_to_ast(expr),
+            Box::new(stmt_tast_to_ast(body)),
+        ),
+        Stmt::Labeled(label, stmt) => Labeled(label.clone(), Box::new(stmt_tast_to_ast(stmt))),
+        Stmt::Switch(expr, cases) => Stmt::Switch(expr_tast_to_ast(expr), cases_tast_to_ast(cases)),
+        Stmt::Yield(expr) => Yield(expr_tast_to_ast(expr)),
         Stmt::If(cond, body, elze) => If(
             expr_tast_to_ast(cond),
             Box::new(stmt_tast_to_ast(body)),
             elze.as_ref().map(|x| Box::new(stmt_tast_to_ast(x))),
         ),
         Stmt::StmtExprStmt(stmt_expr) => StmtExprStmt(stmt_expr_tast_to_ast(stmt_expr)),
+        Stmt::Throw(expr) => Throw(expr_tast_to_ast(expr)),
+        Stmt::Try(body, catches, finally) => Try(
+            Box::new(stmt_tast_to_ast(body)),
+            catches
+                .iter()
+                .map(|catch| CatchClause {
+                    classes: catch
+                        .classes
+                        .iter()
+                        .map(|c| c.rsplit('/').next().unwrap().to_string())
+                        .collect(),
+                    name: catch.name.clone(),
+                    body: stmt_tast_to_ast(&catch.body),
+                })
+                .collect(),
+            finally.as_ref().map(|x| Box::new(stmt_tast_to_ast(x))),
+        ),
         _ => stmt.clone(),
     }
 }
 
+pub fn cases_tast_to_ast(cases: &[SwitchCase]) -> Vec<SwitchCase> {
+    cases
+        .iter()
+        .map(|case| SwitchCase {
+            labels: case.labels.iter().map(expr_tast_to_ast).collect(),
+            is_default: case.is_default,
+            body: case.body.iter().map(stmt_tast_to_ast).collect(),
+        })
+        .collect()
+}
+
 pub fn stmt_expr_tast_to_ast(stmt_expr: &StmtExpr) -> StmtExpr {
     match stmt_expr {
         StmtExpr::Assign(var, val) => Assign(expr_tast_to_ast(var), expr_tast_to_ast(val)),
+        StmtExpr::CompoundAssign(var, op, val) => {
+            CompoundAssign(expr_tast_to_ast(var), op.clone(), expr_tast_to_ast(val))
+        }
+        StmtExpr::PreIncDec(op, var) => PreIncDec(op.clone(), expr_tast_to_ast(var)),
+        StmtExpr::PostIncDec(var, op) => PostIncDec(expr_tast_to_ast(var), op.clone()),
         StmtExpr::New(typ, params) => {
             New(typ.clone(), params.iter().map(expr_tast_to_ast).collect())
         }
+        StmtExpr::QualifiedNew(outer, typ, params) => QualifiedNew(
+            expr_tast_to_ast(outer),
+            typ.clone(),
+            params.iter().map(expr_tast_to_ast).collect(),
+        ),
         StmtExpr::MethodCall(obj, method, params) => MethodCall(
             expr_tast_to_ast(obj),
             method.clone(),
             params.iter().map(expr_tast_to_ast).collect(),
         ),
+        StmtExpr::ThisCall(params) => ThisCall(params.iter().map(expr_tast_to_ast).collect()),
+        StmtExpr::SuperCall(params) => SuperCall(params.iter().map(expr_tast_to_ast).collect()),
         StmtExpr::TypedStmtExpr(x, _typ) => stmt_expr_tast_to_ast(x),
     }
 }
@@ -34,7 +93,25 @@ pub fn stmt_expr_tast_to_ast(stmt_expr: &StmtExpr) -> StmtExpr {
 pub fn expr_tast_to_ast(expr: &Expr) -> Expr {
     match expr {
         Expr::InstVar(x, s) => InstVar(Box::new(expr_tast_to_ast(x)), s.clone()),
+        Expr::ArrayAccess(a, i) => {
+            ArrayAccess(Box::new(expr_tast_to_ast(a)), Box::new(expr_tast_to_ast(i)))
+        }
+        Expr::ArrayLength(a) => InstVar(Box::new(expr_tast_to_ast(a)), "length".to_string()),
+        Expr::NewArray(t, dims) => NewArray(t.clone(), dims.iter().map(expr_tast_to_ast).collect()),
+        Expr::ArrayInit(t, xs) => ArrayInit(t.clone(), xs.iter().map(expr_tast_to_ast).collect()),
+        Expr::Conditional(c, a, b) => Conditional(
+            Box::new(expr_tast_to_ast(c)),
+            Box::new(expr_tast_to_ast(a)),
+            Box::new(expr_tast_to_ast(b)),
+        ),
+        Expr::Switch(x, cases) => {
+            Expr::Switch(Box::new(expr_tast_to_ast(x)), cases_tast_to_ast(cases))
+        }
         Expr::Unary(s, x) => Unary(s.clone(), Box::new(expr_tast_to_ast(x))),
+        Expr::Cast(t, x) => Cast(t.clone(), Box::new(expr_tast_to_ast(x))),
+        Expr::InstanceOf(x, t, name) => {
+            InstanceOf(Box::new(expr_tast_to_ast(x)), t.clone(), name.clone())
+        }
         Expr::Binary(op, l, r) => Binary(
             op.clone(),
             Box::new(expr_tast_to_ast(l)),
@@ -44,31 +121,77 @@ pub fn expr_tast_to_ast(expr: &Expr) -> Expr {
         Expr::TypedExpr(x, _t) => expr_tast_to_ast(x),
         Expr::LocalVar(v) => Expr::LocalOrFieldVar(v.clone()),
         Expr::FieldVar(v) => Expr::LocalOrFieldVar(v.clone()),
+        // Library classes like `java/lang/System` are referred to by their simple name
+        Expr::ClassName(v) => Expr::LocalOrFieldVar(v.rsplit('/').next().unwrap().to_string()),
         _ => expr.clone(),
     }
 }
 
 pub fn tast_to_ast(class: &Class) -> Class {
     Class {
+        modifiers: class.modifiers,
         name: class.name.clone(),
+        is_interface: class.is_interface,
+        is_abstract: class.is_abstract,
+        is_enum: class.is_enum,
+        // Enums implicitly extend java/lang/Enum
+        super_class: class.super_class.clone().filter(|_| !class.is_enum),
+        interfaces: class.interfaces.clone(),
+        enum_constants: class
+            .enum_constants
+            .iter()
+            .map(|constant| EnumConstant {
+                name: constant.name.clone(),
+                args: constant.args.iter().map(expr_tast_to_ast).collect(),
+            })
+            .collect(),
         fields: class
             .fields
             .iter()
             .map(|field| FieldDecl {
+                modifiers: field.modifiers,
                 field_type: field.field_type.clone(),
                 name: field.name.clone(),
                 val: field.val.clone().map(|x| expr_tast_to_ast(&x)),
             })
             .collect(),
+        constructors: class
+            .constructors
+            .iter()
+            .map(|constructor| Constructor {
+                modifiers: constructor.modifiers,
+                params: constructor.params.clone(),
+                throws: constructor
+                    .throws
+                    .iter()
+                    .map(|c| c.rsplit('/').next().unwrap().to_string())
+                    .collect(),
+                body: stmt_tast_to_ast(&constructor.body),
+            })
+            .collect(),
         methods: class
             .methods
             .iter()
             .map(|method| MethodDecl {
+                modifiers: method.modifiers,
                 ret_type: method.ret_type.clone(),
                 name: method.name.clone(),
                 params: method.params.clone(),
-                body: stmt_tast_to_ast(&method.body),
+                throws: method
+                    .throws
+                    .iter()
+                    .map(|c| c.rsplit('/').next().unwrap().to_string())
+                    .collect(),
+                body: method.body.as_ref().map(stmt_tast_to_ast),
             })
             .collect(),
+        static_blocks: class
+            .static_blocks
+            .iter()
+            .map(|(fields_before, block)| (*fields_before, stmt_tast_to_ast(block)))
+            .collect(),
+        outer_class: class.outer_class.clone(),
+        is_local: class.is_local,
+        captured_vars: class.captured_vars.clone(),
     }
 }
@@ -18,11 +18,88 @@ pub fn prg_to_java(prg: &Prg) -> String {
     s
 }
 
+pub fn modifiers_to_java(modifiers: &Modifiers) -> String {
+    let mut s = match modifiers.visibility {
+        Visibility::Public => "public ",
+        Visibility::Protected => "protected ",
+        Visibility::Private => "private ",
+        Visibility::Package => "",
+    }
+    .to_string();
+    if modifiers.is_static {
+        s += "static ";
+    }
+    if modifiers.is_final {
+        s += "final ";
+    }
+    s
+}
+
 pub fn class_to_java(class: &Class) -> String {
-    let mut s: String = format!("class {} ", class.name);
+    let mut s: String = if class.is_interface {
+        format!(
+            "{}interface {} ",
+            modifiers_to_java(&class.modifiers),
+            class.name
+        )
+    } else if class.is_enum {
+        // Enums are implicitly final
+        let modifiers = Modifiers {
+            is_final: false,
+            ..class.modifiers
+        };
+        format!("{}enum {} ", modifiers_to_java(&modifiers), class.name)
+    } else if class.is_abstract {
+        format!(
+            "{}abstract class {} ",
+            modifiers_to_java(&class.modifiers),
+            class.name
+        )
+    } else {
+        format!(
+            "{}class {} ",
+            modifiers_to_java(&class.modifiers),
+            class.name
+        )
+    };
+    if let Some(super_class) = class.super_class.as_ref().filter(|_| !class.is_enum) {
+        s += &format!("extends {} ", super_class);
+    }
+    if !class.interfaces.is_empty() {
+        let keyword = if class.is_interface {
+            "extends"
+        } else {
+            "implements"
+        };
+        s += &format!("{} {} ", keyword, class.interfaces.join(", "));
+    }
     s += "{\n";
-    for field in &class.fields {
-        s = format!("{}{}", s, field_to_java(field));
+    if class.is_enum {
+        let constants: Vec<String> = class
+            .enum_constants
+            .iter()
+            .map(|constant| {
+                let args: Vec<String> = constant.args.iter().map(expr_to_java).collect();
+                format!("\t{}({})", constant.name, args.join(", "))
+            })
+            .collect();
+        s += &format!("{};\n", constants.join(",\n"));
+    }
+    // Static blocks are printed after the fields declared before them
+    for i in 0..=class.fields.len() {
+        for (_, block) in class
+            .static_blocks
+            .iter()
+            .filter(|(before, _)| *before == i)
+        {
+            s = format!("{}\tstatic\n{}", s, stmt_to_java(block, 2));
+        }
+        if let Some(field) = class.fields.get(i) {
+            s = format!("{}{}", s, field_to_java(field));
+        }
+    }
+    for constructor in &class.constructors {
+        s = format!("{}{}", s, constructor_to_java(&class.name, constructor));
     }
     for method in &class.methods {
         s = format!("{}{}", s, method_to_java(method));
@@ -32,7 +109,12 @@ pub fn class_to_java(class: &Class) -> String {
 }
 
 pub fn field_to_java(field: &FieldDecl) -> String {
-    let mut s: String = format!("\t{} {}", field.field_type, field.name);
+    let mut s: String = format!(
+        "\t{}{} {}",
+        modifiers_to_java(&field.modifiers),
+        field.field_type,
+        field.name
+    );
     if let Some(x) = &field.val {
         s = format!("{} = {}", s, expr_to_java(x));
     }
@@ -42,7 +124,13 @@ pub fn field_to_java(field: &FieldDecl) -> String {
 
 pub fn method_to_java(method: &MethodDecl) -> String {
     format!(
-        "\t{} {}({})\n{}",
+        "\t{}{}{} {}({}){}{}",
+        modifiers_to_java(&method.modifiers),
+        if method.body.is_none() {
+            "abstract "
+        } else {
+            ""
+        },
         method.ret_type,
         method.name,
         method
@@ -52,10 +140,39 @@ pub fn method_to_java(method: &MethodDecl) -> String {
             .map(|p| format!("{} {}", p.0, p.1))
             .reduce(|acc, s| format!("{}, {}", acc, s))
             .unwrap_or("".to_string()),
-        stmt_to_java(&method.body, 2)
+        throws_to_java(&method.throws),
+        match &method.body {
+            Some(body) => format!("\n{}", stmt_to_java(body, 2)),
+            None => ";\n".to_string(),
+        }
+    )
+}
+
+pub fn constructor_to_java(class_name: &str, constructor: &Constructor) -> String {
+    format!(
+        "\t{}{}({}){}\n{}",
+        modifiers_to_java(&constructor.modifiers),
+        class_name,
+        constructor
+            .params
+            .clone()
+            .into_iter()
+            .map(|p| format!("{} {}", p.0, p.1))
+            .reduce(|acc, s| format!("{}, {}", acc, s))
+            .unwrap_or("".to_string()),
+        throws_to_java(&constructor.throws),
+        stmt_to_java(&constructor.body, 2)
     )
 }
 
+pub fn throws_to_java(throws: &[String]) -> String {
+    if throws.is_empty() {
+        String::new()
+    } else {
+        format!(" throws {}", throws.join(", "))
+    }
+}
+
 pub fn stmt_to_java(stmt: &Stmt, indent: u8) -> String {
     match stmt {
         Stmt::Block(stmts) => format!(
@@ -81,7 +198,12 @@ pub fn stmt_to_java(stmt: &Stmt, indent: u8) -> String {
             s
         }
         Stmt::LocalVarDecl(typ, name) => format!("{}{} {};\n", get_indents(indent), typ, name),
+        // The classes nested in the local class follow it, like the ones nested in other classes
+        Stmt::LocalClassDecl(classes) => {
+            format!("{}{}", get_indents(indent), class_to_java(&classes[0]))
+        }
         Stmt::Return(expr) => format!("{}return {};\n", get_indents(indent), expr_to_java(expr)),
+        Stmt::ReturnVoid => format!("{}return;\n", get_indents(indent)),
         Stmt::StmtExprStmt(stmt_expr) => {
             format!("{}{};\n", get_indents(indent), stmt_expr_to_java(stmt_expr))
         }
@@ -92,10 +214,110 @@ pub fn stmt_to_java(stmt: &Stmt, indent: u8) -> String {
             expr_to_java(cond),
             stmt_to_java(body, indent + 1),
         ),
+        Stmt::DoWhile(body, cond) => format!(
+            "{}do \n{}\n{}while ({});\n",
+            get_indents(indent),
+            stmt_to_java(body, indent + 1),
+            get_indents(indent),
+            expr_to_java(cond),
+        ),
+        // The initializing statements are put in front of the loop, since a local variable
+        // declaration is split into the declaration and an assignment
+        Stmt::For(init, cond, update, body) => format!(
+            "{}{{\n{}{}for (; {}; {}) \n{}\n{}}}\n",
+            get_indents(indent),
+            init.iter()
+                .map(|stmt| stmt_to_java(stmt, indent + 1))
+                .fold("".to_string(), |acc, s| acc + &s),
+            get_indents(indent + 1),
+            cond.as_ref().map(expr_to_java).unwrap_or_default(),
+            update
+                .iter()
+                .map(|stmt| stmt_to_java(stmt, 0)
+                    .trim_end_matches([';', '\n'])
+                    .to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            stmt_to_java(body, indent + 2),
+            get_indents(indent),
+        ),
+        Stmt::Break(label) => format!(
+            "{}break{};\n",
+            get_indents(indent),
+            label.as_ref().map(|l| format!(" {l}")).unwrap_or_default()
+        ),
+        Stmt::Continue(label) => format!(
+            "{}continue{};\n",
+            get_indents(indent),
+            label.as_ref().map(|l| format!(" {l}")).unwrap_or_default()
+        ),
+        Stmt::Labeled(label, stmt) => format!(
+            "{}{}:\n{}",
+            get_indents(indent),
+            label,
+            stmt_to_java(stmt, indent + 1)
+        ),
+        Stmt::Switch(expr, cases) => format!(
+            "{}{}\n",
+            get_indents(indent),
+            switch_to_java(expr, cases, indent)
+        ),
+        Stmt::Yield(expr) => format!("{}yield {};\n", get_indents(indent), expr_to_java(expr)),
+        Stmt::Throw(expr) => format!("{}throw {};\n", get_indents(indent), expr_to_java(expr)),
+        Stmt::Try(body, catches, finally) => {
+            let mut s = format!(
+                "{}try \n{}",
+                get_indents(indent),
+                stmt_to_java(body, indent + 1)
+            );
+            for catch in catches {
+                s += &format!(
+                    " catch ({} {}) \n{}",
+                    catch.classes.join(" | "),
+                    catch.name,
+                    stmt_to_java(&catch.body, indent + 1)
+                );
+            }
+            if let Some(x) = finally {
+                s += &format!(" finally \n{}", stmt_to_java(x, indent + 1));
+            }
+            s += "\n";
+            s
+        }
+        Stmt::ForEach(typ, name, expr, body) => format!(
+            "{}for ({} {} : {}) \n{}\n",
+            get_indents(indent),
+            typ,
+            name,
+            expr_to_java(expr),
+            stmt_to_java(body, indent + 1),
+        ),
+    }
+}
+
+/// Converts a switch statement or expression into the form with groups of statements,
+/// which the cases of the arrow form have been parsed into
+pub fn switch_to_java(expr: &Expr, cases: &[SwitchCase], indent: u8) -> String {
+    let mut s = format!("switch ({}) {{\n", expr_to_java(expr));
+    for case in cases {
+        if !case.labels.is_empty() {
+            s += &format!(
+                "{}case {}:\n",
+                get_indents(indent + 1),
+                params_to_java(&case.labels)
+            );
+        }
+        if case.is_default {
+            s += &format!("{}default:\n", get_indents(indent + 1));
+        }
+        for stmt in &case.body {
+            s += &stmt_to_java(stmt, indent + 2);
+        }
     }
+    s + &get_indents(indent) + "}"
 }
 
-pub fn params_to_java(params: &Vec<Expr>) -> String {
+pub fn params_to_java(params: &[Expr]) -> String {
     params
         .iter()
         .map(expr_to_java)
@@ -106,6 +328,11 @@ pub fn params_to_java(params: &Vec<Expr>) -> String {
 pub fn stmt_expr_to_java(stmt_expr: &StmtExpr) -> String {
     match stmt_expr {
         StmtExpr::Assign(var, expr) => format!("{} = {}", expr_to_java(var), expr_to_java(expr)),
+        StmtExpr::CompoundAssign(var, op, expr) => {
+            format!("{} {}= {}", expr_to_java(var), op, expr_to_java(expr))
+        }
+        StmtExpr::PreIncDec(op, var) => format!("{}{}", op, expr_to_java(var)),
+        StmtExpr::PostIncDec(var, op) => format!("{}{}", expr_to_java(var), op),
         StmtExpr::MethodCall(expr, name, params) => format!(
             "{}.{}({})",
             expr_to_java(expr),
@@ -113,31 +340,110 @@ pub fn stmt_expr_to_java(stmt_expr: &StmtExpr) -> String {
             params_to_java(params)
         ),
         StmtExpr::New(typ, params) => format!("new {}({})", typ, params_to_java(params)),
+        StmtExpr::QualifiedNew(outer, typ, params) => format!(
+            "({}).new {}({})",
+            expr_to_java(outer),
+            typ,
+            params_to_java(params)
+        ),
+        StmtExpr::ThisCall(params) => format!("this({})", params_to_java(params)),
+        StmtExpr::SuperCall(params) => format!("super({})", params_to_java(params)),
         StmtExpr::TypedStmtExpr(stmt_expr, typ) => stmt_expr_to_java(stmt_expr),
     }
 }
 
+/// Escapes the characters of a string or char literal which can't appear in Java source as-is
+fn escape(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '"' | '\'' | '\\' => format!("\\{c}"),
+            '\n' => "\\n".to_string(),
+            '\r' => "\\r".to_string(),
+            '\t' => "\\t".to_string(),
+            c if c.is_control() => format!("\\u{:04x}", c as u32),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
 pub fn expr_to_java(expr: &Expr) -> String {
     match expr {
         Expr::Binary(op, l, r) => format!("({}) {} ({})", expr_to_java(l), op, expr_to_java(r)),
         Expr::Bool(b) => b.to_string(),
         Expr::Char(c) => {
-            println!("{}", format!("{c}"));
-            format!("'{}'", c)
+            println!("{c}");
+            format!("'{}'", escape(&c.to_string()))
         }
         Expr::InstVar(expr, var) => format!("{}.{}", expr_to_java(expr), var),
+        Expr::ArrayAccess(array, index) => {
+            format!("{}[{}]", expr_to_java(array), expr_to_java(index))
+        }
+        Expr::ArrayLength(array) => format!("{}.length", expr_to_java(array)),
+        Expr::NewArray(typ, dims) => {
+            // The dimensions without a length are the remaining brackets of the type
+            let mut elem_type = typ;
+            let mut lengths = String::new();
+            for dim in dims {
+                let Type::Array(t) = elem_type else {
+                    unreachable!()
+                };
+                elem_type = t;
+                lengths += &format!("[{}]", expr_to_java(dim));
+            }
+            let elem_type = elem_type.to_string();
+            let (base_type, empty_dims) =
+                elem_type.split_at(elem_type.find('[').unwrap_or(elem_type.len()));
+            format!("new {}{}{}", base_type, lengths, empty_dims)
+        }
+        Expr::ArrayInit(typ, exprs) => format!("new {}{{{}}}", typ, params_to_java(exprs)),
         Expr::Integer(i) => i.to_string(),
+        Expr::Long(l) => format!("{l}L"),
+        Expr::Float(f) => format!("{f:?}f"),
+        Expr::Double(d) => format!("{d:?}"),
         Expr::Jnull => "null".to_string(),
         Expr::LocalOrFieldVar(var) => var.to_owned(),
         Expr::LocalVar(var) => var.to_owned(),
         Expr::FieldVar(var) => var.to_owned(),
+        Expr::ClassName(name) => name.to_owned(),
         Expr::StmtExprExpr(stmt_expr) => stmt_expr_to_java(stmt_expr),
         Expr::String(s) => {
-            println!("{}", format!("{s}"));
-            format!("\"{}\"", s)
+            println!("{s}");
+            format!("\"{}\"", escape(s))
         }
         Expr::This => "this".to_string(),
+        Expr::QualifiedThis(class_name) => format!("{}.this", class_name.replace('/', ".")),
+        Expr::Super => "super".to_string(),
         Expr::TypedExpr(expr, typ) => expr_to_java(expr),
         Expr::Unary(op, expr) => format!("{}({})", op, expr_to_java(expr)),
+        Expr::Switch(expr, cases) => format!("({})", switch_to_java(expr, cases, 2)),
+        Expr::Conditional(cond, then_expr, else_expr) => format!(
+            "({}) ? ({}) : ({})",
+            expr_to_java(cond),
+            expr_to_java(then_expr),
+            expr_to_java(else_expr)
+        ),
+        Expr::Cast(typ, expr) => format!("({})({})", typ, expr_to_java(expr)),
+        Expr::InstanceOf(expr, typ, name) => format!(
+            "({}) instanceof {}{}",
+            expr_to_java(expr),
+            typ,
+            name.as_ref().map(|n| format!(" {n}")).unwrap_or_default()
+        ),
+        Expr::Lambda(params, body) => {
+            let params = params
+                .iter()
+                .map(|(typ, name)| match typ {
+                    Some(typ) => format!("{typ} {name}"),
+                    None => name.clone(),
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+            match &**body {
+                Stmt::Return(expr) => format!("({params}) -> {}", expr_to_java(expr)),
+                body => format!("({params}) -> {}", stmt_to_java(body, 2)),
+            }
+        }
+        Expr::MethodRef(expr, name) => format!("{}::{}", expr_to_java(expr), name),
+        Expr::FunctionRef(_) => panic!("Typed lambda expressions can't be converted to Java"),
     }
 }
@@ -27,16 +27,16 @@ fn test_codegen() {
 fn while_class() -> Class {
     Class {
         name: "While".to_string(),
-        fields: vec![FieldDecl {
-            field_type: Type::Int,
-            name: "n".to_string(),
-            val: Some(Expr::Integer(2)),
-        }],
-        methods: vec![MethodDecl {
-            name: "f".to_string(),
-            params: vec![(Type::Int, "x".to_string())],
-            ret_type: Type::Int,
-            body: TypedStmt(
+        fields: vec![field_decl(
+            Type::Int,
+            "n",
+            Some(TypedExpr(Box::new(Integer(2)), Type::Int)),
+        )],
+        methods: vec![method_decl(
+            Type::Int,
+            "f",
+            vec![(Type::Int, "x".to_string())],
+            TypedStmt(
                 Box::new(Block(vec![
                     TypedStmt(
                         Box::new(LocalVarDecl(Type::Int, "i".to_string())),
@@ -158,6 +158,7 @@ fn while_class() -> Class {
                 ])),
                 Type::Int,
             ),
-        }],
+        )],
+        ..Default::default()
     }
 }
@@ -28,42 +28,30 @@ fn wonky_assignments_class() -> Class {
     Class {
         name: "WonkyAssignments".to_string(),
         fields: vec![
-            FieldDecl {
-                field_type: Type::Int,
-                name: "x".to_string(),
-                val: None,
-            },
-            FieldDecl {
-                field_type: Type::Int,
-                name: "y".to_string(),
-                val: Some(Expr::Integer(3)),
-            },
-            FieldDecl {
-                field_type: Type::Int,
-                name: "z".to_string(),
-                val: None,
-            },
-            FieldDecl {
-                field_type: Type::Bool,
-                name: "a".to_string(),
-                val: Some(Expr::Bool(false)),
-            },
-            FieldDecl {
-                field_type: Type::Bool,
-                name: "b".to_string(),
-                val: Some(Expr::Bool(true)),
-            },
-            FieldDecl {
-                field_type: Type::Bool,
-                name: "c".to_string(),
-                val: None,
-            },
+            field_decl(Type::Int, "x", None),
+            field_decl(
+                Type::Int,
+                "y",
+                Some(TypedExpr(Box::new(Integer(3)), Type::Int)),
+            ),
+            field_decl(Type::Int, "z", None),
+            field_decl(
+                Type::Bool,
+                "a",
+                Some(TypedExpr(Box::new(Bool(false)), Type::Bool)),
+            ),
+            field_decl(
+                Type::Bool,
+                "b",
+                Some(TypedExpr(Box::new(Bool(true)), Type::Bool)),
+            ),
+            field_decl(Type::Bool, "c", None),
         ],
-        methods: vec![MethodDecl {
-            ret_type: Type::Int,
-            name: "f".to_string(),
-            params: vec![(Type::Int, "newX".to_string())],
-            body: TypedStmt(
+        methods: vec![method_decl(
+            Type::Int,
+            "f",
+            vec![(Type::Int, "newX".to_string())],
+            TypedStmt(
                 Box::new(Block(vec![
                     TypedStmt(
                         Box::new(StmtExprStmt(TypedStmtExpr(
@@ -225,6 +213,7 @@ fn wonky_assignments_class() -> Class {
                 ])),
                 Type::Int,
             ),
-        }],
+        )],
+        ..Default::default()
     }
 }
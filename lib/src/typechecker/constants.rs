@@ -0,0 +1,266 @@
+//! Evaluation of constant expressions, i.e. expressions only consisting of literals, constant
+//! variables and operators, which are evaluated at compile time.
+//! See https://docs.oracle.com/javase/specs/jls/se17/html/jls-15.html#jls-15.29
+
+use crate::types::*;
+
+/// The value of a constant expression. Bytes, shorts and chars are represented by ints.
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    Bool(bool),
+    String(String),
+}
+
+/// Whether a field of the type may be a constant variable, if it is final and initialized
+/// with a constant expression
+pub fn is_constant_type(t: &Type) -> bool {
+    t.is_numeric() || matches!(t, Type::Bool | Type::String)
+}
+
+/// Evaluates a typed expression, returning its value as a typed literal, e.g.
+/// `TypedExpr(Integer(6), Int)` for `2 * 3`. Returns None if it isn't a constant expression.
+/// Expressions which would throw an exception, like a division by zero, aren't constant.
+pub fn constant_value(typed_expr: &Expr) -> Option<Expr> {
+    let t = typed_expr.get_type()?;
+    to_literal(evaluate(typed_expr)?, &t)
+}
+
+/// Evaluates a typed expression to a value of its type
+fn evaluate(typed_expr: &Expr) -> Option<Value> {
+    let Expr::TypedExpr(expr, t) = typed_expr else {
+        return None;
+    };
+    match &**expr {
+        Expr::Integer(i) => convert(Value::Int(*i), t),
+        Expr::Char(c) => convert(Value::Int(*c as i32), t),
+        Expr::Long(l) => convert(Value::Long(*l), t),
+        Expr::Float(f) => convert(Value::Float(*f), t),
+        Expr::Double(d) => convert(Value::Double(*d), t),
+        Expr::Bool(b) => Some(Value::Bool(*b)),
+        Expr::String(s) => Some(Value::String(s.clone())),
+        // Typed expressions wrapped in another type are converted to that type
+        Expr::TypedExpr(_, _) => convert(evaluate(expr)?, t),
+        Expr::Cast(_, expr) => convert(evaluate(expr)?, t),
+        Expr::Conditional(cond, then_expr, else_expr) => match evaluate(cond)? {
+            Value::Bool(true) => convert(evaluate(then_expr)?, t),
+            Value::Bool(false) => convert(evaluate(else_expr)?, t),
+            _ => None,
+        },
+        Expr::Unary(op, operand) => evaluate_unary(&UnaryOp::from(op.as_str()), evaluate(operand)?),
+        Expr::Binary(op, left, right) => {
+            let op = BinaryOp::from(op.as_str());
+            if *t == Type::String {
+                // The operands of a string concatenation keep their own type
+                let left = to_string(evaluate(left)?, &left.get_type()?)?;
+                let right = to_string(evaluate(right)?, &right.get_type()?)?;
+                return Some(Value::String(left + &right));
+            }
+            evaluate_binary(&op, evaluate(left)?, evaluate(right)?)
+        }
+        _ => None,
+    }
+}
+
+/// Converts a value to the type `to` like a cast, e.g. narrowing `300` to the byte `44`
+fn convert(value: Value, to: &Type) -> Option<Value> {
+    let value = match (value, to) {
+        (Value::Bool(b), Type::Bool) => Value::Bool(b),
+        (Value::String(s), Type::String) => Value::String(s),
+        (Value::Bool(_) | Value::String(_), _) => return None,
+        (value, Type::Int) => Value::Int(to_int(&value)),
+        (value, Type::Byte) => Value::Int(to_int(&value) as i8 as i32),
+        (value, Type::Short) => Value::Int(to_int(&value) as i16 as i32),
+        (value, Type::Char) => Value::Int(to_int(&value) as u16 as i32),
+        (Value::Int(i), Type::Long) => Value::Long(i as i64),
+        (Value::Long(l), Type::Long) => Value::Long(l),
+        (Value::Float(f), Type::Long) => Value::Long(f as i64),
+        (Value::Double(d), Type::Long) => Value::Long(d as i64),
+        (Value::Int(i), Type::Float) => Value::Float(i as f32),
+        (Value::Long(l), Type::Float) => Value::Float(l as f32),
+        (Value::Float(f), Type::Float) => Value::Float(f),
+        (Value::Double(d), Type::Float) => Value::Float(d as f32),
+        (Value::Int(i), Type::Double) => Value::Double(i as f64),
+        (Value::Long(l), Type::Double) => Value::Double(l as f64),
+        (Value::Float(f), Type::Double) => Value::Double(f as f64),
+        (Value::Double(d), Type::Double) => Value::Double(d),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Converts a number to an int. Like in Java, floating point numbers are rounded towards zero
+/// and saturated, while longs are truncated.
+fn to_int(value: &Value) -> i32 {
+    match value {
+        Value::Int(i) => *i,
+        Value::Long(l) => *l as i32,
+        Value::Float(f) => *f as i32,
+        Value::Double(d) => *d as i32,
+        Value::Bool(_) | Value::String(_) => unreachable!(),
+    }
+}
+
+/// The string a value of type `t` is converted to by a string concatenation
+fn to_string(value: Value, t: &Type) -> Option<String> {
+    match (value, t) {
+        (Value::Int(c), Type::Char) => char::from_u32(c as u32).map(String::from),
+        (Value::Int(i), _) => Some(i.to_string()),
+        (Value::Long(l), _) => Some(l.to_string()),
+        (Value::Bool(b), _) => Some(b.to_string()),
+        (Value::String(s), _) => Some(s),
+        // Rust's scientific notation uses the fewest digits which identify the number, like Java
+        (Value::Float(f), _) => Some(floating_to_string(&format!("{f:e}"))),
+        (Value::Double(d), _) => Some(floating_to_string(&format!("{d:e}"))),
+    }
+}
+
+/// Formats a floating point number given in Rust's scientific notation, e.g. `1.5e-4`,
+/// like Java's `Double.toString`. Numbers from 10^-3 to 10^7 are written as decimals, the others
+/// in Java's scientific notation, e.g. `0.001`, `1.5E-4` and `1.0E7`. Both have at least one digit
+/// after the point. See https://docs.oracle.com/en/java/javase/17/docs/api/java.base/java/lang/Double.html#toString(double)
+fn floating_to_string(scientific: &str) -> String {
+    match scientific {
+        "NaN" => return "NaN".to_string(),
+        "inf" => return "Infinity".to_string(),
+        "-inf" => return "-Infinity".to_string(),
+        _ => {}
+    }
+    let (sign, scientific) = match scientific.strip_prefix('-') {
+        Some(scientific) => ("-", scientific),
+        None => ("", scientific),
+    };
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let exponent: i32 = exponent.parse().unwrap();
+    let digits = mantissa.replace('.', "");
+    if digits == "0" {
+        return format!("{sign}0.0");
+    }
+    match exponent {
+        -3..=-1 => format!("{sign}0.{}{digits}", "0".repeat((-exponent - 1) as usize)),
+        0..=6 => {
+            // The digits are padded with zeros up to the point, e.g. of `1.0E6`
+            let point = exponent as usize + 1;
+            let digits = format!("{digits:0<point$}");
+            let (int, fraction) = digits.split_at(point);
+            let fraction = if fraction.is_empty() { "0" } else { fraction };
+            format!("{sign}{int}.{fraction}")
+        }
+        _ => {
+            let (first, rest) = digits.split_at(1);
+            let rest = if rest.is_empty() { "0" } else { rest };
+            format!("{sign}{first}.{rest}E{exponent}")
+        }
+    }
+}
+
+fn evaluate_unary(op: &UnaryOp, value: Value) -> Option<Value> {
+    let value = match (op, value) {
+        (UnaryOp::Pos, value) => value,
+        (UnaryOp::Neg, Value::Int(i)) => Value::Int(i.wrapping_neg()),
+        (UnaryOp::Neg, Value::Long(l)) => Value::Long(l.wrapping_neg()),
+        (UnaryOp::Neg, Value::Float(f)) => Value::Float(-f),
+        (UnaryOp::Neg, Value::Double(d)) => Value::Double(-d),
+        (UnaryOp::Not, Value::Bool(b)) => Value::Bool(!b),
+        (UnaryOp::BitNot, Value::Int(i)) => Value::Int(!i),
+        (UnaryOp::BitNot, Value::Long(l)) => Value::Long(!l),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Evaluates a binary operator, whose operands have already been converted to the same type,
+/// except for the distance of shifts
+fn evaluate_binary(op: &BinaryOp, left: Value, right: Value) -> Option<Value> {
+    let value = match (left, right) {
+        (Value::Int(a), Value::Int(b)) => match op {
+            BinaryOp::Add => Value::Int(a.wrapping_add(b)),
+            BinaryOp::Sub => Value::Int(a.wrapping_sub(b)),
+            BinaryOp::Mul => Value::Int(a.wrapping_mul(b)),
+            BinaryOp::Div => Value::Int((b != 0).then(|| a.wrapping_div(b))?),
+            BinaryOp::Mod => Value::Int((b != 0).then(|| a.wrapping_rem(b))?),
+            BinaryOp::BitAnd => Value::Int(a & b),
+            BinaryOp::BitOr => Value::Int(a | b),
+            BinaryOp::BitXor => Value::Int(a ^ b),
+            BinaryOp::Shl => Value::Int(a.wrapping_shl(b as u32)),
+            BinaryOp::Shr => Value::Int(a.wrapping_shr(b as u32)),
+            BinaryOp::UShr => Value::Int((a as u32).wrapping_shr(b as u32) as i32),
+            op => Value::Bool(compare(op, Some(a.cmp(&b)))?),
+        },
+        (Value::Long(a), Value::Int(b)) => match op {
+            BinaryOp::Shl => Value::Long(a.wrapping_shl(b as u32)),
+            BinaryOp::Shr => Value::Long(a.wrapping_shr(b as u32)),
+            BinaryOp::UShr => Value::Long((a as u64).wrapping_shr(b as u32) as i64),
+            _ => return None,
+        },
+        (Value::Long(a), Value::Long(b)) => match op {
+            BinaryOp::Add => Value::Long(a.wrapping_add(b)),
+            BinaryOp::Sub => Value::Long(a.wrapping_sub(b)),
+            BinaryOp::Mul => Value::Long(a.wrapping_mul(b)),
+            BinaryOp::Div => Value::Long((b != 0).then(|| a.wrapping_div(b))?),
+            BinaryOp::Mod => Value::Long((b != 0).then(|| a.wrapping_rem(b))?),
+            BinaryOp::BitAnd => Value::Long(a & b),
+            BinaryOp::BitOr => Value::Long(a | b),
+            BinaryOp::BitXor => Value::Long(a ^ b),
+            op => Value::Bool(compare(op, Some(a.cmp(&b)))?),
+        },
+        (Value::Float(a), Value::Float(b)) => match op {
+            BinaryOp::Add => Value::Float(a + b),
+            BinaryOp::Sub => Value::Float(a - b),
+            BinaryOp::Mul => Value::Float(a * b),
+            BinaryOp::Div => Value::Float(a / b),
+            BinaryOp::Mod => Value::Float(a % b),
+            op => Value::Bool(compare(op, a.partial_cmp(&b))?),
+        },
+        (Value::Double(a), Value::Double(b)) => match op {
+            BinaryOp::Add => Value::Double(a + b),
+            BinaryOp::Sub => Value::Double(a - b),
+            BinaryOp::Mul => Value::Double(a * b),
+            BinaryOp::Div => Value::Double(a / b),
+            BinaryOp::Mod => Value::Double(a % b),
+            op => Value::Bool(compare(op, a.partial_cmp(&b))?),
+        },
+        (Value::Bool(a), Value::Bool(b)) => match op {
+            BinaryOp::And | BinaryOp::BitAnd => Value::Bool(a && b),
+            BinaryOp::Or | BinaryOp::BitOr => Value::Bool(a || b),
+            BinaryOp::BitXor | BinaryOp::Ne => Value::Bool(a != b),
+            BinaryOp::Eq => Value::Bool(a == b),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Evaluates a comparison of two numbers given their ordering, which is None if one of them is NaN.
+/// Like in Java, all comparisons with NaN except `!=` are false.
+fn compare(op: &BinaryOp, ordering: Option<std::cmp::Ordering>) -> Option<bool> {
+    use std::cmp::Ordering::*;
+    let result = match op {
+        BinaryOp::Lt => ordering == Some(Less),
+        BinaryOp::Le => matches!(ordering, Some(Less | Equal)),
+        BinaryOp::Gt => ordering == Some(Greater),
+        BinaryOp::Ge => matches!(ordering, Some(Greater | Equal)),
+        BinaryOp::Eq => ordering == Some(Equal),
+        BinaryOp::Ne => ordering != Some(Equal),
+        _ => return None,
+    };
+    Some(result)
+}
+
+/// The literal of a value of type `t`, which is typed with that type
+fn to_literal(value: Value, t: &Type) -> Option<Expr> {
+    let literal = match (value, t) {
+        (Value::Int(c), Type::Char) => Expr::Char(char::from_u32(c as u32)?),
+        (Value::Int(i), _) => Expr::Integer(i),
+        (Value::Long(l), _) => Expr::Long(l),
+        (Value::Float(f), _) => Expr::Float(f),
+        (Value::Double(d), _) => Expr::Double(d),
+        (Value::Bool(b), _) => Expr::Bool(b),
+        (Value::String(s), _) => Expr::String(s),
+    };
+    Some(Expr::TypedExpr(Box::new(literal), t.clone()))
+}
@@ -0,0 +1,389 @@
+use crate::types::*;
+
+/// The internal name of the class all classes extend
+pub const OBJECT: &str = "java/lang/Object";
+
+/// The internal name of the interface of all objects, which can be iterated by an enhanced for-loop
+pub const ITERABLE: &str = "java/lang/Iterable";
+
+/// The internal name of the superclass of all exceptions and errors, which can be thrown
+pub const THROWABLE: &str = "java/lang/Throwable";
+
+/// The internal name of the class all enums extend
+pub const ENUM: &str = "java/lang/Enum";
+
+/// The internal names of the superclasses of the exceptions, which don't have to be caught or
+/// declared to be thrown, i.e. which aren't checked
+pub const UNCHECKED_EXCEPTIONS: [&str; 2] = ["java/lang/RuntimeException", "java/lang/Error"];
+
+/// Classes of this package can be used by their simple name without importing them
+const IMPLICIT_PACKAGE: &str = "java/lang/";
+
+/// Gets the internal name of the library class, which is referred to by `name` in a program
+pub fn resolve_library_class(name: &str) -> String {
+    format!("{IMPLICIT_PACKAGE}{name}")
+}
+
+/// A field or method of a library class, described by its name and descriptor
+struct LibraryMember {
+    name: &'static str,
+    descriptor: &'static str,
+    is_static: bool,
+}
+
+const fn member(name: &'static str, descriptor: &'static str) -> LibraryMember {
+    LibraryMember {
+        name,
+        descriptor,
+        is_static: false,
+    }
+}
+
+const fn static_member(name: &'static str, descriptor: &'static str) -> LibraryMember {
+    LibraryMember {
+        name,
+        descriptor,
+        is_static: true,
+    }
+}
+
+/// The constructors of exceptions, which create them without or with a message
+const EXCEPTION_CONSTRUCTORS: &[LibraryMember] = &[
+    member("<init>", "()V"),
+    member("<init>", "(Ljava/lang/String;)V"),
+];
+
+/// The classes of the Java class library, which can be used by programs, with the fields and
+/// methods used by programs. Constructors are the methods called `<init>`.
+/// See https://docs.oracle.com/en/java/javase/17/docs/api/
+const LIBRARY: &[(&str, &[LibraryMember], &[LibraryMember])] = &[
+    (OBJECT, &[], &[]),
+    (
+        THROWABLE,
+        &[],
+        &[
+            member("<init>", "()V"),
+            member("<init>", "(Ljava/lang/String;)V"),
+            member("getMessage", "()Ljava/lang/String;"),
+        ],
+    ),
+    // Its constructor taking the name and ordinal of a constant isn't listed, since it is only
+    // called by the code generated for the constructors of enums
+    (
+        ENUM,
+        &[],
+        &[
+            member("name", "()Ljava/lang/String;"),
+            member("ordinal", "()I"),
+            member("toString", "()Ljava/lang/String;"),
+            member("compareTo", "(Ljava/lang/Enum;)I"),
+            member("equals", "(Ljava/lang/Object;)Z"),
+            member("hashCode", "()I"),
+        ],
+    ),
+    ("java/lang/Exception", &[], EXCEPTION_CONSTRUCTORS),
+    ("java/lang/Error", &[], EXCEPTION_CONSTRUCTORS),
+    ("java/lang/RuntimeException", &[], EXCEPTION_CONSTRUCTORS),
+    (
+        "java/lang/IllegalArgumentException",
+        &[],
+        EXCEPTION_CONSTRUCTORS,
+    ),
+    (
+        "java/lang/NumberFormatException",
+        &[],
+        EXCEPTION_CONSTRUCTORS,
+    ),
+    (
+        "java/lang/IllegalStateException",
+        &[],
+        EXCEPTION_CONSTRUCTORS,
+    ),
+    ("java/lang/ArithmeticException", &[], EXCEPTION_CONSTRUCTORS),
+    (
+        "java/lang/NullPointerException",
+        &[],
+        EXCEPTION_CONSTRUCTORS,
+    ),
+    ("java/lang/ClassCastException", &[], EXCEPTION_CONSTRUCTORS),
+    (
+        "java/lang/UnsupportedOperationException",
+        &[],
+        EXCEPTION_CONSTRUCTORS,
+    ),
+    (
+        "java/lang/IndexOutOfBoundsException",
+        &[],
+        EXCEPTION_CONSTRUCTORS,
+    ),
+    (
+        "java/lang/ArrayIndexOutOfBoundsException",
+        &[],
+        EXCEPTION_CONSTRUCTORS,
+    ),
+    (
+        "java/lang/InterruptedException",
+        &[],
+        EXCEPTION_CONSTRUCTORS,
+    ),
+    (
+        "java/lang/CloneNotSupportedException",
+        &[],
+        EXCEPTION_CONSTRUCTORS,
+    ),
+    ("java/io/IOException", &[], EXCEPTION_CONSTRUCTORS),
+    (
+        "java/lang/String",
+        &[],
+        &[
+            member("length", "()I"),
+            member("isEmpty", "()Z"),
+            member("charAt", "(I)C"),
+            member("equals", "(Ljava/lang/Object;)Z"),
+            member("equalsIgnoreCase", "(Ljava/lang/String;)Z"),
+            member("hashCode", "()I"),
+            member("compareTo", "(Ljava/lang/String;)I"),
+            member("compareToIgnoreCase", "(Ljava/lang/String;)I"),
+            member("indexOf", "(I)I"),
+            member("indexOf", "(Ljava/lang/String;)I"),
+            member("startsWith", "(Ljava/lang/String;)Z"),
+            member("endsWith", "(Ljava/lang/String;)Z"),
+            member("substring", "(I)Ljava/lang/String;"),
+            member("substring", "(II)Ljava/lang/String;"),
+            member("concat", "(Ljava/lang/String;)Ljava/lang/String;"),
+            member("toUpperCase", "()Ljava/lang/String;"),
+            member("toLowerCase", "()Ljava/lang/String;"),
+            member("trim", "()Ljava/lang/String;"),
+            static_member("valueOf", "(I)Ljava/lang/String;"),
+            static_member("valueOf", "(Z)Ljava/lang/String;"),
+            static_member("valueOf", "(C)Ljava/lang/String;"),
+            static_member("valueOf", "(J)Ljava/lang/String;"),
+            static_member("valueOf", "(F)Ljava/lang/String;"),
+            static_member("valueOf", "(D)Ljava/lang/String;"),
+            static_member("valueOf", "(Ljava/lang/Object;)Ljava/lang/String;"),
+        ],
+    ),
+    (
+        "java/lang/Math",
+        &[],
+        &[
+            static_member("max", "(II)I"),
+            static_member("max", "(JJ)J"),
+            static_member("max", "(FF)F"),
+            static_member("max", "(DD)D"),
+            static_member("min", "(II)I"),
+            static_member("min", "(JJ)J"),
+            static_member("min", "(FF)F"),
+            static_member("min", "(DD)D"),
+            static_member("abs", "(I)I"),
+            static_member("abs", "(J)J"),
+            static_member("abs", "(F)F"),
+            static_member("abs", "(D)D"),
+            static_member("floorDiv", "(II)I"),
+            static_member("floorMod", "(II)I"),
+        ],
+    ),
+    (
+        "java/lang/Integer",
+        &[
+            static_member("MAX_VALUE", "I"),
+            static_member("MIN_VALUE", "I"),
+        ],
+        &[
+            static_member("parseInt", "(Ljava/lang/String;)I"),
+            static_member("toString", "(I)Ljava/lang/String;"),
+            static_member("compare", "(II)I"),
+            static_member("signum", "(I)I"),
+        ],
+    ),
+    (
+        "java/lang/Character",
+        &[],
+        &[
+            static_member("isDigit", "(C)Z"),
+            static_member("isLetter", "(C)Z"),
+            static_member("toUpperCase", "(C)C"),
+            static_member("toLowerCase", "(C)C"),
+        ],
+    ),
+    (
+        "java/lang/System",
+        &[static_member("out", "Ljava/io/PrintStream;")],
+        &[],
+    ),
+    (
+        ITERABLE,
+        &[],
+        &[member("iterator", "()Ljava/util/Iterator;")],
+    ),
+    (
+        "java/util/Iterator",
+        &[],
+        &[
+            member("hasNext", "()Z"),
+            member("next", "()Ljava/lang/Object;"),
+        ],
+    ),
+    ("java/lang/Runnable", &[], &[member("run", "()V")]),
+    (
+        "java/io/PrintStream",
+        &[],
+        &[
+            member("println", "()V"),
+            member("println", "(I)V"),
+            member("println", "(Z)V"),
+            member("println", "(C)V"),
+            member("println", "(J)V"),
+            member("println", "(F)V"),
+            member("println", "(D)V"),
+            member("println", "(Ljava/lang/String;)V"),
+            member("println", "(Ljava/lang/Object;)V"),
+            member("print", "(I)V"),
+            member("print", "(Z)V"),
+            member("print", "(C)V"),
+            member("print", "(J)V"),
+            member("print", "(F)V"),
+            member("print", "(D)V"),
+            member("print", "(Ljava/lang/String;)V"),
+            member("print", "(Ljava/lang/Object;)V"),
+        ],
+    ),
+];
+
+/// The library classes, which are interfaces. Their default methods aren't part of the library,
+/// so classes implementing them only have to implement the listed methods.
+const LIBRARY_INTERFACES: &[&str] = &[ITERABLE, "java/util/Iterator", "java/lang/Runnable"];
+
+/// The superclasses of the library classes, which don't extend java/lang/Object
+const LIBRARY_SUPERCLASSES: &[(&str, &str)] = &[
+    ("java/lang/Exception", THROWABLE),
+    ("java/lang/Error", THROWABLE),
+    ("java/lang/RuntimeException", "java/lang/Exception"),
+    (
+        "java/lang/IllegalArgumentException",
+        "java/lang/RuntimeException",
+    ),
+    (
+        "java/lang/NumberFormatException",
+        "java/lang/IllegalArgumentException",
+    ),
+    (
+        "java/lang/IllegalStateException",
+        "java/lang/RuntimeException",
+    ),
+    (
+        "java/lang/ArithmeticException",
+        "java/lang/RuntimeException",
+    ),
+    (
+        "java/lang/NullPointerException",
+        "java/lang/RuntimeException",
+    ),
+    ("java/lang/ClassCastException", "java/lang/RuntimeException"),
+    (
+        "java/lang/UnsupportedOperationException",
+        "java/lang/RuntimeException",
+    ),
+    (
+        "java/lang/IndexOutOfBoundsException",
+        "java/lang/RuntimeException",
+    ),
+    (
+        "java/lang/ArrayIndexOutOfBoundsException",
+        "java/lang/IndexOutOfBoundsException",
+    ),
+    ("java/lang/InterruptedException", "java/lang/Exception"),
+    (
+        "java/lang/CloneNotSupportedException",
+        "java/lang/Exception",
+    ),
+    ("java/io/IOException", "java/lang/Exception"),
+];
+
+/// The classes of the Java class library as class declarations, which are named by their
+/// internal names, e.g. `java/lang/System`. Only the methods of interfaces are abstract, so they
+/// are declared without a body. The bodies of all other methods are empty, since they are only
+/// needed to type calls and are never compiled.
+pub fn library_classes() -> Vec<Class> {
+    LIBRARY
+        .iter()
+        .map(|(name, fields, methods)| Class {
+            modifiers: public(false),
+            name: name.to_string(),
+            is_interface: LIBRARY_INTERFACES.contains(name),
+            is_abstract: LIBRARY_INTERFACES.contains(name),
+            super_class: LIBRARY_SUPERCLASSES
+                .iter()
+                .find(|(class, _)| class == name)
+                .map(|(_, super_class)| super_class.to_string()),
+            fields: fields.iter().map(field_decl).collect(),
+            constructors: methods
+                .iter()
+                .filter(|m| m.name == "<init>")
+                .map(constructor)
+                .collect(),
+            methods: methods
+                .iter()
+                .filter(|m| m.name != "<init>")
+                .map(|m| method_decl(m, LIBRARY_INTERFACES.contains(name)))
+                .collect(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn public(is_static: bool) -> Modifiers {
+    Modifiers {
+        visibility: Visibility::Public,
+        is_static,
+        is_final: false,
+    }
+}
+
+fn field_decl(field: &LibraryMember) -> FieldDecl {
+    FieldDecl {
+        // Library fields are constants, which can't be assigned
+        modifiers: Modifiers {
+            is_final: true,
+            ..public(field.is_static)
+        },
+        field_type: Type::from_ir_string(field.descriptor)
+            .expect("Invalid library field descriptor")
+            .0,
+        name: field.name.to_string(),
+        val: None,
+    }
+}
+
+fn method_decl(method: &LibraryMember, is_abstract: bool) -> MethodDecl {
+    let (param_types, ret_type) =
+        Type::from_method_descriptor(method.descriptor).expect("Invalid library method descriptor");
+    MethodDecl {
+        modifiers: public(method.is_static),
+        ret_type,
+        name: method.name.to_string(),
+        params: params(param_types),
+        // None of the library methods throws a checked exception
+        throws: vec![],
+        body: (!is_abstract).then_some(Stmt::Block(vec![])),
+    }
+}
+
+fn constructor(constructor: &LibraryMember) -> Constructor {
+    let (param_types, _) = Type::from_method_descriptor(constructor.descriptor)
+        .expect("Invalid library constructor descriptor");
+    Constructor {
+        modifiers: public(false),
+        params: params(param_types),
+        throws: vec![],
+        body: Stmt::Block(vec![]),
+    }
+}
+
+fn params(param_types: Vec<Type>) -> Vec<(Type, String)> {
+    param_types
+        .into_iter()
+        .enumerate()
+        .map(|(i, t)| (t, format!("arg{i}")))
+        .collect()
+}
@@ -0,0 +1,174 @@
+//! The names used by local classes, from which the local variables they capture are determined.
+//! Their constructors are passed the values of those variables, so they have to be known before
+//! typing any use of the class, including the ones in its own body.
+//! See https://docs.oracle.com/javase/specs/jls/se17/html/jls-8.html#jls-8.1.3
+
+use crate::types::*;
+
+/// The simple names the classes use, in the order of their first use, i.e. the names of the variables
+/// and classes referred to by them, e.g. `x` and `A` of `new A(x)`, and the names of their superclasses.
+/// The names of the classes' own variables are included as well, since they are only known after typing.
+pub fn used_names(classes: &[Class]) -> Vec<String> {
+    let mut names = UsedNames { names: vec![] };
+    for class in classes {
+        names.class(class);
+    }
+    names.names
+}
+
+struct UsedNames {
+    names: Vec<String>,
+}
+
+impl UsedNames {
+    fn add(&mut self, name: &str) {
+        if !self.names.iter().any(|used| used == name) {
+            self.names.push(name.to_string());
+        }
+    }
+
+    fn class(&mut self, class: &Class) {
+        if let Some(super_class) = &class.super_class {
+            self.add(super_class);
+        }
+        for constant in &class.enum_constants {
+            constant.args.iter().for_each(|arg| self.expr(arg));
+        }
+        for val in class.fields.iter().filter_map(|field| field.val.as_ref()) {
+            self.expr(val);
+        }
+        for (_, block) in &class.static_blocks {
+            self.stmt(block);
+        }
+        for constructor in &class.constructors {
+            self.stmt(&constructor.body);
+        }
+        for body in class
+            .methods
+            .iter()
+            .filter_map(|method| method.body.as_ref())
+        {
+            self.stmt(body);
+        }
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Block(stmts) => stmts.iter().for_each(|stmt| self.stmt(stmt)),
+            Stmt::Return(expr) | Stmt::Yield(expr) | Stmt::Throw(expr) => self.expr(expr),
+            Stmt::While(expr, stmt) | Stmt::DoWhile(stmt, expr) => {
+                self.expr(expr);
+                self.stmt(stmt);
+            }
+            Stmt::For(init, cond, update, body) => {
+                init.iter().for_each(|stmt| self.stmt(stmt));
+                cond.iter().for_each(|cond| self.expr(cond));
+                update.iter().for_each(|stmt| self.stmt(stmt));
+                self.stmt(body);
+            }
+            Stmt::ForEach(_, _, expr, body) => {
+                self.expr(expr);
+                self.stmt(body);
+            }
+            Stmt::Labeled(_, stmt) | Stmt::TypedStmt(stmt, _) => self.stmt(stmt),
+            Stmt::Switch(expr, cases) => {
+                self.expr(expr);
+                self.cases(cases);
+            }
+            Stmt::If(cond, then_stmt, else_stmt) => {
+                self.expr(cond);
+                self.stmt(then_stmt);
+                else_stmt.iter().for_each(|stmt| self.stmt(stmt));
+            }
+            Stmt::Try(body, catches, finally) => {
+                self.stmt(body);
+                catches.iter().for_each(|c| self.stmt(&c.body));
+                finally.iter().for_each(|stmt| self.stmt(stmt));
+            }
+            // The variables used by the classes nested in a local class may be captured by it,
+            // so the nested classes can get their values from it
+            Stmt::LocalClassDecl(classes) => classes.iter().for_each(|class| self.class(class)),
+            Stmt::StmtExprStmt(stmt_expr) => self.stmt_expr(stmt_expr),
+            Stmt::ReturnVoid | Stmt::Break(_) | Stmt::Continue(_) | Stmt::LocalVarDecl(_, _) => {}
+        }
+    }
+
+    fn cases(&mut self, cases: &[SwitchCase]) {
+        for case in cases {
+            case.labels.iter().for_each(|label| self.expr(label));
+            case.body.iter().for_each(|stmt| self.stmt(stmt));
+        }
+    }
+
+    fn stmt_expr(&mut self, stmt_expr: &StmtExpr) {
+        match stmt_expr {
+            StmtExpr::Assign(var, val) | StmtExpr::CompoundAssign(var, _, val) => {
+                self.expr(var);
+                self.expr(val);
+            }
+            StmtExpr::PreIncDec(_, var) | StmtExpr::PostIncDec(var, _) => self.expr(var),
+            StmtExpr::New(t, args) => {
+                if let Type::Class(name) = t {
+                    self.add(name);
+                }
+                args.iter().for_each(|arg| self.expr(arg));
+            }
+            StmtExpr::QualifiedNew(obj, _, args) | StmtExpr::MethodCall(obj, _, args) => {
+                self.expr(obj);
+                args.iter().for_each(|arg| self.expr(arg));
+            }
+            StmtExpr::ThisCall(args) | StmtExpr::SuperCall(args) => {
+                args.iter().for_each(|arg| self.expr(arg))
+            }
+            StmtExpr::TypedStmtExpr(stmt_expr, _) => self.stmt_expr(stmt_expr),
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::LocalOrFieldVar(name) | Expr::LocalVar(name) => self.add(name),
+            Expr::InstVar(expr, _)
+            | Expr::Unary(_, expr)
+            | Expr::ArrayLength(expr)
+            | Expr::Cast(_, expr)
+            | Expr::InstanceOf(expr, _, _)
+            | Expr::MethodRef(expr, _)
+            | Expr::TypedExpr(expr, _) => self.expr(expr),
+            Expr::Binary(_, left, right) | Expr::ArrayAccess(left, right) => {
+                self.expr(left);
+                self.expr(right);
+            }
+            Expr::NewArray(_, exprs) | Expr::ArrayInit(_, exprs) => {
+                exprs.iter().for_each(|expr| self.expr(expr))
+            }
+            Expr::Conditional(cond, then_expr, else_expr) => {
+                self.expr(cond);
+                self.expr(then_expr);
+                self.expr(else_expr);
+            }
+            Expr::Switch(expr, cases) => {
+                self.expr(expr);
+                self.cases(cases);
+            }
+            Expr::Lambda(_, body) => self.stmt(body),
+            Expr::StmtExprExpr(stmt_expr) => self.stmt_expr(stmt_expr),
+            Expr::FunctionRef(function_ref) => function_ref
+                .captured
+                .iter()
+                .for_each(|expr| self.expr(expr)),
+            Expr::This
+            | Expr::Super
+            | Expr::ClassName(_)
+            | Expr::QualifiedThis(_)
+            | Expr::FieldVar(_)
+            | Expr::Integer(_)
+            | Expr::Long(_)
+            | Expr::Float(_)
+            | Expr::Double(_)
+            | Expr::Bool(_)
+            | Expr::Char(_)
+            | Expr::String(_)
+            | Expr::Jnull => {}
+        }
+    }
+}
@@ -1,2 +1,5 @@
+pub(crate) mod constants;
+pub(crate) mod library;
+pub(crate) mod local_classes;
 #[allow(clippy::module_inception)]
 pub mod typechecker;
@@ -3,22 +3,71 @@
 #![allow(non_snake_case)]
 #![allow(dead_code)]
 
+use super::constants::*;
+use super::library::*;
+use super::local_classes::*;
 use crate::types::*;
 use std::any::type_name;
 use std::collections::HashMap;
+use std::ops::Deref;
 
 pub struct TypeChecker {
     classes: HashMap<String, Class>,
+    // Names of all classes in the order they were declared in, so the typed program keeps that order
+    class_names: Vec<String>,
     pub typed_classes: HashMap<String, Class>,
     current_class: Option<Class>,
     current_typed_class: Class,
     fields: HashMap<String, Vec<FieldDecl>>,
     current_local_vars: HashMap<String, Type>,
     methods: HashMap<String, Vec<MethodDecl>>,
+    constructors: HashMap<String, Vec<Constructor>>,
+    // Whether the method currently being typed is static, so `this` and instance members can't be used
+    is_static_context: bool,
+    // Whether a constructor is currently being typed, which may assign final fields
+    is_constructor: bool,
+    // Whether a static initializer is currently being typed, which may assign static final fields
+    is_static_initializer: bool,
+    // The label and whether it is a loop of each statement enclosing the current statement,
+    // which can be left by `break`. Loops can also be continued by `continue`.
+    jump_targets: Vec<(Option<String>, bool)>,
+    // The types of the values yielded so far by each switch expression enclosing the current statement
+    yield_types: Vec<Vec<Type>>,
+    // The return type of the method currently being typed, which returned values are converted to
+    return_type: Type,
+    // The exceptions caught by each try statement enclosing the current statement, from the
+    // outermost to the innermost one, together with the checked exceptions thrown in its body so far
+    exception_handlers: Vec<(Vec<Type>, Vec<Type>)>,
+    // The exceptions the method or constructor currently being typed declares to throw
+    declared_exceptions: Vec<Type>,
+    // The values of the constant variables, keyed by their class and name, which are computed
+    // when they are used first. None if the field isn't a constant variable.
+    constants: HashMap<(String, String), Option<Expr>>,
+    // The private methods of the current class, which the bodies of its lambda expressions are lifted to
+    lambda_methods: Vec<MethodDecl>,
+    // The lambda expressions enclosing the current expression, from the outermost to the innermost one
+    lambda_scopes: Vec<LambdaScope>,
+    // The names of the local classes declared by the blocks enclosing the current statement,
+    // keyed by their simple name
+    local_classes: HashMap<String, String>,
+}
+
+/// A lambda expression enclosing the expression currently being typed
+struct LambdaScope {
+    // The local variables declared outside of the lambda expression
+    outer_local_vars: HashMap<String, Type>,
+    // The local variables of `outer_local_vars` used by the lambda expression so far, which it captures
+    captured: Vec<(String, Type)>,
 }
 
 impl TypeChecker {
     pub fn new(program: Prg) -> Result<Self, String> {
+        Self::with_classpath(program, vec![])
+    }
+
+    /// Creates a typechecker for a program, which may use the classes read from the classpath
+    /// in addition to the library classes. The classes of the program take precedence over them.
+    pub fn with_classpath(program: Prg, classpath: Prg) -> Result<Self, String> {
         let mut class_names = Vec::new();
         let mut classes = HashMap::new();
         for class in program {
@@ -29,150 +78,1909 @@ impl TypeChecker {
                 class_names.push(class.name.clone());
             }
 
-            classes.insert(class.name.clone().to_string(), class.clone());
+            classes.insert(class.name.clone().to_string(), class.clone());
+        }
+        // Library and classpath classes are only used to type the programs,
+        // so they aren't in `class_names`
+        let mut fields = HashMap::new();
+        let mut methods = HashMap::new();
+        let mut constructors = HashMap::new();
+        for class in library_classes().into_iter().chain(classpath) {
+            if classes.contains_key(&class.name) {
+                continue;
+            }
+            fields.insert(class.name.clone(), class.fields.clone());
+            methods.insert(class.name.clone(), class.methods.clone());
+            constructors.insert(class.name.clone(), class.constructors.clone());
+            classes.insert(class.name.clone(), class);
+        }
+        let mut type_checker = Self {
+            classes,
+            class_names,
+            typed_classes: HashMap::new(),
+            current_class: None,
+            current_typed_class: Class::default(),
+            fields,
+            methods,
+            constructors,
+            current_local_vars: HashMap::new(),
+            is_static_context: false,
+            is_constructor: false,
+            is_static_initializer: false,
+            jump_targets: vec![],
+            yield_types: vec![],
+            return_type: Type::Void,
+            exception_handlers: vec![],
+            declared_exceptions: vec![],
+            constants: HashMap::new(),
+            lambda_methods: vec![],
+            lambda_scopes: vec![],
+            local_classes: HashMap::new(),
+        };
+        type_checker.resolve_declared_types();
+        Ok(type_checker)
+    }
+
+    /// Resolves the names of the classes used by the declarations of the program's classes,
+    /// i.e. their superclasses, interfaces and the types of their members
+    fn resolve_declared_types(&mut self) {
+        for class_name in self.class_names.clone() {
+            self.resolve_class_types(&class_name);
+        }
+        self.current_class = None;
+    }
+
+    /// Resolves the names of the classes used by the declaration of the class `class_name`
+    fn resolve_class_types(&mut self, class_name: &str) {
+        let mut class = self.classes.get(class_name).unwrap().clone();
+        // Nested classes can be referred to by their simple name inside the classes enclosing them
+        self.current_class = Some(class.clone());
+        class.super_class = class
+            .super_class
+            .map(|name| self.resolve_class_name(&name).unwrap_or(name));
+        // Enums can't be extended, since their only instances are their constants
+        if class.is_enum {
+            class.super_class = Some(ENUM.to_string());
+            class.modifiers.is_final = true;
+        }
+        class.interfaces = class
+            .interfaces
+            .into_iter()
+            .map(|name| self.resolve_class_name(&name).unwrap_or(name))
+            .collect();
+        for field in &mut class.fields {
+            field.field_type = self.resolve_type(&field.field_type);
+        }
+        for constructor in &mut class.constructors {
+            constructor.params = self.resolve_params(&constructor.params);
+            constructor.throws = self.resolve_class_names(&constructor.throws);
+        }
+        for method in &mut class.methods {
+            method.ret_type = self.resolve_type(&method.ret_type);
+            method.params = self.resolve_params(&method.params);
+            method.throws = self.resolve_class_names(&method.throws);
+        }
+        self.classes.insert(class_name.to_string(), class);
+    }
+
+    /// Resolves the name of the class of a type, so library classes can be referred to by their
+    /// simple name, e.g. `Object`. Unknown classes are left as they are.
+    fn resolve_type(&self, t: &Type) -> Type {
+        match t {
+            Type::Class(name) => Type::Class(
+                self.resolve_class_name(name)
+                    .unwrap_or_else(|| name.clone()),
+            ),
+            Type::Array(elem_type) => Type::Array(Box::new(self.resolve_type(elem_type))),
+            t => t.clone(),
+        }
+    }
+
+    fn resolve_params(&self, params: &[(Type, String)]) -> Vec<(Type, String)> {
+        params
+            .iter()
+            .map(|(t, name)| (self.resolve_type(t), name.clone()))
+            .collect()
+    }
+
+    fn resolve_class_names(&self, names: &[String]) -> Vec<String> {
+        names
+            .iter()
+            .map(|name| self.resolve_class_name(name).unwrap_or(name.clone()))
+            .collect()
+    }
+
+    pub fn check_and_type_program(&mut self) -> Result<Vec<Class>, String> {
+        self.check_and_type_classes(&self.class_names.clone())?;
+        println!("Typechecked program succesfully!🎉🧙");
+        // The local classes lifted out of the methods follow the classes of the program
+        let mut typed_classes = vec![];
+        for class_name in &self.class_names {
+            typed_classes.push(self.typed_classes.get(class_name).unwrap().clone());
+        }
+        Ok(typed_classes)
+    }
+
+    /// Checks and types the classes, which are added to the typed classes
+    fn check_and_type_classes(&mut self, class_names: &[String]) -> Result<(), String> {
+        for class_name in class_names {
+            self.check_super_class(class_name)?;
+        }
+        // The fields and methods of all classes have to be known before typing any method,
+        // since methods can access members of classes declared after their own class
+        for class_name in class_names {
+            let class = self.classes.get(class_name).unwrap().clone();
+            self.current_class = Some(class.clone());
+            self.check_class_members(&class)?;
+        }
+        for class_name in class_names {
+            self.check_overridden_methods(class_name)?;
+            self.check_abstract_methods(class_name)?;
+        }
+        for class_name in class_names {
+            let class = self.classes.get(class_name).unwrap().clone();
+            self.current_class = Some(class.clone());
+            self.current_typed_class = Class::default();
+
+            self.check_and_type_class(&class)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that the superclass of a class exists and that the class doesn't inherit from itself.
+    /// Also checks that only interfaces are implemented and that interfaces only extend interfaces.
+    fn check_super_class(&self, class_name: &str) -> Result<(), String> {
+        let mut current = self.classes.get(class_name).unwrap();
+        if let Some(super_class) = &current.super_class {
+            // Its constructors would have to be passed an enclosing instance by the subclass
+            if self.classes.get(super_class).is_some_and(Class::is_inner) {
+                return Err(format!(
+                    "Class {class_name} can't extend the inner class {super_class}"
+                ));
+            }
+        }
+        while let Some(super_class) = &current.super_class {
+            if super_class == class_name {
+                return Err(format!("Cyclic inheritance involving class {class_name}"));
+            }
+            current = self.classes.get(super_class).ok_or(format!(
+                "Superclass {super_class} of class {} does not exist",
+                current.name
+            ))?;
+            if current.is_interface {
+                return Err(format!(
+                    "Class {class_name} can't extend the interface {super_class}"
+                ));
+            }
+            if current.modifiers.is_final {
+                return Err(format!(
+                    "Class {class_name} can't extend the final class {super_class}"
+                ));
+            }
+        }
+
+        let class = self.classes.get(class_name).unwrap();
+        for interface in &class.interfaces {
+            let Some(implemented) = self.classes.get(interface) else {
+                return Err(format!(
+                    "Interface {interface} of class {class_name} does not exist"
+                ));
+            };
+            if !implemented.is_interface {
+                return Err(format!(
+                    "{interface} implemented by class {class_name} is not an interface"
+                ));
+            }
+        }
+        // Interfaces can extend multiple interfaces, so all of them have to be visited
+        let mut unvisited = class.interfaces.clone();
+        let mut visited: Vec<String> = vec![];
+        while let Some(interface) = unvisited.pop() {
+            if interface == class_name {
+                return Err(format!("Cyclic inheritance involving class {class_name}"));
+            }
+            if let Some(extended) = self.classes.get(&interface) {
+                if !visited.contains(&interface) {
+                    unvisited.extend(extended.interfaces.clone());
+                    visited.push(interface);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the class itself followed by all of its superclasses
+    fn superclasses(&self, class_name: &str) -> Vec<String> {
+        let mut result = vec![];
+        let mut current = self.classes.get(class_name);
+        while let Some(class) = current {
+            result.push(class.name.clone());
+            current = class
+                .super_class
+                .as_ref()
+                .and_then(|name| self.classes.get(name));
+        }
+        result
+    }
+
+    /// Returns the class itself followed by all of its superclasses and then all interfaces
+    /// implemented by them, including the ones extended by those interfaces
+    fn supertypes(&self, class_name: &str) -> Vec<String> {
+        let mut result = self.superclasses(class_name);
+        let mut i = 0;
+        while i < result.len() {
+            for interface in &self.classes.get(&result[i]).unwrap().interfaces {
+                if !result.contains(interface) {
+                    result.push(interface.clone());
+                }
+            }
+            i += 1;
+        }
+        result
+    }
+
+    /// Checks that methods overriding a method of a superclass or interface have the same return type,
+    /// don't restrict its access or throw other checked exceptions and that final methods aren't overridden.
+    /// Private methods aren't inherited, so they can't be overridden.
+    fn check_overridden_methods(&self, class_name: &str) -> Result<(), String> {
+        for method in self.methods.get(class_name).unwrap() {
+            for super_type in self.supertypes(class_name).iter().skip(1) {
+                let overridden = self.methods.get(super_type).unwrap().iter().find(|m| {
+                    m.name == method.name
+                        && Self::param_types(&m.params) == Self::param_types(&method.params)
+                        && m.modifiers.visibility != Visibility::Private
+                });
+                if let Some(overridden) = overridden {
+                    if overridden.modifiers.is_final {
+                        return Err(format!(
+                            "Method {} of class {class_name} can't override the final method of class {super_type}",
+                            method.name
+                        ));
+                    }
+                    if overridden.modifiers.is_static != method.modifiers.is_static {
+                        return Err(format!(
+                            "Method {} of class {class_name} can't override a method of class {super_type}, since only one of them is static",
+                            method.name
+                        ));
+                    }
+                    if method.modifiers.visibility < overridden.modifiers.visibility {
+                        return Err(format!(
+                            "Method {} of class {class_name} can't override a method of class {super_type} with weaker access",
+                            method.name
+                        ));
+                    }
+                    if overridden.ret_type != method.ret_type {
+                        return Err(format!(
+                            "Method {} of class {class_name} overrides a method of class {super_type} with a different return type",
+                            method.name
+                        ));
+                    }
+                    // The overriding method can't throw checked exceptions callers of the
+                    // overridden method don't expect
+                    for exception in &method.throws {
+                        let t = Type::Class(exception.clone());
+                        if self.is_checked_exception(&t)
+                            && !overridden
+                                .throws
+                                .iter()
+                                .any(|e| self.is_assignable(&t, &Type::Class(e.clone())))
+                        {
+                            return Err(format!(
+                                "Method {} of class {class_name} can't override a method of class {super_type}, since the overridden method does not throw {t}",
+                                method.name
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that a class, which isn't abstract, implements all abstract methods
+    /// of its superclasses and interfaces
+    fn check_abstract_methods(&self, class_name: &str) -> Result<(), String> {
+        if self.classes.get(class_name).unwrap().is_abstract {
+            return Ok(());
+        }
+        for super_type in self.supertypes(class_name) {
+            for method in self.methods.get(&super_type).unwrap() {
+                if method.body.is_some() {
+                    continue;
+                }
+                // The closest declaration of the method decides, whether it is implemented,
+                // as an abstract class can redeclare an implemented method as abstract
+                let implemented = self.superclasses(class_name).iter().find_map(|c| {
+                    self.methods.get(c).unwrap().iter().find(|m| {
+                        m.name == method.name
+                            && Self::param_types(&m.params) == Self::param_types(&method.params)
+                    })
+                });
+                if implemented.is_none_or(|m| m.body.is_none()) {
+                    return Err(format!(
+                        "Class {class_name} must be declared abstract or implement the abstract method {} of {super_type}",
+                        method.name
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the fields, constructor and method declarations of a class and registers them,
+    /// so they can be looked up while typing the methods of any class
+    fn check_class_members(&mut self, class: &Class) -> Result<(), String> {
+        self.fields.insert(class.name.clone(), vec![]);
+        // The constants of enums are accessed like static fields
+        for constant in &class.enum_constants {
+            self.check_field(&Self::enum_constant_field(&class.name, constant))?;
+        }
+        if let Some(field) = class.outer_instance_field() {
+            self.check_field(&field)?;
+        }
+        for field in class.captured_var_fields() {
+            self.check_field(&field)?;
+        }
+        for field in &class.fields {
+            self.check_field(field)?;
+        }
+
+        self.constructors.insert(class.name.clone(), vec![]);
+        for constructor in &class.constructors {
+            let constructors = self.constructors.get_mut(&class.name).unwrap();
+            if constructors
+                .iter()
+                .any(|c| Self::param_types(&c.params) == Self::param_types(&constructor.params))
+            {
+                return Err(format!(
+                    "Duplicate constructor {}({}) in class {}",
+                    class.name,
+                    Self::join_types(&Self::param_types(&constructor.params)),
+                    class.name
+                ));
+            }
+            constructors.push(constructor.clone());
+            self.check_throws(&constructor.throws)?;
+        }
+
+        let enum_methods = if class.is_enum {
+            Self::enum_methods(&class.name)
+        } else {
+            vec![]
+        };
+        self.methods.insert(class.name.clone(), enum_methods);
+        for method in &class.methods {
+            if method.body.is_none() && !class.is_abstract {
+                return Err(format!(
+                    "Class {} must be declared abstract, since it declares the abstract method {}",
+                    class.name, method.name
+                ));
+            }
+            self.check_throws(&method.throws)?;
+            // Overloaded methods have to differ in their parameter types, not only the return type
+            let methods = self.methods.get_mut(&class.name).unwrap();
+            if methods.iter().any(|m| {
+                m.name == method.name
+                    && Self::param_types(&m.params) == Self::param_types(&method.params)
+            }) {
+                return Err(format!(
+                    "Duplicate method {}({}) in class {}",
+                    method.name,
+                    Self::join_types(&Self::param_types(&method.params)),
+                    class.name
+                ));
+            }
+            methods.push(method.clone());
+        }
+        Ok(())
+    }
+
+    /// The static field of the constant of an enum, which is initialized with the constant
+    fn enum_constant_field(class_name: &str, constant: &EnumConstant) -> FieldDecl {
+        FieldDecl {
+            modifiers: Modifiers {
+                visibility: Visibility::Public,
+                is_static: true,
+                is_final: true,
+            },
+            field_type: Type::Class(class_name.to_string()),
+            name: constant.name.clone(),
+            val: None,
+        }
+    }
+
+    /// The static methods every enum implicitly declares, which are generated with its class file.
+    /// `values()` returns all constants of the enum and `valueOf(String)` the one with the name.
+    fn enum_methods(class_name: &str) -> Vec<MethodDecl> {
+        let enum_type = Type::Class(class_name.to_string());
+        let modifiers = Modifiers {
+            visibility: Visibility::Public,
+            is_static: true,
+            is_final: false,
+        };
+        vec![
+            MethodDecl {
+                modifiers,
+                ret_type: Type::Array(Box::new(enum_type.clone())),
+                name: "values".to_string(),
+                params: vec![],
+                throws: vec![],
+                body: Some(Stmt::Block(vec![])),
+            },
+            MethodDecl {
+                modifiers,
+                ret_type: enum_type,
+                name: "valueOf".to_string(),
+                params: vec![(Type::String, "name".to_string())],
+                throws: vec![],
+                body: Some(Stmt::Block(vec![])),
+            },
+        ]
+    }
+
+    /// Checks that the classes of the `throws` clause of a method or constructor can be thrown
+    fn check_throws(&self, throws: &[String]) -> Result<(), String> {
+        match throws
+            .iter()
+            .map(|name| Type::Class(name.clone()))
+            .find(|t| !self.is_throwable(t))
+        {
+            Some(t) => Err(format!(
+                "Incompatible types: {t} cannot be converted to Throwable"
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn check_and_type_class(&mut self, class: &Class) -> Result<(), String> {
+        self.current_typed_class.modifiers = class.modifiers;
+        self.current_typed_class.name = class.name.clone();
+        self.current_typed_class.is_interface = class.is_interface;
+        self.current_typed_class.is_abstract = class.is_abstract;
+        self.current_typed_class.is_enum = class.is_enum;
+        self.current_typed_class.super_class = class.super_class.clone();
+        self.current_typed_class.interfaces = class.interfaces.clone();
+        self.current_typed_class.outer_class = class.outer_class.clone();
+        self.current_typed_class.is_local = class.is_local;
+        self.current_typed_class.captured_vars = class.captured_vars.clone();
+        // The fields of enum constants, the enclosing instance of inner classes and the captured
+        // variables of local classes, which are registered first, are generated with the class file
+        let implicit_fields =
+            class.enum_constants.len() + usize::from(class.is_inner()) + class.captured_vars.len();
+        self.current_typed_class.fields =
+            self.fields.get(&class.name).unwrap()[implicit_fields..].to_vec();
+
+        // The constants are created by the static initializer
+        for constant in &class.enum_constants {
+            self.is_static_context = true;
+            self.declared_exceptions = vec![];
+            let args = self.type_constructor_args(&class.name, &constant.args);
+            self.current_typed_class.enum_constants.push(EnumConstant {
+                name: constant.name.clone(),
+                args,
+            });
+            self.current_local_vars.clear();
+        }
+        // The values of fields are converted to their types. Constant variables are initialized
+        // with their value instead, which is computed at compile time.
+        for i in 0..self.current_typed_class.fields.len() {
+            let field = self.current_typed_class.fields[i].clone();
+            if field.val.is_some() {
+                let typed_val = match self.field_constant(&class.name, &field.name) {
+                    Some(value) => value,
+                    None => self.type_field_value(&class.name, &field),
+                };
+                self.current_typed_class.fields[i].val = Some(typed_val);
+            }
+        }
+        // Static initializers can assign static final fields, but can't throw checked exceptions
+        for (fields_before, block) in &class.static_blocks {
+            self.is_static_context = true;
+            self.is_constructor = false;
+            self.is_static_initializer = true;
+            self.return_type = Type::Void;
+            self.declared_exceptions = vec![];
+            let typed_block = self.type_stmt(block);
+            self.is_static_initializer = false;
+            self.current_typed_class
+                .static_blocks
+                .push((*fields_before, typed_block));
+            self.current_local_vars.clear();
+        }
+
+        // Interfaces have no constructors
+        if class.constructors.is_empty() && !class.is_interface {
+            if self.super_class_captures_vars() {
+                // The default constructor is typed like a declared one, so it passes the captured
+                // variables of the superclass to its constructor
+                let constructor = Constructor {
+                    modifiers: Modifiers {
+                        visibility: class.modifiers.visibility,
+                        ..Default::default()
+                    },
+                    params: vec![],
+                    throws: vec![],
+                    body: Stmt::Block(vec![]),
+                };
+                let typed_constructor = self.check_and_type_constructor(&constructor)?;
+                self.current_typed_class
+                    .constructors
+                    .push(typed_constructor);
+                self.current_local_vars.clear();
+            } else {
+                // The default constructor implicitly calls the constructor of the superclass without arguments
+                self.declared_exceptions = vec![];
+                self.type_super_call(&[]);
+            }
+        }
+        for constructor in &class.constructors {
+            let typed_constructor = self.check_and_type_constructor(constructor)?;
+            self.current_typed_class
+                .constructors
+                .push(typed_constructor);
+            self.current_local_vars.clear();
+        }
+        Self::check_constructor_delegation(&self.current_typed_class)?;
+
+        for method in &class.methods {
+            let typed_method = self.check_and_type_method(method)?;
+            self.current_typed_class.methods.push(typed_method);
+            self.current_local_vars.clear();
+        }
+        // The lifted lambda expressions follow the methods declared by the class
+        self.current_typed_class
+            .methods
+            .append(&mut self.lambda_methods);
+        self.typed_classes
+            .insert(class.name.clone(), self.current_typed_class.clone());
+        Ok(())
+    }
+
+    /// Gets the value of the field `name` of the class `owner`, if it is a constant variable, i.e. a
+    /// final field of a primitive type or String, which is initialized with a constant expression.
+    /// The value is computed when the field is used first, since constants can be used by classes
+    /// typed before the class declaring them.
+    fn field_constant(&mut self, owner: &str, name: &str) -> Option<Expr> {
+        let key = (owner.to_string(), name.to_string());
+        if let Some(value) = self.constants.get(&key) {
+            return value.clone();
+        }
+        let field = self
+            .fields
+            .get(owner)?
+            .iter()
+            .find(|f| f.name == name)?
+            .clone();
+        if !field.modifiers.is_final || field.val.is_none() || !is_constant_type(&field.field_type)
+        {
+            return None;
+        }
+        // A field whose value refers to the field itself isn't constant
+        self.constants.insert(key.clone(), None);
+        let typed_val = self.type_field_value(owner, &field);
+        let value = constant_value(&typed_val);
+        self.constants.insert(key, value.clone());
+        value
+    }
+
+    /// Types the value of a field of the class `owner`, which is converted to the type of the field.
+    /// The value is typed like in a constructor or, for static fields, a static initializer of its class.
+    fn type_field_value(&mut self, owner: &str, field: &FieldDecl) -> Expr {
+        let val = field.val.as_ref().unwrap();
+        // The value may be typed while typing another class or method, whose state is restored afterwards
+        let current_class = self
+            .current_class
+            .replace(self.classes.get(owner).unwrap().clone());
+        let local_vars = std::mem::take(&mut self.current_local_vars);
+        let declared_exceptions = std::mem::take(&mut self.declared_exceptions);
+        let is_static_context = self.is_static_context;
+        let is_constructor = self.is_constructor;
+        let is_static_initializer = self.is_static_initializer;
+        self.is_static_context = field.modifiers.is_static;
+        self.is_constructor = false;
+        self.is_static_initializer = false;
+
+        let typed_val = self.type_assigned_expr(val, &field.field_type);
+
+        self.current_class = current_class;
+        self.current_local_vars = local_vars;
+        self.declared_exceptions = declared_exceptions;
+        self.is_static_context = is_static_context;
+        self.is_constructor = is_constructor;
+        self.is_static_initializer = is_static_initializer;
+        typed_val
+    }
+
+    fn check_field(&mut self, field: &FieldDecl) -> Result<(), String> {
+        let names = self
+            .fields
+            .get_mut(&self.current_class.as_ref().unwrap().name.to_string())
+            .unwrap();
+
+        // Check for duplicate field names
+        if names.iter().any(|vec_field| vec_field.name == field.name) {
+            return Err(format!("Duplicate field name: {}", field.name));
+        } else {
+            names.push(field.clone());
+        }
+
+        Ok(())
+    }
+
+    fn param_types(params: &[(Type, String)]) -> Vec<Type> {
+        params.iter().map(|(t, _)| t.clone()).collect()
+    }
+
+    /// Returns the parameter types of all constructors of the class `class_name`.
+    /// A class without any declared constructors only has the default constructor.
+    fn get_constructors(&self, class_name: &str) -> Vec<Vec<Type>> {
+        match self.constructors.get(class_name) {
+            Some(constructors) if !constructors.is_empty() => constructors
+                .iter()
+                .map(|c| Self::param_types(&c.params))
+                .collect(),
+            _ => vec![vec![]],
+        }
+    }
+
+    /// Types the arguments of a constructor call of the class `class_name`
+    /// and converts them to the parameter types of the matching constructor
+    fn type_constructor_args(&mut self, class_name: &str, exprs: &[Expr]) -> Vec<Expr> {
+        let typed_exprs: Vec<Expr> = exprs.iter().map(|e| self.type_arg(e)).collect();
+        let constructors = self.get_constructors(class_name);
+        let matching = self.select_overloads(&constructors, |params| {
+            self.accepts_args(params, &typed_exprs)
+        });
+        match matching[..] {
+            [] => panic!(
+                "No constructor of class {class_name} takes arguments of types ({})",
+                Self::join_arg_types(&typed_exprs)
+            ),
+            [index] => {
+                let param_types = &constructors[index];
+                self.check_constructor_call(class_name, param_types);
+                typed_exprs
+                    .into_iter()
+                    .zip(param_types.iter())
+                    .map(|(e, param_type)| self.convert_arg(e, param_type))
+                    .collect()
+            }
+            _ => panic!(
+                "Ambiguous constructor call of class {class_name} with arguments of types ({})",
+                Self::join_arg_types(&typed_exprs)
+            ),
+        }
+    }
+
+    /// Checks that the constructor of the class `class_name` with the given parameter types can be
+    /// called from the current class and that the exceptions it throws are caught or declared
+    fn check_constructor_call(&mut self, class_name: &str, param_types: &[Type]) {
+        let constructor = self
+            .constructors
+            .get(class_name)
+            .and_then(|constructors| {
+                constructors
+                    .iter()
+                    .find(|c| Self::param_types(&c.params) == param_types)
+            })
+            .cloned();
+        let visibility = constructor
+            .as_ref()
+            .map_or(Visibility::Package, |c| c.modifiers.visibility);
+        let signature = format!("{class_name}({})", Self::join_types(param_types));
+        self.check_access(visibility, class_name, &signature);
+        for exception in constructor.map_or(vec![], |c| c.throws) {
+            self.check_thrown(&Type::Class(exception));
+        }
+    }
+
+    /// Finds a call of another constructor via `this(...)` or `super(...)` in the statement,
+    /// returning the keyword it is called with
+    fn constructor_call(stmt: &Stmt) -> Option<&'static str> {
+        match stmt {
+            Stmt::Block(stmts) => stmts.iter().find_map(Self::constructor_call),
+            Stmt::While(_, body)
+            | Stmt::DoWhile(body, _)
+            | Stmt::ForEach(_, _, _, body)
+            | Stmt::Labeled(_, body) => Self::constructor_call(body),
+            Stmt::For(init, _, update, body) => init
+                .iter()
+                .chain(update)
+                .find_map(Self::constructor_call)
+                .or_else(|| Self::constructor_call(body)),
+            Stmt::Switch(_, cases) => cases
+                .iter()
+                .flat_map(|case| &case.body)
+                .find_map(Self::constructor_call),
+            Stmt::If(_, if_body, else_body) => Self::constructor_call(if_body)
+                .or_else(|| else_body.as_ref().and_then(|s| Self::constructor_call(s))),
+            Stmt::Try(body, catches, finally) => Self::constructor_call(body)
+                .or_else(|| catches.iter().find_map(|c| Self::constructor_call(&c.body)))
+                .or_else(|| finally.as_ref().and_then(|s| Self::constructor_call(s))),
+            Stmt::StmtExprStmt(StmtExpr::ThisCall(_)) => Some("this"),
+            Stmt::StmtExprStmt(StmtExpr::SuperCall(_)) => Some("super"),
+            Stmt::TypedStmt(stmt, _) => Self::constructor_call(stmt),
+            _ => None,
+        }
+    }
+
+    /// Types the arguments of a call of a constructor of the superclass of the current class
+    fn type_super_call(&mut self, exprs: &[Expr]) -> Vec<Expr> {
+        let super_class = self
+            .current_class
+            .as_ref()
+            .unwrap()
+            .super_class
+            .clone()
+            .unwrap_or_else(|| "java/lang/Object".to_string());
+        let mut typed_exprs = self.type_constructor_args(&super_class, exprs);
+        typed_exprs.append(&mut self.captured_args(&super_class));
+        typed_exprs
+    }
+
+    /// Whether the superclass of the current class is a local class, which captures local variables,
+    /// so they have to be passed to its constructors by an explicit call
+    fn super_class_captures_vars(&self) -> bool {
+        let super_class = self.current_class.as_ref().unwrap().super_class.as_ref();
+        super_class
+            .and_then(|name| self.classes.get(name))
+            .is_some_and(|class| !class.captured_vars.is_empty())
+    }
+
+    /// Gets the parameter types of the constructor a typed constructor delegates to via `this(...)`.
+    /// The arguments have already been converted to these types while typing the call.
+    fn delegated_constructor(constructor: &Constructor) -> Option<Vec<Type>> {
+        let Stmt::TypedStmt(body, _) = &constructor.body else {
+            return None;
+        };
+        let Stmt::Block(stmts) = body.deref() else {
+            return None;
+        };
+        let Some(Stmt::TypedStmt(first, _)) = stmts.first() else {
+            return None;
+        };
+        let Stmt::StmtExprStmt(StmtExpr::TypedStmtExpr(stmt_expr, _)) = first.deref() else {
+            return None;
+        };
+        match stmt_expr.deref() {
+            StmtExpr::ThisCall(args) => Some(args.iter().map(|a| a.get_type().unwrap()).collect()),
+            _ => None,
+        }
+    }
+
+    /// Checks that no constructor of a typed class ends up calling itself through `this(...)`
+    fn check_constructor_delegation(class: &Class) -> Result<(), String> {
+        for constructor in &class.constructors {
+            let mut visited = vec![Self::param_types(&constructor.params)];
+            let mut current = constructor;
+            while let Some(target) = Self::delegated_constructor(current) {
+                if visited.contains(&target) {
+                    return Err(format!(
+                        "Recursive constructor invocation in class {}",
+                        class.name
+                    ));
+                }
+                current = class
+                    .constructors
+                    .iter()
+                    .find(|c| Self::param_types(&c.params) == target)
+                    .unwrap();
+                visited.push(target);
+            }
+        }
+        Ok(())
+    }
+
+    /// Looks up the field `name` of the class `class_name` or one of its superclasses,
+    /// returning it together with the name of the class declaring it
+    fn get_field(&self, class_name: &str, name: &str) -> Option<(String, &FieldDecl)> {
+        self.superclasses(class_name)
+            .into_iter()
+            .find_map(|class_name| {
+                let field = self
+                    .fields
+                    .get(&class_name)?
+                    .iter()
+                    .find(|field| field.name == name)?;
+                Some((class_name, field))
+            })
+    }
+
+    /// Returns all methods called `name` of the class `class_name` and the ones it inherits,
+    /// each together with the name of the class declaring it.
+    /// Methods overridden by a subclass are only returned once, i.e. for the subclass.
+    fn get_methods(&self, class_name: &str, name: &str) -> Vec<(String, MethodDecl)> {
+        let mut result: Vec<(String, MethodDecl)> = vec![];
+        for class_name in self.supertypes(class_name) {
+            for method in self.methods.get(&class_name).into_iter().flatten() {
+                if method.name == name
+                    && !result.iter().any(|(_, m)| {
+                        Self::param_types(&m.params) == Self::param_types(&method.params)
+                    })
+                {
+                    result.push((class_name.clone(), method.clone()));
+                }
+            }
+        }
+        result
+    }
+
+    /// Checks that a member called `name` with the given visibility, which is declared by the class
+    /// `owner`, can be accessed from the current class.
+    /// All classes are in the same package, so only private members are restricted to their top level
+    /// class, i.e. the class itself, the classes enclosing it and the other classes nested in them.
+    fn check_access(&self, visibility: Visibility, owner: &str, name: &str) {
+        let current_class = &self.current_class.as_ref().unwrap().name;
+        if visibility == Visibility::Private
+            && self.top_level_class(current_class) != self.top_level_class(owner)
+        {
+            panic!("{name} has private access in {owner}");
+        }
+    }
+
+    /// The outermost class enclosing the class `class_name`, which is the class itself if it isn't nested
+    fn top_level_class<'a>(&'a self, class_name: &'a str) -> &'a str {
+        match self
+            .classes
+            .get(class_name)
+            .and_then(|class| class.outer_class.as_ref())
+        {
+            Some(outer_class) => self.top_level_class(outer_class),
+            None => class_name,
+        }
+    }
+
+    /// The current class followed by the classes enclosing it, from the innermost to the outermost one
+    fn enclosing_classes(&self) -> Vec<String> {
+        let mut result = vec![];
+        let mut current = self.current_class.as_ref();
+        while let Some(class) = current {
+            result.push(class.name.clone());
+            current = class
+                .outer_class
+                .as_ref()
+                .and_then(|name| self.classes.get(name));
+        }
+        result
+    }
+
+    /// The typed expression of the instance of the class `class_name` the current code is executed
+    /// for, i.e. `this` for the current class or `Outer.this` for a class enclosing it. The enclosing
+    /// instances are accessed via the fields of the inner classes holding them.
+    /// None in a static context or if a static nested class is in between.
+    fn enclosing_instance(&self, class_name: &str) -> Option<Expr> {
+        if self.is_static_context {
+            return None;
+        }
+        let mut current = self.current_class.as_ref().unwrap();
+        let mut instance = Expr::TypedExpr(Box::new(Expr::This), Type::Class(current.name.clone()));
+        while current.name != class_name {
+            let outer_class = current
+                .outer_class
+                .as_ref()
+                .filter(|_| current.is_inner())?;
+            instance = Expr::TypedExpr(
+                Box::new(Expr::InstVar(
+                    Box::new(instance),
+                    OUTER_INSTANCE_FIELD.to_string(),
+                )),
+                Type::Class(outer_class.clone()),
+            );
+            current = self.classes.get(outer_class).unwrap();
+        }
+        Some(instance)
+    }
+
+    /// Types the creation of an instance of the inner class `class_name` with the typed enclosing instance
+    fn type_qualified_new(
+        &mut self,
+        typed_outer: Expr,
+        class_name: &str,
+        exprs: &[Expr],
+    ) -> StmtExpr {
+        let class = self.classes.get(class_name).unwrap();
+        let outer_class = class.outer_class.clone().unwrap();
+        if class.is_abstract {
+            panic!("{class_name} is abstract and can't be instantiated");
+        }
+        let outer_type = Type::Class(outer_class);
+        if !self.is_assignable(&typed_outer.get_type().unwrap(), &outer_type) {
+            panic!(
+                "Enclosing instance of type {} can't be converted to {outer_type}",
+                typed_outer.get_type().unwrap()
+            );
+        }
+        let typed_outer = self.convert_expr(typed_outer, &outer_type);
+        let mut typed_exprs = self.type_constructor_args(class_name, exprs);
+        typed_exprs.append(&mut self.captured_args(class_name));
+        let t = Type::Class(class_name.to_string());
+        StmtExpr::TypedStmtExpr(
+            Box::new(StmtExpr::QualifiedNew(typed_outer, t.clone(), typed_exprs)),
+            t,
+        )
+    }
+
+    /// The values of the local variables captured by the local class `class_name`, which are passed to
+    /// its constructors after the declared arguments. Inside of local classes capturing them as well,
+    /// they are the values of their fields.
+    fn captured_args(&mut self, class_name: &str) -> Vec<Expr> {
+        let captured_vars = match self.classes.get(class_name) {
+            Some(class) => class.captured_vars.clone(),
+            None => vec![],
+        };
+        captured_vars
+            .into_iter()
+            .map(|(_, name)| self.type_var(&Expr::LocalOrFieldVar(name), false))
+            .collect()
+    }
+
+    /// Looks up the field holding the value of the local variable `name`, if the class `class_name`
+    /// is a local class capturing it
+    fn captured_var_field(&self, class_name: &str, name: &str) -> Option<(String, &FieldDecl)> {
+        let class = self.classes.get(class_name)?;
+        if !class.captured_vars.iter().any(|(_, var)| var == name) {
+            return None;
+        }
+        self.get_field(class_name, &format!("{CAPTURED_VAR_PREFIX}{name}"))
+    }
+
+    /// Types the receiver of a field access or method call, which can also be the name of a class
+    /// to access its static members, unless a variable with the same name exists
+    fn type_receiver(&mut self, expr: &Expr) -> Expr {
+        match expr {
+            Expr::LocalOrFieldVar(name)
+                if !self.current_local_vars.contains_key(name)
+                    && self
+                        .enclosing_classes()
+                        .iter()
+                        .all(|class_name| self.get_field(class_name, name).is_none()) =>
+            {
+                match self.resolve_class_name(name) {
+                    Some(class_name) => Self::class_name_expr(&class_name),
+                    None => self.type_expr(expr),
+                }
+            }
+            // Nested classes are accessed like static members of their outer class, e.g. `Outer.Inner.x`
+            Expr::InstVar(obj, name) => {
+                let typed_obj = self.type_receiver(obj);
+                let nested_class = match &typed_obj {
+                    Expr::TypedExpr(class_name, Type::Class(outer_class))
+                        if matches!(**class_name, Expr::ClassName(_))
+                            && self.get_field(outer_class, name).is_none() =>
+                    {
+                        Some(format!("{outer_class}${name}"))
+                    }
+                    _ => None,
+                };
+                match nested_class.filter(|class_name| self.classes.contains_key(class_name)) {
+                    Some(class_name) => Self::class_name_expr(&class_name),
+                    None => self.type_expr(expr),
+                }
+            }
+            _ => self.type_expr(expr),
+        }
+    }
+
+    /// Gets the name of the class called `name` in the program, which is either declared by the
+    /// program, read from the classpath or a library class.
+    /// Nested classes are called by their simple name in the classes enclosing them, e.g. `Inner`
+    /// for `Outer$Inner`, and by the name qualified with their outer class elsewhere, e.g. `Outer.Inner`.
+    /// Local classes are called by their simple name in the block declaring them, which shadows other classes.
+    fn resolve_class_name(&self, name: &str) -> Option<String> {
+        if let Some(local_class) = self.local_classes.get(name) {
+            return Some(local_class.clone());
+        }
+        if self.classes.contains_key(name) {
+            return Some(name.to_string());
+        }
+        let nested_class = match name.rsplit_once('/') {
+            Some((outer_name, simple_name)) => self
+                .resolve_class_name(outer_name)
+                .map(|outer_class| format!("{outer_class}${simple_name}"))
+                .filter(|class_name| self.classes.contains_key(class_name)),
+            None => self
+                .enclosing_classes()
+                .into_iter()
+                .map(|outer_class| format!("{outer_class}${name}"))
+                .find(|class_name| self.classes.contains_key(class_name)),
+        };
+        if nested_class.is_some() {
+            return nested_class;
+        }
+        let library_class = resolve_library_class(name);
+        self.classes
+            .contains_key(&library_class)
+            .then_some(library_class)
+    }
+
+    /// The typed expression of a class name, used as receiver when accessing static members
+    fn class_name_expr(class_name: &str) -> Expr {
+        Expr::TypedExpr(
+            Box::new(Expr::ClassName(class_name.to_string())),
+            Type::Class(class_name.to_string()),
+        )
+    }
+
+    /// Whether the typed expression is the name of a class instead of an object
+    fn is_class_name(typed_expr: &Expr) -> bool {
+        matches!(typed_expr, Expr::TypedExpr(expr, _) if matches!(**expr, Expr::ClassName(_)))
+    }
+
+    /// Checks that the field accessed by a typed assignment target may be assigned.
+    /// Final fields can only be assigned once in the constructors of their class,
+    /// if they don't have an initial value. Static final fields are assigned in static initializers instead.
+    fn check_final_assignment(&self, typed_var: &Expr) {
+        let Expr::TypedExpr(var, _) = typed_var else {
+            panic!("Expected typed expr");
+        };
+        let current_class = &self.current_class.as_ref().unwrap().name;
+        let (name, class_name, is_this) = match var.deref() {
+            Expr::FieldVar(name) => (name, current_class.clone(), true),
+            Expr::InstVar(obj, name) => (
+                name,
+                obj.get_type().unwrap().to_internal_name(),
+                matches!(obj.deref(), Expr::TypedExpr(obj, _) if **obj == Expr::This),
+            ),
+            // The length of an array can't be changed
+            Expr::ArrayLength(_) => panic!("Cannot assign a value to final variable length"),
+            _ => return,
+        };
+        let (owner, field) = self.get_field(&class_name, name).unwrap();
+        let is_initializer = if field.modifiers.is_static {
+            self.is_static_initializer
+        } else {
+            self.is_constructor && is_this
+        };
+        if field.modifiers.is_final
+            && !(is_initializer && owner == *current_class && field.val.is_none())
+        {
+            panic!("Cannot assign a value to final variable {name}");
+        }
+    }
+
+    /// Types a variable, which is updated by an arithmetic operator like `++` or `+=`,
+    /// so it must be a number, which can be assigned
+    fn type_numeric_var(&mut self, var: &Expr, op: &str) -> Expr {
+        let typed_var = self.type_var(var, true);
+        match typed_var.get_type().unwrap() {
+            t if t.is_numeric() => {}
+            t => panic!("Operator {op} can't be applied to a variable of type {t}"),
+        }
+        self.check_final_assignment(&typed_var);
+        typed_var
+    }
+
+    /// Gets the name of the class of a typed expression, whose fields or methods are accessed
+    fn receiver_class_name(typed_expr: &Expr, member: &str) -> String {
+        match typed_expr.get_type() {
+            Some(Type::Class(name)) => name,
+            Some(Type::String) => Type::String.to_internal_name(),
+            Some(t) => panic!("Can't access {member} on a value of type {t}"),
+            None => panic!("Expected typed expr"),
+        }
+    }
+
+    /// Whether values of type `t` can be thrown, i.e. are instances of Throwable
+    fn is_throwable(&self, t: &Type) -> bool {
+        self.is_assignable(t, &Type::Class(THROWABLE.to_string()))
+    }
+
+    /// Whether an exception of type `t` has to be caught or declared to be thrown,
+    /// i.e. it is neither a RuntimeException nor an Error
+    fn is_checked_exception(&self, t: &Type) -> bool {
+        !UNCHECKED_EXCEPTIONS
+            .iter()
+            .any(|unchecked| self.is_assignable(t, &Type::Class(unchecked.to_string())))
+    }
+
+    /// Checks that an exception of type `t`, which may be thrown by the current statement,
+    /// is caught by an enclosing try statement or declared to be thrown by the current method,
+    /// if it is a checked exception. The try statements it passes through record it as thrown.
+    fn check_thrown(&mut self, t: &Type) {
+        if !self.is_checked_exception(t) {
+            return;
+        }
+        for i in (0..self.exception_handlers.len()).rev() {
+            self.exception_handlers[i].1.push(t.clone());
+            let (caught, _) = &self.exception_handlers[i];
+            if caught.iter().any(|c| self.is_assignable(t, c)) {
+                return;
+            }
+        }
+        if !self
+            .declared_exceptions
+            .iter()
+            .any(|declared| self.is_assignable(t, declared))
+        {
+            panic!("Unreported exception {t}; must be caught or declared to be thrown");
+        }
+    }
+
+    /// The type of a conditional or switch expression, whose values have the types `a` and `b`,
+    /// None if they are incompatible
+    fn common_type(&self, a: &Type, b: &Type) -> Option<Type> {
+        match (a, b) {
+            (a, b) if self.is_assignable(b, a) => Some(a.clone()),
+            (a, b) if self.is_assignable(a, b) => Some(b.clone()),
+            // Numbers are promoted, e.g. to an int for a short and a char
+            (a, b) if a.is_numeric() && b.is_numeric() => Some(Type::binary_promoted(a, b)),
+            // The closest common superclass, e.g. `Animal` for a `Dog` and a `Cat`
+            (Type::Class(a), Type::Class(b)) => {
+                let superclasses = self.superclasses(b);
+                let common = self
+                    .superclasses(a)
+                    .into_iter()
+                    .find(|c| superclasses.contains(c));
+                Some(Type::Class(common.unwrap_or(OBJECT.to_string())))
+            }
+            (
+                Type::String | Type::Class(_) | Type::Array(_),
+                Type::String | Type::Class(_) | Type::Array(_),
+            ) => Some(Type::Class(OBJECT.to_string())),
+            _ => None,
+        }
+    }
+
+    /// Whether a value of type `from` can be assigned to a variable of type `to`
+    fn is_assignable(&self, from: &Type, to: &Type) -> bool {
+        match (from, to) {
+            // All objects are instances of Object
+            (Type::String | Type::Class(_) | Type::Array(_), Type::Class(to)) if to == OBJECT => {
+                true
+            }
+            (Type::Class(from), Type::Class(to)) => self.supertypes(from).contains(to),
+            // Arrays of objects are covariant, e.g. a `Dog[]` is a `Animal[]`
+            (Type::Array(from), Type::Array(to))
+                if matches!(**from, Type::String | Type::Class(_) | Type::Array(_)) =>
+            {
+                self.is_assignable(from, to)
+            }
+            (Type::Null, Type::String | Type::Class(_) | Type::Array(_)) => true,
+            // Widening primitive conversions, e.g. an int to a long.
+            // See https://docs.oracle.com/javase/specs/jls/se17/html/jls-5.html#jls-5.1.2
+            (Type::Byte, Type::Short) => true,
+            (Type::Byte | Type::Short | Type::Char, Type::Int) => true,
+            (Type::Byte | Type::Short | Type::Char | Type::Int, Type::Long) => true,
+            (Type::Byte | Type::Short | Type::Char | Type::Int | Type::Long, Type::Float) => true,
+            (from, Type::Double) if from.is_numeric() => true,
+            (from, to) => from == to,
+        }
+    }
+
+    /// Whether the constant int value fits into the given integral type,
+    /// e.g. `100` into a byte, but not `200`
+    fn constant_fits(value: i32, t: &Type) -> bool {
+        match t {
+            Type::Int => true,
+            Type::Byte => i8::try_from(value).is_ok(),
+            Type::Short => i16::try_from(value).is_ok(),
+            Type::Char => u16::try_from(value).is_ok(),
+            _ => false,
+        }
+    }
+
+    /// Gets the value of the typed expression, if it is an int or char constant, which fits into
+    /// the type `to`, so it can be assigned to a variable of that type, e.g. `10` to a byte
+    fn narrowable_constant(typed_expr: &Expr, to: &Type) -> Option<Expr> {
+        let Some(Expr::TypedExpr(literal, Type::Int | Type::Char)) = constant_value(typed_expr)
+        else {
+            return None;
+        };
+        let fits = match *literal {
+            Expr::Integer(i) => Self::constant_fits(i, to),
+            Expr::Char(c) => Self::constant_fits(c as i32, to),
+            _ => false,
+        };
+        fits.then_some(*literal)
+    }
+
+    /// Converts a typed expression assigned to a variable of type `to`. Unlike method arguments,
+    /// int and char constants are narrowed to bytes, shorts and chars if they fit, e.g. `byte b = 10`.
+    fn convert_assigned_expr(&self, typed_expr: Expr, to: &Type) -> Expr {
+        match Self::narrowable_constant(&typed_expr, to) {
+            Some(literal) if typed_expr.get_type().as_ref() != Some(to) => {
+                Expr::TypedExpr(Box::new(literal), to.clone())
+            }
+            _ => self.convert_expr(typed_expr, to),
+        }
+    }
+
+    /// Types an expression assigned to a variable of type `to` and converts it to that type.
+    /// The type of lambda expressions and method references is inferred from the variable.
+    fn type_assigned_expr(&mut self, expr: &Expr, to: &Type) -> Expr {
+        if Self::is_function_expr(expr) {
+            return self.type_function(expr, to);
+        }
+        let typed_expr = self.type_expr(expr);
+        self.convert_assigned_expr(typed_expr, to)
+    }
+
+    /// Whether the untyped expression is a lambda expression or method reference, whose type is
+    /// inferred from the functional interface it is assigned, passed, returned or cast to
+    fn is_function_expr(expr: &Expr) -> bool {
+        matches!(expr, Expr::Lambda(_, _) | Expr::MethodRef(_, _))
+    }
+
+    /// Gets the single abstract method of the type `t`, None if it isn't a functional interface.
+    /// Abstract methods inherited from several interfaces with the same signature count as one.
+    fn functional_method(&self, t: &Type) -> Option<MethodDecl> {
+        let Type::Class(name) = t else {
+            return None;
+        };
+        if !self.classes.get(name)?.is_interface {
+            return None;
+        }
+        let mut signatures: Vec<(String, Vec<Type>)> = vec![];
+        let mut abstract_methods = vec![];
+        for interface in self.supertypes(name) {
+            for method in self.methods.get(&interface).into_iter().flatten() {
+                let signature = (method.name.clone(), Self::param_types(&method.params));
+                if method.modifiers.is_static || signatures.contains(&signature) {
+                    continue;
+                }
+                // Methods with a body are default methods, which may override abstract ones
+                if method.body.is_none() {
+                    abstract_methods.push(method.clone());
+                }
+                signatures.push(signature);
+            }
+        }
+        match abstract_methods.len() {
+            1 => abstract_methods.pop(),
+            _ => None,
+        }
+    }
+
+    /// Whether the untyped lambda expression or method reference can be passed to a parameter of
+    /// type `t`, i.e. `t` is a functional interface whose method takes as many parameters as the
+    /// lambda expression
+    fn is_function_target(&self, expr: &Expr, t: &Type) -> bool {
+        match (expr, self.functional_method(t)) {
+            (Expr::Lambda(params, _), Some(method)) => params.len() == method.params.len(),
+            (_, method) => method.is_some(),
         }
-        Ok(Self {
-            classes,
-            typed_classes: HashMap::new(),
-            current_class: None,
-            current_typed_class: Class::default(),
-            fields: HashMap::new(),
-            methods: HashMap::new(),
-            current_local_vars: HashMap::new(),
-        })
     }
 
-    pub fn check_and_type_program(&mut self) -> Result<Vec<Class>, String> {
-        let classes = self.classes.clone();
-        for class in classes.values() {
-            self.current_class = Some(class.clone());
+    /// Types a lambda expression or method reference as an instance of the functional interface `t`
+    fn type_function(&mut self, expr: &Expr, t: &Type) -> Expr {
+        let Some(method) = self.functional_method(t) else {
+            panic!("Incompatible types: {t} is not a functional interface");
+        };
+        let function_ref = match expr {
+            Expr::Lambda(params, body) => self.type_lambda(params, body, &method),
+            Expr::MethodRef(obj, name) => self.type_method_ref(obj, name, &method),
+            _ => panic!("Expected Lambda or MethodRef"),
+        };
+        Expr::TypedExpr(
+            Box::new(Expr::FunctionRef(Box::new(function_ref))),
+            t.clone(),
+        )
+    }
 
-            self.check_and_type_class(class)?;
-            self.fields.clear();
+    /// Types a lambda expression implementing the abstract method `method` of a functional interface.
+    /// Its body is lifted to a private method of the current class, which is static in a static
+    /// context. The local variables it captures are passed to that method before its parameters.
+    fn type_lambda(
+        &mut self,
+        params: &[(Option<Type>, String)],
+        body: &Stmt,
+        method: &MethodDecl,
+    ) -> FunctionRef {
+        let param_types = Self::param_types(&method.params);
+        if params.len() != param_types.len() {
+            panic!(
+                "Incompatible parameter types in lambda expression: expected ({}), but got {} parameters",
+                Self::join_types(&param_types),
+                params.len()
+            );
         }
-        println!("Typechecked program succesfully!🎉🧙");
-        let mut typed_classes = vec![];
-        for class in self.typed_classes.values() {
-            typed_classes.push(class.clone());
+        let mut lambda_params = vec![];
+        for ((t, name), param_type) in params.iter().zip(&param_types) {
+            let t = t
+                .as_ref()
+                .map_or(param_type.clone(), |t| self.resolve_type(t));
+            if t != *param_type {
+                panic!("Incompatible parameter types in lambda expression: expected {param_type}, but got {t}");
+            }
+            if self.current_local_vars.contains_key(name) {
+                panic!("Duplicate local var declaration");
+            }
+            lambda_params.push((t, name.clone()));
+        }
+        // The body is typed like the body of the method it is lifted to, which can't leave the
+        // statements around the lambda expression
+        let local_vars = self.current_local_vars.clone();
+        let return_type = std::mem::replace(&mut self.return_type, method.ret_type.clone());
+        let declared_exceptions = std::mem::replace(
+            &mut self.declared_exceptions,
+            Self::exception_types(&method.throws),
+        );
+        let exception_handlers = std::mem::take(&mut self.exception_handlers);
+        let jump_targets = std::mem::take(&mut self.jump_targets);
+        let yield_types = std::mem::take(&mut self.yield_types);
+        let is_constructor = std::mem::replace(&mut self.is_constructor, false);
+        let is_static_initializer = std::mem::replace(&mut self.is_static_initializer, false);
+        self.lambda_scopes.push(LambdaScope {
+            outer_local_vars: local_vars.clone(),
+            captured: vec![],
+        });
+        for (t, name) in &lambda_params {
+            self.current_local_vars.insert(name.clone(), t.clone());
+        }
+
+        let typed_body = self.type_lambda_body(body);
+
+        let captured = self.lambda_scopes.pop().unwrap().captured;
+        self.current_local_vars = local_vars;
+        self.return_type = return_type;
+        self.declared_exceptions = declared_exceptions;
+        self.exception_handlers = exception_handlers;
+        self.jump_targets = jump_targets;
+        self.yield_types = yield_types;
+        self.is_constructor = is_constructor;
+        self.is_static_initializer = is_static_initializer;
+
+        let class_name = self.current_class.as_ref().unwrap().name.clone();
+        let is_static = self.is_static_context;
+        let name = format!("{LAMBDA_METHOD_PREFIX}{}", self.lambda_methods.len());
+        let params: Vec<(Type, String)> = captured
+            .iter()
+            .map(|(name, t)| (t.clone(), name.clone()))
+            .chain(lambda_params)
+            .collect();
+        let impl_params = Self::param_types(&params);
+        self.lambda_methods.push(MethodDecl {
+            modifiers: Modifiers {
+                visibility: Visibility::Private,
+                is_static,
+                is_final: false,
+            },
+            ret_type: method.ret_type.clone(),
+            name: name.clone(),
+            params,
+            throws: method.throws.clone(),
+            body: Some(typed_body),
+        });
+        // The lifted instance method is called on `this`
+        let this = (!is_static)
+            .then(|| Expr::TypedExpr(Box::new(Expr::This), Type::Class(class_name.clone())));
+        let captured = this
+            .into_iter()
+            .chain(
+                captured
+                    .into_iter()
+                    .map(|(name, t)| Expr::TypedExpr(Box::new(Expr::LocalVar(name)), t)),
+            )
+            .collect();
+        FunctionRef {
+            method: method.name.clone(),
+            params: param_types,
+            ret_type: method.ret_type.clone(),
+            kind: if is_static {
+                MethodKind::Static
+            } else {
+                MethodKind::Private
+            },
+            class: class_name,
+            impl_method: name,
+            impl_params,
+            impl_ret_type: method.ret_type.clone(),
+            captured,
         }
-        Ok(typed_classes)
     }
 
-    fn check_and_type_class(&mut self, class: &Class) -> Result<(), String> {
-        self.current_typed_class.name = class.name.clone();
+    /// Types the body of a lambda expression. An expression as body is returned, unless the
+    /// functional method returns void, in which case it has to be a statement expression.
+    fn type_lambda_body(&mut self, body: &Stmt) -> Stmt {
+        match body {
+            Stmt::Return(Expr::StmtExprExpr(stmt_expr)) if self.return_type == Type::Void => self
+                .type_stmt(&Stmt::Block(vec![Stmt::StmtExprStmt(
+                    stmt_expr.deref().clone(),
+                )])),
+            Stmt::Return(_) if self.return_type == Type::Void => {
+                panic!("Bad return type in lambda expression: expected a statement expression for void")
+            }
+            Stmt::Return(_) => self.type_stmt(&Stmt::Block(vec![body.clone()])),
+            _ => self.type_stmt(body),
+        }
+    }
 
-        self.fields.insert(class.name.clone(), vec![]);
-        for field in &class.fields {
-            self.check_field(field)?;
+    /// Records that the local variable `name` of type `t` is used inside of the lambda expressions
+    /// enclosing the current expression, which declare it outside of them.
+    /// Those lambda expressions capture its value, so it can't be assigned inside of them.
+    fn capture_local_var(&mut self, name: &str, t: &Type, is_assigned: bool) {
+        for scope in &mut self.lambda_scopes {
+            if !scope.outer_local_vars.contains_key(name) {
+                continue;
+            }
+            if is_assigned {
+                panic!("Local variables referenced from a lambda expression must be final or effectively final");
+            }
+            if !scope
+                .captured
+                .iter()
+                .any(|(captured_name, _)| captured_name == name)
+            {
+                scope.captured.push((name.to_string(), t.clone()));
+            }
         }
+    }
 
-        self.current_typed_class.fields = self.fields.get(&class.name).unwrap().clone();
+    /// Types the declaration of a local class, which is lifted out of the method together with the
+    /// classes nested in it. They are named after the current class, e.g. `Outer$1Local` for the first
+    /// local class called `Local`, and typed like the classes of the program.
+    /// A local class captures the local variables it uses, which must not be assigned by it.
+    fn type_local_class(&mut self, classes: &[Class]) {
+        let outer_class = self.current_class.as_ref().unwrap().name.clone();
+        let simple_name = classes[0].name.clone();
+        if self.local_classes.contains_key(&simple_name) {
+            panic!("Duplicate class name: {simple_name}");
+        }
+        let name = (1..)
+            .map(|i| format!("{outer_class}${i}{simple_name}"))
+            .find(|name| !self.classes.contains_key(name))
+            .unwrap();
+        // The local variables used by the class, including the ones captured by the local classes
+        // it uses, are captured, unless a field of the class hides them. Local interfaces and enums
+        // are static, so they can't capture any.
+        let mut used_names = used_names(classes);
+        for used_name in used_names.clone() {
+            if let Some(local_class) = self.local_classes.get(&used_name) {
+                let captured_vars = &self.classes.get(local_class).unwrap().captured_vars;
+                used_names.extend(captured_vars.iter().map(|(_, name)| name.clone()));
+            }
+        }
+        let is_static = classes[0].is_interface || classes[0].is_enum;
+        let mut captured_vars: Vec<(Type, String)> = vec![];
+        for name in used_names {
+            let is_hidden = classes[0].fields.iter().any(|field| field.name == name);
+            let is_captured = captured_vars.iter().any(|(_, captured)| *captured == name);
+            if let Some(t) = self.current_local_vars.get(&name) {
+                if !is_static && !is_hidden && !is_captured {
+                    captured_vars.push((t.clone(), name));
+                }
+            }
+        }
 
-        self.methods.insert(class.name.clone(), vec![]);
-        for method in &class.methods {
-            if self.methods.get(&class.name).unwrap().contains(method) {
-                return Err(format!("Duplicatess method name: {}", method.name));
+        let mut class_names = vec![];
+        for class in classes {
+            let mut class = class.clone();
+            // The names of the nested classes start with the one of the local class
+            let rename = |class_name: &str| format!("{name}{}", &class_name[simple_name.len()..]);
+            class.outer_class = Some(
+                class
+                    .outer_class
+                    .as_deref()
+                    .map_or(outer_class.clone(), rename),
+            );
+            class.name = format!("{name}{}", &class.name[simple_name.len()..]);
+            class_names.push(class.name.clone());
+            self.class_names.push(class.name.clone());
+            self.classes.insert(class.name.clone(), class);
+        }
+        let class = self.classes.get_mut(&name).unwrap();
+        class.is_local = true;
+        class.captured_vars = captured_vars;
+        // Without an enclosing instance, the class is static like a nested class
+        class.modifiers.is_static = is_static || self.is_static_context;
+        self.local_classes.insert(simple_name, name);
+
+        // The state of typing the current method is restored afterwards
+        let current_class = self.current_class.take();
+        let current_typed_class = std::mem::take(&mut self.current_typed_class);
+        let local_vars = std::mem::take(&mut self.current_local_vars);
+        let is_static_context = self.is_static_context;
+        let is_constructor = self.is_constructor;
+        let is_static_initializer = self.is_static_initializer;
+        let jump_targets = std::mem::take(&mut self.jump_targets);
+        let yield_types = std::mem::take(&mut self.yield_types);
+        let return_type = self.return_type.clone();
+        let exception_handlers = std::mem::take(&mut self.exception_handlers);
+        let declared_exceptions = std::mem::take(&mut self.declared_exceptions);
+        let lambda_methods = std::mem::take(&mut self.lambda_methods);
+        let lambda_scopes = std::mem::take(&mut self.lambda_scopes);
+
+        for class_name in &class_names {
+            self.resolve_class_types(class_name);
+        }
+        if let Err(error) = self.check_and_type_classes(&class_names) {
+            panic!("{error}");
+        }
+
+        self.current_class = current_class;
+        self.current_typed_class = current_typed_class;
+        self.current_local_vars = local_vars;
+        self.is_static_context = is_static_context;
+        self.is_constructor = is_constructor;
+        self.is_static_initializer = is_static_initializer;
+        self.jump_targets = jump_targets;
+        self.yield_types = yield_types;
+        self.return_type = return_type;
+        self.exception_handlers = exception_handlers;
+        self.declared_exceptions = declared_exceptions;
+        self.lambda_methods = lambda_methods;
+        self.lambda_scopes = lambda_scopes;
+    }
+
+    /// Types a reference to a method or constructor implementing the abstract method `method` of a
+    /// functional interface, e.g. `this::m`, `Math::abs` or `Foo::new`. Instance methods referred
+    /// to by the name of their class are called on the first argument, e.g. `String::length`.
+    fn type_method_ref(&mut self, obj: &Expr, name: &str, method: &MethodDecl) -> FunctionRef {
+        if *obj == Expr::Super {
+            panic!("Method references to methods of the superclass are not supported");
+        }
+        let typed_obj = self.type_receiver(obj);
+        let param_types = Self::param_types(&method.params);
+        // The referred method may only throw the checked exceptions of the functional method
+        let declared_exceptions = std::mem::replace(
+            &mut self.declared_exceptions,
+            Self::exception_types(&method.throws),
+        );
+        let exception_handlers = std::mem::take(&mut self.exception_handlers);
+        let function_ref = if name == "new" {
+            self.type_constructor_ref(&typed_obj, method)
+        } else {
+            let is_class_name = Self::is_class_name(&typed_obj);
+            let class_name = Self::receiver_class_name(&typed_obj, name);
+            let receiver_type = if class_name == Type::String.to_internal_name() {
+                Type::String
             } else {
-                self.methods
-                    .get_mut(&class.name)
-                    .unwrap()
-                    .push(method.clone());
+                Type::Class(class_name.clone())
+            };
+            // Static methods can't be referred to via an object
+            let mut candidates: Vec<(String, MethodDecl)> = self
+                .get_methods(&class_name, name)
+                .into_iter()
+                .filter(|(_, m)| is_class_name || !m.modifiers.is_static)
+                .collect();
+            let overloads: Vec<Vec<Type>> = candidates
+                .iter()
+                .map(|(_, m)| match (is_class_name, m.modifiers.is_static) {
+                    (true, false) => std::iter::once(receiver_type.clone())
+                        .chain(Self::param_types(&m.params))
+                        .collect(),
+                    _ => Self::param_types(&m.params),
+                })
+                .collect();
+            let selected = self.select_overloads(&overloads, |params| {
+                self.is_applicable(params, &param_types)
+            });
+            let [index] = selected[..] else {
+                panic!(
+                    "Invalid method reference: no unique method {name} of class {class_name} is applicable to the arguments ({})",
+                    Self::join_types(&param_types)
+                );
+            };
+            let (owner, impl_method) = candidates.swap_remove(index);
+            self.check_access(impl_method.modifiers.visibility, &owner, name);
+            for exception in &impl_method.throws {
+                self.check_thrown(&Type::Class(exception.clone()));
             }
-
-            let typed_method = self.check_and_type_method(method)?;
-            self.current_typed_class.methods.push(typed_method);
-            self.current_local_vars.clear();
+            let current_class = &self.current_class.as_ref().unwrap().name;
+            let kind = if impl_method.modifiers.is_static {
+                MethodKind::Static
+            } else if impl_method.modifiers.visibility == Visibility::Private
+                && owner == *current_class
+            {
+                MethodKind::Private
+            } else {
+                MethodKind::Virtual
+            };
+            // Methods referred to via an object are bound to it
+            let captured = if is_class_name {
+                vec![]
+            } else {
+                vec![typed_obj]
+            };
+            FunctionRef {
+                method: method.name.clone(),
+                params: param_types,
+                ret_type: method.ret_type.clone(),
+                kind,
+                class: owner,
+                impl_method: impl_method.name,
+                impl_params: Self::param_types(&impl_method.params),
+                impl_ret_type: impl_method.ret_type,
+                captured,
+            }
+        };
+        self.declared_exceptions = declared_exceptions;
+        self.exception_handlers = exception_handlers;
+        // The result of the referred method is discarded if the functional method returns void
+        let result_type = match function_ref.kind {
+            MethodKind::Constructor => Type::Class(function_ref.class.clone()),
+            _ => function_ref.impl_ret_type.clone(),
+        };
+        if method.ret_type != Type::Void && !self.is_assignable(&result_type, &method.ret_type) {
+            panic!(
+                "Bad return type in method reference: {result_type} can't be converted to {}",
+                method.ret_type
+            );
         }
-        self.typed_classes
-            .insert(class.name.clone(), self.current_typed_class.clone());
-        Ok(())
+        function_ref
     }
 
-    fn check_field(&mut self, field: &FieldDecl) -> Result<(), String> {
-        match self.check_field_type(&field.field_type, &field.val) {
-            Err(error) => Err(format!(
-                "Field value '{:?}' does not match type '{}'\n'{}'",
-                field.val.clone().unwrap(),
-                field.field_type,
-                error
-            )),
-            _ => Ok(()),
+    /// Types a reference to a constructor of the class given by the typed class name, e.g. `Foo::new`
+    fn type_constructor_ref(
+        &mut self,
+        typed_class_name: &Expr,
+        method: &MethodDecl,
+    ) -> FunctionRef {
+        let Some(Type::Class(class_name)) = typed_class_name
+            .get_type()
+            .filter(|_| Self::is_class_name(typed_class_name))
+        else {
+            panic!("Invalid constructor reference: expected the name of a class");
+        };
+        let class = self.classes.get(&class_name).unwrap();
+        if class.is_abstract {
+            panic!("{class_name} is abstract and can't be instantiated");
+        }
+        if class.is_enum {
+            panic!("Enum {class_name} can't be instantiated");
+        }
+        if class.is_inner() {
+            panic!("Constructor references to inner classes are not supported");
+        }
+        let param_types = Self::param_types(&method.params);
+        let constructors = self.get_constructors(&class_name);
+        let selected = self.select_overloads(&constructors, |params| {
+            self.is_applicable(params, &param_types)
+        });
+        let [index] = selected[..] else {
+            panic!(
+                "Invalid constructor reference: no unique constructor of class {class_name} takes arguments of types ({})",
+                Self::join_types(&param_types)
+            );
         };
+        self.check_constructor_call(&class_name, &constructors[index]);
+        FunctionRef {
+            method: method.name.clone(),
+            params: param_types,
+            ret_type: method.ret_type.clone(),
+            kind: MethodKind::Constructor,
+            class: class_name,
+            impl_method: "<init>".to_string(),
+            impl_params: constructors[index].clone(),
+            impl_ret_type: Type::Void,
+            captured: vec![],
+        }
+    }
 
-        let names = self
-            .fields
-            .get_mut(&self.current_class.as_ref().unwrap().name.to_string())
-            .unwrap();
+    /// Types an argument of a method or constructor call. Lambda expressions and method references
+    /// are left untyped, since their type depends on the parameter they are passed to.
+    fn type_arg(&mut self, expr: &Expr) -> Expr {
+        if Self::is_function_expr(expr) {
+            expr.clone()
+        } else {
+            self.type_expr(expr)
+        }
+    }
 
-        // Check for duplicate field names
-        if names.iter().any(|vec_field| vec_field.name == field.name) {
-            return Err(format!("Duplicate field name: {}", field.name));
+    /// Converts an argument typed by `type_arg` to the type of the parameter it is passed to
+    fn convert_arg(&mut self, arg: Expr, to: &Type) -> Expr {
+        if Self::is_function_expr(&arg) {
+            self.type_function(&arg, to)
         } else {
-            names.push(field.clone());
+            self.convert_expr(arg, to)
         }
+    }
 
-        Ok(())
+    /// Types an operand of a numeric operator, which is converted to the promoted type `t`
+    fn type_operand(&mut self, expr: &Expr, t: &Type) -> Expr {
+        let typed_expr = self.type_expr(expr);
+        self.convert_expr(typed_expr, t)
     }
 
-    fn type_of_expr(&self, expr: &Expr) -> Type {
-        if let Expr::TypedExpr(x, t) = expr {
-            t.clone()
-        } else if let Expr::TypedExpr(x, t) = self.type_expr(expr) {
-            t
+    /// The type two numbers are compared as. Numbers of the same type are compared as they are,
+    /// e.g. two chars, since the promotion wouldn't change the result.
+    fn comparison_type(a: &Type, b: &Type) -> Type {
+        if a == b {
+            a.clone()
         } else {
-            unreachable!()
+            Type::binary_promoted(a, b)
         }
     }
 
-    fn check_field_type(&self, field_type: &Type, val: &Option<Expr>) -> Result<(), String> {
-        if let Some(val) = val {
-            match field_type {
-                Type::Int => {
-                    if self.type_of_expr(val) != *field_type {
-                        return Err("Field type is int, but val is not int".to_string());
-                    }
-                }
-                Type::Bool => {
-                    if self.type_of_expr(val) != *field_type {
-                        return Err("Field type is bool, but val is not bool".to_string());
-                    }
-                }
-                Type::Char => {
-                    if self.type_of_expr(val) != *field_type {
-                        return Err("Field type is char, but val is not char".to_string());
-                    }
-                }
-                Type::String => {
-                    if self.type_of_expr(val) != *field_type {
-                        return Err("Field type is string, but val is not string".to_string());
-                    }
-                }
-                Type::Void => {
-                    return Err("Field type cannot be void".to_string());
-                }
-                Type::Null => {
-                    return Err("Field type cannot be null".to_string());
-                }
-                Type::Class(str) => {
-                    if !self.classes.contains_key(str) {
-                        return Err(format!(
-                            "Field type is {str}, but class {str} does not exist"
-                        ));
-                    }
-                }
+    /// Types the distance of a shift, which is converted to an int, even if it is a long
+    fn type_shift_distance(&self, typed_expr: Expr) -> Expr {
+        match typed_expr.get_type().unwrap() {
+            Type::Long => Expr::TypedExpr(Box::new(typed_expr), Type::Int),
+            t if t.is_integral() => self.convert_expr(typed_expr, &Type::Int),
+            t => panic!("Shift distance must be an integer, but is {t}"),
+        }
+    }
+
+    /// Whether a value of type `from` can be cast to the type `to`, i.e. numbers to other numbers
+    /// and references along the class hierarchy. Classes can also be cast to interfaces and vice versa,
+    /// since a subclass may implement the interface.
+    /// See https://docs.oracle.com/javase/specs/jls/se17/html/jls-5.html#jls-5.5
+    fn is_castable(&self, from: &Type, to: &Type) -> bool {
+        let is_interface = |name: &String| self.classes.get(name).is_some_and(|c| c.is_interface);
+        match (from, to) {
+            (from, to) if from.is_numeric() && to.is_numeric() => true,
+            (from, to) if self.is_assignable(from, to) || self.is_assignable(to, from) => true,
+            (Type::Class(from), Type::Class(to)) => is_interface(from) || is_interface(to),
+            (Type::Array(from), Type::Array(to)) if from.is_reference() && to.is_reference() => {
+                self.is_castable(from, to)
             }
+            _ => false,
         }
-        Ok(())
+    }
+
+    /// Whether a method or constructor with the given parameter types can be called with arguments
+    /// of the types `arg_types`
+    fn is_applicable(&self, param_types: &[Type], arg_types: &[Type]) -> bool {
+        param_types.len() == arg_types.len()
+            && param_types
+                .iter()
+                .zip(arg_types)
+                .all(|(param_type, arg_type)| self.is_assignable(arg_type, param_type))
+    }
+
+    /// Whether a method or constructor with the given parameter types can be called with the typed
+    /// arguments. Lambda expressions and method references are still untyped, so they are only
+    /// checked to be passed to a functional interface.
+    fn accepts_args(&self, param_types: &[Type], args: &[Expr]) -> bool {
+        param_types.len() == args.len()
+            && param_types
+                .iter()
+                .zip(args)
+                .all(|(param_type, arg)| match arg.get_type() {
+                    Some(arg_type) => self.is_assignable(&arg_type, param_type),
+                    None => self.is_function_target(arg, param_type),
+                })
+    }
+
+    /// Selects the overloads of a method or constructor, given by their parameter types, which are
+    /// applicable to the arguments of a call, returning their indices.
+    /// Of the applicable overloads only the most specific one is selected, i.e. the one whose
+    /// parameters could be passed to all other applicable overloads. If there is none, the call
+    /// is ambiguous and all applicable overloads are returned.
+    fn select_overloads(
+        &self,
+        overloads: &[Vec<Type>],
+        is_applicable: impl Fn(&[Type]) -> bool,
+    ) -> Vec<usize> {
+        let applicable: Vec<usize> = (0..overloads.len())
+            .filter(|&i| is_applicable(&overloads[i]))
+            .collect();
+        let most_specific: Vec<usize> = applicable
+            .iter()
+            .copied()
+            .filter(|&i| {
+                applicable
+                    .iter()
+                    .all(|&other| self.is_applicable(&overloads[other], &overloads[i]))
+            })
+            .collect();
+        if most_specific.is_empty() {
+            applicable
+        } else {
+            most_specific
+        }
+    }
+
+    /// Joins types for error messages, e.g. `int, String`
+    fn join_types(types: &[Type]) -> String {
+        types
+            .iter()
+            .map(|t| t.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// Joins the types of typed arguments for error messages. Lambda expressions and method
+    /// references don't have a type before they are passed to a parameter.
+    fn join_arg_types(args: &[Expr]) -> String {
+        args.iter()
+            .map(|arg| match (arg.get_type(), arg) {
+                (Some(t), _) => t.to_string(),
+                (None, Expr::Lambda(_, _)) => "lambda".to_string(),
+                (None, _) => "method reference".to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// Converts a typed expression into one of type `to`, if this is allowed by an assignment.
+    /// If the types differ, the expression is wrapped in another TypedExpr with the new type.
+    fn convert_expr(&self, typed_expr: Expr, to: &Type) -> Expr {
+        let from = typed_expr.get_type().expect("Expected typed expr");
+        if from == *to {
+            typed_expr
+        } else if self.is_assignable(&from, to) {
+            Expr::TypedExpr(Box::new(typed_expr), to.clone())
+        } else {
+            panic!("Value of type {from} can't be assigned to a variable of type {to}")
+        }
+    }
+
+    fn check_and_type_constructor(
+        &mut self,
+        constructor: &Constructor,
+    ) -> Result<Constructor, String> {
+        let (explicit_call, rest) = match &constructor.body {
+            Stmt::Block(stmts) => match stmts.split_first() {
+                Some((
+                    Stmt::StmtExprStmt(call @ (StmtExpr::ThisCall(_) | StmtExpr::SuperCall(_))),
+                    rest,
+                )) => (Some(call), rest),
+                _ => (None, stmts.as_slice()),
+            },
+            body => (None, std::slice::from_ref(body)),
+        };
+        if let Some(call) = rest.iter().find_map(Self::constructor_call) {
+            return Err(format!(
+                "Call to {call}(...) must be the first statement in a constructor"
+            ));
+        }
+        // The captured variables of a local superclass are passed to its constructor by an explicit call
+        if explicit_call.is_none() && self.super_class_captures_vars() {
+            let super_call = Stmt::StmtExprStmt(StmtExpr::SuperCall(vec![]));
+            let constructor = Constructor {
+                body: Stmt::Block(std::iter::once(super_call).chain(rest.to_vec()).collect()),
+                ..constructor.clone()
+            };
+            return self.check_and_type_constructor(&constructor);
+        }
+        // The constructor of java/lang/Enum is called with the name and ordinal of the constant
+        // by the generated code instead
+        if self.current_class.as_ref().unwrap().is_enum
+            && matches!(explicit_call, Some(StmtExpr::SuperCall(_)))
+        {
+            return Err("Call to super(...) not allowed in enum constructor".to_string());
+        }
+        let has_explicit_call = explicit_call.is_some();
+        self.is_static_context = false;
+        self.is_constructor = true;
+        self.return_type = Type::Void;
+        self.declared_exceptions = Self::exception_types(&constructor.throws);
+        if !has_explicit_call {
+            // Without an explicit call, the constructor of the superclass is called without arguments
+            self.type_super_call(&[]);
+        }
+        constructor.params.iter().for_each(|(t, name)| {
+            self.current_local_vars.insert(name.clone(), t.clone());
+        });
+        let mut typed_constructor = constructor.clone();
+        typed_constructor.body = self.type_stmt(&constructor.body);
+
+        self.check_stmt(&typed_constructor.body)?;
+
+        Ok(typed_constructor)
+    }
+
+    /// The types of the exceptions of a resolved `throws` clause
+    fn exception_types(throws: &[String]) -> Vec<Type> {
+        throws
+            .iter()
+            .map(|name| Type::Class(name.clone()))
+            .collect()
     }
 
     fn check_and_type_method(&mut self, method: &MethodDecl) -> Result<MethodDecl, String> {
+        // Abstract methods have no body to type
+        let Some(body) = &method.body else {
+            return Ok(method.clone());
+        };
+        if let Some(call) = Self::constructor_call(body) {
+            return Err(format!(
+                "Call to {call}(...) must be the first statement in a constructor"
+            ));
+        }
+        self.is_static_context = method.modifiers.is_static;
+        self.is_constructor = false;
+        self.return_type = self.resolve_type(&method.ret_type);
+        self.declared_exceptions = Self::exception_types(&method.throws);
         method.params.iter().for_each(|(t, name)| {
             self.current_local_vars.insert(name.clone(), t.clone());
         });
         let mut typed_method = method.clone();
-        typed_method.body = self.type_stmt(&method.body);
+        let typed_body = self.type_stmt(body);
 
-        self.check_stmt(&typed_method.body)?;
+        self.check_stmt(&typed_body)?;
+        typed_method.body = Some(typed_body);
 
         let name = self.current_class.as_ref().unwrap().name.clone();
 
@@ -223,7 +2031,7 @@ impl TypeChecker {
         Ok(())
     }
 
-    fn check_expr(&self, expr: &Expr) -> Result<(), String> {
+    fn check_expr(&mut self, expr: &Expr) -> Result<(), String> {
         match expr {
             Expr::LocalOrFieldVar(name) => {
                 let class = self.current_class.as_ref().ok_or("No current class")?;
@@ -244,13 +2052,44 @@ impl TypeChecker {
             }
             Expr::StmtExprExpr(stmt_expr) => self.check_stmt_expr(stmt_expr),
             Expr::InstVar(expr, _) => self.check_expr(expr),
+            Expr::ArrayAccess(array, index) => {
+                self.check_expr(array)?;
+                self.check_expr(index)
+            }
+            Expr::ArrayLength(array) => self.check_expr(array),
+            Expr::NewArray(_, exprs) | Expr::ArrayInit(_, exprs) => {
+                for expr in exprs {
+                    self.check_expr(expr)?;
+                }
+                Ok(())
+            }
             Expr::Unary(_, expr) => self.check_expr(expr),
+            Expr::Switch(expr, _) => self.check_expr(expr),
+            Expr::Conditional(cond, then_expr, else_expr) => {
+                self.check_expr(cond)?;
+                self.check_expr(then_expr)?;
+                self.check_expr(else_expr)
+            }
+            Expr::Cast(_, expr) | Expr::InstanceOf(expr, _, _) => self.check_expr(expr),
             Expr::Integer(_) => Ok(()),
+            Expr::Long(_) => Ok(()),
+            Expr::Float(_) => Ok(()),
+            Expr::Double(_) => Ok(()),
             Expr::Bool(_) => Ok(()),
             Expr::Char(_) => Ok(()),
             Expr::String(_) => Ok(()),
             Expr::Jnull => Ok(()),
             Expr::This => Ok(()),
+            Expr::QualifiedThis(_) => Ok(()),
+            Expr::Super => Ok(()),
+            Expr::ClassName(_) => Ok(()),
+            Expr::Lambda(_, _) | Expr::MethodRef(_, _) => Ok(()),
+            Expr::FunctionRef(function_ref) => {
+                for expr in &function_ref.captured {
+                    self.check_expr(expr)?;
+                }
+                Ok(())
+            }
             Expr::LocalVar(_) => Ok(()),
             Expr::FieldVar(_) => Ok(()),
             // Why part two?
@@ -258,7 +2097,7 @@ impl TypeChecker {
         }
     }
 
-    fn check_stmt_expr(&self, stmt_expr: &StmtExpr) -> Result<(), String> {
+    fn check_stmt_expr(&mut self, stmt_expr: &StmtExpr) -> Result<(), String> {
         match stmt_expr {
             StmtExpr::Assign(var, expr) => {
                 let class = self.current_class.as_ref().ok_or("No current class")?;
@@ -272,7 +2111,7 @@ impl TypeChecker {
                     Expr::TypedExpr(_, t) => t,
                     _ => panic!("Expected typed stmt"),
                 };
-                if t1 != t2 {
+                if !self.is_assignable(t2, t1) {
                     Err(format!(
                         "Value of type {t2} can't be assigned to a variable of type {t1}"
                     ))
@@ -280,12 +2119,24 @@ impl TypeChecker {
                     Ok(())
                 }
             }
+            StmtExpr::CompoundAssign(var, _, expr) => {
+                self.check_expr(var)?;
+                self.check_expr(expr)
+            }
+            StmtExpr::PreIncDec(_, var) | StmtExpr::PostIncDec(var, _) => self.check_expr(var),
             StmtExpr::New(_, exprs) => {
                 for expr in exprs {
                     self.check_expr(expr)?;
                 }
                 Ok(())
             }
+            StmtExpr::QualifiedNew(outer, _, exprs) => {
+                self.check_expr(outer)?;
+                for expr in exprs {
+                    self.check_expr(expr)?;
+                }
+                Ok(())
+            }
             StmtExpr::MethodCall(expr, _, exprs) => {
                 self.check_expr(expr)?;
                 for expr in exprs {
@@ -293,6 +2144,12 @@ impl TypeChecker {
                 }
                 Ok(())
             }
+            StmtExpr::ThisCall(exprs) | StmtExpr::SuperCall(exprs) => {
+                for expr in exprs {
+                    self.check_expr(expr)?;
+                }
+                Ok(())
+            }
             StmtExpr::TypedStmtExpr(expr, _) => {
                 panic!("TypedStmtExpr not expected here: {:?}", expr);
                 Ok(())
@@ -306,7 +2163,10 @@ impl TypeChecker {
                 if stmts.is_empty() {
                     return Stmt::TypedStmt(Box::new(Stmt::Block(vec![])), Type::Void);
                 }
+                // Local classes can only be used in the block declaring them
+                let local_classes = self.local_classes.clone();
                 let typed_stmts: Vec<Stmt> = stmts.iter().map(|s| self.type_stmt(s)).collect();
+                self.local_classes = local_classes;
 
                 let mut return_stmt_types: Vec<Type> = vec![];
 
@@ -314,8 +2174,14 @@ impl TypeChecker {
                     if let Stmt::TypedStmt(boxed_stmt, t) = s {
                         match **boxed_stmt {
                             Stmt::While(_, _) => return_stmt_types.push(t.clone()),
+                            Stmt::DoWhile(_, _) => return_stmt_types.push(t.clone()),
+                            Stmt::For(_, _, _, _) => return_stmt_types.push(t.clone()),
+                            Stmt::ForEach(_, _, _, _) => return_stmt_types.push(t.clone()),
+                            Stmt::Labeled(_, _) => return_stmt_types.push(t.clone()),
+                            Stmt::Switch(_, _) => return_stmt_types.push(t.clone()),
                             Stmt::If(_, _, _) => return_stmt_types.push(t.clone()),
-                            Stmt::Return(_) => return_stmt_types.push(t.clone()),
+                            Stmt::Try(_, _, _) => return_stmt_types.push(t.clone()),
+                            Stmt::Return(_) | Stmt::ReturnVoid => return_stmt_types.push(t.clone()),
 
                             _ => {}
                         }
@@ -336,14 +2202,34 @@ impl TypeChecker {
 
                 Stmt::TypedStmt(Box::new(Stmt::Block(typed_stmts)), return_type)
             }
+            Stmt::Return(_) | Stmt::ReturnVoid if !self.yield_types.is_empty() => {
+                panic!("Return inside of switch expression")
+            }
+            Stmt::ReturnVoid if self.return_type != Type::Void => {
+                panic!("Missing return value: expected {}", self.return_type)
+            }
+            Stmt::ReturnVoid => Stmt::TypedStmt(Box::new(Stmt::ReturnVoid), Type::Void),
+            // The type of returned lambda expressions and method references is the return type
+            Stmt::Return(expr) if Self::is_function_expr(expr) => {
+                let typed_expr = self.type_function(expr, &self.return_type.clone());
+                Stmt::TypedStmt(Box::new(Stmt::Return(typed_expr)), self.return_type.clone())
+            }
             Stmt::Return(expr) => {
-                let typed_expr = match self.type_expr(expr) {
+                let mut typed_expr = match self.type_expr(expr) {
                     Expr::TypedExpr(e, t) => (Expr::TypedExpr(Box::new(*e), t.clone()), t),
                     _ => panic!("Expected typed expr"),
                 };
+                // Returned numbers are converted to the return type, e.g. an int to a long
+                if typed_expr.1.is_numeric() && self.return_type.is_numeric() {
+                    typed_expr = (
+                        self.convert_assigned_expr(typed_expr.0, &self.return_type),
+                        self.return_type.clone(),
+                    );
+                }
                 Stmt::TypedStmt(Box::new(Stmt::Return(typed_expr.0.clone())), typed_expr.1)
             }
             Stmt::While(expr, stmt) => {
+                let label = self.loop_label();
                 let typed_expr = match self.type_expr(expr) {
                     Expr::TypedExpr(e, t) => {
                         if t != Type::Bool {
@@ -353,27 +2239,147 @@ impl TypeChecker {
                     }
                     _ => panic!("Expected typed expr"),
                 };
-                let typed_stmt = match self.type_stmt(stmt) {
+                // The pattern variables matched by the condition can be used in the loop
+                let typed_stmt = match self
+                    .type_in_pattern_scope(Self::pattern_vars(&typed_expr, true), |this| {
+                        this.type_loop_body(stmt)
+                    }) {
                     Stmt::TypedStmt(boxed_stmt, t) => {
                         (Stmt::TypedStmt(Box::new(*boxed_stmt), t.clone()), t)
                     }
                     _ => panic!("Expected typed stmt"),
                 };
+                self.declare_pattern_vars_after_loop(&typed_expr, stmt, label);
                 Stmt::TypedStmt(
                     Box::new(Stmt::While(typed_expr, Box::new(typed_stmt.0))),
                     typed_stmt.1,
                 )
             }
+            Stmt::DoWhile(stmt, expr) => {
+                let label = self.loop_label();
+                // Variables declared in the body can't be used in the condition
+                let typed_stmt = self.type_loop_body(stmt);
+                let typed_expr = self.type_condition(expr, "Do-while");
+                self.declare_pattern_vars_after_loop(&typed_expr, stmt, label);
+                let t = Self::stmt_type(&typed_stmt);
+                Stmt::TypedStmt(Box::new(Stmt::DoWhile(Box::new(typed_stmt), typed_expr)), t)
+            }
+            Stmt::For(init, expr, update, stmt) => {
+                let label = self.loop_label();
+                // Variables declared by the initializing statements can only be used in the loop
+                let outer_local_vars = self.current_local_vars.clone();
+                let typed_init = init.iter().map(|s| self.type_stmt(s)).collect();
+                let typed_expr = expr.as_ref().map(|e| self.type_condition(e, "For"));
+                // The pattern variables matched by the condition can be used in the loop
+                let vars = typed_expr
+                    .as_ref()
+                    .map_or(vec![], |e| Self::pattern_vars(e, true));
+                let (typed_update, typed_stmt) = self.type_in_pattern_scope(vars, |this| {
+                    let typed_update = update.iter().map(|s| this.type_stmt(s)).collect();
+                    (typed_update, this.type_loop_body(stmt))
+                });
+                self.current_local_vars = outer_local_vars;
+                if let Some(typed_expr) = &typed_expr {
+                    self.declare_pattern_vars_after_loop(typed_expr, stmt, label);
+                }
+                let t = Self::stmt_type(&typed_stmt);
+                Stmt::TypedStmt(
+                    Box::new(Stmt::For(
+                        typed_init,
+                        typed_expr,
+                        typed_update,
+                        Box::new(typed_stmt),
+                    )),
+                    t,
+                )
+            }
+            Stmt::ForEach(var_type, name, expr, stmt) => {
+                let var_type = self.resolve_type(var_type);
+                let typed_expr = self.type_expr(expr);
+                // Iterables aren't generic, so their elements are only known to be objects
+                let elem_type = match typed_expr.get_type().unwrap() {
+                    Type::Array(elem_type) => *elem_type,
+                    t if self.is_assignable(&t, &Type::Class(ITERABLE.to_string())) => {
+                        Type::Class(OBJECT.to_string())
+                    }
+                    t => panic!("For-each loop can't iterate over a value of type {t}"),
+                };
+                if !self.is_assignable(&elem_type, &var_type) {
+                    panic!("Element of type {elem_type} can't be assigned to a variable of type {var_type}");
+                }
+                let outer_local_vars = self.current_local_vars.clone();
+                self.type_stmt(&Stmt::LocalVarDecl(var_type.clone(), name.clone()));
+                let typed_stmt = self.type_loop_body(stmt);
+                self.current_local_vars = outer_local_vars;
+                let t = Self::stmt_type(&typed_stmt);
+                Stmt::TypedStmt(
+                    Box::new(Stmt::ForEach(
+                        var_type,
+                        name.clone(),
+                        typed_expr,
+                        Box::new(typed_stmt),
+                    )),
+                    t,
+                )
+            }
+            Stmt::Break(label) | Stmt::Continue(label) => {
+                self.check_jump_target(label, matches!(stmt, Stmt::Continue(_)));
+                Stmt::TypedStmt(Box::new(stmt.clone()), Type::Void)
+            }
+            Stmt::Switch(expr, cases) => {
+                self.jump_targets.push((None, false));
+                let (typed_expr, typed_cases, case_types) = self.type_switch(expr, cases);
+                self.jump_targets.pop();
+                let t = case_types
+                    .into_iter()
+                    .find(|t| *t != Type::Void)
+                    .unwrap_or(Type::Void);
+                Stmt::TypedStmt(Box::new(Stmt::Switch(typed_expr, typed_cases)), t)
+            }
+            Stmt::Yield(expr) => {
+                let typed_expr = self.type_expr(expr);
+                match self.yield_types.last_mut() {
+                    Some(yield_types) => yield_types.push(typed_expr.get_type().unwrap()),
+                    None => panic!("Yield outside of switch expression"),
+                }
+                Stmt::TypedStmt(Box::new(Stmt::Yield(typed_expr)), Type::Void)
+            }
+            Stmt::Labeled(label, stmt) => {
+                if self
+                    .jump_targets
+                    .iter()
+                    .any(|(l, _)| l.as_ref() == Some(label))
+                {
+                    panic!("Label {label} is already in use");
+                }
+                let is_loop = matches!(
+                    **stmt,
+                    Stmt::While(_, _)
+                        | Stmt::DoWhile(_, _)
+                        | Stmt::For(_, _, _, _)
+                        | Stmt::ForEach(_, _, _, _)
+                );
+                self.jump_targets.push((Some(label.clone()), is_loop));
+                let typed_stmt = self.type_stmt(stmt);
+                self.jump_targets.pop();
+                let t = Self::stmt_type(&typed_stmt);
+                Stmt::TypedStmt(
+                    Box::new(Stmt::Labeled(label.clone(), Box::new(typed_stmt))),
+                    t,
+                )
+            }
             Stmt::LocalVarDecl(t, name) => {
+                let t = self.resolve_type(t);
                 if self.current_local_vars.contains_key(name) {
                     panic!("Duplicate local var declaration");
                 } else {
                     self.current_local_vars.insert(name.clone(), t.clone());
                 }
-                Stmt::TypedStmt(
-                    Box::new(Stmt::LocalVarDecl(t.clone(), name.clone())),
-                    t.clone(),
-                )
+                Stmt::TypedStmt(Box::new(Stmt::LocalVarDecl(t.clone(), name.clone())), t)
+            }
+            Stmt::LocalClassDecl(classes) => {
+                self.type_local_class(classes);
+                Stmt::TypedStmt(Box::new(Stmt::Block(vec![])), Type::Void)
             }
             Stmt::If(expr, stmt1, stmt2) => {
                 let typed_expr = match self.type_expr(expr) {
@@ -385,15 +2391,22 @@ impl TypeChecker {
                     }
                     _ => panic!("Expected typed expr"),
                 };
-                let typed_stmt1 = match self.type_stmt(stmt1) {
+                // The pattern variables matched by the condition can be used in the branches
+                let true_vars = Self::pattern_vars(&typed_expr, true);
+                let false_vars = Self::pattern_vars(&typed_expr, false);
+                let typed_stmt1 = match self
+                    .type_in_pattern_scope(true_vars.clone(), |this| this.type_stmt(stmt1))
+                {
                     Stmt::TypedStmt(boxed_stmt, t) => {
                         (Stmt::TypedStmt(Box::new(*boxed_stmt), t.clone()), t)
                     }
                     _ => panic!("Expected typed stmt"),
                 };
-                match stmt2 {
+                let typed_stmt = match stmt2 {
                     Some(stmt2) => {
-                        let typed_stmt2 = match self.type_stmt(stmt2) {
+                        let typed_stmt2 = match self
+                            .type_in_pattern_scope(false_vars.clone(), |this| this.type_stmt(stmt2))
+                        {
                             Stmt::TypedStmt(boxed_stmt, t) => {
                                 (Stmt::TypedStmt(Box::new(*boxed_stmt), t.clone()), t)
                             }
@@ -412,131 +2425,651 @@ impl TypeChecker {
                         Box::new(Stmt::If(typed_expr, Box::new(typed_stmt1.0), None)),
                         typed_stmt1.1,
                     ),
+                };
+                // If a branch can't complete normally, the rest of the block is only executed if the
+                // other one is taken, e.g. after `if (!(o instanceof String s)) return;`
+                if !Self::can_complete_normally(std::slice::from_ref(stmt1)) {
+                    self.declare_pattern_vars(false_vars);
+                } else if stmt2
+                    .as_ref()
+                    .is_some_and(|s| !Self::can_complete_normally(std::slice::from_ref(s)))
+                {
+                    self.declare_pattern_vars(true_vars);
+                }
+                typed_stmt
+            }
+            Stmt::Throw(expr) => {
+                let typed_expr = self.type_expr(expr);
+                let t = typed_expr.get_type().unwrap();
+                if !self.is_throwable(&t) {
+                    panic!("Incompatible types: {t} cannot be converted to Throwable");
+                }
+                self.check_thrown(&t);
+                Stmt::TypedStmt(Box::new(Stmt::Throw(typed_expr)), Type::Void)
+            }
+            // The values of the enclosing switch expressions would be lost on the stack,
+            // when an exception is caught
+            Stmt::Try(_, _, _) if !self.yield_types.is_empty() => {
+                panic!("Try statements inside of switch expressions aren't supported")
+            }
+            Stmt::Try(body, catches, finally) => self.type_try(body, catches, finally),
+            Stmt::StmtExprStmt(stmt_expr) => {
+                let typed_stmt_expr = match self.type_stmt_expr(stmt_expr) {
+                    StmtExpr::TypedStmtExpr(boxed_stmt_expr, t) => (
+                        StmtExpr::TypedStmtExpr(Box::new(*boxed_stmt_expr), t.clone()),
+                        t,
+                    ),
+                    _ => panic!("Expected typed stmt expr"),
+                };
+                Stmt::TypedStmt(
+                    Box::new(Stmt::StmtExprStmt(typed_stmt_expr.0)),
+                    typed_stmt_expr.1,
+                )
+            }
+            Stmt::TypedStmt(stmt, t) => Stmt::TypedStmt(Box::new(self.type_stmt(stmt)), t.clone()),
+        }
+    }
+
+    /// Types a try statement. The exceptions of each catch clause must not have been caught by the
+    /// ones before and checked exceptions must be thrown by the body to be caught. The variables
+    /// declared in the body and each clause can only be used there.
+    fn type_try(
+        &mut self,
+        body: &Stmt,
+        catches: &[CatchClause],
+        finally: &Option<Box<Stmt>>,
+    ) -> Stmt {
+        let mut caught: Vec<Type> = vec![];
+        let mut catch_types = vec![];
+        for catch in catches {
+            let types: Vec<Type> = catch
+                .classes
+                .iter()
+                .map(|name| self.resolve_type(&Type::Class(name.clone())))
+                .collect();
+            for (i, t) in types.iter().enumerate() {
+                if !self.is_throwable(t) {
+                    panic!("Incompatible types: {t} cannot be converted to Throwable");
+                }
+                if caught.iter().any(|c| self.is_assignable(t, c)) {
+                    panic!("Exception {t} has already been caught");
+                }
+                if types[..i]
+                    .iter()
+                    .any(|other| self.is_assignable(t, other) || self.is_assignable(other, t))
+                {
+                    panic!(
+                        "Alternatives in a multi-catch statement cannot be related by subclassing"
+                    );
+                }
+            }
+            caught.extend(types.iter().cloned());
+            catch_types.push(types);
+        }
+
+        let outer_local_vars = self.current_local_vars.clone();
+        self.exception_handlers.push((caught, vec![]));
+        let typed_body = self.type_stmt(body);
+        let (_, thrown) = self.exception_handlers.pop().unwrap();
+        self.current_local_vars = outer_local_vars.clone();
+        let mut stmt_types = vec![Self::stmt_type(&typed_body)];
+
+        let mut typed_catches = vec![];
+        for (catch, types) in catches.iter().zip(catch_types) {
+            // Exception and its superclasses may also catch unchecked exceptions
+            let exception = Type::Class("java/lang/Exception".to_string());
+            for t in &types {
+                if self.is_checked_exception(t)
+                    && !self.is_assignable(&exception, t)
+                    && !thrown
+                        .iter()
+                        .any(|e| self.is_assignable(e, t) || self.is_assignable(t, e))
+                {
+                    panic!("Exception {t} is never thrown in body of corresponding try statement");
                 }
             }
-            Stmt::StmtExprStmt(stmt_expr) => {
-                let typed_stmt_expr = match self.type_stmt_expr(stmt_expr) {
-                    StmtExpr::TypedStmtExpr(boxed_stmt_expr, t) => (
-                        StmtExpr::TypedStmtExpr(Box::new(*boxed_stmt_expr), t.clone()),
-                        t,
-                    ),
-                    _ => panic!("Expected typed stmt expr"),
-                };
-                Stmt::TypedStmt(
-                    Box::new(Stmt::StmtExprStmt(typed_stmt_expr.0)),
-                    typed_stmt_expr.1,
-                )
+            // The parameter of a multi-catch clause has the closest common superclass as type
+            let param_type = types
+                .iter()
+                .cloned()
+                .reduce(|a, b| self.common_type(&a, &b).unwrap())
+                .unwrap();
+            self.type_stmt(&Stmt::LocalVarDecl(param_type, catch.name.clone()));
+            let typed_catch_body = self.type_stmt(&catch.body);
+            self.current_local_vars = outer_local_vars.clone();
+            stmt_types.push(Self::stmt_type(&typed_catch_body));
+            typed_catches.push(CatchClause {
+                classes: types.iter().map(|t| t.to_internal_name()).collect(),
+                name: catch.name.clone(),
+                body: typed_catch_body,
+            });
+        }
+
+        let typed_finally = finally.as_ref().map(|finally| {
+            let typed_finally = self.type_stmt(finally);
+            self.current_local_vars = outer_local_vars.clone();
+            stmt_types.push(Self::stmt_type(&typed_finally));
+            Box::new(typed_finally)
+        });
+        let t = stmt_types
+            .into_iter()
+            .find(|t| *t != Type::Void)
+            .unwrap_or(Type::Void);
+        Stmt::TypedStmt(
+            Box::new(Stmt::Try(
+                Box::new(typed_body),
+                typed_catches,
+                typed_finally,
+            )),
+            t,
+        )
+    }
+
+    /// Types the body of a loop. The variables declared in it can't be used after the loop.
+    fn type_loop_body(&mut self, stmt: &Stmt) -> Stmt {
+        let outer_local_vars = self.current_local_vars.clone();
+        self.jump_targets.push((None, true));
+        let typed_stmt = self.type_stmt(stmt);
+        self.jump_targets.pop();
+        self.current_local_vars = outer_local_vars;
+        typed_stmt
+    }
+
+    /// The label of the loop which is typed next, if it is labeled, e.g. `outer` of `outer: while (...) {}`
+    fn loop_label(&self) -> Option<String> {
+        match self.jump_targets.last() {
+            Some((Some(label), true)) => Some(label.clone()),
+            _ => None,
+        }
+    }
+
+    /// Declares the pattern variables matched if the typed condition of a loop is false, which are
+    /// definitely matched after it, unless the loop is left by `break`, e.g. `s` after
+    /// `while (!(o instanceof String s)) {}`.
+    /// See https://docs.oracle.com/javase/specs/jls/se17/html/jls-6.html#jls-6.3.2.3
+    fn declare_pattern_vars_after_loop(
+        &mut self,
+        typed_cond: &Expr,
+        body: &Stmt,
+        label: Option<String>,
+    ) {
+        if !Self::breaks_loop(body, label.as_ref(), false) {
+            self.declare_pattern_vars(Self::pattern_vars(typed_cond, false));
+        }
+    }
+
+    /// Whether the body of a loop contains a `break` leaving the loop, i.e. one with its label or
+    /// an unlabeled one, which isn't inside of a nested loop or switch
+    fn breaks_loop(stmt: &Stmt, label: Option<&String>, is_nested: bool) -> bool {
+        let breaks = |stmt: &Stmt| Self::breaks_loop(stmt, label, is_nested);
+        match stmt {
+            Stmt::Break(None) => !is_nested,
+            Stmt::Break(Some(target)) => label == Some(target),
+            Stmt::Block(stmts) => stmts.iter().any(breaks),
+            Stmt::If(_, then_stmt, else_stmt) => {
+                breaks(then_stmt) || else_stmt.as_deref().is_some_and(breaks)
+            }
+            Stmt::Labeled(_, stmt) | Stmt::TypedStmt(stmt, _) => breaks(stmt),
+            Stmt::Try(body, catches, finally) => {
+                breaks(body)
+                    || catches.iter().any(|c| breaks(&c.body))
+                    || finally.as_deref().is_some_and(breaks)
             }
-            Stmt::TypedStmt(stmt, t) => Stmt::TypedStmt(Box::new(self.type_stmt(stmt)), t.clone()),
+            Stmt::While(_, body)
+            | Stmt::DoWhile(body, _)
+            | Stmt::For(_, _, _, body)
+            | Stmt::ForEach(_, _, _, body) => Self::breaks_loop(body, label, true),
+            Stmt::Switch(_, cases) => cases
+                .iter()
+                .flat_map(|case| &case.body)
+                .any(|stmt| Self::breaks_loop(stmt, label, true)),
+            _ => false,
         }
     }
 
-    fn type_expr(&self, expr: &Expr) -> Expr {
-        return match expr {
-            Expr::This => Expr::TypedExpr(
-                Box::new(Expr::This),
-                Type::Class(self.current_class.as_ref().unwrap().name.clone()),
-            ),
-            Expr::LocalOrFieldVar(name) => {
-                if let Some(t) = self.current_local_vars.get(name) {
-                    return Expr::TypedExpr(Box::new(Expr::LocalVar(name.clone())), t.clone());
+    /// Checks that a `break` or `continue` statement with the given label is inside of a statement,
+    /// which it can leave or continue
+    fn check_jump_target(&self, label: &Option<String>, is_continue: bool) {
+        match label {
+            Some(label) => match self
+                .jump_targets
+                .iter()
+                .find(|(l, _)| l.as_ref() == Some(label))
+            {
+                None => panic!("Undefined label: {label}"),
+                Some((_, false)) if is_continue => panic!("Not a loop label: {label}"),
+                _ => {}
+            },
+            None if is_continue => {
+                if !self.jump_targets.iter().any(|(_, is_loop)| *is_loop) {
+                    panic!("Continue outside of loop");
                 }
-                if let Some(field) = self
-                    .current_class
-                    .as_ref()
-                    .unwrap()
-                    .fields
+            }
+            // Switch statements are the only unlabeled targets, which aren't loops
+            None => {
+                if !self
+                    .jump_targets
                     .iter()
-                    .find(|field| field.name == *name)
+                    .any(|(l, is_loop)| *is_loop || l.is_none())
                 {
-                    return Expr::TypedExpr(
-                        Box::new(Expr::FieldVar(name.clone())),
-                        field.field_type.clone(),
-                    );
+                    panic!("Break outside of switch or loop");
                 }
-                panic!("Unknown variable: {}", name)
             }
-            Expr::InstVar(expr, name) => {
-                let field_decl = self
-                    .fields
-                    .get(self.current_class.as_ref().unwrap().name.as_str());
-                match field_decl {
-                    Some(field_decl) => {
-                        let decl = field_decl.iter().find(|field| field.name == *name);
-                        match decl {
-                            Some(decl) => {
-                                return Expr::TypedExpr(
-                                    Box::new(Expr::InstVar(
-                                        Box::new(self.type_expr(expr)),
-                                        name.clone(),
-                                    )),
-                                    decl.field_type.clone(),
-                                );
-                            }
-                            None => panic!("Unknown field: {}", name),
-                        }
-                    }
-                    None => panic!(
-                        "Unknown class: {}",
-                        self.current_class.as_ref().unwrap().name.as_str()
-                    ),
+        }
+    }
+
+    /// Types the selector and the cases of a switch. The selector must be an int, char, byte, short or String,
+    /// and the labels must be distinct constants of its type. The variables declared in the cases
+    /// can't be used after the switch. Also returns the type of the statements of each case.
+    fn type_switch(
+        &mut self,
+        expr: &Expr,
+        cases: &[SwitchCase],
+    ) -> (Expr, Vec<SwitchCase>, Vec<Type>) {
+        let typed_expr = self.type_expr(expr);
+        let selector_type = typed_expr.get_type().unwrap();
+        if !matches!(
+            selector_type,
+            Type::Int | Type::Char | Type::Byte | Type::Short | Type::String
+        ) {
+            panic!("Switch can't be applied to a value of type {selector_type}");
+        }
+        let outer_local_vars = self.current_local_vars.clone();
+        let mut constants = vec![];
+        let mut has_default = false;
+        let mut typed_cases = vec![];
+        let mut case_types = vec![];
+        for case in cases {
+            if case.is_default {
+                if has_default {
+                    panic!("Duplicate default label");
                 }
+                has_default = true;
             }
-            Expr::Unary(s, expr) => {
-                let t = match self.type_expr(expr) {
-                    Expr::TypedExpr(_, t) => t,
-                    _ => panic!("Expected typed expr"),
+            let mut labels = vec![];
+            for label in &case.labels {
+                let typed_label = self.type_expr(label);
+                let label_type = typed_label.get_type().unwrap();
+                // Labels can be constant expressions, e.g. `MAX + 1`, which are replaced by their value
+                let Some(typed_label) = constant_value(&typed_label) else {
+                    panic!("Case label must be a constant");
                 };
-                let op = UnaryOp::from(s.as_str());
-                match op {
-                    UnaryOp::Pos => {
-                        if t != Type::Int {
-                            panic!("Type mismatch");
-                        }
+                let Expr::TypedExpr(value, _) = &typed_label else {
+                    unreachable!()
+                };
+                // Chars and ints are compared by their value, e.g. `'A'` and `65` are the same label
+                let constant = match (&**value, &selector_type) {
+                    (Expr::Integer(i), t) if Self::constant_fits(*i, t) => Expr::Integer(*i),
+                    (Expr::Char(c), t) if Self::constant_fits(*c as i32, t) => {
+                        Expr::Integer(*c as i32)
+                    }
+                    (Expr::String(s), Type::String) => Expr::String(s.clone()),
+                    _ => panic!("Case label of type {label_type} can't be compared to a value of type {selector_type}"),
+                };
+                if constants.contains(&constant) {
+                    panic!("Duplicate case label");
+                }
+                constants.push(constant);
+                labels.push(typed_label);
+            }
+            let Stmt::TypedStmt(typed_body, t) = self.type_stmt(&Stmt::Block(case.body.clone()))
+            else {
+                panic!("Expected typed stmt");
+            };
+            let Stmt::Block(body) = *typed_body else {
+                panic!("Expected typed block");
+            };
+            typed_cases.push(SwitchCase {
+                labels,
+                is_default: case.is_default,
+                body,
+            });
+            case_types.push(t);
+        }
+        self.current_local_vars = outer_local_vars;
+        (typed_expr, typed_cases, case_types)
+    }
+
+    /// Whether the execution of the statements can continue after them,
+    /// i.e. they don't end with a `yield`, `return`, `break`, `continue` or `throw`
+    fn can_complete_normally(stmts: &[Stmt]) -> bool {
+        match stmts.last() {
+            Some(
+                Stmt::Yield(_)
+                | Stmt::Return(_)
+                | Stmt::ReturnVoid
+                | Stmt::Break(_)
+                | Stmt::Continue(_)
+                | Stmt::Throw(_),
+            ) => false,
+            Some(Stmt::Block(stmts)) => Self::can_complete_normally(stmts),
+            Some(Stmt::If(_, stmt1, Some(stmt2))) => {
+                Self::can_complete_normally(std::slice::from_ref(stmt1))
+                    || Self::can_complete_normally(std::slice::from_ref(stmt2))
+            }
+            Some(Stmt::Try(body, catches, finally)) => {
+                (Self::can_complete_normally(std::slice::from_ref(body))
+                    || catches
+                        .iter()
+                        .any(|c| Self::can_complete_normally(std::slice::from_ref(&c.body))))
+                    && finally
+                        .as_ref()
+                        .is_none_or(|f| Self::can_complete_normally(std::slice::from_ref(f)))
+            }
+            _ => true,
+        }
+    }
+
+    /// Gets the pattern variables declared by the typed condition, which are definitely matched if it
+    /// evaluates to `when_true`, e.g. `s` of `o instanceof String s` if it is true and of
+    /// `!(o instanceof String s)` if it is false. Only those can be used, since the others may not be assigned.
+    /// See https://docs.oracle.com/javase/specs/jls/se17/html/jls-6.html#jls-6.3.1
+    fn pattern_vars(typed_cond: &Expr, when_true: bool) -> Vec<(String, Type)> {
+        let Expr::TypedExpr(cond, _) = typed_cond else {
+            return vec![];
+        };
+        match cond.deref() {
+            Expr::InstanceOf(_, t, Some(name)) if when_true => vec![(name.clone(), t.clone())],
+            Expr::Unary(op, operand) if matches!(UnaryOp::from(op.as_str()), UnaryOp::Not) => {
+                Self::pattern_vars(operand, !when_true)
+            }
+            // `a && b` is only true if both are true and `a || b` only false if both are false
+            Expr::Binary(op, left, right)
+                if matches!(
+                    (BinaryOp::from(op.as_str()), when_true),
+                    (BinaryOp::And, true) | (BinaryOp::Or, false)
+                ) =>
+            {
+                let mut vars = Self::pattern_vars(left, when_true);
+                vars.append(&mut Self::pattern_vars(right, when_true));
+                vars
+            }
+            Expr::TypedExpr(_, _) => Self::pattern_vars(cond, when_true),
+            _ => vec![],
+        }
+    }
+
+    /// Declares the pattern variables as local variables of the current scope
+    fn declare_pattern_vars(&mut self, vars: Vec<(String, Type)>) {
+        for (name, t) in vars {
+            if self.current_local_vars.contains_key(&name) {
+                panic!("Duplicate local var declaration");
+            }
+            self.current_local_vars.insert(name, t);
+        }
+    }
+
+    /// Types a part of a statement or expression, e.g. the body of an if, in which the pattern
+    /// variables are declared. They, and the local variables declared by that part, can't be used afterwards.
+    fn type_in_pattern_scope<T>(
+        &mut self,
+        vars: Vec<(String, Type)>,
+        type_part: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        let outer_local_vars = self.current_local_vars.clone();
+        self.declare_pattern_vars(vars);
+        let typed_part = type_part(self);
+        self.current_local_vars = outer_local_vars;
+        typed_part
+    }
+
+    /// Types the condition of a loop or conditional expression, which must be a boolean
+    fn type_condition(&mut self, expr: &Expr, stmt_name: &str) -> Expr {
+        let typed_expr = self.type_expr(expr);
+        if typed_expr.get_type() != Some(Type::Bool) {
+            panic!("{stmt_name} condition must be bool");
+        }
+        typed_expr
+    }
+
+    /// Gets the type of a typed statement
+    fn stmt_type(typed_stmt: &Stmt) -> Type {
+        match typed_stmt {
+            Stmt::TypedStmt(_, t) => t.clone(),
+            _ => panic!("Expected typed stmt"),
+        }
+    }
+
+    /// Types a variable or field access. Uses of constant variables are replaced by their value,
+    /// unless they are `is_assigned`, so assigning them can be reported.
+    fn type_var(&mut self, var: &Expr, is_assigned: bool) -> Expr {
+        match var {
+            Expr::LocalOrFieldVar(name) => {
+                if let Some(t) = self.current_local_vars.get(name).cloned() {
+                    self.capture_local_var(name, &t, is_assigned);
+                    return Expr::TypedExpr(Box::new(Expr::LocalVar(name.clone())), t);
+                }
+                // Fields of enclosing classes can be accessed by their simple name as well,
+                // unless a class enclosed by them has a field with the same name. The local variables
+                // captured by local classes are accessed via their fields after their own fields.
+                let field = self.enclosing_classes().into_iter().find_map(|class_name| {
+                    let (owner, field) = self
+                        .get_field(&class_name, name)
+                        .or_else(|| self.captured_var_field(&class_name, name))?;
+                    Some((class_name, owner, field.clone()))
+                });
+                if let Some((class_name, owner, field)) = field {
+                    let is_captured = field.name != *name;
+                    if is_captured && is_assigned {
+                        panic!("Local variables referenced from an inner class must be final or effectively final");
+                    }
+                    let name = &field.name;
+                    // Constructors can't read the fields before calling another constructor,
+                    // but are passed the captured values as parameters named like the fields
+                    let current_class = &self.current_class.as_ref().unwrap().name;
+                    if is_captured && self.is_constructor && class_name == *current_class {
                         return Expr::TypedExpr(
-                            Box::new(Expr::Unary(s.clone(), Box::new(self.type_expr(expr)))),
-                            t,
+                            Box::new(Expr::LocalVar(name.clone())),
+                            field.field_type.clone(),
                         );
                     }
-                    UnaryOp::Neg => {
-                        if t != Type::Int {
-                            panic!("Type mismatch");
+                    self.check_access(field.modifiers.visibility, &owner, name);
+                    let instance = self.enclosing_instance(&class_name);
+                    if instance.is_none() && !field.modifiers.is_static {
+                        panic!("Non-static field {name} can't be referenced from a static context");
+                    }
+                    if !is_assigned {
+                        if let Some(value) = self.field_constant(&owner, name) {
+                            return value;
                         }
+                    }
+                    // Static fields are accessed via their class instead of `this`
+                    if field.modifiers.is_static {
                         return Expr::TypedExpr(
-                            Box::new(Expr::Unary(s.clone(), Box::new(self.type_expr(expr)))),
-                            t,
+                            Box::new(Expr::InstVar(
+                                Box::new(Self::class_name_expr(&class_name)),
+                                name.clone(),
+                            )),
+                            field.field_type.clone(),
                         );
                     }
-                    UnaryOp::Not => {
-                        if t != Type::Bool {
-                            panic!("Type mismatch");
-                        }
+                    if class_name != self.current_class.as_ref().unwrap().name {
                         return Expr::TypedExpr(
-                            Box::new(Expr::Unary(s.clone(), Box::new(self.type_expr(expr)))),
-                            t,
+                            Box::new(Expr::InstVar(Box::new(instance.unwrap()), name.clone())),
+                            field.field_type.clone(),
                         );
                     }
+                    return Expr::TypedExpr(
+                        Box::new(Expr::FieldVar(name.clone())),
+                        field.field_type.clone(),
+                    );
+                }
+                panic!("Unknown variable: {}", name)
+            }
+            Expr::InstVar(expr, name) => {
+                let mut typed_expr = self.type_receiver(expr);
+                // Arrays only have the field length
+                if let Some(Type::Array(_)) = typed_expr.get_type() {
+                    if name != "length" {
+                        panic!("Unknown field {name} of an array");
+                    }
+                    return Expr::TypedExpr(
+                        Box::new(Expr::ArrayLength(Box::new(typed_expr))),
+                        Type::Int,
+                    );
+                }
+                let class_name = Self::receiver_class_name(&typed_expr, name);
+                let field = self.get_field(&class_name, name);
+                match field.map(|(owner, field)| (owner, field.clone())) {
+                    Some((owner, decl)) => {
+                        self.check_access(decl.modifiers.visibility, &owner, name);
+                        let is_class_name = Self::is_class_name(&typed_expr);
+                        if decl.modifiers.is_static {
+                            typed_expr = Self::class_name_expr(&class_name);
+                        } else if is_class_name {
+                            panic!(
+                                "Non-static field {name} can't be referenced from a static context"
+                            );
+                        }
+                        // Only constants referred to by the name of their class are replaced,
+                        // since evaluating the object may have side effects
+                        if !is_assigned && is_class_name {
+                            if let Some(value) = self.field_constant(&owner, name) {
+                                return value;
+                            }
+                        }
+                        Expr::TypedExpr(
+                            Box::new(Expr::InstVar(Box::new(typed_expr), name.clone())),
+                            decl.field_type.clone(),
+                        )
+                    }
+                    None => panic!("Unknown field {name} in class {class_name}"),
+                }
+            }
+            _ => self.type_expr(var),
+        }
+    }
+
+    fn type_expr(&mut self, expr: &Expr) -> Expr {
+        match expr {
+            Expr::This | Expr::Super if self.is_static_context => {
+                let keyword = if *expr == Expr::This { "this" } else { "super" };
+                panic!("Non-static variable {keyword} can't be referenced from a static context")
+            }
+            Expr::This => Expr::TypedExpr(
+                Box::new(Expr::This),
+                Type::Class(self.current_class.as_ref().unwrap().name.clone()),
+            ),
+            Expr::QualifiedThis(name) => {
+                let class_name = self
+                    .resolve_class_name(name)
+                    .unwrap_or_else(|| panic!("Unknown class {name}"));
+                if !self.enclosing_classes().contains(&class_name) {
+                    panic!("Not an enclosing class: {class_name}");
+                }
+                self.enclosing_instance(&class_name).unwrap_or_else(|| {
+                    panic!("Non-static variable this can't be referenced from a static context")
+                })
+            }
+            Expr::Super => match &self.current_class.as_ref().unwrap().super_class {
+                Some(super_class) => {
+                    Expr::TypedExpr(Box::new(Expr::Super), Type::Class(super_class.clone()))
                 }
+                None => panic!(
+                    "Class {} doesn't extend another class, so super can't be used",
+                    self.current_class.as_ref().unwrap().name
+                ),
+            },
+            Expr::LocalOrFieldVar(_) | Expr::InstVar(_, _) => self.type_var(expr, false),
+            Expr::ArrayAccess(array, index) => {
+                let typed_array = self.type_expr(array);
+                let Some(Type::Array(elem_type)) = typed_array.get_type() else {
+                    panic!(
+                        "Array required, but {} found",
+                        typed_array.get_type().unwrap()
+                    );
+                };
+                let typed_index = self.type_array_index(index);
                 Expr::TypedExpr(
-                    Box::new(Expr::Unary(s.clone(), Box::new(self.type_expr(expr)))),
-                    match self.type_expr(expr) {
-                        Expr::TypedExpr(_, t) => t,
-                        _ => panic!("Expected typed expr"),
-                    },
+                    Box::new(Expr::ArrayAccess(
+                        Box::new(typed_array),
+                        Box::new(typed_index),
+                    )),
+                    *elem_type,
                 )
             }
+            Expr::NewArray(t, dims) => {
+                let t = &self.resolve_type(t);
+                self.check_array_type(t);
+                let typed_dims = dims.iter().map(|dim| self.type_array_index(dim)).collect();
+                Expr::TypedExpr(Box::new(Expr::NewArray(t.clone(), typed_dims)), t.clone())
+            }
+            Expr::ArrayInit(t, exprs) => {
+                let t = &self.resolve_type(t);
+                self.check_array_type(t);
+                let Type::Array(elem_type) = t else {
+                    panic!("Illegal initializer for {t}");
+                };
+                let typed_exprs = exprs
+                    .iter()
+                    .map(|e| self.type_assigned_expr(e, elem_type))
+                    .collect();
+                Expr::TypedExpr(Box::new(Expr::ArrayInit(t.clone(), typed_exprs)), t.clone())
+            }
+            Expr::Unary(s, expr) => {
+                // The operand is only typed once, since it may declare pattern variables
+                let typed_expr = self.type_expr(expr);
+                let t = typed_expr.get_type().expect("Expected typed expr");
+                let t = match UnaryOp::from(s.as_str()) {
+                    UnaryOp::Pos | UnaryOp::Neg if t.is_numeric() => t.promoted(),
+                    UnaryOp::Pos | UnaryOp::Neg => panic!("Type mismatch"),
+                    UnaryOp::Not if t == Type::Bool => t,
+                    UnaryOp::Not => panic!("Type mismatch"),
+                    UnaryOp::BitNot if t.is_integral() => t.promoted(),
+                    UnaryOp::BitNot => {
+                        panic!("Operator ~ can't be applied to a value of type {t}")
+                    }
+                };
+                let typed_expr = self.convert_expr(typed_expr, &t);
+                Expr::TypedExpr(Box::new(Expr::Unary(s.clone(), Box::new(typed_expr))), t)
+            }
             Expr::Binary(s, expr1, expr2) => {
                 let op = BinaryOp::from(s.as_str());
-                let t1 = match self.type_expr(expr1) {
-                    Expr::TypedExpr(_, t) => t,
-                    _ => panic!("Expected typed expr"),
-                };
-                let t2 = match self.type_expr(expr2) {
-                    Expr::TypedExpr(_, t) => t,
-                    _ => panic!("Expected typed expr"),
+                // The operands are only typed once, since they may declare pattern variables.
+                // The ones matched when the right operand of `&&` or `||` is evaluated can be used by it.
+                let typed_expr1 = self.type_expr(expr1);
+                let typed_expr2 = match op {
+                    BinaryOp::And | BinaryOp::Or => {
+                        let vars = Self::pattern_vars(&typed_expr1, matches!(op, BinaryOp::And));
+                        self.type_in_pattern_scope(vars, |this| this.type_expr(expr2))
+                    }
+                    _ => self.type_expr(expr2),
                 };
+                let t1 = typed_expr1.get_type().unwrap();
+                let t2 = typed_expr2.get_type().unwrap();
                 match op {
-                    BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div => {
+                    // If one operand is a String, the other one is converted to a string,
+                    // e.g. `"x = " + x`. See https://docs.oracle.com/javase/specs/jls/se17/html/jls-15.html#jls-15.18.1
+                    BinaryOp::Add if t1 == Type::String || t2 == Type::String => {
+                        if t1 == Type::Void || t2 == Type::Void {
+                            panic!("Operator {s} can't be applied to values of type {t1} and {t2}");
+                        }
+                        return Expr::TypedExpr(
+                            Box::new(Expr::Binary(
+                                s.clone(),
+                                Box::new(typed_expr1),
+                                Box::new(typed_expr2),
+                            )),
+                            Type::String,
+                        );
+                    }
+                    BinaryOp::Add
+                    | BinaryOp::Sub
+                    | BinaryOp::Mul
+                    | BinaryOp::Div
+                    | BinaryOp::Mod => {
+                        // Both operands are promoted to the same type, e.g. a long for `1 + 2L`
+                        if t1.is_numeric() && t2.is_numeric() {
+                            let t = Type::binary_promoted(&t1, &t2);
+                            return Expr::TypedExpr(
+                                Box::new(Expr::Binary(
+                                    s.clone(),
+                                    Box::new(self.convert_expr(typed_expr1, &t)),
+                                    Box::new(self.convert_expr(typed_expr2, &t)),
+                                )),
+                                t,
+                            );
+                        }
                         if t1 != t2 {
                             println!("{:#?}", expr1);
                             println!("{:#?}", expr2);
@@ -545,34 +3078,96 @@ impl TypeChecker {
                         return Expr::TypedExpr(
                             Box::new(Expr::Binary(
                                 s.clone(),
-                                Box::new(self.type_expr(expr1)),
-                                Box::new(self.type_expr(expr2)),
+                                Box::new(typed_expr1),
+                                Box::new(typed_expr2),
                             )),
                             t1,
                         );
                     }
-                    BinaryOp::Lt | BinaryOp::Le => {
-                        if t1 != t2 {
+                    BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
+                        if !t1.is_numeric() || !t2.is_numeric() {
                             panic!("Type mismatch");
                         }
+                        let t = Self::comparison_type(&t1, &t2);
                         return Expr::TypedExpr(
                             Box::new(Expr::Binary(
                                 s.clone(),
-                                Box::new(self.type_expr(expr1)),
-                                Box::new(self.type_expr(expr2)),
+                                Box::new(self.convert_expr(typed_expr1, &t)),
+                                Box::new(self.convert_expr(typed_expr2, &t)),
                             )),
                             Type::Bool,
                         );
                     }
+                    BinaryOp::BitAnd
+                    | BinaryOp::BitOr
+                    | BinaryOp::BitXor
+                    | BinaryOp::Shl
+                    | BinaryOp::Shr
+                    | BinaryOp::UShr => {
+                        let is_shift = matches!(op, BinaryOp::Shl | BinaryOp::Shr | BinaryOp::UShr);
+                        let t = match (&t1, &t2) {
+                            // `&`, `|` and `^` are logical operators for booleans, which evaluate
+                            // both operands
+                            (Type::Bool, Type::Bool) if !is_shift => {
+                                return Expr::TypedExpr(
+                                    Box::new(Expr::Binary(
+                                        s.clone(),
+                                        Box::new(typed_expr1),
+                                        Box::new(typed_expr2),
+                                    )),
+                                    Type::Bool,
+                                );
+                            }
+                            // The shifted value is only promoted by itself, e.g. `1 << 2L` is an int
+                            (t1, t2) if is_shift && t1.is_integral() && t2.is_integral() => {
+                                t1.promoted()
+                            }
+                            (t1, t2) if t1.is_integral() && t2.is_integral() => {
+                                Type::binary_promoted(t1, t2)
+                            }
+                            _ => panic!(
+                                "Operator {s} can't be applied to values of type {t1} and {t2}"
+                            ),
+                        };
+                        let typed_expr2 = if is_shift {
+                            self.type_shift_distance(typed_expr2)
+                        } else {
+                            self.convert_expr(typed_expr2, &t)
+                        };
+                        return Expr::TypedExpr(
+                            Box::new(Expr::Binary(
+                                s.clone(),
+                                Box::new(self.convert_expr(typed_expr1, &t)),
+                                Box::new(typed_expr2),
+                            )),
+                            t,
+                        );
+                    }
                     BinaryOp::Eq | BinaryOp::Ne => {
-                        if t1 != t2 {
+                        if t1.is_numeric() && t2.is_numeric() {
+                            let t = Self::comparison_type(&t1, &t2);
+                            return Expr::TypedExpr(
+                                Box::new(Expr::Binary(
+                                    s.clone(),
+                                    Box::new(self.convert_expr(typed_expr1, &t)),
+                                    Box::new(self.convert_expr(typed_expr2, &t)),
+                                )),
+                                Type::Bool,
+                            );
+                        }
+                        // References are equal if they refer to the same object, so they can
+                        // only be compared if one could be cast to the other, e.g. a String and null
+                        let is_reference = |t: &Type| t.is_reference() || *t == Type::Null;
+                        let are_comparable_references =
+                            is_reference(&t1) && is_reference(&t2) && self.is_castable(&t1, &t2);
+                        if t1 != t2 && !are_comparable_references {
                             panic!("Type mismatch");
                         }
                         return Expr::TypedExpr(
                             Box::new(Expr::Binary(
                                 s.clone(),
-                                Box::new(self.type_expr(expr1)),
-                                Box::new(self.type_expr(expr2)),
+                                Box::new(typed_expr1),
+                                Box::new(typed_expr2),
                             )),
                             Type::Bool,
                         );
@@ -582,13 +3177,55 @@ impl TypeChecker {
                 Expr::TypedExpr(
                     Box::new(Expr::Binary(
                         s.clone(),
-                        Box::new(self.type_expr(expr1)),
-                        Box::new(self.type_expr(expr2)),
+                        Box::new(typed_expr1),
+                        Box::new(typed_expr2),
                     )),
                     Type::Bool,
                 )
             }
+            Expr::Cast(t, expr) => {
+                let t = self.resolve_type(t);
+                // Lambda expressions and method references are typed as the type they are cast to
+                if Self::is_function_expr(expr) {
+                    let typed_expr = self.type_function(expr, &t);
+                    return Expr::TypedExpr(
+                        Box::new(Expr::Cast(t.clone(), Box::new(typed_expr))),
+                        t,
+                    );
+                }
+                let typed_expr = self.type_expr(expr);
+                let from = typed_expr.get_type().unwrap();
+                if !self.is_castable(&from, &t) {
+                    panic!("Value of type {from} can't be cast to {t}");
+                }
+                Expr::TypedExpr(Box::new(Expr::Cast(t.clone(), Box::new(typed_expr))), t)
+            }
+            Expr::InstanceOf(expr, t, name) => {
+                let t = self.resolve_type(t);
+                let typed_expr = self.type_expr(expr);
+                let from = typed_expr.get_type().unwrap();
+                if !t.is_reference() || !(from.is_reference() || from == Type::Null) {
+                    panic!("Operator instanceof can't be applied to a value of type {from} and type {t}");
+                }
+                if !self.is_castable(&from, &t) {
+                    panic!("Value of type {from} can't be cast to {t}");
+                }
+                // The pattern variable is declared where it is definitely matched, see `pattern_vars`
+                if name
+                    .as_ref()
+                    .is_some_and(|name| self.current_local_vars.contains_key(name))
+                {
+                    panic!("Duplicate local var declaration");
+                }
+                Expr::TypedExpr(
+                    Box::new(Expr::InstanceOf(Box::new(typed_expr), t, name.clone())),
+                    Type::Bool,
+                )
+            }
             Expr::Integer(i) => Expr::TypedExpr(Box::new(Expr::Integer(*i)), Type::Int),
+            Expr::Long(l) => Expr::TypedExpr(Box::new(Expr::Long(*l)), Type::Long),
+            Expr::Float(f) => Expr::TypedExpr(Box::new(Expr::Float(*f)), Type::Float),
+            Expr::Double(d) => Expr::TypedExpr(Box::new(Expr::Double(*d)), Type::Double),
             Expr::Bool(b) => Expr::TypedExpr(Box::new(Expr::Bool(*b)), Type::Bool),
             Expr::Char(c) => Expr::TypedExpr(Box::new(Expr::Char(*c)), Type::Char),
             Expr::String(s) => Expr::TypedExpr(Box::new(Expr::String(s.clone())), Type::String),
@@ -604,78 +3241,319 @@ impl TypeChecker {
                     },
                 )
             }
+            Expr::Switch(expr, cases) => {
+                if !cases.iter().any(|case| case.is_default) {
+                    panic!("The switch expression does not cover all possible input values");
+                }
+                // The cases of an arrow with a block don't fall through, so they have to yield
+                // a value, just like the last case
+                if cases.iter().enumerate().any(|(i, case)| {
+                    (i == cases.len() - 1 || matches!(case.body[..], [Stmt::Block(_)]))
+                        && Self::can_complete_normally(&case.body)
+                }) {
+                    panic!("Switch expression completes without providing a value");
+                }
+                // `break` and `continue` can't leave a switch expression
+                let outer_jump_targets = std::mem::take(&mut self.jump_targets);
+                self.yield_types.push(vec![]);
+                let (typed_expr, typed_cases, _) = self.type_switch(expr, cases);
+                let yield_types = self.yield_types.pop().unwrap();
+                self.jump_targets = outer_jump_targets;
+                let t = yield_types
+                    .into_iter()
+                    .reduce(|a, b| {
+                        self.common_type(&a, &b).unwrap_or_else(|| {
+                            panic!("Incompatible types in switch expression: {a} and {b}")
+                        })
+                    })
+                    .unwrap();
+                Expr::TypedExpr(Box::new(Expr::Switch(Box::new(typed_expr), typed_cases)), t)
+            }
+            Expr::Conditional(cond, then_expr, else_expr) => {
+                let typed_cond = self.type_condition(cond, "Ternary");
+                let typed_then = self
+                    .type_in_pattern_scope(Self::pattern_vars(&typed_cond, true), |this| {
+                        this.type_expr(then_expr)
+                    });
+                let typed_else = self
+                    .type_in_pattern_scope(Self::pattern_vars(&typed_cond, false), |this| {
+                        this.type_expr(else_expr)
+                    });
+                let (then_type, else_type) = (
+                    typed_then.get_type().unwrap(),
+                    typed_else.get_type().unwrap(),
+                );
+                let is_char_constant = |typed_expr: &Expr| match typed_expr {
+                    Expr::TypedExpr(x, _) => matches!(**x, Expr::Integer(0..=0xFFFF)),
+                    _ => false,
+                };
+                let t = match (&then_type, &else_type) {
+                    // An int constant representable as a char is used as a char, e.g. `c ? 'a' : 98`
+                    (Type::Char, Type::Int) if is_char_constant(&typed_else) => Type::Char,
+                    (Type::Int, Type::Char) if is_char_constant(&typed_then) => Type::Char,
+                    _ => self.common_type(&then_type, &else_type).unwrap_or_else(|| {
+                        panic!("Incompatible types in conditional expression: {then_type} and {else_type}")
+                    }),
+                };
+                // Ints and chars are both represented by ints, so they don't need to be converted
+                let convert = |typed_expr: Expr| match typed_expr.get_type() {
+                    Some(from) if self.is_assignable(&from, &t) => {
+                        self.convert_expr(typed_expr, &t)
+                    }
+                    _ => typed_expr,
+                };
+                Expr::TypedExpr(
+                    Box::new(Expr::Conditional(
+                        Box::new(typed_cond),
+                        Box::new(convert(typed_then)),
+                        Box::new(convert(typed_else)),
+                    )),
+                    t,
+                )
+            }
             Expr::TypedExpr(expr, t) => Expr::TypedExpr(Box::new(self.type_expr(expr)), t.clone()),
             Expr::LocalVar(name) => panic!("Expected LocalOrFieldVar, got LocalVar"),
             Expr::FieldVar(name) => panic!("Expected LocalOrFieldVar, got FieldVar"),
-        };
+            // Their type is inferred from the functional interface they are used as
+            Expr::Lambda(_, _) => panic!("Lambda expression not expected here"),
+            Expr::MethodRef(_, _) => panic!("Method reference not expected here"),
+            Expr::FunctionRef(_) => panic!("Expected Lambda or MethodRef, got FunctionRef"),
+            Expr::ClassName(name) => panic!("Expected LocalOrFieldVar, got ClassName"),
+            Expr::ArrayLength(_) => panic!("Expected InstVar, got ArrayLength"),
+        }
+    }
+
+    /// Types the index of an array element or the length of a created array, which must be an int
+    fn type_array_index(&mut self, expr: &Expr) -> Expr {
+        let typed_expr = self.type_expr(expr);
+        match typed_expr.get_type() {
+            Some(Type::Int) => typed_expr,
+            Some(t) => panic!("Array index must be int, but is {t}"),
+            None => panic!("Expected typed expr"),
+        }
     }
 
-    fn type_stmt_expr(&self, stmt_expr: &StmtExpr) -> StmtExpr {
+    /// Checks that the elements of arrays of the given type can be created
+    fn check_array_type(&self, array_type: &Type) {
+        let mut elem_type = array_type;
+        while let Type::Array(t) = elem_type {
+            elem_type = t;
+        }
+        match elem_type {
+            Type::Void | Type::Null => panic!("Illegal array element type {elem_type}"),
+            Type::Class(name) if !self.classes.contains_key(name) => {
+                panic!("Unknown class {name}")
+            }
+            _ => {}
+        }
+    }
+
+    fn type_stmt_expr(&mut self, stmt_expr: &StmtExpr) -> StmtExpr {
         match stmt_expr {
             StmtExpr::Assign(var, expr) => {
-                let typed_expr = match self.type_expr(expr) {
-                    Expr::TypedExpr(expr, t) => (Expr::TypedExpr(Box::new(*expr), t.clone()), t),
-                    _ => panic!("Expected typed stmt"),
-                };
-                let typed_var = match self.type_expr(var) {
+                let typed_var = match self.type_var(var, true) {
                     Expr::TypedExpr(var, t) => (Expr::TypedExpr(Box::new(*var), t.clone()), t),
                     _ => panic!("Expected typed stmt"),
                 };
+                self.check_final_assignment(&typed_var.0);
+                let typed_expr = self.type_assigned_expr(expr, &typed_var.1);
+                StmtExpr::TypedStmtExpr(
+                    Box::new(StmtExpr::Assign(typed_var.0, typed_expr)),
+                    typed_var.1,
+                )
+            }
+            // `&=`, `|=` and `^=` can be applied to booleans as well
+            StmtExpr::CompoundAssign(var, op, expr)
+                if matches!(op.as_str(), "&" | "|" | "^")
+                    && self.type_expr(var).get_type() == Some(Type::Bool) =>
+            {
+                let typed_var = self.type_var(var, true);
+                self.check_final_assignment(&typed_var);
+                let typed_expr = self.type_expr(expr);
+                match typed_expr.get_type().unwrap() {
+                    Type::Bool => {}
+                    t => panic!("Operator {op}= can't be applied to a value of type {t}"),
+                }
+                StmtExpr::TypedStmtExpr(
+                    Box::new(StmtExpr::CompoundAssign(typed_var, op.clone(), typed_expr)),
+                    Type::Bool,
+                )
+            }
+            // `+=` appends a value of any type to a String, e.g. `s += 1` is `s = s + 1`
+            StmtExpr::CompoundAssign(var, op, expr)
+                if op == "+" && self.type_expr(var).get_type() == Some(Type::String) =>
+            {
+                let typed_var = self.type_var(var, true);
+                self.check_final_assignment(&typed_var);
+                let typed_expr = self.type_expr(expr);
+                if typed_expr.get_type() == Some(Type::Void) {
+                    panic!("Operator {op}= can't be applied to a value of type void");
+                }
+                StmtExpr::TypedStmtExpr(
+                    Box::new(StmtExpr::CompoundAssign(typed_var, op.clone(), typed_expr)),
+                    Type::String,
+                )
+            }
+            StmtExpr::CompoundAssign(var, op, expr) => {
+                let typed_var = self.type_numeric_var(var, &format!("{op}="));
+                let t = typed_var.get_type().unwrap();
+                let typed_expr = self.type_expr(expr);
+                let value_type = typed_expr.get_type().unwrap();
+                // The result of the operation is converted back to the type of the variable,
+                // e.g. `b += 1` for a byte `b` is `b = (byte) (b + 1)`
+                let typed_expr = match op.as_str() {
+                    "<<" | ">>" | ">>>" if t.is_integral() => self.type_shift_distance(typed_expr),
+                    "&" | "|" | "^" | "<<" | ">>" | ">>>"
+                        if !t.is_integral() || !value_type.is_integral() =>
+                    {
+                        panic!("Operator {op}= can't be applied to values of type {t} and {value_type}")
+                    }
+                    _ if value_type.is_numeric() => {
+                        self.convert_expr(typed_expr, &Type::binary_promoted(&t, &value_type))
+                    }
+                    _ => panic!("Operator {op}= can't be applied to a value of type {value_type}"),
+                };
                 StmtExpr::TypedStmtExpr(
-                    Box::new(StmtExpr::Assign(typed_var.0, typed_expr.0)),
-                    typed_expr.1,
+                    Box::new(StmtExpr::CompoundAssign(typed_var, op.clone(), typed_expr)),
+                    t,
                 )
             }
+            StmtExpr::PreIncDec(op, var) => {
+                let typed_var = self.type_numeric_var(var, op);
+                let t = typed_var.get_type().unwrap();
+                StmtExpr::TypedStmtExpr(Box::new(StmtExpr::PreIncDec(op.clone(), typed_var)), t)
+            }
+            StmtExpr::PostIncDec(var, op) => {
+                let typed_var = self.type_numeric_var(var, op);
+                let t = typed_var.get_type().unwrap();
+                StmtExpr::TypedStmtExpr(Box::new(StmtExpr::PostIncDec(typed_var, op.clone())), t)
+            }
             StmtExpr::TypedStmtExpr(stmt_expr, t) => panic!("Expected untyped stmt"),
             StmtExpr::New(t, exprs) => {
-                if exprs.is_empty() {
-                    return StmtExpr::TypedStmtExpr(
-                        Box::new(StmtExpr::New(t.clone(), exprs.clone())),
-                        t.clone(),
-                    );
+                let t = &self.resolve_type(t);
+                let class_name = match t {
+                    Type::Class(name) if self.classes.contains_key(name) => name,
+                    _ => panic!("Unknown class {t}"),
+                };
+                if self.classes.get(class_name).unwrap().is_abstract {
+                    panic!("{class_name} is abstract and can't be instantiated");
                 }
-                let typed_exprs = exprs.iter().map(|e| self.type_expr(e)).collect();
+                if self.classes.get(class_name).unwrap().is_enum {
+                    panic!("Enum {class_name} can't be instantiated");
+                }
+                // Instances of inner classes created inside their outer class belong to its instance
+                let class = self.classes.get(class_name).unwrap();
+                if let (true, Some(outer_class)) = (class.is_inner(), &class.outer_class) {
+                    let typed_outer = self.enclosing_instance(outer_class).unwrap_or_else(|| {
+                        panic!("No enclosing instance of type {outer_class} is in scope")
+                    });
+                    return self.type_qualified_new(typed_outer, class_name, exprs);
+                }
+                let mut typed_exprs = self.type_constructor_args(class_name, exprs);
+                typed_exprs.append(&mut self.captured_args(class_name));
                 StmtExpr::TypedStmtExpr(Box::new(StmtExpr::New(t.clone(), typed_exprs)), t.clone())
             }
+            StmtExpr::QualifiedNew(outer, t, exprs) => {
+                let typed_outer = self.type_expr(outer);
+                let Some(Type::Class(outer_type)) = typed_outer.get_type() else {
+                    panic!("Enclosing instance must be an object");
+                };
+                // The class is looked up among the member classes of the enclosing instance's class
+                let class_name = match t {
+                    Type::Class(name) => self
+                        .superclasses(&outer_type)
+                        .into_iter()
+                        .map(|outer_class| format!("{outer_class}${name}"))
+                        .chain(std::iter::once(name.clone()))
+                        .find(|class_name| self.classes.contains_key(class_name)),
+                    _ => None,
+                }
+                .unwrap_or_else(|| panic!("Unknown class {t}"));
+                if !self.classes.get(&class_name).unwrap().is_inner() {
+                    panic!("Qualified new of static class {class_name}");
+                }
+                self.type_qualified_new(typed_outer, &class_name, exprs)
+            }
+            StmtExpr::ThisCall(exprs) => {
+                let class_name = self.current_class.as_ref().unwrap().name.clone();
+                let typed_exprs = self.type_constructor_args(&class_name, exprs);
+                StmtExpr::TypedStmtExpr(Box::new(StmtExpr::ThisCall(typed_exprs)), Type::Void)
+            }
+            StmtExpr::SuperCall(exprs) => {
+                let typed_exprs = self.type_super_call(exprs);
+                StmtExpr::TypedStmtExpr(Box::new(StmtExpr::SuperCall(typed_exprs)), Type::Void)
+            }
             StmtExpr::MethodCall(expr, name, exprs) => {
-                let mut method: Vec<MethodDecl> = self
-                    .methods
-                    .get(&self.current_class.clone().unwrap().name)
-                    .unwrap()
-                    .clone();
-                method.retain(|m| m.name == *name);
-
-                match method.len() {
-                    0 => panic!("Method not found: {}", name),
-                    1 => {
-                        let current_method = method[0].clone();
-                        let typed_expr: Vec<Expr> =
-                            exprs.iter().map(|e| self.type_expr(e)).collect();
-
-                        for (method_param, given_method_param) in
-                            typed_expr.iter().zip(current_method.params.iter())
-                        {
-                            match method_param {
-                                Expr::TypedExpr(_, t) => {
-                                    if *t != given_method_param.0 {
-                                        panic!("Type mismatch {} {}", t, given_method_param.0);
-                                    }
-                                }
-                                _ => panic!("Expected typed expr"),
-                            }
-                        }
-
-                        StmtExpr::TypedStmtExpr(
-                            Box::new(StmtExpr::MethodCall(
-                                self.type_expr(expr),
-                                name.clone(),
-                                typed_expr,
-                            )),
-                            current_method.ret_type,
-                        )
+                // Methods called without an object are looked up in the current class and then in
+                // the classes enclosing it. In a static context, they are called on the class.
+                let mut typed_obj = if *expr == Expr::This {
+                    let class_name = self
+                        .enclosing_classes()
+                        .into_iter()
+                        .find(|class_name| !self.get_methods(class_name, name).is_empty())
+                        .unwrap_or_else(|| self.current_class.as_ref().unwrap().name.clone());
+                    self.enclosing_instance(&class_name)
+                        .unwrap_or_else(|| Self::class_name_expr(&class_name))
+                } else {
+                    self.type_receiver(expr)
+                };
+                let class_name = Self::receiver_class_name(&typed_obj, name);
+                let typed_args: Vec<Expr> = exprs.iter().map(|e| self.type_arg(e)).collect();
+                let mut method = self.get_methods(&class_name, name);
+                // Of overloaded methods only the most specific one applicable to the arguments
+                // is called
+                if method.len() > 1 {
+                    let overloads: Vec<Vec<Type>> = method
+                        .iter()
+                        .map(|(_, m)| Self::param_types(&m.params))
+                        .collect();
+                    let selected = self.select_overloads(&overloads, |params| {
+                        self.accepts_args(params, &typed_args)
+                    });
+                    match selected[..] {
+                        [] => panic!(
+                            "No method {name} of class {class_name} is applicable to the arguments ({})",
+                            Self::join_arg_types(&typed_args)
+                        ),
+                        [index] => method = vec![method.swap_remove(index)],
+                        _ => panic!(
+                            "Ambiguous method call {name}({}) of class {class_name}",
+                            Self::join_arg_types(&typed_args)
+                        ),
                     }
-                    _ => panic!("Ambiguous method call"),
                 }
+
+                let Some((owner, current_method)) = method.pop() else {
+                    panic!("Method {name} not found in class {class_name}");
+                };
+                self.check_access(current_method.modifiers.visibility, &owner, name);
+                for exception in &current_method.throws {
+                    self.check_thrown(&Type::Class(exception.clone()));
+                }
+                if current_method.modifiers.is_static {
+                    typed_obj = Self::class_name_expr(&class_name);
+                } else if Self::is_class_name(&typed_obj) {
+                    panic!("Non-static method {name} can't be referenced from a static context");
+                }
+                if exprs.len() != current_method.params.len() {
+                    panic!(
+                        "Method {name} of class {class_name} expects {} arguments, but got {}",
+                        current_method.params.len(),
+                        exprs.len()
+                    );
+                }
+                // The arguments are converted to the parameter types of the called method,
+                // so its descriptor is the one of the selected overload
+                let typed_expr: Vec<Expr> = typed_args
+                    .into_iter()
+                    .zip(current_method.params.iter())
+                    .map(|(e, (param_type, _))| self.convert_arg(e, param_type))
+                    .collect();
+
+                StmtExpr::TypedStmtExpr(
+                    Box::new(StmtExpr::MethodCall(typed_obj, name.clone(), typed_expr)),
+                    current_method.ret_type,
+                )
             }
         }
     }
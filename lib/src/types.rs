@@ -7,73 +7,218 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 /// All types necessary for the AST.
-
 pub type Prg = Vec<Class>;
 
 #[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq)]
 pub struct Class {
+    pub modifiers: Modifiers,
     pub name: String,
+    pub is_interface: bool, // interfaces only declare abstract methods, which are implemented by classes
+    pub is_abstract: bool,
+    pub is_enum: bool, // enums implicitly extend java/lang/Enum, their only instances are their constants
+    pub super_class: Option<String>, // None if the class doesn't extend another class, i.e. it extends java/lang/Object
+    pub interfaces: Vec<String>,     // the interfaces a class implements or an interface extends
+    pub enum_constants: Vec<EnumConstant>, // the constants of an enum in the order of their ordinals
     pub fields: Vec<FieldDecl>,
+    pub constructors: Vec<Constructor>,
     pub methods: Vec<MethodDecl>,
+    // The `static { ... }` blocks, each with the number of fields declared before it,
+    // since static fields and blocks are initialized in the order they are declared in
+    pub static_blocks: Vec<(usize, Stmt)>,
+    // The class directly enclosing a nested class, whose name is the name of the nested class
+    // prefixed by the one of the enclosing class and `$`, e.g. `Outer$Inner`. None for top level classes.
+    pub outer_class: Option<String>,
+    // Whether the class is a local class, i.e. declared by a statement, whose name has a number between
+    // the names of the enclosing class and the local class, e.g. `Outer$1Local`. It isn't a member of
+    // its outer class, which is the class declaring the method.
+    pub is_local: bool,
+    // The local variables of the enclosing method a local class uses, whose values are passed to its
+    // constructors after the declared arguments and stored in synthetic fields, e.g. `val$x`
+    pub captured_vars: Vec<(Type, String)>,
+}
+
+/// A constant of an enum, which is created by calling a constructor of the enum with the arguments,
+/// e.g. `MERCURY(3.303e+23, 2.4397e6)`
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct EnumConstant {
+    pub name: String,
+    pub args: Vec<Expr>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct FieldDecl {
+    pub modifiers: Modifiers,
     pub field_type: Type,
     pub name: String,
     pub val: Option<Expr>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+/// A constructor declared by the user. If a class doesn't declare any constructors,
+/// a default constructor without parameters is generated instead
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Constructor {
+    pub modifiers: Modifiers,
+    pub params: Vec<(Type, String)>,
+    pub throws: Vec<String>, // the names of the exception classes of the `throws` clause
+    pub body: Stmt,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct MethodDecl {
+    pub modifiers: Modifiers,
     pub ret_type: Type,
     pub name: String,
     pub params: Vec<(Type, String)>,
-    pub body: Stmt,
+    pub throws: Vec<String>, // the names of the exception classes of the `throws` clause
+    pub body: Option<Stmt>,  // None for abstract methods
+}
+
+/// The modifiers of a class, field, constructor or method.
+/// Being abstract is represented by `Class::is_abstract` and methods without a body instead.
+#[derive(Debug, Default, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Modifiers {
+    pub visibility: Visibility,
+    pub is_static: bool,
+    pub is_final: bool,
+}
+
+/// From where a class or member can be accessed, ordered from the most to the least restrictive.
+/// Since all classes are in the same package, only private members can't be accessed from other classes.
+#[derive(Debug, Default, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Visibility {
+    Private,
+    #[default]
+    Package, // no access modifier
+    Protected,
+    Public,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub enum Stmt {
     Block(Vec<Stmt>),
     Return(Expr),
+    ReturnVoid, // `return;` in a method, constructor or lambda expression returning void
     While(Expr, Box<Stmt>), // first condition, then body of the while-statement
-    LocalVarDecl(Type, String), // first type of the local variable, then it's name
+    DoWhile(Box<Stmt>, Expr), // first body of the do-statement, then its condition
+    For(Vec<Stmt>, Option<Expr>, Vec<Stmt>, Box<Stmt>), // first the initializing statements, then the optional condition, the updating statements and lastly the body of the for-statement
+    ForEach(Type, String, Expr, Box<Stmt>), // first type and name of the loop variable, then the iterated array or Iterable and lastly the body of the for-statement
+    Break(Option<String>),                  // the optional label of the statement, which is left
+    Continue(Option<String>),               // the optional label of the loop, which is continued
+    Labeled(String, Box<Stmt>),             // first the label, then the labeled statement
+    Switch(Expr, Vec<SwitchCase>), // first the selector, then the groups of statements of the cases
+    Yield(Expr),                   // the value of the enclosing switch expression
+    LocalVarDecl(Type, String),    // first type of the local variable, then it's name
+    LocalClassDecl(Vec<Class>), // the local class followed by the classes nested in it, which the typechecker lifts out of the method
     If(Expr, Box<Stmt>, Option<Box<Stmt>>), // first condition, then body ofthe if-statement and lastly the optional body of the else-statement
+    Throw(Expr),                            // the thrown exception
+    Try(Box<Stmt>, Vec<CatchClause>, Option<Box<Stmt>>), // first the body of the try-statement, then the catch clauses and lastly the optional finally block
     StmtExprStmt(StmtExpr),
     TypedStmt(Box<Stmt>, Type),
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub enum StmtExpr {
     Assign(Expr, Expr), // first the name of the variable, then the value it is being assigned to
+    CompoundAssign(Expr, String, Expr), // first the variable, then the operator combining it with the value, e.g. "+" for `x += 2`, and lastly the value
+    PreIncDec(String, Expr), // first the operator "++" or "--", then the variable, e.g. `++i`
+    PostIncDec(Expr, String), // first the variable, then the operator "++" or "--", e.g. `i++`
     New(Type, Vec<Expr>), // first the class type, that should be instantiated, then the list of arguments for the constructor
+    QualifiedNew(Expr, Type, Vec<Expr>), // instantiation of an inner class, first the enclosing instance, then the class type and lastly the list of arguments, e.g. `outer.new Inner(x)`
     MethodCall(Expr, String, Vec<Expr>), // first the object to which the method belongs (e.g. Expr::This), then the name of the method and lastly the list of arguments for the method call
+    ThisCall(Vec<Expr>), // call of another constructor of the same class via `this(...)` with the list of arguments
+    SuperCall(Vec<Expr>), // call of a constructor of the superclass via `super(...)` with the list of arguments
     TypedStmtExpr(Box<StmtExpr>, Type),
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub enum Expr {
     This,
+    Super, // `this` viewed as an instance of the superclass, used for `super.x` and `super.m()`
     LocalOrFieldVar(String), // name of the variable
+    ClassName(String), // name of a class, whose static members are accessed, e.g. `A` in `A.x` or `A.f()`
+    QualifiedThis(String), // the instance of an enclosing class of an inner class, e.g. `Outer.this`
     InstVar(Box<Expr>, String),
     LocalVar(String),                     // name of the variable
     FieldVar(String),                     // name of the variable
     Unary(String, Box<Expr>),             // operation first, then operand
     Binary(String, Box<Expr>, Box<Expr>), // operation first, then left and right operands
     Integer(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
     Bool(bool),
     Char(char),
     String(String),
     Jnull,
     StmtExprExpr(Box<StmtExpr>),
+    ArrayAccess(Box<Expr>, Box<Expr>), // first the array, then the index of the accessed element
+    ArrayLength(Box<Expr>), // the length of an array, i.e. `a.length`, which is parsed as InstVar
+    NewArray(Type, Vec<Expr>), // first the type of the created array, then the lengths of its first dimensions, e.g. `new int[n][]`
+    ArrayInit(Type, Vec<Expr>), // first the type of the created array, then its elements, e.g. `{1, 2}` or `new int[]{1, 2}`
+    Conditional(Box<Expr>, Box<Expr>, Box<Expr>), // first the condition, then the values if it is true and if it is false, e.g. `c ? a : b`
+    Switch(Box<Expr>, Vec<SwitchCase>), // first the selector, then the groups of statements of the cases, which yield the value
+    Cast(Type, Box<Expr>), // first the type the value is converted to, then the value, e.g. `(int) c`
+    InstanceOf(Box<Expr>, Type, Option<String>), // first the tested value, then the type and the name of the pattern variable, if any, e.g. `o instanceof Foo f`
+    Lambda(Vec<(Option<Type>, String)>, Box<Stmt>), // first the parameters, whose types are inferred if they are omitted, then the body, e.g. `(a, b) -> a + b`. An expression as body is returned by a `Stmt::Return`.
+    MethodRef(Box<Expr>, String), // first the object or class, then the name of the method or `new` for a constructor, e.g. `this::m` or `Foo::new`
+    FunctionRef(Box<FunctionRef>), // a typed lambda expression or method reference, i.e. an instance of the functional interface it is typed as
     TypedExpr(Box<Expr>, Type),
 }
 
+/// A group of statements in a switch, which are executed if the selector equals one of the labels.
+/// Without a `break`, the statements of the next group are executed afterwards.
+/// The cases of the arrow form, e.g. `case 1 -> x;`, are parsed into groups ending with `break` or `yield`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct SwitchCase {
+    pub labels: Vec<Expr>, // the constants of the `case` labels
+    pub is_default: bool,  // whether the statements are executed for the `default` label as well
+    pub body: Vec<Stmt>,
+}
+
+/// A catch clause of a try statement, whose body is executed if the body of the try statement throws
+/// an exception of one of its classes, which is stored in the variable `name`.
+/// A multi-catch clause like `catch (A | B e)` has several classes.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct CatchClause {
+    pub classes: Vec<String>,
+    pub name: String,
+    pub body: Stmt,
+}
+
+/// An instance of a functional interface, whose single abstract method calls the method `impl_method`
+/// of the class `class`, which is created by `invokedynamic` with `LambdaMetafactory`.
+/// The bodies of lambda expressions are lifted to private methods of their class, so lambda
+/// expressions and method references are represented the same way. The captured values are passed
+/// to the implementing method first, followed by the arguments of the abstract method.
+/// See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-6.html#jvms-6.5.invokedynamic
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct FunctionRef {
+    pub method: String, // the name of the abstract method of the functional interface
+    pub params: Vec<Type>,
+    pub ret_type: Type,
+    pub kind: MethodKind,
+    pub class: String,
+    pub impl_method: String, // `<init>` for constructors
+    pub impl_params: Vec<Type>,
+    pub impl_ret_type: Type,
+    pub captured: Vec<Expr>, // e.g. the object of a method reference like `obj::m` or the local variables used by a lambda
+}
+
+/// How the implementing method of a `FunctionRef` is called, which decides the kind of its method handle
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum MethodKind {
+    Static,
+    Virtual,
+    Private, // a private instance method of the current class, which is called via invokespecial
+    Constructor,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub enum UnaryOp {
     Pos,
     Neg,
     Not,
+    BitNot,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -91,6 +236,12 @@ pub enum BinaryOp {
     Gt,
     Eq,
     Ne,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
+    UShr, // unsigned right shift, i.e. `>>>`
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Hash, Eq)]
@@ -98,24 +249,35 @@ pub enum Type {
     Int,
     Bool,
     Char,
+    Byte,
+    Short,
+    Long,
+    Float,
+    Double,
     String,
     Void,
     Null,
     Class(String),
+    Array(Box<Type>), // type of the elements, e.g. `String` for `String[]`
 }
 
 /// All necessary methods/implementations for the type system
-
 impl Display for Type {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Type::Int => write!(f, "int"),
             Type::Bool => write!(f, "boolean"),
             Type::Char => write!(f, "char"),
+            Type::Byte => write!(f, "byte"),
+            Type::Short => write!(f, "short"),
+            Type::Long => write!(f, "long"),
+            Type::Float => write!(f, "float"),
+            Type::Double => write!(f, "double"),
             Type::String => write!(f, "String"),
             Type::Void => write!(f, "void"),
             Type::Null => write!(f, "null"),
             Type::Class(name) => write!(f, "{}", name),
+            Type::Array(elem_type) => write!(f, "{elem_type}[]"),
         }
     }
 }
@@ -129,13 +291,128 @@ impl Type {
             Type::Int => "I",
             Type::Char => "C",
             Type::Bool => "Z",
+            Type::Byte => "B",
+            Type::Short => "S",
+            Type::Long => "J",
+            Type::Float => "F",
+            Type::Double => "D",
             Type::String => "Ljava/lang/String;",
             Type::Void => "V",
-            Type::Class(name) => name,
+            Type::Class(name) => return format!("L{name};"),
+            Type::Array(elem_type) => return format!("[{}", elem_type.to_ir_string()),
             _ => panic!("Invalid type: {}", self),
         }
         .to_string()
     }
+    /// Parses the type of a field descriptor, e.g. `I` or `Ljava/lang/String;`,
+    /// returning it together with the rest of the descriptor.
+    /// Returns None if the descriptor is invalid or describes a type we don't support.
+    pub fn from_ir_string(descriptor: &str) -> Option<(Type, &str)> {
+        let mut chars = descriptor.chars();
+        let t = match chars.next()? {
+            'I' => Type::Int,
+            'C' => Type::Char,
+            'Z' => Type::Bool,
+            'B' => Type::Byte,
+            'S' => Type::Short,
+            'J' => Type::Long,
+            'F' => Type::Float,
+            'D' => Type::Double,
+            'V' => Type::Void,
+            '[' => {
+                let (elem_type, rest) = Type::from_ir_string(chars.as_str())?;
+                return Some((Type::Array(Box::new(elem_type)), rest));
+            }
+            'L' => {
+                let (name, rest) = chars.as_str().split_once(';')?;
+                let t = match name {
+                    "java/lang/String" => Type::String,
+                    name => Type::Class(name.to_string()),
+                };
+                return Some((t, rest));
+            }
+            _ => return None,
+        };
+        Some((t, chars.as_str()))
+    }
+
+    /// Parses a method descriptor, e.g. `(ILjava/lang/String;)V`,
+    /// into the types of the parameters and the return type
+    pub fn from_method_descriptor(descriptor: &str) -> Option<(Vec<Type>, Type)> {
+        let mut rest = descriptor.strip_prefix('(')?;
+        let mut params = vec![];
+        while !rest.starts_with(')') {
+            let (param, next) = Type::from_ir_string(rest)?;
+            params.push(param);
+            rest = next;
+        }
+        let (ret_type, rest) = Type::from_ir_string(&rest[1..])?;
+        rest.is_empty().then_some((params, ret_type))
+    }
+
+    /// Whether values of this type are numbers, which includes chars
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            Type::Int
+                | Type::Char
+                | Type::Byte
+                | Type::Short
+                | Type::Long
+                | Type::Float
+                | Type::Double
+        )
+    }
+
+    /// Whether values of this type are references to objects, i.e. Strings, class instances or arrays
+    pub fn is_reference(&self) -> bool {
+        matches!(self, Type::String | Type::Class(_) | Type::Array(_))
+    }
+
+    /// Whether values of this type are integers, which includes chars
+    pub fn is_integral(&self) -> bool {
+        self.is_numeric() && !matches!(self, Type::Float | Type::Double)
+    }
+
+    /// The number of slots a value of this type takes on the stack and in the local variables.
+    /// Longs and doubles take two slots, void values none.
+    pub fn size(&self) -> u16 {
+        match self {
+            Type::Long | Type::Double => 2,
+            Type::Void => 0,
+            _ => 1,
+        }
+    }
+
+    /// The type of a number after unary numeric promotion, which converts bytes, shorts and chars
+    /// to ints, e.g. for the operand of `-x`.
+    /// See https://docs.oracle.com/javase/specs/jls/se17/html/jls-5.html#jls-5.6
+    pub fn promoted(&self) -> Type {
+        match self {
+            Type::Byte | Type::Short | Type::Char => Type::Int,
+            t => t.clone(),
+        }
+    }
+
+    /// The type both operands of a binary operator like `+` are converted to by binary numeric
+    /// promotion, i.e. the wider of both types, but at least int
+    pub fn binary_promoted(a: &Type, b: &Type) -> Type {
+        [Type::Double, Type::Float, Type::Long]
+            .into_iter()
+            .find(|t| a == t || b == t)
+            .unwrap_or(Type::Int)
+    }
+
+    /// The name of the class in its internal form, as used in `CONSTANT_Class` entries
+    pub fn to_internal_name(&self) -> String {
+        match self {
+            Type::String => "java/lang/String".to_string(),
+            Type::Class(name) => name.clone(),
+            // Array classes are named by their descriptor
+            Type::Array(_) => self.to_ir_string(),
+            _ => panic!("Type {} is not a class", self),
+        }
+    }
 }
 
 impl Display for Class {
@@ -156,16 +433,91 @@ impl Display for Class {
     }
 }
 
+/// The name of the synthetic field of inner classes, which holds their enclosing instance
+pub const OUTER_INSTANCE_FIELD: &str = "this$0";
+/// The prefix of the names of the synthetic methods the bodies of lambda expressions are lifted to,
+/// which are followed by a number, e.g. `lambda$0`
+pub const LAMBDA_METHOD_PREFIX: &str = "lambda$";
+/// The prefix of the names of the synthetic methods, through which the classes of a nest access
+/// each other's private members in class files older than Java 11, e.g. `access$000`
+pub const ACCESSOR_METHOD_PREFIX: &str = "access$";
+/// The prefix of the names of the synthetic fields of local classes, which hold the values of the
+/// local variables they capture, e.g. `val$x`
+pub const CAPTURED_VAR_PREFIX: &str = "val$";
+
+impl Class {
+    /// Whether the class is an inner class, i.e. a nested class, which isn't static,
+    /// so each of its instances has an enclosing instance of its outer class
+    pub fn is_inner(&self) -> bool {
+        self.outer_class.is_some() && !self.modifiers.is_static
+    }
+
+    /// The name of the class without the names of the classes enclosing it, e.g. `Inner` for `Outer$Inner`
+    /// and `Local` for the local class `Outer$1Local`
+    pub fn simple_name(&self) -> &str {
+        let name = self.name.rsplit('$').next().unwrap();
+        name.trim_start_matches(|c: char| c.is_ascii_digit())
+    }
+
+    /// The final field of an inner class holding its enclosing instance, which is assigned by its
+    /// constructors. They are passed the enclosing instance as their first argument.
+    pub fn outer_instance_field(&self) -> Option<FieldDecl> {
+        let outer_class = self.outer_class.as_ref().filter(|_| self.is_inner())?;
+        Some(FieldDecl {
+            modifiers: Modifiers {
+                visibility: Visibility::Package,
+                is_static: false,
+                is_final: true,
+            },
+            field_type: Type::Class(outer_class.clone()),
+            name: OUTER_INSTANCE_FIELD.to_string(),
+            val: None,
+        })
+    }
+
+    /// The final fields of a local class holding the values of the local variables it captures,
+    /// which are assigned by its constructors like the one holding the enclosing instance
+    pub fn captured_var_fields(&self) -> Vec<FieldDecl> {
+        self.captured_vars
+            .iter()
+            .map(|(t, name)| FieldDecl {
+                modifiers: Modifiers {
+                    visibility: Visibility::Package,
+                    is_static: false,
+                    is_final: true,
+                },
+                field_type: t.clone(),
+                name: format!("{CAPTURED_VAR_PREFIX}{name}"),
+                val: None,
+            })
+            .collect()
+    }
+}
+
+impl MethodDecl {
+    /// Whether the method is the lifted body of a lambda expression, which is synthetic
+    pub fn is_lambda(&self) -> bool {
+        self.name.starts_with(LAMBDA_METHOD_PREFIX)
+    }
+
+    /// Whether the method is an accessor of a private member for the other classes of the nest,
+    /// which is synthetic
+    pub fn is_accessor(&self) -> bool {
+        self.name.starts_with(ACCESSOR_METHOD_PREFIX)
+    }
+}
+
 impl FieldDecl {
     /// See https://docs.oracle.com/javase/specs/jvms/se15/html/jvms-4.html#jvms-4.5
-    pub fn as_bytes(&self, class_name: &str, constant_pool: &mut ConstantPool) -> Vec<u8> {
+    /// The access flags are combined with the `extra_flags`, which aren't represented by the
+    /// modifiers, e.g. the one of synthetic fields
+    pub fn as_bytes(&self, extra_flags: u16, constant_pool: &mut ConstantPool) -> Vec<u8> {
         use crate::codegen::Constant;
         use crate::codegen::FieldRef;
         use crate::codegen::NameAndType;
 
         let mut bytes = Vec::new();
-        // No access modifier
-        bytes.extend_from_slice(&[0x0, 0x0]);
+        bytes.extend_from_slice(&(self.modifiers.access_flags() | extra_flags).to_be_bytes());
         // Name index
         bytes.extend_from_slice(
             &constant_pool
@@ -178,11 +530,53 @@ impl FieldDecl {
                 .add(Constant::Utf8(self.field_type.to_ir_string()))
                 .to_be_bytes(),
         );
+        // Static constants are initialized by the JVM with the value of their ConstantValue attribute
+        // See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.2
+        let Some(literal) = self.static_constant() else {
+            // Attributes count
+            bytes.extend_from_slice(&[0x0, 0x0]);
+            return bytes;
+        };
+        let constant = match literal {
+            Expr::Integer(i) => Constant::Integer(*i),
+            Expr::Char(c) => Constant::Integer(*c as i32),
+            Expr::Bool(b) => Constant::Integer(*b as i32),
+            Expr::Long(l) => Constant::Long(*l),
+            Expr::Float(f) => Constant::Float(*f),
+            Expr::Double(d) => Constant::Double(*d),
+            Expr::String(s) => Constant::String(s.clone()),
+            _ => unreachable!(),
+        };
         // Attributes count
-        bytes.extend_from_slice(&[0x0, 0x0]);
-        if let Some(val) = &self.val {}
+        bytes.extend_from_slice(&[0x0, 0x1]);
+        bytes.extend_from_slice(
+            &constant_pool
+                .add(Constant::Utf8("ConstantValue".to_string()))
+                .to_be_bytes(),
+        );
+        bytes.extend_from_slice(&2u32.to_be_bytes());
+        bytes.extend_from_slice(&constant_pool.add(constant).to_be_bytes());
         bytes
     }
+
+    /// The value of a static constant, i.e. a static final field, whose value was computed by the
+    /// typechecker, so it is a literal. Static constants aren't assigned by the static initializer.
+    pub fn static_constant(&self) -> Option<&Expr> {
+        let Some(Expr::TypedExpr(literal, _)) = &self.val else {
+            return None;
+        };
+        let is_literal = matches!(
+            **literal,
+            Expr::Integer(_)
+                | Expr::Char(_)
+                | Expr::Bool(_)
+                | Expr::Long(_)
+                | Expr::Float(_)
+                | Expr::Double(_)
+                | Expr::String(_)
+        );
+        (self.modifiers.is_static && self.modifiers.is_final && is_literal).then_some(literal)
+    }
 }
 
 impl Expr {
@@ -201,6 +595,7 @@ impl Display for UnaryOp {
             UnaryOp::Pos => write!(f, "+"),
             UnaryOp::Neg => write!(f, "-"),
             UnaryOp::Not => write!(f, "!"),
+            UnaryOp::BitNot => write!(f, "~"),
         }
     }
 }
@@ -211,6 +606,7 @@ impl From<&str> for UnaryOp {
             "+" => UnaryOp::Pos,
             "-" => UnaryOp::Neg,
             "!" => UnaryOp::Not,
+            "~" => UnaryOp::BitNot,
             _ => panic!("Invalid unary operator: {}", s),
         }
     }
@@ -232,6 +628,12 @@ impl Display for BinaryOp {
             BinaryOp::Gt => write!(f, ">"),
             BinaryOp::Eq => write!(f, "=="),
             BinaryOp::Ne => write!(f, "!="),
+            BinaryOp::BitAnd => write!(f, "&"),
+            BinaryOp::BitOr => write!(f, "|"),
+            BinaryOp::BitXor => write!(f, "^"),
+            BinaryOp::Shl => write!(f, "<<"),
+            BinaryOp::Shr => write!(f, ">>"),
+            BinaryOp::UShr => write!(f, ">>>"),
         }
     }
 }
@@ -252,6 +654,12 @@ impl From<&str> for BinaryOp {
             ">" => BinaryOp::Gt,
             "==" => BinaryOp::Eq,
             "!=" => BinaryOp::Ne,
+            "&" => BinaryOp::BitAnd,
+            "|" => BinaryOp::BitOr,
+            "^" => BinaryOp::BitXor,
+            "<<" => BinaryOp::Shl,
+            ">>" => BinaryOp::Shr,
+            ">>>" => BinaryOp::UShr,
             _ => panic!("Invalid binary operator: {}", s),
         }
     }
@@ -265,14 +673,20 @@ impl BinaryOp {
             "%" => 0,
             "+" => 1,
             "-" => 1,
-            "<=" => 2,
-            ">=" => 2,
-            "<" => 2,
-            ">" => 2,
-            "==" => 3,
-            "!=" => 3,
-            "&&" => 4,
-            "||" => 4,
+            "<<" => 2,
+            ">>" => 2,
+            ">>>" => 2,
+            "<=" => 3,
+            ">=" => 3,
+            "<" => 3,
+            ">" => 3,
+            "==" => 4,
+            "!=" => 4,
+            "&" => 5,
+            "^" => 6,
+            "|" => 7,
+            "&&" => 8,
+            "||" => 9,
             _ => panic!("Invalid binary operator: {}", op),
         }
     }
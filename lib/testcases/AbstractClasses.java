@@ -0,0 +1,74 @@
+abstract class Animal {
+	int legs;
+
+	Animal(int legs) {
+		this.legs = legs;
+	}
+
+	abstract String sound();
+
+	abstract int speed(int time);
+
+	String describe() {
+		return sound();
+	}
+
+	int distance(int time) {
+		return speed(time) * legs;
+	}
+}
+
+class Dog extends Animal {
+	Dog() {
+		super(4);
+	}
+
+	String sound() {
+		return "woof";
+	}
+
+	int speed(int time) {
+		return time * 3;
+	}
+}
+
+abstract class Bird extends Animal {
+	Bird() {
+		super(2);
+	}
+
+	int speed(int time) {
+		return time * 5 + wings();
+	}
+
+	abstract int wings();
+}
+
+class Duck extends Bird {
+	String sound() {
+		return "quack";
+	}
+
+	int wings() {
+		return 2;
+	}
+}
+
+class Zoo {
+	int race(int time, boolean useDog) {
+		Animal a;
+		if (useDog) {
+			a = new Dog();
+		} else {
+			a = new Duck();
+		}
+		Bird b = new Duck();
+		return a.distance(time) + b.speed(time);
+	}
+
+	String sounds() {
+		Animal a = new Dog();
+		Animal b = new Duck();
+		return b.describe();
+	}
+}
@@ -0,0 +1,111 @@
+class Arrays {
+    int[] primes = {2, 3, 5, 7, 11,};
+    static int[] counts = new int[3];
+    char[] letters;
+    String[] words;
+    int value;
+
+    Arrays() {
+        letters = new char[]{'a', 'b', 'c'};
+        words = new String[2];
+        words[0] = "hello";
+        this.words[1] = "world";
+        value = 7;
+    }
+
+    int sumOfPrimes() {
+        int sum = 0;
+        int i = 0;
+        while (i < primes.length) {
+            sum = sum + primes[i];
+            i = i + 1;
+        }
+        return sum;
+    }
+
+    int square(int n) {
+        int[] squares = new int[n + 1];
+        int i = 0;
+        while (i < squares.length) {
+            squares[i] = i * i;
+            i = i + 1;
+        }
+        return squares[n];
+    }
+
+    char letter(int i) {
+        return letters[Math.floorMod(i, letters.length)];
+    }
+
+    String word(int i) {
+        String w = words[Math.floorMod(i, 2)];
+        if (w.length() < 5) {
+            return "";
+        }
+        return w;
+    }
+
+    int replacePrime(int p) {
+        int old = this.primes[0];
+        int replaced = primes[0] = p;
+        return old + replaced * primes[0];
+    }
+
+    int grid(int x) {
+        int[][] grid = new int[3][4];
+        grid[1][2] = x;
+        return grid[1][2] + grid.length * grid[0].length;
+    }
+
+    int jagged() {
+        int[][] rows = new int[3][];
+        rows[0] = new int[1];
+        rows[2] = new int[]{4, 5, 6};
+        int[][] matrix = {{1, 2}, {3}};
+        return rows[2][1] + rows[0].length * 10 + matrix[1][0] * 100 + matrix[0].length * 1000;
+    }
+
+    boolean flag(boolean b) {
+        boolean[] flags = new boolean[2];
+        flags[1] = b;
+        return flags[1] && !flags[0];
+    }
+
+    int choose(boolean b) {
+        int[] chosen;
+        if (b) {
+            chosen = new int[1];
+        } else {
+            chosen = new int[]{4, 5};
+        }
+        return chosen.length;
+    }
+
+    int children(int v) {
+        Arrays[] children = new Arrays[2];
+        children[1] = new Arrays();
+        children[1].value = v;
+        Arrays[][] nested = {children, {this}};
+        return children[1].value + nested[0][1].sumOfPrimes() + nested[1][0].value;
+    }
+
+    int sumAll(int[] xs) {
+        int sum = 0;
+        int i = 0;
+        while (i < xs.length) {
+            sum = sum + xs[i];
+            i = i + 1;
+        }
+        return sum;
+    }
+
+    int sumOf(int a, int b) {
+        return sumAll(new int[]{a, b, a});
+    }
+
+    static int count(int i) {
+        int idx = Math.floorMod(i, counts.length);
+        counts[idx] = counts[idx] + 1;
+        return counts[idx];
+    }
+}
@@ -0,0 +1,81 @@
+class Bitwise {
+    int and(int a, int b) {
+        return a & b;
+    }
+
+    int or(int a, int b) {
+        return a | b;
+    }
+
+    int xor(int a, int b) {
+        return a ^ b;
+    }
+
+    int not(int n) {
+        return ~n;
+    }
+
+    int shifts(int n) {
+        return (n << 3) + (-n >> 1) + (-n >>> 28) + (n >> 1);
+    }
+
+    int precedence(int a, int b) {
+        return a | b & 6 ^ a << 2 + 1;
+    }
+
+    int leftAssociative(int n) {
+        return n << 2 >> 1 ^ 5 ^ 3;
+    }
+
+    boolean comparisons(int a, int b) {
+        return a < b == b > 10 & a != 3 | a >= b && b <= 20 || a == 0;
+    }
+
+    boolean logical(boolean a, boolean b) {
+        return a & !b | a ^ b & true;
+    }
+
+    int evaluatesBoth(int n) {
+        int count = 0;
+        if (n > 5 | ++count > 0) {
+            count += 10;
+        }
+        if (n < 5 & count++ > 100) {
+            count += 100;
+        }
+        return count;
+    }
+
+    int chars(char c) {
+        return c & 31 | ~c << 8;
+    }
+
+    int bits(int n) {
+        int count = 0;
+        while (n != 0) {
+            count += n & 1;
+            n >>>= 1;
+        }
+        return count;
+    }
+
+    int compound(int n) {
+        int x = n;
+        x &= 12;
+        x |= n << 4;
+        x ^= 255;
+        x <<= 2;
+        x >>= 1;
+        int y = -n;
+        y >>>= 3;
+        return x + y;
+    }
+
+    boolean logicalCompound(boolean a, boolean b) {
+        boolean x = a;
+        x |= b;
+        x &= a ^ b;
+        x ^= true;
+        return x;
+    }
+}
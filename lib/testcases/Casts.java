@@ -0,0 +1,151 @@
+interface Wheeled {
+    int wheels();
+}
+
+class Vehicle {
+    int speed = 10;
+
+    int describe() {
+        return speed;
+    }
+}
+
+class Car extends Vehicle implements Wheeled {
+    int doors = 4;
+
+    public int wheels() {
+        return 4;
+    }
+
+    int describe() {
+        return speed * 2 + doors;
+    }
+}
+
+class Bike extends Vehicle implements Wheeled {
+    public int wheels() {
+        return 2;
+    }
+}
+
+class Casts {
+    int toInt(double d, long l, char c) {
+        return (int) d + (int) l + (int) c + (int) -d;
+    }
+
+    char toChar(int i) {
+        return (char) (i + 65);
+    }
+
+    long toLong(float f, int i) {
+        return (long) f + (long) i * 1000000000;
+    }
+
+    double toDouble(int i) {
+        return (double) i / 4 + (float) i / 3;
+    }
+
+    int narrowing(int i) {
+        byte b = (byte) (i * 20);
+        short s = (short) (i * 5000);
+        char c = (char) -i;
+        return b + s + c + (byte) 200 + (short) 70000 + (char) 65601;
+    }
+
+    int truncation(double d) {
+        return (int) (d * 1e10) + (int) (0.0 / 0.0) + (int) -2.9 + (byte) 3.99;
+    }
+
+    int parameters(byte b, short s) {
+        return b * s;
+    }
+
+    int downcast(Vehicle v) {
+        Vehicle car = new Car();
+        Car c = (Car) car;
+        Object o = c;
+        Vehicle ov = (Vehicle) o;
+        Car oc = (Car) o;
+        return c.doors + ov.speed + oc.describe() + v.describe();
+    }
+
+    int interfaces(Vehicle v) {
+        Vehicle bike = new Bike();
+        Wheeled w = (Wheeled) bike;
+        Object o = w;
+        Bike ob = (Bike) o;
+        Vehicle wv = (Vehicle) w;
+        return w.wheels() + ob.wheels() + wv.speed;
+    }
+
+    int arrays(int n) {
+        Object o = new int[n];
+        int[] a = (int[]) o;
+        Object objects = new Car[]{new Car()};
+        Vehicle[] vehicles = (Vehicle[]) objects;
+        return a.length + vehicles[0].describe();
+    }
+
+    int instanceOf(Vehicle v) {
+        Vehicle car = new Car();
+        Vehicle bike = new Bike();
+        int result = 0;
+        if (car instanceof Car) {
+            result += 1;
+        }
+        if (bike instanceof Car) {
+            result += 10;
+        }
+        if (bike instanceof Wheeled) {
+            result += 100;
+        }
+        if (v instanceof Vehicle) {
+            result += 1000;
+        }
+        Vehicle nothing = null;
+        if (nothing instanceof Vehicle) {
+            result += 10000;
+        }
+        return result;
+    }
+
+    int patterns(int n) {
+        Vehicle v = new Car();
+        if (n > 2) {
+            v = new Bike();
+        }
+        int result = 0;
+        if (v instanceof Car c) {
+            result = c.doors;
+        } else if (v instanceof Bike b) {
+            result = b.wheels() * 100;
+        }
+        if (v instanceof Car c && c.doors > 3) {
+            result += c.describe();
+        }
+        if (!(v instanceof Wheeled w)) {
+            return -1;
+        } else {
+            result += w.wheels();
+        }
+        return result;
+    }
+
+    int afterLoops(int n) {
+        Vehicle v = null;
+        int steps = 0;
+        while (!(v instanceof Wheeled w)) {
+            v = steps++ < n % 4 ? new Vehicle() : new Bike();
+        }
+        for (Vehicle u = new Car(); !(u instanceof Car c); ) {}
+        return steps * 100 + w.wheels() * 10 + c.doors;
+    }
+
+    boolean precedence(Vehicle v) {
+        return v instanceof Car == true && !(v instanceof Bike);
+    }
+
+    int parenthesized(int a, int b) {
+        return (a) - b + (a) + -(b) * (int) +a;
+    }
+}
@@ -0,0 +1,26 @@
+public class ClasspathHelper {
+	public int base;
+	public static int created = 0;
+	private int hidden;
+
+	public ClasspathHelper(int base) {
+		this.base = base;
+		created++;
+	}
+
+	public int add(int a) {
+		return base + a;
+	}
+
+	public static String greet(String name) {
+		return "Hello " + name;
+	}
+
+	public long big() {
+		return 1L << 40;
+	}
+}
+
+interface ClasspathShape {
+	int area();
+}
@@ -0,0 +1,34 @@
+public class ClasspathUser implements ClasspathShape {
+	int side;
+
+	ClasspathUser(int side) {
+		this.side = side;
+	}
+
+	public int area() {
+		return side * side;
+	}
+
+	public static void main(String[] args) {
+		ClasspathHelper helper = new ClasspathHelper(40);
+		System.out.println(helper.add(2));
+		System.out.println(ClasspathHelper.greet("RustyJ"));
+		ClasspathShape shape = new ClasspathUser(3);
+		System.out.println(shape.area());
+		ClasspathChild child = new ClasspathChild();
+		System.out.println(child.twice());
+		System.out.println(ClasspathHelper.created);
+		helper.base = 7;
+		System.out.println(helper.add(1));
+	}
+}
+
+class ClasspathChild extends ClasspathHelper {
+	ClasspathChild() {
+		super(5);
+	}
+
+	int twice() {
+		return add(base);
+	}
+}
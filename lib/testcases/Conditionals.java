@@ -0,0 +1,80 @@
+class Conditionals {
+    int max(int a, int b) {
+        return a > b ? a : b;
+    }
+
+    int sign(int n) {
+        return n > 10 ? 1 : n < 10 ? -1 : 0;
+    }
+
+    int lowestPrecedence(int n) {
+        int x = n > 5 && n < 20 ? n + 100 : n * 2 - 1;
+        return x;
+    }
+
+    boolean nested(int n) {
+        return (n % 2 == 0 ? n > 10 : n < 10) ? n % 3 == 0 : !(n % 3 == 0);
+    }
+
+    String parity(int n) {
+        return n == 0 ? "zero" : n % 2 == 0 ? "even" : "odd";
+    }
+
+    char letter(int n) {
+        return n < 10 ? 'a' : 98;
+    }
+
+    int code(char c) {
+        return c == 'x' ? c : 0;
+    }
+
+    String orNull(int n) {
+        String s = n % 3 == 0 ? null : "value";
+        return s;
+    }
+
+    int pet(int n) {
+        Pet a = n % 2 == 0 ? new Puppy() : new Kitten();
+        Pet b = n > 10 ? new Puppy() : null;
+        return a.legs() * 100 + (n > 10 ? b.legs() : -1);
+    }
+
+    int inArguments(int n) {
+        return max(n > 15 ? n : -n, n % 2 == 0 ? 7 : 8);
+    }
+
+    int inLoop(int n) {
+        int sum = 0;
+        for (int i = 0; i < n; i++) {
+            sum += i % 3 == 0 ? i : 1;
+        }
+        return sum;
+    }
+
+    int[] values(int n) {
+        return n > 3 ? new int[n] : null;
+    }
+
+    int length(int n) {
+        int[] a = values(n);
+        return n > 3 ? a.length : 0;
+    }
+}
+
+class Pet {
+    int legs() {
+        return 0;
+    }
+}
+
+class Puppy extends Pet {
+    int legs() {
+        return 4;
+    }
+}
+
+class Kitten extends Pet {
+    int legs() {
+        return 3;
+    }
+}
@@ -0,0 +1,121 @@
+class Limits {
+    static final int MAX = Constants.BASE * 4;
+    static final long BIG = (long) MAX << 33;
+    static final String NAME = "limits" + MAX;
+}
+
+class Constants {
+    static final int BASE = 25;
+    static final char FIRST = 'a' + 2;
+    static final byte SMALL = (byte) (BASE * 11);
+    static final short MEDIUM = BASE * 100;
+    static final double RATIO = BASE / 2.0;
+    static final float HALF = 1 / 2f;
+    static final boolean DEBUG = BASE > 10 && !false;
+    static final String GREETING = "Hello " + BASE + FIRST + DEBUG + 'x';
+    static final String MIXED = "ratio " + RATIO;
+    static final String FLOATING = "a" + 1 + 1.5f;
+    static final String SCIENTIFIC = 1e7 + " " + 1.0E-4f + " " + 123456.789 + " " + -0.001 + " " + 1 / 3.0;
+    static final int SHIFTED = -1 >>> 28 | BASE << 2 ^ ~3;
+    static final long LONG = 1L << 40 | BASE % 7;
+    static final int CHOSEN = DEBUG ? BASE : -BASE;
+    static final int[] TABLE;
+    static final int COMPUTED;
+    static int counter = BASE;
+    static String log = "";
+    final int instanceConstant = BASE + 1;
+
+    static {
+        TABLE = new int[4];
+        for (int i = 0; i < TABLE.length; i++) {
+            TABLE[i] = i * BASE;
+        }
+        log = log + "first ";
+    }
+
+    static int later = counter * 2;
+
+    static {
+        int x;
+        try {
+            x = later / (counter - BASE);
+        } catch (ArithmeticException e) {
+            x = -1;
+        }
+        COMPUTED = x + later;
+        log = log + "second";
+    }
+
+    String constants() {
+        return BASE + " " + FIRST + " " + SMALL + " " + MEDIUM + " " + RATIO + " " + HALF + " " + DEBUG;
+    }
+
+    String strings() {
+        return GREETING + ", " + MIXED + ", " + Limits.NAME + ", " + SCIENTIFIC;
+    }
+
+    long bits() {
+        return SHIFTED + LONG + Limits.BIG + CHOSEN;
+    }
+
+    String initializers() {
+        return log + " " + COMPUTED + " " + later + " " + TABLE[3] + " " + instanceConstant;
+    }
+
+    String label(int x) {
+        switch (x % 4) {
+            case BASE - 25:
+                return "zero";
+            case Limits.MAX / 100:
+                return "one";
+            case FIRST - 'a':
+                return "two";
+            default:
+                return "other";
+        }
+    }
+
+    String floating(int x) {
+        String s = "a" + x + 1.5f;
+        switch (s) {
+            case FLOATING:
+                return "folded " + (FLOATING == "a11.5");
+            default:
+                return s;
+        }
+    }
+
+    String kind(int x) {
+        char c = (char) ('a' + x % 4);
+        return switch (c) {
+            case FIRST -> "first";
+            case FIRST + 1 -> "second";
+            case 'a' -> "a";
+            default -> "?";
+        };
+    }
+
+    String named(String s) {
+        switch (s) {
+            case "a" + "":
+                return "a";
+            case "te" + 's' + 't':
+                return "test";
+            default:
+                return s;
+        }
+    }
+
+    int narrowed(int x) {
+        byte b = BASE + 2;
+        char c = FIRST + 1;
+        short s = (short) (MEDIUM * 2);
+        b += x;
+        return b + c + s;
+    }
+
+    int count(int x) {
+        counter += x;
+        return counter;
+    }
+}
@@ -0,0 +1,59 @@
+class Counter {
+	int count = 10;
+	int step;
+	String name = "counter";
+
+	Counter(int start, int step) {
+		count = count + start;
+		this.step = step;
+	}
+
+	Counter(int start) {
+		this(start, 1);
+		name = "simple";
+	}
+
+	int next() {
+		count = count + step;
+		return count;
+	}
+
+	String getName() {
+		return name;
+	}
+}
+
+class Range {
+	Counter counter;
+	int end;
+	boolean open;
+
+	Range(int start, int end, boolean open) {
+		counter = new Counter(start, 2);
+		this.open = open;
+		if (open) {
+			this.end = end + 1;
+		} else {
+			this.end = end - 1;
+		}
+	}
+
+	int countSteps(int a) {
+		Counter c = new Counter(a);
+		int steps = 0;
+		while (c.next() < end + a) {
+			steps = steps + 1;
+		}
+		return steps + counter.next();
+	}
+
+	String name() {
+		Counter c = new Counter(end);
+		return c.getName();
+	}
+
+	int copyEnd() {
+		Range r = new Range(end, end, !open);
+		return r.end;
+	}
+}
@@ -0,0 +1,42 @@
+class Point {
+	int x;
+	int y;
+	Point next;
+
+	int sum() {
+		return x + y;
+	}
+
+	void set(int a, int b) {
+		x = a;
+		this.y = b;
+	}
+
+	Point getNext() {
+		return next;
+	}
+}
+
+class PointUser {
+	Point p;
+
+	int usePoints(int a, int b) {
+		p = new Point();
+		p.set(a, b);
+		p.next = new Point();
+		p.next.set(b, a);
+		p.next.next = null;
+		p.next.x = p.next.x + this.p.y;
+		Point other = p.getNext();
+		other.y = 7;
+		return p.sum() + p.next.x * other.sum();
+	}
+
+	boolean hasNext(int a) {
+		p = new Point();
+		p.x = a;
+		p.next = p;
+		Point n = p.next.getNext();
+		return n.x == a;
+	}
+}
@@ -0,0 +1,117 @@
+interface Described {
+    String describe();
+}
+
+enum Color {
+    RED, GREEN, BLUE
+}
+
+enum Planet implements Described {
+    MERCURY(3.303e+23, 2.4397e6),
+    EARTH(5.976e+24, 6.37814e6),
+    JUPITER(1.9e+27, 7.1492e7, "giant"),;
+
+    static final double G = 6.673e-11;
+    static int lookups;
+
+    private final double mass;
+    private final double radius;
+    String kind = "rocky";
+
+    Planet(double mass, double radius) {
+        this.mass = mass;
+        this.radius = radius;
+    }
+
+    Planet(double mass, double radius, String kind) {
+        this(mass, radius);
+        this.kind = kind;
+    }
+
+    double surfaceGravity() {
+        lookups++;
+        return G * mass / (radius * radius);
+    }
+
+    public String describe() {
+        return name() + " is " + kind;
+    }
+}
+
+enum Direction {
+    NORTH("N"), EAST("E"), SOUTH("S"), WEST("W");
+
+    private String symbol;
+
+    private Direction(String symbol) {
+        this.symbol = symbol;
+    }
+
+    Direction turnRight() {
+        Direction[] all = values();
+        return all[(ordinal() + 1) % all.length];
+    }
+
+    Direction opposite() {
+        return turnRight().turnRight();
+    }
+
+    String symbol() {
+        return symbol;
+    }
+}
+
+class Enums {
+    Color favorite = Color.GREEN;
+
+    String colors() {
+        String s = "";
+        for (Color c : Color.values()) {
+            s = s + c + c.ordinal() + c.name().length() + ",";
+        }
+        return s + Color.values().length;
+    }
+
+    boolean isFavorite(int x) {
+        Color c = Color.values()[x % 3];
+        return c == favorite && c != Color.RED;
+    }
+
+    String lookup(int x) {
+        Color c = Color.valueOf(x % 2 == 0 ? "RED" : "BLUE");
+        return c.toString() + (c == Color.RED) + c.compareTo(Color.GREEN);
+    }
+
+    String invalidName() {
+        try {
+            Color.valueOf("PURPLE");
+            return "found";
+        } catch (IllegalArgumentException e) {
+            return "not found";
+        }
+    }
+
+    String planets(int x) {
+        Planet p = Planet.values()[x % 3];
+        Described d = p;
+        return d.describe() + " " + (int) p.surfaceGravity() + " " + Planet.lookups;
+    }
+
+    String directions(int x) {
+        Direction d = Direction.values()[x % 4];
+        Direction opposite = d.opposite();
+        return d.symbol() + d.turnRight().symbol() + opposite + (opposite.opposite() == d);
+    }
+
+    int equality(int x) {
+        Direction d = x > 10 ? Direction.NORTH : Direction.SOUTH;
+        Object o = d;
+        if (d.equals(Direction.NORTH)) {
+            return 1;
+        }
+        if (o == Direction.SOUTH) {
+            return 2;
+        }
+        return d.hashCode() == d.hashCode() ? 3 : 4;
+    }
+}
@@ -0,0 +1,255 @@
+class InvalidValueException extends Exception {
+    int value;
+
+    InvalidValueException(String message, int value) {
+        super(message);
+        this.value = value;
+    }
+}
+
+class Exceptions {
+    int cleanups = 0;
+
+    Exceptions() {
+    }
+
+    Exceptions(int x) throws InvalidValueException {
+        cleanups = check(x);
+    }
+
+    private int check(int x) throws InvalidValueException {
+        if (x > 15) {
+            throw new InvalidValueException("too big", x);
+        }
+        return x;
+    }
+
+    String checked(int x) {
+        try {
+            return "ok " + check(x);
+        } catch (InvalidValueException e) {
+            return e.getMessage() + " " + e.value;
+        }
+    }
+
+    int divide(int a, int b) {
+        try {
+            return a / b;
+        } catch (ArithmeticException e) {
+            return -1;
+        }
+    }
+
+    String multiCatch(int x) {
+        int[] values = new int[20];
+        try {
+            if (x > 25) {
+                throw new IllegalStateException("state");
+            }
+            return "value " + values[x] / (x % 4 - 1);
+        } catch (ArithmeticException | ArrayIndexOutOfBoundsException e) {
+            return "caught " + (e instanceof ArithmeticException);
+        } catch (RuntimeException e) {
+            return e.getMessage();
+        }
+    }
+
+    int finallyAfterReturn(int x) {
+        try {
+            return x;
+        } finally {
+            cleanups++;
+        }
+    }
+
+    int getCleanups() {
+        return cleanups;
+    }
+
+    int finallyOverridesReturn(int x) {
+        try {
+            if (x > 10) {
+                return x;
+            }
+        } finally {
+            if (x > 20) {
+                return -x;
+            }
+        }
+        return 0;
+    }
+
+    long finallyKeepsValue(long l) {
+        long result = l;
+        try {
+            return result;
+        } finally {
+            result = 0;
+        }
+    }
+
+    int loopWithFinally(int n) {
+        int sum = 0;
+        for (int i = 0; i < n; i++) {
+            try {
+                if (i % 3 == 0) {
+                    continue;
+                }
+                if (i > 12) {
+                    break;
+                }
+                sum += i;
+            } finally {
+                sum += 100;
+            }
+        }
+        return sum;
+    }
+
+    String labeled(int x) {
+        String s = "";
+        outer:
+        for (int i = 0; i < 4; i++) {
+            for (int j = 0; j < 4; j++) {
+                try {
+                    try {
+                        if (j == x % 5) {
+                            continue outer;
+                        }
+                        if (i + j > 4) {
+                            break outer;
+                        }
+                        s = s + i + j;
+                    } finally {
+                        s = s + ",";
+                    }
+                } finally {
+                    s = s + ";";
+                }
+            }
+        }
+        return s;
+    }
+
+    String nested(int x) {
+        String s = "";
+        try {
+            try {
+                if (x % 2 == 0) {
+                    throw new IllegalArgumentException("inner");
+                }
+                s = s + "a";
+            } finally {
+                s = s + "b";
+            }
+            if (x > 15) {
+                throw new UnsupportedOperationException("outer");
+            }
+        } catch (IllegalArgumentException e) {
+            s = s + e.getMessage();
+        } catch (RuntimeException e) {
+            s = s + "runtime " + e.getMessage();
+        } finally {
+            s = s + "c";
+        }
+        return s;
+    }
+
+    String rethrow(int x) {
+        try {
+            try {
+                check(x);
+                return "fine";
+            } catch (InvalidValueException e) {
+                throw new IllegalStateException("wrapped " + e.getMessage());
+            }
+        } catch (RuntimeException e) {
+            return e.getMessage();
+        }
+    }
+
+    String nullPointer(int x) {
+        String s = x > 10 ? null : "abc";
+        try {
+            return "length " + s.length();
+        } catch (NullPointerException e) {
+            return "null";
+        }
+    }
+
+    int locals(int x) {
+        long big = 1;
+        try {
+            double d = x;
+            big = (long) d * 2;
+            if (x > 10) {
+                throw new IllegalStateException();
+            }
+        } catch (IllegalStateException e) {
+            big = -big;
+        }
+        return (int) big;
+    }
+
+    void withoutCatch(int x) {
+        try {
+            if (x > 10) {
+                return;
+            }
+            cleanups += x;
+        } finally {
+            cleanups++;
+        }
+    }
+
+    int earlyReturns(int x) {
+        int before = cleanups;
+        withoutCatch(x);
+        withoutCatch(x + 20);
+        return cleanups - before;
+    }
+
+    String switchInTry(int x) {
+        try {
+            switch (x % 4) {
+                case 1:
+                    throw new IllegalStateException("one");
+                case 2:
+                    return "two";
+                default:
+                    break;
+            }
+            return "other";
+        } catch (IllegalStateException e) {
+            return e.getMessage();
+        } finally {
+            cleanups += 10;
+        }
+    }
+
+    int constructorThrows(int x) {
+        try {
+            Exceptions created = new Exceptions(x);
+            return created.cleanups;
+        } catch (InvalidValueException e) {
+            return -e.value;
+        }
+    }
+
+    int declared(int x) throws Exception {
+        if (x < 0) {
+            throw new Exception("negative");
+        }
+        return check(x % 10);
+    }
+
+    int emptyTry(int x) {
+        try {
+        } catch (RuntimeException e) {
+            x = 0;
+        } finally {
+            x++;
+        }
+        return x;
+    }
+}
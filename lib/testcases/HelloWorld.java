@@ -0,0 +1,35 @@
+public class HelloWorld {
+	static int square(int a) {
+		return a * a;
+	}
+
+	public static void main(String[] args) {
+		System.out.println("Hello World!");
+		System.out.println(square(7));
+		System.out.println(square(3) == 9);
+		System.out.println('x');
+		String greeting = "Hi";
+		Greeter greeter = new Greeter(greeting, 3);
+		greeter.greet();
+		System.out.println();
+		System.out.println(greeter.times > 2);
+	}
+}
+
+class Greeter {
+	String greeting;
+	int times;
+
+	Greeter(String greeting, int times) {
+		this.greeting = greeting;
+		this.times = times;
+	}
+
+	void greet() {
+		int i = 0;
+		while (i < times) {
+			System.out.println(greeting);
+			i = i + 1;
+		}
+	}
+}
@@ -0,0 +1,118 @@
+class IncDec {
+    static int calls;
+    int count;
+    char letter = 'a';
+    int[] values = {1, 2, 3};
+    IncDec next;
+
+    int postfix(int n) {
+        int old = n++;
+        return old * 100 + n;
+    }
+
+    int prefix(int n) {
+        int result = ++n * 100;
+        return result + --n;
+    }
+
+    int loop(int n) {
+        int sum = 0;
+        for (int i = 0; i < n; i++) {
+            sum += i;
+        }
+        int j = n;
+        while (j > 0) {
+            sum -= 2;
+            j--;
+        }
+        return sum;
+    }
+
+    int compound(int n) {
+        int x = n + 3;
+        x *= 7;
+        x /= 2;
+        x %= 100;
+        x += 1000;
+        x -= n;
+        return x;
+    }
+
+    int largeIncrement(int n) {
+        n += 200;
+        n -= -128;
+        n += 127;
+        return n;
+    }
+
+    int assignedValues(int n) {
+        int x = n;
+        int y = (x += 5) * 2;
+        int z = x-- + --x;
+        return x + y * 10 + z * 1000;
+    }
+
+    int fields(int n) {
+        count = n;
+        count++;
+        ++count;
+        this.count += 10;
+        int old = this.count--;
+        int updated = count *= 2;
+        return old * 1000 + updated + count;
+    }
+
+    int staticFields(int n) {
+        calls = n;
+        calls++;
+        int old = IncDec.calls++;
+        IncDec.calls *= 3;
+        return old * 100 + calls;
+    }
+
+    int otherObject(int n) {
+        next = new IncDec();
+        next.count = n;
+        next.count++;
+        int value = ++next.count;
+        next.next = next;
+        next.next.count -= 3;
+        return value * 100 + next.count;
+    }
+
+    int arrays(int n) {
+        int[] a = new int[3];
+        int i = 0;
+        a[i++] = n;
+        a[i] += a[0]++;
+        a[++i] = a[1]--;
+        values[n % 3] *= 4;
+        int x = values[1]++ + ++values[2];
+        return a[0] * 10000 + a[1] * 100 + a[2] + x + values[0] + values[1] + values[2];
+    }
+
+    char chars(char c) {
+        char d = c;
+        d++;
+        d += 2;
+        letter = c;
+        letter--;
+        return d;
+    }
+
+    char letter(char c) {
+        letter = c;
+        char old = letter++;
+        letter += old;
+        return letter;
+    }
+
+    char charValues(char c) {
+        char d = c;
+        char old = d++;
+        if (old == c) {
+            return ++d;
+        }
+        return old;
+    }
+}
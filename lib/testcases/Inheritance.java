@@ -0,0 +1,98 @@
+class Shape {
+	int x;
+	int y;
+	String kind = "shape";
+
+	Shape(int x, int y) {
+		this.x = x;
+		this.y = y;
+	}
+
+	Shape() {
+		this(1, 2);
+	}
+
+	int area() {
+		return 0;
+	}
+
+	String describe() {
+		return kind;
+	}
+
+	int offset(int d) {
+		return x + y + d;
+	}
+}
+
+class Rect extends Shape {
+	int w;
+	int h;
+
+	Rect(int w, int h) {
+		super(w, h);
+		this.w = w;
+		this.h = h;
+		kind = "rect";
+	}
+
+	Rect() {
+		w = 3;
+		h = 4;
+	}
+
+	int area() {
+		return w * h;
+	}
+
+	int offset(int d) {
+		return super.offset(d) * 2 + super.x;
+	}
+}
+
+class Square extends Rect {
+	Square(int s) {
+		super(s, s);
+	}
+
+	Square() {
+		this(5);
+	}
+
+	String describe() {
+		return "square";
+	}
+
+	String superDescribe() {
+		return super.describe();
+	}
+}
+
+class ShapeUser {
+	int totalArea(int a, int b) {
+		Shape s = new Shape();
+		int total = s.area();
+		s = new Rect(a, b);
+		total = total + s.area();
+		s = new Square(a);
+		total = total + s.area();
+		Rect r = new Square(b);
+		return total + r.area() + r.offset(a);
+	}
+
+	String describeAll(boolean useRect) {
+		Shape s;
+		if (useRect) {
+			s = new Rect();
+		} else {
+			s = new Square();
+		}
+		return s.describe();
+	}
+
+	int inheritedField(int a) {
+		Square sq = new Square(a);
+		sq.x = sq.x + sq.w;
+		return sq.x + sq.offset(1);
+	}
+}
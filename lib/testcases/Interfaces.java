@@ -0,0 +1,76 @@
+interface Named {
+	String name();
+}
+
+interface Measurable {
+	int size();
+}
+
+interface Item extends Named, Measurable {
+	boolean heavy(int limit);
+}
+
+abstract class Container implements Item {
+	int weight;
+
+	Container(int weight) {
+		this.weight = weight;
+	}
+
+	public boolean heavy(int limit) {
+		return size() * weight > limit;
+	}
+}
+
+class Box extends Container {
+	Box(int weight) {
+		super(weight);
+	}
+
+	public String name() {
+		return "box";
+	}
+
+	public int size() {
+		return 3;
+	}
+}
+
+class Bag extends Container implements Named {
+	Bag() {
+		super(2);
+	}
+
+	public String name() {
+		return "bag";
+	}
+
+	public int size() {
+		return weight + 1;
+	}
+}
+
+class Inventory {
+	int total(int a) {
+		Item i = new Box(a);
+		Measurable m = i;
+		Item bag = new Bag();
+		return m.size() * a + bag.size();
+	}
+
+	String describe(boolean useBox) {
+		Item i;
+		if (useBox) {
+			i = new Box(1);
+		} else {
+			i = new Bag();
+		}
+		Named n = i;
+		return n.name();
+	}
+
+	boolean heavy(int a, int limit) {
+		Item i = new Box(a);
+		return i.heavy(limit);
+	}
+}
@@ -0,0 +1,110 @@
+class Jumps {
+    int[] values = {3, 1, 4, 1, 5, 9, 2, 6};
+
+    int firstAbove(int n) {
+        int i = 0;
+        while (i < values.length) {
+            if (values[i] > n) {
+                break;
+            }
+            i = i + 1;
+        }
+        return i;
+    }
+
+    int sumOfOdd(int n) {
+        int sum = 0;
+        for (int i = 0; i < n; i = i + 1) {
+            if (i % 2 == 0) {
+                continue;
+            }
+            sum = sum + i;
+        }
+        return sum;
+    }
+
+    int countUntilZero(int n) {
+        int count = 0;
+        for (;;) {
+            int half = n / 2;
+            if (n == 0) {
+                break;
+            }
+            n = half;
+            count = count + 1;
+        }
+        return count;
+    }
+
+    int skipDigits(int n) {
+        int count = 0;
+        do {
+            n = n - 1;
+            if (n % 3 == 0) {
+                continue;
+            }
+            count = count + 1;
+        } while (n > 0);
+        return count;
+    }
+
+    int findPair(int sum) {
+        int found = -1;
+        outer:
+        for (int i = 0; i < values.length; i = i + 1) {
+            for (int j = i + 1; j < values.length; j = j + 1) {
+                if (values[i] + values[j] == sum) {
+                    found = i * 10 + j;
+                    break outer;
+                }
+            }
+        }
+        return found;
+    }
+
+    int countRows(int n) {
+        int count = 0;
+        rows:
+        for (int i = 0; i < n; i = i + 1) {
+            int j = 0;
+            while (true) {
+                if (j == i) {
+                    count = count + 1;
+                    continue rows;
+                }
+                if (j > 2) {
+                    continue rows;
+                }
+                j = j + 1;
+            }
+        }
+        return count;
+    }
+
+    int sumUntil(int limit) {
+        int sum = 0;
+        for (int value : values) {
+            if (value == 1) {
+                continue;
+            }
+            if (sum + value > limit) {
+                break;
+            }
+            sum = sum + value;
+        }
+        return sum;
+    }
+
+    int labeledBlock(int n) {
+        int result = 0;
+        block:
+        {
+            if (n > 10) {
+                result = 1;
+                break block;
+            }
+            result = 2;
+        }
+        return result;
+    }
+}
@@ -0,0 +1,128 @@
+interface IntOp {
+    int apply(int a, int b);
+}
+
+interface Transformer {
+    String transform(String s);
+}
+
+interface PositionFactory {
+    Position create(int x, int y);
+}
+
+interface PositionFunction {
+    int of(Position p);
+}
+
+interface Check {
+    boolean test(int value) throws Exception;
+}
+
+class Position {
+    int x;
+    int y;
+
+    Position(int x, int y) {
+        this.x = x;
+        this.y = y;
+    }
+
+    int sum() {
+        return x + y;
+    }
+
+    int scale(int a, int b) {
+        return (x + a) * (y + b);
+    }
+
+    static int difference(int a, int b) {
+        return a - b;
+    }
+}
+
+class Lambdas {
+    private int base = 10;
+
+    private int addBase(int a, int b) {
+        return a + b + base;
+    }
+
+    static int max(int a, int b) {
+        return a > b ? a : b;
+    }
+
+    static int combine(IntOp op, int a, int b) {
+        return op.apply(a, b);
+    }
+
+    static String combine(Transformer transformer, String s) {
+        return transformer.transform(s);
+    }
+
+    static boolean check(Check check, int value) throws Exception {
+        return check.test(value);
+    }
+
+    IntOp adder(int offset) {
+        return (a, b) -> a + b + offset + base;
+    }
+
+    void run() {
+        IntOp viaThis = this::addBase;
+        System.out.println(viaThis.apply(1, 2));
+        Runnable printBase = () -> System.out.println("base is " + base);
+        printBase.run();
+        System.out.println(adder(5).apply(1, 2));
+        base = 20;
+        printBase.run();
+    }
+
+    public static void main(String[] args) {
+        IntOp add = (a, b) -> a + b;
+        IntOp multiply = (int a, int b) -> {
+            int product = a * b;
+            return product;
+        };
+        System.out.println(add.apply(2, 3));
+        System.out.println(multiply.apply(4, 5));
+        System.out.println(combine((a, b) -> a - b, 10, 4));
+        System.out.println(combine(Lambdas::max, 3, 7));
+        System.out.println(combine(Position::difference, 3, 7));
+
+        int factor = 3;
+        double half = 0.5;
+        String suffix = "!";
+        Transformer shout = s -> s + suffix + factor;
+        System.out.println(combine(shout, "hey"));
+        IntOp halve = (a, b) -> (int) ((a + b) * half);
+        System.out.println(halve.apply(7, 4));
+
+        PositionFactory factory = Position::new;
+        Position p = factory.create(2, 5);
+        PositionFunction sum = Position::sum;
+        System.out.println(sum.of(p));
+        IntOp scaled = p::scale;
+        System.out.println(scaled.apply(1, 1));
+
+        Runnable nested = () -> {
+            IntOp inner = (a, b) -> a * factor + b;
+            System.out.println(inner.apply(2, 1));
+        };
+        nested.run();
+        Runnable cast = (Runnable) () -> System.out.println("cast");
+        cast.run();
+        new Lambdas().run();
+
+        try {
+            System.out.println(check(v -> v > 0, -1));
+            System.out.println(check(v -> {
+                if (v == 0) {
+                    throw new Exception("zero");
+                }
+                return v > 0;
+            }, 0));
+        } catch (Exception e) {
+            System.out.println("caught " + e.getMessage());
+        }
+    }
+}
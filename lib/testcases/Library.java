@@ -0,0 +1,58 @@
+class Library {
+	int maxOf(int a, int b) {
+		return Math.max(a, b) + Math.min(a, b) * 2;
+	}
+
+	int distance(int a, int b) {
+		return Math.abs(a - b) + Integer.compare(a, b);
+	}
+
+	double wider(long l, float f, double d) {
+		return Math.abs(l) + Math.max(l, 3) + Math.min(f, 1) + Math.abs(f) + Math.max(d, l) + Math.abs(-d);
+	}
+
+	long mixed(int a, long b) {
+		return Math.min(a, b) + (long) Math.max(a, 2.5) + Math.abs(a * 1L);
+	}
+
+	int length(String s) {
+		return s.length();
+	}
+
+	char first(String s) {
+		return s.charAt(0);
+	}
+
+	boolean same(String s, String t) {
+		return s.equals(t) || s.equals("test");
+	}
+
+	String shout(String s) {
+		String upper = s.toUpperCase();
+		return upper.concat("!");
+	}
+
+	int parse(int a) {
+		String s = Integer.toString(a);
+		return Integer.parseInt(s) + s.length();
+	}
+
+	int limit() {
+		return Integer.MAX_VALUE / 2;
+	}
+
+	String describe(char c) {
+		if (Character.isLetter(c)) {
+			return String.valueOf(Character.toUpperCase(c));
+		}
+		return String.valueOf(c == '!');
+	}
+
+	void print(int a, char c, boolean b) {
+		System.out.print(a);
+		System.out.print(c);
+		System.out.println(b);
+		System.out.print("done");
+		System.out.println();
+	}
+}
@@ -0,0 +1,144 @@
+interface Greeting {
+    String greet(String name);
+}
+
+class LocalClasses {
+    private int visits = 0;
+    String title = "Dr.";
+
+    Greeting greeter(String greeting) {
+        String punctuation = "!";
+        class Polite implements Greeting {
+            public String greet(String name) {
+                visits++;
+                return greeting + ", " + title + " " + name + punctuation;
+            }
+        }
+        return new Polite();
+    }
+
+    int count(int from, int step) {
+        class Counter {
+            int value;
+
+            Counter(int start) {
+                value = start;
+            }
+
+            Counter() {
+                this(from);
+            }
+
+            int next() {
+                value += step;
+                return value;
+            }
+
+            Counter copy() {
+                return new Counter(value);
+            }
+        }
+        Counter counter = new Counter();
+        counter.next();
+        Counter copy = counter.copy();
+        copy.next();
+        return counter.next() + copy.value;
+    }
+
+    // Local classes with the same name are numbered
+    String count(String word) {
+        class Counter {
+            int length() {
+                return word.length();
+            }
+        }
+        return word + " has " + new Counter().length() + " letters";
+    }
+
+    static String shapes(double scale) {
+        String unit = "cm";
+        class Shape {
+            double area() {
+                return 0;
+            }
+
+            String describe() {
+                return area() * scale + unit;
+            }
+        }
+        class Square extends Shape {
+            final double side;
+
+            Square(double side) {
+                this.side = side;
+            }
+
+            double area() {
+                return side * side;
+            }
+        }
+        class Unit extends Shape {
+        }
+        Shape square = new Square(2);
+        Shape plain = new Unit();
+        return square.describe() + " " + plain.describe();
+    }
+
+    static int sum(int[] values) {
+        int offset = 100;
+        interface Op {
+            int apply(int a, int b);
+        }
+        class Add implements Op {
+            public int apply(int a, int b) {
+                return a + b;
+            }
+        }
+        Op op = new Add();
+        int total = offset;
+        for (int value : values) {
+            class Doubled {
+                int get() {
+                    return value * 2;
+                }
+            }
+            Doubled doubled = new Doubled();
+            total = op.apply(total, doubled.get());
+        }
+        return total;
+    }
+
+    static String nested(int n) {
+        class Outer {
+            class Inner {
+                int get() {
+                    return n + 1;
+                }
+            }
+
+            int get() {
+                Inner inner = new Inner();
+                return inner.get() * n;
+            }
+        }
+        Runnable print = () -> System.out.println("outer " + new Outer().get());
+        print.run();
+        Outer outer = new Outer();
+        Outer.Inner inner = outer.new Inner();
+        return "nested " + inner.get();
+    }
+
+    public static void main(String[] args) {
+        LocalClasses local = new LocalClasses();
+        Greeting hello = local.greeter("Hello");
+        System.out.println(hello.greet("Who"));
+        Greeting hi = local.greeter("Hi");
+        System.out.println(hi.greet("No"));
+        System.out.println(local.visits);
+        System.out.println(local.count(3, 2));
+        System.out.println(local.count("local"));
+        System.out.println(shapes(1.5));
+        System.out.println(sum(new int[] {1, 2, 3}));
+        System.out.println(nested(4));
+    }
+}
@@ -0,0 +1,157 @@
+class Loops {
+    int[] values = {4, 8, 15, 16, 23, 42};
+
+    int sum(int n) {
+        int sum = 0;
+        for (int i = 0; i <= n; i = i + 1) {
+            sum = sum + i;
+        }
+        return sum;
+    }
+
+    int countDown(int n) {
+        int steps = 0;
+        int i;
+        for (i = n, steps = 1; i > 0; i = i - 2, steps = steps + 1) {}
+        return steps * 100 + i;
+    }
+
+    int firstSquareAbove(int n) {
+        for (int i = 0; ; i = i + 1) {
+            if (i * i > n) {
+                return i;
+            }
+        }
+    }
+
+    int firstCubeAbove(int n) {
+        for (int i = 0; true; i++) {
+            if (i * i * i > n) {
+                return i;
+            }
+        }
+    }
+
+    int countUp(int x) {
+        while (true) {
+            if (x > 5) {
+                return x;
+            }
+            x++;
+        }
+    }
+
+    int untilBreak(int x) {
+        while (1 < 2) {
+            if (x++ > 10) {
+                break;
+            }
+        }
+        return x;
+    }
+
+    int digits(int n) {
+        int count = 0;
+        do {
+            count = count + 1;
+            n = n / 10;
+        } while (n > 0);
+        return count;
+    }
+
+    int sumOfValues() {
+        int sum = 0;
+        for (int value : values) {
+            sum = sum + value;
+        }
+        return sum;
+    }
+
+    int countChars(char c) {
+        int count = 0;
+        for (char letter : new char[]{'a', 'b', 'a', 'c'}) {
+            if (letter == c) {
+                count = count + 1;
+            }
+        }
+        return count;
+    }
+
+    int longWords() {
+        String[] words = {"for", "while", "do", "iterable"};
+        int count = 0;
+        for (String word : words) {
+            if (word.length() > 3) {
+                count = count + 1;
+            }
+        }
+        for (String word : words) {
+            for (int i = 0; i < word.length(); i = i + 1) {
+                count = count + 1;
+            }
+        }
+        return count;
+    }
+
+    int table(int n) {
+        int[][] table = new int[n][n];
+        for (int i = 0; i < n; i = i + 1) {
+            for (int j = 0; j < n; j = j + 1) {
+                table[i][j] = i * j;
+            }
+        }
+        int sum = 0;
+        for (int[] row : table) {
+            for (int cell : row) {
+                sum = sum + cell;
+            }
+        }
+        return sum;
+    }
+
+    int iterate(int n) {
+        int count = 0;
+        for (Object element : new IntRange(n)) {
+            count = count + 1;
+        }
+        return count;
+    }
+}
+
+class IntRange implements Iterable {
+    int end;
+
+    IntRange(int end) {
+        this.end = end;
+    }
+
+    public java.util.Iterator iterator() {
+        return new IntRangeIterator(end);
+    }
+
+    public String toString() {
+        return "range";
+    }
+}
+
+class IntRangeIterator implements java.util.Iterator {
+    int current;
+    int end;
+
+    IntRangeIterator(int end) {
+        this.end = end;
+    }
+
+    public boolean hasNext() {
+        return current < end;
+    }
+
+    public Object next() {
+        current = current + 1;
+        return "element";
+    }
+
+    public String toString() {
+        return "iterator";
+    }
+}
@@ -0,0 +1,51 @@
+public class Modifiers {
+	private static int count = 10;
+	static final int STEP = 2;
+	static int total;
+	private final int id;
+	protected int offset;
+
+	public Modifiers() {
+		this.id = next();
+		offset = 1;
+	}
+
+	static int next() {
+		count = count + STEP;
+		return count;
+	}
+
+	public static int peek(int a) {
+		return count + a;
+	}
+
+	public int id() {
+		return id + secret();
+	}
+
+	private int secret() {
+		return offset * 3;
+	}
+
+	final boolean isEven(int a) {
+		return a / 2 * 2 == a;
+	}
+}
+
+final class ModifiersUser {
+	int useCounters(int a) {
+		Modifiers c = new Modifiers();
+		Modifiers d = new Modifiers();
+		Modifiers.total = Modifiers.total + a;
+		int diff = d.id() - c.id();
+		return Modifiers.peek(a) + diff + Modifiers.STEP + Modifiers.total;
+	}
+
+	static int twice(int a) {
+		return helper(a) + helper(a);
+	}
+
+	private static int helper(int a) {
+		return a * 2 + Modifiers.next();
+	}
+}
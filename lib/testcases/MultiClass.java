@@ -0,0 +1,13 @@
+class MultiClassA {
+	int x = 3;
+
+	int getX() {
+		return x;
+	}
+}
+
+class MultiClassB {
+	boolean negate(boolean b) {
+		return !b;
+	}
+}
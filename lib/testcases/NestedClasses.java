@@ -0,0 +1,121 @@
+class NestedClasses {
+    private int count;
+    private static int created = 0;
+    String name = "outer";
+
+    interface Shape {
+        double area();
+    }
+
+    static class Square implements Shape {
+        private final double side;
+
+        Square(double side) {
+            this.side = side;
+            created++;
+        }
+
+        public double area() {
+            return side * side;
+        }
+    }
+
+    enum Color {
+        RED, GREEN;
+
+        Color next() {
+            return this == RED ? GREEN : RED;
+        }
+    }
+
+    class Counter {
+        int step;
+        String name = "counter of " + NestedClasses.this.name;
+
+        Counter(int step) {
+            this.step = step;
+        }
+
+        Counter() {
+            this(1);
+        }
+
+        int increment() {
+            count += step;
+            return count;
+        }
+
+        String describe() {
+            return name + ", " + NestedClasses.this.name + ", " + outerName();
+        }
+
+        class Snapshot {
+            final int value = count * step;
+
+            String show() {
+                return name + ": " + value + " of " + NestedClasses.this.count;
+            }
+        }
+    }
+
+    private class Hidden {
+        private int secret;
+
+        private Hidden(int secret) {
+            this.secret = secret + count;
+        }
+    }
+
+    String outerName() {
+        return name;
+    }
+
+    int count(int n) {
+        Counter counter = new Counter(n);
+        counter.increment();
+        Counter single = this.new Counter();
+        single.increment();
+        return count + counter.step;
+    }
+
+    String snapshot(int n) {
+        Counter counter = new Counter(n);
+        counter.increment();
+        name = "renamed";
+        Counter.Snapshot snapshot = counter.new Snapshot();
+        return snapshot.show() + " " + counter.describe();
+    }
+
+    int hidden(int n) {
+        count = 3;
+        Hidden hidden = new Hidden(n);
+        hidden.secret++;
+        return hidden.secret;
+    }
+
+    double shapes(double side) {
+        Shape shape = new Square(side);
+        return shape.area() + created;
+    }
+
+    String colors(boolean red) {
+        Color color = red ? Color.RED : Color.GREEN;
+        NestedClasses.Color green = NestedClasses.Color.valueOf("GREEN");
+        return color.next() + " " + green.ordinal();
+    }
+
+    static int fromStatic(int n) {
+        NestedClasses outer = new NestedClasses();
+        NestedClasses.Counter counter = outer.new Counter(n);
+        return counter.increment() + counter.increment();
+    }
+}
+
+class NestedUser {
+    int use(int n) {
+        NestedClasses.Square square = new NestedClasses.Square(n);
+        NestedClasses outer = new NestedClasses();
+        NestedClasses.Counter counter = outer.new Counter(2);
+        return (int) square.area() + counter.increment();
+    }
+}
@@ -0,0 +1,111 @@
+class Figure {
+    String describe() {
+        return "figure";
+    }
+
+    String accept(Figure figure) {
+        return "figure";
+    }
+}
+
+class Circle extends Figure {
+    String describe() {
+        return "circle";
+    }
+
+    String accept(Circle circle) {
+        return "circle";
+    }
+}
+
+class Overloading {
+    int count;
+
+    Overloading() {
+        count = 0;
+    }
+
+    Overloading(int count) {
+        this.count = count;
+    }
+
+    Overloading(long count) {
+        this.count = (int) (count >> 40);
+    }
+
+    String kind(int x) {
+        return "int " + x;
+    }
+
+    String kind(long x) {
+        return "long " + x;
+    }
+
+    String kind(double x) {
+        return "double " + x;
+    }
+
+    String kind(char c) {
+        return "char " + c;
+    }
+
+    String kind(String s) {
+        return "String " + s;
+    }
+
+    private String kind(Object o) {
+        return "Object " + o;
+    }
+
+    int sum(int a, int b) {
+        return a + b;
+    }
+
+    long sum(long a, long b) {
+        return a + b;
+    }
+
+    int sum(int a, int b, int c) {
+        return a + b + c;
+    }
+
+    String widening(short s, byte b, float f) {
+        return kind(s) + ", " + kind(b) + ", " + kind(f) + ", " + kind(s * 2L);
+    }
+
+    String nulls() {
+        String s = null;
+        Object o = "object";
+        return kind(null) + ", " + kind(s) + ", " + kind(o);
+    }
+
+    String chars(char c) {
+        return kind(c) + ", " + kind(c + 1) + ", " + sum(c, 1);
+    }
+
+    long mixedSum(int a, long b) {
+        return sum(a, b) + sum(a, a) + sum(a, a, a);
+    }
+
+    int constructors(int x) {
+        return new Overloading(x).count + new Overloading((long) x << 40).count;
+    }
+
+    String inherited() {
+        Circle circle = new Circle();
+        Figure figure = circle;
+        return circle.accept(circle) + " " + circle.accept(figure) + " " + figure.accept(circle);
+    }
+
+    static int twice(int x) {
+        return 2 * x;
+    }
+
+    static double twice(double x) {
+        return 2 * x;
+    }
+
+    double statics(int x) {
+        return twice(x) + twice(x / 4.0);
+    }
+}
@@ -0,0 +1,183 @@
+class Primitives {
+    long big = 10000000000L;
+    double pi = 3.14159;
+    float ratio = 2.5f;
+    byte small = 100;
+    short medium = -1000;
+    double widened = 7;
+    static long counter = 1L;
+
+    double literals() {
+        long a = 0L + 1L + 2l + 9223372036854775807L;
+        double b = 0.0 + 1.0 + .5 + 1e10 + 2.5E-3 + 4d + 1000.25;
+        float c = 0f + 1.0f + 2F + 3.25f + 1e-3f;
+        return a + b + c;
+    }
+
+    double doubles(double x, double y) {
+        return x * y - x / 2 + y % 3.5;
+    }
+
+    float floats(float x, float y) {
+        return x * y - x / 2 + y % 1.5f;
+    }
+
+    long longs(long x, long y) {
+        return x * 3 + y / 7 - x % 5;
+    }
+
+    double twoSlotLocals(long a, double b, int c) {
+        long l = a + c;
+        double d = b * c;
+        int i = c + 1;
+        long m = l * i;
+        return d + m + i;
+    }
+
+    double widening(int i, char c) {
+        byte b = 10;
+        short s = b;
+        int x = s + c;
+        long l = x;
+        float f = l;
+        double d = f;
+        return l + i + d;
+    }
+
+    double promotion(int i, long l, float f) {
+        return i / 2 + l / 3 + f / 4 + i * 0.5;
+    }
+
+    double mixedFields() {
+        return big + pi + ratio + small + medium + widened + counter;
+    }
+
+    long compound(long l, int i) {
+        l += i;
+        l -= 3;
+        l *= 2;
+        l <<= 3;
+        l >>= 1;
+        l >>>= 1;
+        l |= 256;
+        l &= 4095;
+        l ^= i;
+        l /= 3;
+        l %= 1000;
+        l++;
+        ++l;
+        l--;
+        return l + l++ + ++l;
+    }
+
+    double compoundDouble(double d, int i) {
+        d += i;
+        d *= 1.5;
+        d /= 4;
+        d -= 0.25;
+        d %= 10;
+        d++;
+        return d-- + --d;
+    }
+
+    int byteOverflow() {
+        byte b = 120;
+        b += 10;
+        short s = 32767;
+        s++;
+        return b + s;
+    }
+
+    boolean comparisons(long a, double b) {
+        double nan = 0.0 / 0.0;
+        return a > b && !(nan < b) && !(nan >= b) && nan != nan && a != 5 || a <= 3L;
+    }
+
+    int compareFloats(float a, float b) {
+        if (a < b) {
+            return -1;
+        }
+        if (a > b) {
+            return 1;
+        }
+        if (a == b) {
+            return 0;
+        }
+        return 2;
+    }
+
+    long shifts(long l, int n) {
+        long x = l << n;
+        long y = -l >> 3;
+        long z = -l >>> 40;
+        int w = n << 33L;
+        return x + y + z + w + (~l) + (l & 7) + (l | 16) + (l ^ n);
+    }
+
+    double arrays(int n) {
+        long[] ls = new long[n + 1];
+        double[] ds = {1.5, 2, 'a', n};
+        float[] fs = new float[2];
+        byte[] bs = {1, 2, 127};
+        short[] ss = new short[3];
+        ls[0] = n;
+        ls[n] += 5;
+        fs[1] = ls[0];
+        bs[0]++;
+        ss[2] = 300;
+        ss[2] -= bs[2];
+        double sum = 0;
+        for (double d : ds) {
+            sum += d;
+        }
+        for (long l : ls) {
+            sum += l;
+        }
+        for (double b : bs) {
+            sum += b;
+        }
+        return sum + fs[1] + ss[2] + bs[0];
+    }
+
+    double negation(long l, double d, float f) {
+        return -l + -d + -f + +d;
+    }
+
+    long updateFields(int n) {
+        big += n;
+        counter *= 3;
+        small++;
+        medium -= small;
+        return big + counter + small + medium;
+    }
+
+    double conditional(boolean b, int i, long l) {
+        long x = b ? i : l;
+        double y = b ? 1.5f : 2.5;
+        return x + y;
+    }
+
+    long switchExpr(int i) {
+        return switch (i % 3) {
+            case 0 -> 1;
+            case 1 -> 10000000000L;
+            default -> 'a';
+        };
+    }
+
+    double largeConstants() {
+        long a = 1234567890123L;
+        double b = 123456.789;
+        long c = 1234567890123L;
+        float d = 1234.5f;
+        return a + c + b + d;
+    }
+
+    long call(int i) {
+        return longs(i, 5) + longs(3L, i);
+    }
+
+    double max(double a, double b) {
+        return a > b ? a : b;
+    }
+}
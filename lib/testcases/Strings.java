@@ -0,0 +1,106 @@
+class Strings {
+    String name = "Strings";
+
+    String describe(int x) {
+        return "x = " + x;
+    }
+
+    String primitives(char c, boolean b, long l, double d) {
+        return c + ", " + b + ", " + l + ", " + d;
+    }
+
+    String smallNumbers(byte b, short s, float f) {
+        return "b: " + b + ", s: " + s + ", f: " + f;
+    }
+
+    String leftToRight(int a, int b) {
+        return a + b + " = " + a + b;
+    }
+
+    String chars(char c) {
+        return c + 1 + "|" + c + 1 + "|" + (char) (c + 1);
+    }
+
+    String literals() {
+        return "a" + 1 + 'b' + true + 2L + null + "c" + -3;
+    }
+
+    String escapes() {
+        return "tab\t\"quoted\" \\ \u0041" + '\'' + '\n';
+    }
+
+    String floatingLiterals() {
+        return "d" + 1.5 + 2.5f + 1e10;
+    }
+
+    String references(String s) {
+        String nothing = null;
+        Object object = nothing;
+        return s + nothing + object;
+    }
+
+    String nested(String s) {
+        return s + (s + 1) + ("" + 2 + 3) + (1 + 2);
+    }
+
+    String fields() {
+        return name + "." + name.length() + "." + this.name;
+    }
+
+    String conditional(int a) {
+        return "a is " + (a > 5 ? "big" : "small") + "!";
+    }
+
+    String loop(int n) {
+        String s = "";
+        for (int i = 0; i < n; i++) {
+            s = s + i + ",";
+        }
+        return s;
+    }
+
+    String compoundField(int x) {
+        name += x;
+        this.name += '!';
+        return name;
+    }
+
+    String compoundLocal(String s, double d) {
+        String t = s;
+        t += d;
+        t += 1 + 2;
+        String nothing = null;
+        nothing += t;
+        return t += "|" + nothing.length();
+    }
+
+    String compoundArray(int i) {
+        String[] parts = {"a", "b"};
+        parts[i & 1] += i;
+        parts[0] += parts[1] += 'c';
+        return parts[0] + "," + parts[1];
+    }
+
+    String methodCalls(String s) {
+        return s.length() + s.toUpperCase() + String.valueOf(2.5) + String.valueOf(7L);
+    }
+
+    String onlyValues(String s, int a) {
+        return s + a;
+    }
+
+    boolean identity(String s) {
+        String t = s;
+        String nothing = null;
+        return s == t && s != nothing && nothing == null && null != s && "a" == "a";
+    }
+
+    boolean equality(String s, String t) {
+        String both = s + t;
+        return s.equals(t) || s.equalsIgnoreCase(t.toUpperCase()) || both.equals(t + s);
+    }
+
+    int comparison(String s, String t) {
+        return s.compareToIgnoreCase(t) + s.hashCode() % 10;
+    }
+}
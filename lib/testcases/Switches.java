@@ -0,0 +1,192 @@
+class Switches {
+    int dense(int n) {
+        int result = 0;
+        switch (n % 5) {
+            case 0:
+                result = 10;
+                break;
+            case 1:
+                result = 11;
+            case 2:
+                result = result + 12;
+                break;
+            case 4:
+                return 14;
+            default:
+                result = -1;
+        }
+        return result;
+    }
+
+    int sparse(int n) {
+        switch (n) {
+            case 1:
+                return 1;
+            case 10:
+            case 20:
+                return 2;
+            case 1000:
+                return 3;
+            case -5:
+                return 4;
+        }
+        return 0;
+    }
+
+    int vowel(char c) {
+        int result = 0;
+        switch (c) {
+            case 'a', 'e', 'i', 'o', 'u':
+                result = 1;
+                break;
+            case 'x':
+                result = 2;
+                break;
+            default:
+                result = 3;
+                break;
+        }
+        return result;
+    }
+
+    int words(String s) {
+        switch (s) {
+            case "a":
+                return 1;
+            case "test":
+                return 2;
+            case "Aa":
+                return 3;
+            case "BB":
+                return 4;
+            default:
+                return 5;
+        }
+    }
+
+    int collision(int n) {
+        String s = "Aa";
+        if (n % 2 == 0) {
+            s = "BB";
+        }
+        switch (s) {
+            case "Aa" -> n = n + 100;
+            case "BB" -> n = n + 200;
+        }
+        return n;
+    }
+
+    int arrows(int n) {
+        int result = 0;
+        switch (n % 4) {
+            case 0, 1 -> result = 1;
+            case 2 -> {
+                result = 2;
+                if (n > 10) {
+                    break;
+                }
+                result = 3;
+            }
+            default -> result = 4;
+        }
+        return result;
+    }
+
+    int days(int n) {
+        return switch (n % 7) {
+            case 0, 6 -> 0;
+            case 1 -> 8;
+            default -> {
+                int hours = n % 7 + 4;
+                yield hours;
+            }
+        };
+    }
+
+    char grade(int n) {
+        char c = switch (n / 10) {
+            case 0:
+                yield 'c';
+            case 1:
+            case 2:
+                if (n > 15) {
+                    yield 'a';
+                }
+                yield 'b';
+            default:
+                yield 'f';
+        };
+        return c;
+    }
+
+    String name(char c) {
+        return switch (c) {
+            case 'a' -> "first";
+            case 'c' -> "third";
+            case 'f' -> "sixth";
+            default -> "other";
+        };
+    }
+
+    int sum(int n) {
+        return 1 + switch (n % 3) {
+            case 0 -> n;
+            case 1 -> switch (n) {
+                case 1 -> 100;
+                default -> 200;
+            };
+            default -> -n;
+        };
+    }
+
+    int countInLoop(int n) {
+        int count = 0;
+        for (int i = 0; i < n; i = i + 1) {
+            switch (i % 3) {
+                case 0:
+                    continue;
+                case 1:
+                    count = count + 1;
+                    break;
+                default:
+                    count = count + 10;
+            }
+            count = count + 100;
+        }
+        return count;
+    }
+
+    int nested(int n) {
+        int result = 0;
+        outer:
+        switch (n % 2) {
+            case 0:
+                while (true) {
+                    result = result + 1;
+                    if (result > 3) {
+                        break outer;
+                    }
+                }
+            default:
+                result = 7;
+        }
+        return result;
+    }
+
+    int loopInExpression(int n) {
+        int result = switch (n % 2) {
+            case 0 -> {
+                int i = 0;
+                while (true) {
+                    if (i > n) {
+                        break;
+                    }
+                    i = i + 3;
+                }
+                yield i;
+            }
+            default -> 1;
+        };
+        return result;
+    }
+}
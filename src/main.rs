@@ -1,38 +1,83 @@
-use lib::codegen::generate_dir;
+use lib::codegen::{
+    generate_dir_with_version, read_classpath, release_major_version, DEFAULT_MAJOR_VERSION,
+    SUPPORTED_RELEASES,
+};
 use lib::parser::parse_programm;
 use lib::typechecker::typechecker::TypeChecker;
-use std::fs::{read_to_string, File};
+use std::fs::{create_dir_all, read_to_string, File};
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use tracing::info;
 
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
     tracing_subscriber::fmt::init();
-    let args = std::env::args().collect::<Vec<_>>();
+    let mut args = std::env::args().collect::<Vec<_>>();
+    let usage = format!(
+        "Usage: {} [--classpath <entries>] [--release <version>] <input_file> [<output_dir>]",
+        args[0]
+    );
+    // The entries of the classpath are separated like the ones of the PATH environment variable,
+    // i.e. by `:` on Unix and `;` on Windows
+    let mut classpath: Vec<PathBuf> = vec![];
+    if let Some(idx) = args
+        .iter()
+        .position(|arg| arg == "--classpath" || arg == "-cp")
+    {
+        let entries = args
+            .get(idx + 1)
+            .unwrap_or_else(|| panic!("No classpath provided. {usage}"));
+        classpath = std::env::split_paths(entries).collect();
+        args.drain(idx..idx + 2);
+    }
+    // Like for javac, the release is the version of Java the class files are generated for, e.g. 17,
+    // whose class files have the major version 61
+    let mut major_version = DEFAULT_MAJOR_VERSION;
+    if let Some(idx) = args.iter().position(|arg| arg == "--release") {
+        let release: u16 = args
+            .get(idx + 1)
+            .and_then(|release| release.parse().ok())
+            .unwrap_or_else(|| panic!("No valid release provided. {usage}"));
+        major_version = release_major_version(release).unwrap_or_else(|| {
+            panic!(
+                "Unsupported release {release}, expected one from {} to {}. {usage}",
+                SUPPORTED_RELEASES.start(),
+                SUPPORTED_RELEASES.end()
+            )
+        });
+        args.drain(idx..idx + 2);
+    }
     let input_file = args.get(1).unwrap_or_else(|| {
-        panic!("No input file provided. Usage: {} <input_file>", args[0]);
+        panic!("No input file provided. {usage}");
     });
+    info!("Reading the classpath...");
+    let classpath = read_classpath(
+        &classpath
+            .iter()
+            .map(PathBuf::as_path)
+            .collect::<Vec<&Path>>(),
+    )
+    .unwrap_or_else(|e| panic!("{}", e));
     info!("Parsing the file {}", input_file);
     let file = read_to_string(input_file)?;
     let prg_parsed = parse_programm(&file)?;
     info!("Typechecking the program...");
-    let prg_typechecked = TypeChecker::new(prg_parsed)
+    let prg_typechecked = TypeChecker::with_classpath(prg_parsed, classpath.clone())
         .unwrap_or_else(|e| panic!("{}", e))
         .check_and_type_program()
         .unwrap_or_else(|e| panic!("{}", e));
     info!("Generating code using ducc...");
     // Generate code using codegen_ducc
-    let mut dir = generate_dir(&prg_typechecked);
-    let bytes = dir.as_bytes();
-    // The output file, which is the input file with the extension replaced by .class and the folder cut off
-    let alt_outfile = input_file
-        .split('/')
-        .last()
-        .unwrap()
-        .replace(".java", ".class");
-    let out_file = args.get(2).unwrap_or(&alt_outfile);
-    info!("Writing code to {}", out_file);
-    let mut file = File::create(out_file)?;
-    file.write_all(bytes.as_slice())?;
+    let mut dir = generate_dir_with_version(&prg_typechecked, &classpath, major_version);
+    // Every class is written into its own <ClassName>.class file inside the output directory,
+    // which defaults to the current working directory
+    let out_dir = Path::new(args.get(2).map(String::as_str).unwrap_or("."));
+    create_dir_all(out_dir)?;
+    for (class_name, bytes) in dir.as_bytes() {
+        let out_file = out_dir.join(format!("{class_name}.class"));
+        info!("Writing code to {}", out_file.display());
+        let mut file = File::create(out_file)?;
+        file.write_all(bytes.as_slice())?;
+    }
     Ok(())
 }